@@ -0,0 +1,153 @@
+//! Interference (clash) detection between bodies.
+//!
+//! Pairs whose bounding boxes overlap are intersected with the same manifold
+//! boolean as `BooleanOp::Intersection`; a non-empty common solid is a clash,
+//! measured by its volume. Touching faces produce slivers of numerically zero
+//! volume, so results below a fraction of the smaller body are dropped.
+
+use std::collections::HashMap;
+
+use glam::Vec3;
+use shared::{Clash, InterferenceReport, SceneDescriptionV2};
+use vcad::Part;
+
+use super::mass::MassMoments;
+use crate::build::{build_body_part, evaluate_feature_bindings, extract_mesh_data};
+use crate::mesh::MeshData;
+
+/// Clash volumes below this fraction of the smaller body are contact, not overlap
+const RELATIVE_VOLUME_TOLERANCE: f64 = 1e-6;
+
+/// Clash report plus the common solids, in the order of `report.clashes`
+#[derive(Clone)]
+pub struct Interference {
+    pub report: InterferenceReport,
+    pub solids: Vec<MeshData>,
+}
+
+/// Axis-aligned bounds (min, max) of a mesh
+pub fn mesh_bounds(mesh: &MeshData) -> (Vec3, Vec3) {
+    mesh.vertices.chunks_exact(9).fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(min, max), v| {
+            let p = Vec3::new(v[0], v[1], v[2]);
+            (min.min(p), max.max(p))
+        },
+    )
+}
+
+/// Id pairs (sorted, first < second) whose bounds overlap
+pub fn overlapping_pairs(bounds: &HashMap<String, (Vec3, Vec3)>) -> Vec<(String, String)> {
+    let mut ids: Vec<&String> = bounds.keys().collect();
+    ids.sort();
+
+    let mut pairs = Vec::new();
+    for (i, a) in ids.iter().enumerate() {
+        let (a_min, a_max) = bounds[*a];
+        for b in &ids[i + 1..] {
+            let (b_min, b_max) = bounds[*b];
+            if a_min.cmple(b_max).all() && b_min.cmple(a_max).all() {
+                pairs.push(((*a).clone(), (*b).clone()));
+            }
+        }
+    }
+    pairs
+}
+
+/// Clashes between the built bodies of `scene`. `bounds` holds the bounds of
+/// every built, visible body by id (as cached by the viewport); only those
+/// bodies are checked, and only pairs whose bounds overlap are intersected.
+pub fn scene_interference(scene: &SceneDescriptionV2, bounds: &HashMap<String, (Vec3, Vec3)>) -> Interference {
+    let pairs = overlapping_pairs(bounds);
+    let (scene, _) = evaluate_feature_bindings(scene);
+
+    // Each body is built once, however many candidate pairs it is in
+    let mut parts: HashMap<&str, (Part, f64)> = HashMap::new();
+    for (a, b) in &pairs {
+        for id in [a, b] {
+            if parts.contains_key(id.as_str()) {
+                continue;
+            }
+            let Some(body) = scene.bodies.iter().find(|body| &body.id == id) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let Some(part) = build_body_part(body, &scene.bodies, &mut errors).filter(|p| !p.is_empty()) else {
+                continue;
+            };
+            let volume = extract_mesh_data(&part, false).map_or(0.0, |m| MassMoments::from_mesh(&m, 1.0).volume);
+            parts.insert(id.as_str(), (part, volume));
+        }
+    }
+
+    let name = |id: &str| scene.bodies.iter().find(|b| b.id == id).map_or_else(|| id.to_string(), |b| b.name.clone());
+    let mut found: Vec<(Clash, MeshData)> = Vec::new();
+    for (a, b) in &pairs {
+        let (Some((part_a, volume_a)), Some((part_b, volume_b))) = (parts.get(a.as_str()), parts.get(b.as_str())) else {
+            continue;
+        };
+        let common = part_a.intersection(part_b);
+        if common.is_empty() {
+            continue;
+        }
+        let Some(mesh) = extract_mesh_data(&common, false) else {
+            continue;
+        };
+        let volume = MassMoments::from_mesh(&mesh, 1.0).volume;
+        if volume <= volume_a.min(*volume_b) * RELATIVE_VOLUME_TOLERANCE {
+            continue;
+        }
+
+        let (min, max) = mesh_bounds(&mesh);
+        let clash = Clash {
+            body_a: a.clone(),
+            name_a: name(a),
+            body_b: b.clone(),
+            name_b: name(b),
+            volume,
+            bbox_min: min.as_dvec3().to_array(),
+            bbox_max: max.as_dvec3().to_array(),
+        };
+        found.push((clash, mesh));
+    }
+    found.sort_by(|x, y| y.0.volume.total_cmp(&x.0.volume));
+
+    let (clashes, solids) = found.into_iter().unzip();
+    Interference {
+        report: InterferenceReport { clashes, body_count: bounds.len(), checked_pairs: pairs.len() },
+        solids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::box_mesh;
+
+    #[test]
+    fn test_mesh_bounds() {
+        let (min, max) = mesh_bounds(&box_mesh([1.0, -2.0, 0.5], [3.0, 0.0, 2.5]));
+        assert_eq!(min, Vec3::new(1.0, -2.0, 0.5));
+        assert_eq!(max, Vec3::new(3.0, 0.0, 2.5));
+    }
+
+    #[test]
+    fn test_overlapping_pairs_prefilter() {
+        let bounds: HashMap<String, (Vec3, Vec3)> = [
+            ("c", box_mesh([0.5; 3], [1.5; 3])),
+            ("a", box_mesh([0.0; 3], [1.0; 3])),
+            ("b", box_mesh([5.0, 0.0, 0.0], [6.0, 1.0, 1.0])),
+            // Touching `b` along a face still counts as a candidate
+            ("d", box_mesh([6.0, 0.0, 0.0], [7.0, 1.0, 1.0])),
+        ]
+        .into_iter()
+        .map(|(id, mesh)| (id.to_string(), mesh_bounds(&mesh)))
+        .collect();
+
+        let pairs = overlapping_pairs(&bounds);
+        assert_eq!(
+            pairs,
+            vec![("a".to_string(), "c".to_string()), ("b".to_string(), "d".to_string())]
+        );
+    }
+}
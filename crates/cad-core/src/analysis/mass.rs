@@ -0,0 +1,267 @@
+//! Mass properties of closed triangle meshes.
+//!
+//! Volume integrals come from the divergence theorem: every triangle spans a
+//! signed tetrahedron with the origin, whose volume, first moment and second
+//! moment (`∫ x xᵀ dV`) have closed forms. The moments scaled by density are
+//! additive, so a scene total is the plain sum of its bodies. Inertia follows
+//! from the second moment as `I = tr(S)·E − S`; principal axes are the
+//! eigenvectors of the centroidal tensor (Jacobi rotations).
+
+use std::collections::HashMap;
+
+use glam::{DMat3, DVec3};
+use shared::mass::body_density;
+use shared::{BodyMassProperties, MassProperties, MassReport, SceneDescriptionV2};
+
+use crate::mesh::MeshData;
+
+/// Density unit conversion: g/cm³ → g/mm³
+const G_PER_CM3_TO_G_PER_MM3: f64 = 1e-3;
+
+/// Density-weighted volume moments of a solid, in mm and g
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassMoments {
+    /// Volume, mm³
+    pub volume: f64,
+    /// Surface area, mm²
+    pub area: f64,
+    /// Mass, g
+    pub mass: f64,
+    /// `∫ρ x dV`, g·mm
+    pub first: DVec3,
+    /// `∫ρ x xᵀ dV` about the origin, g·mm²
+    pub second: DMat3,
+}
+
+impl Default for MassMoments {
+    fn default() -> Self {
+        Self { volume: 0.0, area: 0.0, mass: 0.0, first: DVec3::ZERO, second: DMat3::ZERO }
+    }
+}
+
+impl std::ops::AddAssign for MassMoments {
+    fn add_assign(&mut self, other: Self) {
+        self.volume += other.volume;
+        self.area += other.area;
+        self.mass += other.mass;
+        self.first += other.first;
+        self.second += other.second;
+    }
+}
+
+impl MassMoments {
+    /// Moments of a closed, outward-oriented mesh with uniform density (g/cm³)
+    pub fn from_mesh(mesh: &MeshData, density: f64) -> Self {
+        let position = |i: u32| {
+            let v = &mesh.vertices[i as usize * 9..i as usize * 9 + 3];
+            DVec3::new(v[0] as f64, v[1] as f64, v[2] as f64)
+        };
+
+        let mut volume = 0.0;
+        let mut area = 0.0;
+        let mut first = DVec3::ZERO;
+        let mut second = DMat3::ZERO;
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(position);
+            area += (b - a).cross(c - a).length() / 2.0;
+
+            // Tetrahedron (0, a, b, c): V = det/6, ∫x = V·(a+b+c)/4,
+            // ∫x xᵀ = det/120 · (a aᵀ + b bᵀ + c cᵀ + s sᵀ) with s = a+b+c
+            let det = a.dot(b.cross(c));
+            let sum = a + b + c;
+            volume += det / 6.0;
+            first += sum * (det / 24.0);
+            second += (outer(a) + outer(b) + outer(c) + outer(sum)) * (det / 120.0);
+        }
+
+        let rho = density * G_PER_CM3_TO_G_PER_MM3;
+        Self { volume, area, mass: volume * rho, first: first * rho, second: second * rho }
+    }
+
+    /// Centre of mass, inertia tensors and principal axes
+    pub fn properties(&self) -> MassProperties {
+        if self.mass.abs() < 1e-15 {
+            return MassProperties { volume: self.volume, surface_area: self.area, ..Default::default() };
+        }
+
+        let center = self.first / self.mass;
+        let inertia_origin = inertia(self.second);
+        let inertia_centroid = inertia(self.second - outer(center) * self.mass);
+        let (moments, axes) = principal_axes(inertia_centroid);
+
+        MassProperties {
+            volume: self.volume,
+            surface_area: self.area,
+            density: if self.volume.abs() > 1e-15 { self.mass / self.volume / G_PER_CM3_TO_G_PER_MM3 } else { 0.0 },
+            mass: self.mass,
+            center_of_mass: center.to_array(),
+            inertia_centroid: rows(inertia_centroid),
+            inertia_origin: rows(inertia_origin),
+            principal_moments: moments.to_array(),
+            principal_axes: axes.to_cols_array_2d(),
+        }
+    }
+}
+
+/// Mass properties of every built body (meshes keyed by body id, as from
+/// `build_scene_meshes_v2`), in scene order, plus the total. Bodies without a
+/// material use `DEFAULT_DENSITY`; meshes without a scene body (operation
+/// results) are listed after them by id.
+pub fn scene_mass_properties(scene: &SceneDescriptionV2, meshes: &HashMap<String, MeshData>) -> MassReport {
+    let mut keys: Vec<&String> = scene.bodies.iter().map(|b| &b.id).filter(|id| meshes.contains_key(*id)).collect();
+    let mut orphans: Vec<&String> = meshes.keys().filter(|id| scene.bodies.iter().all(|b| &b.id != *id)).collect();
+    orphans.sort();
+    keys.extend(orphans);
+
+    let mut total = MassMoments::default();
+    let bodies = keys
+        .into_iter()
+        .map(|id| {
+            let body = scene.bodies.iter().find(|b| &b.id == id);
+            let density = body.map_or(shared::mass::DEFAULT_DENSITY, body_density);
+            let moments = MassMoments::from_mesh(&meshes[id], density);
+            total += moments;
+            BodyMassProperties {
+                id: id.clone(),
+                name: body.map_or_else(|| id.clone(), |b| b.name.clone()),
+                material: body
+                    .and_then(|b| b.appearance.as_ref())
+                    .and_then(|a| a.material.as_ref())
+                    .map(|m| m.name.clone()),
+                properties: moments.properties(),
+            }
+        })
+        .collect();
+
+    MassReport { bodies, total: total.properties(), errors: HashMap::new() }
+}
+
+fn outer(v: DVec3) -> DMat3 {
+    DMat3::from_cols(v * v.x, v * v.y, v * v.z)
+}
+
+/// Inertia tensor from the second moment `S`: `tr(S)·E − S`
+fn inertia(second: DMat3) -> DMat3 {
+    let trace = second.x_axis.x + second.y_axis.y + second.z_axis.z;
+    DMat3::from_diagonal(DVec3::splat(trace)) - second
+}
+
+fn rows(m: DMat3) -> [[f64; 3]; 3] {
+    m.transpose().to_cols_array_2d()
+}
+
+/// Eigenvalues (ascending) and eigenvectors (columns, right-handed) of a
+/// symmetric matrix by cyclic Jacobi rotations
+pub(crate) fn principal_axes(m: DMat3) -> (DVec3, DMat3) {
+    let mut a = m.to_cols_array_2d();
+    let mut v = DMat3::IDENTITY.to_cols_array_2d();
+
+    for _ in 0..50 {
+        let off = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+        let scale = a[0][0].powi(2) + a[1][1].powi(2) + a[2][2].powi(2);
+        if off <= 1e-24 * scale.max(f64::MIN_POSITIVE) {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < f64::MIN_POSITIVE {
+                continue;
+            }
+            // Rotation zeroing a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            // A ← Jᵀ A J, V ← V J; `v[j]` is the j-th eigenvector
+            for row in a.iter_mut() {
+                let (akp, akq) = (row[p], row[q]);
+                row[p] = c * akp - s * akq;
+                row[q] = s * akp + c * akq;
+            }
+            rotate(&mut a, p, q, c, s);
+            rotate(&mut v, p, q, c, s);
+        }
+    }
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+    let values = DVec3::from_array(order.map(|i| a[i][i]));
+    let [x, y, z] = order.map(|i| DVec3::from_array(v[i]));
+    // Right-handed: z = x × y
+    let z = if x.cross(y).dot(z) < 0.0 { -z } else { z };
+    (values, DMat3::from_cols(x, y, z))
+}
+
+/// Replace vectors `p`, `q` of `m` with `c·p − s·q` and `s·p + c·q`
+fn rotate(m: &mut [[f64; 3]; 3], p: usize, q: usize, c: f64, s: f64) {
+    let (mp, mq) = (m[p], m[q]);
+    m[p] = std::array::from_fn(|k| c * mp[k] - s * mq[k]);
+    m[q] = std::array::from_fn(|k| s * mp[k] + c * mq[k]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::box_mesh;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= 1e-6 * expected.abs().max(1.0), "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_box_mass_properties() {
+        // 10 × 20 × 30 mm box centred at (5, 10, 15) + (1, 2, 3), steel
+        let mesh = box_mesh([1.0, 2.0, 3.0], [11.0, 22.0, 33.0]);
+        let props = MassMoments::from_mesh(&mesh, 7.85).properties();
+
+        let mass = 6000.0 * 7.85e-3;
+        assert_close(props.volume, 6000.0);
+        assert_close(props.surface_area, 2200.0);
+        assert_close(props.mass, mass);
+        assert_close(props.density, 7.85);
+        for (c, e) in props.center_of_mass.iter().zip([6.0, 12.0, 18.0]) {
+            assert_close(*c, e);
+        }
+
+        // Box about its centroid: m/12·(b² + c²) etc.
+        let [ix, iy, iz] = [mass / 12.0 * 1300.0, mass / 12.0 * 1000.0, mass / 12.0 * 500.0];
+        assert_close(props.inertia_centroid[0][0], ix);
+        assert_close(props.inertia_centroid[1][1], iy);
+        assert_close(props.inertia_centroid[2][2], iz);
+        assert!(props.inertia_centroid[0][1].abs() < 1e-6);
+        // Parallel axis theorem about the origin
+        assert_close(props.inertia_origin[0][0], ix + mass * (12.0f64.powi(2) + 18.0f64.powi(2)));
+        assert_close(props.inertia_origin[0][1], -mass * 6.0 * 12.0);
+
+        assert_close(props.principal_moments[0], iz);
+        assert_close(props.principal_moments[2], ix);
+        assert_close(props.principal_axes[0][2].abs(), 1.0);
+        assert_close(props.principal_axes[2][0].abs(), 1.0);
+    }
+
+    #[test]
+    fn test_principal_axes_of_rotated_tensor() {
+        // diag(1, 2, 3) rotated 30° about Z
+        let rotation = DMat3::from_rotation_z(30f64.to_radians());
+        let tensor = rotation * DMat3::from_diagonal(DVec3::new(2.0, 1.0, 3.0)) * rotation.transpose();
+        let (values, axes) = principal_axes(tensor);
+
+        assert!((values - DVec3::new(1.0, 2.0, 3.0)).length() < 1e-9);
+        // Smallest moment lies along the rotated Y axis
+        assert!(axes.x_axis.dot(rotation.y_axis).abs() > 1.0 - 1e-9);
+        assert!((axes.determinant() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_scene_total_sums_bodies() {
+        let meshes = HashMap::from([
+            ("a".to_string(), box_mesh([0.0; 3], [1.0; 3])),
+            ("b".to_string(), box_mesh([2.0, 0.0, 0.0], [3.0, 1.0, 1.0])),
+        ]);
+        let report = scene_mass_properties(&SceneDescriptionV2::default(), &meshes);
+
+        assert_eq!(report.bodies.iter().map(|b| b.id.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        assert_close(report.total.mass, 2e-3);
+        assert_close(report.total.center_of_mass[0], 1.5);
+        assert_close(report.total.density, shared::mass::DEFAULT_DENSITY);
+    }
+}
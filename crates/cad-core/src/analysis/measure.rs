@@ -0,0 +1,547 @@
+//! Distances, angles and radii between picked mesh entities.
+//!
+//! Viewport picks become [`MeasureEntity`] values: points, edge segments,
+//! planar faces and cylindrical faces. Faces are recognised on the tessellated
+//! mesh by splitting it into coplanar facet groups: a group wider than its
+//! curved neighbours is a plane, otherwise the chain of similar facet strips
+//! around the axis is fitted with a circle (least squares, Kåsa) to recover
+//! the cylinder axis and radius.
+
+use std::collections::HashMap;
+
+use glam::{DMat3, DVec3, Vec3};
+
+use super::mass::principal_axes;
+use crate::mesh::MeshData;
+
+/// Adjacent facets within this angle belong to the same smooth surface
+const SMOOTH_ANGLE_DEG: f32 = 25.0;
+/// Normals closer than this (cosine) belong to the same planar facet group
+const COPLANAR_COS: f32 = 0.9999;
+/// Sine below which two directions count as parallel (no angle, only distance)
+const PARALLEL_SIN: f32 = 1e-3;
+/// Largest |n·axis| of a facet normal on a cylinder
+const AXIS_TOLERANCE: f64 = 0.05;
+/// Largest deviation of fitted vertices from the circle, relative to radius
+const CIRCLE_TOLERANCE: f64 = 0.02;
+/// Vertex quantisation for edge adjacency
+const QUANT_SCALE: f32 = 1e4;
+
+/// Geometric entity picked in the viewport
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeasureEntity {
+    /// A vertex or a point on a surface
+    Point(Vec3),
+    /// A straight mesh edge
+    Edge { start: Vec3, end: Vec3 },
+    /// A planar face: its centroid and unit normal
+    Plane { point: Vec3, normal: Vec3 },
+    /// A cylindrical face: axis point at mid-height, unit axis and radius
+    Cylinder { center: Vec3, axis: Vec3, radius: f32 },
+}
+
+impl MeasureEntity {
+    /// Representative point for labels and leader lines
+    pub fn anchor(&self) -> Vec3 {
+        match *self {
+            MeasureEntity::Point(p) => p,
+            MeasureEntity::Edge { start, end } => (start + end) * 0.5,
+            MeasureEntity::Plane { point, .. } => point,
+            MeasureEntity::Cylinder { center, .. } => center,
+        }
+    }
+
+    /// Unit direction and whether it is a face normal (as opposed to a line
+    /// direction); points have none
+    fn direction(&self) -> Option<(Vec3, bool)> {
+        match *self {
+            MeasureEntity::Point(_) => None,
+            MeasureEntity::Edge { start, end } => Some(((end - start).normalize_or_zero(), false)),
+            MeasureEntity::Plane { normal, .. } => Some((normal, true)),
+            MeasureEntity::Cylinder { axis, .. } => Some((axis, false)),
+        }
+    }
+
+    /// Shape used for distances: a cylinder measures from its axis
+    fn shape(&self) -> Shape {
+        match *self {
+            MeasureEntity::Point(p) => Shape::Point(p),
+            MeasureEntity::Edge { start, end } => Shape::Line { origin: start, dir: end - start, range: (0.0, 1.0) },
+            MeasureEntity::Plane { point, normal } => Shape::Plane { point, normal },
+            MeasureEntity::Cylinder { center, axis, .. } => {
+                Shape::Line { origin: center, dir: axis, range: (f32::NEG_INFINITY, f32::INFINITY) }
+            }
+        }
+    }
+}
+
+/// One measured value with the points it is drawn between
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measurement {
+    /// Shortest distance from `from` to `to` (the length of a single edge)
+    Distance { value: f32, from: Vec3, to: Vec3 },
+    /// Angle in degrees between faces and/or edges, labelled at `at`
+    Angle { degrees: f32, at: Vec3 },
+    /// Radius of a cylindrical face
+    Radius { value: f32, center: Vec3, axis: Vec3 },
+}
+
+#[derive(Clone, Copy)]
+enum Shape {
+    Point(Vec3),
+    /// `origin + dir·s` for `s` in `range`
+    Line { origin: Vec3, dir: Vec3, range: (f32, f32) },
+    Plane { point: Vec3, normal: Vec3 },
+}
+
+/// Measure one entity (edge length, cylinder radius) or a pair: the minimum
+/// distance when defined, the angle unless parallel, and any cylinder radii.
+/// Intersecting lines/planes report no distance.
+pub fn measure(first: &MeasureEntity, second: Option<&MeasureEntity>) -> Vec<Measurement> {
+    let mut out = Vec::new();
+    match second {
+        None => {
+            if let MeasureEntity::Edge { start, end } = *first {
+                out.push(Measurement::Distance { value: start.distance(end), from: start, to: end });
+            }
+        }
+        Some(second) => {
+            if let Some((from, to)) = closest_points(first.shape(), second.shape()) {
+                out.push(Measurement::Distance { value: from.distance(to), from, to });
+            }
+            out.extend(angle(first, second));
+        }
+    }
+    for entity in std::iter::once(first).chain(second) {
+        if let MeasureEntity::Cylinder { center, axis, radius } = *entity {
+            out.push(Measurement::Radius { value: radius, center, axis });
+        }
+    }
+    out
+}
+
+/// Closest pair of points, or `None` when the shapes intersect at an angle
+fn closest_points(a: Shape, b: Shape) -> Option<(Vec3, Vec3)> {
+    match (a, b) {
+        (Shape::Point(p), Shape::Point(q)) => Some((p, q)),
+        (Shape::Point(p), Shape::Line { origin, dir, range }) => {
+            let s = ((p - origin).dot(dir) / dir.length_squared()).clamp(range.0, range.1);
+            Some((p, origin + dir * s))
+        }
+        (Shape::Point(p), Shape::Plane { point, normal }) => Some((p, p - normal * normal.dot(p - point))),
+        (Shape::Line { origin: o1, dir: d1, range: r1 }, Shape::Line { origin: o2, dir: d2, range: r2 }) => {
+            Some(closest_between_lines((o1, d1, r1), (o2, d2, r2)))
+        }
+        (Shape::Line { origin, dir, range }, Shape::Plane { point, normal }) => {
+            if dir.normalize().dot(normal).abs() <= PARALLEL_SIN {
+                let p = origin + dir * 0f32.clamp(range.0, range.1);
+                return closest_points(Shape::Point(p), b);
+            }
+            // An infinite line at an angle always crosses the plane; a
+            // segment only when its ends lie on opposite sides, otherwise
+            // its nearer end is the closest point
+            if range.0.is_infinite() || range.1.is_infinite() {
+                return None;
+            }
+            let height = |s: f32| normal.dot(origin + dir * s - point);
+            let (h0, h1) = (height(range.0), height(range.1));
+            if h0 * h1 <= 0.0 {
+                return None;
+            }
+            let s = if h0.abs() <= h1.abs() { range.0 } else { range.1 };
+            closest_points(Shape::Point(origin + dir * s), b)
+        }
+        (Shape::Plane { point, normal }, Shape::Plane { normal: other, .. }) => {
+            if normal.cross(other).length() > PARALLEL_SIN {
+                return None;
+            }
+            closest_points(Shape::Point(point), b)
+        }
+        (Shape::Line { .. } | Shape::Plane { .. }, Shape::Point(_)) | (Shape::Plane { .. }, Shape::Line { .. }) => {
+            closest_points(b, a).map(|(p, q)| (q, p))
+        }
+    }
+}
+
+/// Closest points of two (possibly bounded) lines, after Ericson's
+/// segment–segment algorithm. Directions must be non-zero.
+fn closest_between_lines(
+    (o1, d1, r1): (Vec3, Vec3, (f32, f32)),
+    (o2, d2, r2): (Vec3, Vec3, (f32, f32)),
+) -> (Vec3, Vec3) {
+    let r = o1 - o2;
+    let (a, b, e) = (d1.dot(d1), d1.dot(d2), d2.dot(d2));
+    let (c, f) = (d1.dot(r), d2.dot(r));
+    let denom = a * e - b * b;
+
+    // Parallel lines: any point of the first works, start from its origin
+    let mut s = if denom > 1e-9 * a * e { (b * f - c * e) / denom } else { 0.0 };
+    s = s.clamp(r1.0, r1.1);
+    let mut t = (b * s + f) / e;
+    if t < r2.0 || t > r2.1 {
+        t = t.clamp(r2.0, r2.1);
+        s = ((b * t - c) / a).clamp(r1.0, r1.1);
+    }
+    (o1 + d1 * s, o2 + d2 * t)
+}
+
+/// Angle between two directed entities, placed where they meet
+fn angle(first: &MeasureEntity, second: &MeasureEntity) -> Option<Measurement> {
+    let (da, a_is_normal) = first.direction()?;
+    let (db, b_is_normal) = second.direction()?;
+    let cos = da.dot(db).clamp(-1.0, 1.0);
+    if da.cross(db).length() <= PARALLEL_SIN && a_is_normal == b_is_normal {
+        return None;
+    }
+
+    let degrees = match (a_is_normal, b_is_normal) {
+        // Between face normals, so opposite faces of a wedge read > 90°
+        (true, true) => cos.acos().to_degrees(),
+        (false, false) => cos.abs().acos().to_degrees(),
+        // Line against plane: complement of the angle to the normal
+        _ => 90.0 - cos.abs().acos().to_degrees(),
+    };
+    if degrees.abs() < 0.01 {
+        return None;
+    }
+    Some(Measurement::Angle { degrees, at: meeting_point(first, second) })
+}
+
+/// Where two non-parallel entities meet (the middle of their closest points
+/// for skew lines), falling back to the middle of their anchors
+fn meeting_point(first: &MeasureEntity, second: &MeasureEntity) -> Vec3 {
+    let middle = (first.anchor() + second.anchor()) * 0.5;
+    let unbounded = |shape: Shape| match shape {
+        Shape::Line { origin, dir, .. } => Shape::Line { origin, dir, range: (f32::NEG_INFINITY, f32::INFINITY) },
+        other => other,
+    };
+    match (unbounded(first.shape()), unbounded(second.shape())) {
+        (Shape::Line { origin: o1, dir: d1, range: r1 }, Shape::Line { origin: o2, dir: d2, range: r2 }) => {
+            let (p, q) = closest_between_lines((o1, d1, r1), (o2, d2, r2));
+            (p + q) * 0.5
+        }
+        (Shape::Line { origin, dir, .. }, Shape::Plane { point, normal })
+        | (Shape::Plane { point, normal }, Shape::Line { origin, dir, .. }) => {
+            let along = normal.dot(dir);
+            if along.abs() < 1e-9 {
+                return middle;
+            }
+            origin + dir * (normal.dot(point - origin) / along)
+        }
+        (Shape::Plane { point: p1, normal: n1 }, Shape::Plane { point: p2, normal: n2 }) => {
+            // Point on both planes, moved along their intersection towards `middle`
+            let u = n1.cross(n2);
+            let (h1, h2) = (n1.dot(p1), n2.dot(p2));
+            let on_line = (n2.cross(u) * h1 + u.cross(n1) * h2) / u.length_squared();
+            on_line + u * (u.dot(middle - on_line) / u.length_squared())
+        }
+        _ => middle,
+    }
+}
+
+/// Classify the face containing triangle `seed` as a plane or a cylinder.
+/// Curved faces that are not cylinders (spheres, tori) give `None`.
+pub fn face_entity(mesh: &MeshData, seed: usize) -> Option<MeasureEntity> {
+    let facets = Facets::new(mesh);
+    let normal0 = facets.normals.get(seed).copied().flatten()?;
+    let group0 = facets.group[seed];
+    let plane = || MeasureEntity::Plane { point: facets.centroid(group0), normal: normal0 };
+
+    // Curvature axis from the first smooth neighbour
+    let smooth_cos = SMOOTH_ANGLE_DEG.to_radians().cos();
+    let neighbours = facets.group_neighbours(group0);
+    let Some(axis) = neighbours
+        .iter()
+        .filter_map(|&g| facets.group_normal(g))
+        .filter(|n| n.dot(normal0) >= smooth_cos)
+        .map(|n| normal0.cross(n).normalize())
+        .next()
+    else {
+        return Some(plane());
+    };
+
+    // Facet strips of a tessellated cylinder are about equally wide; a planar
+    // face next to a tangent fillet is much wider than the fillet's strips
+    let width0 = facets.width(group0, axis);
+    let is_strip = |g: usize| {
+        facets.group_normal(g).is_some_and(|n| (n.dot(axis) as f64).abs() < AXIS_TOLERANCE)
+            && facets.width(g, axis) <= 2.0 * width0
+    };
+    let narrowest = neighbours
+        .iter()
+        .filter(|&&g| facets.group_normal(g).is_some_and(|n| n.dot(normal0) >= smooth_cos))
+        .map(|&g| facets.width(g, axis))
+        .fold(f32::INFINITY, f32::min);
+    if width0 > 2.0 * narrowest {
+        return Some(plane());
+    }
+
+    // Grow along the curvature through similar strips
+    let mut accepted = vec![group0];
+    let mut stack = vec![group0];
+    while let Some(group) = stack.pop() {
+        let normal = facets.group_normal(group)?;
+        for next in facets.group_neighbours(group) {
+            if accepted.contains(&next)
+                || !facets.group_normal(next).is_some_and(|n| n.dot(normal) >= smooth_cos)
+                || !is_strip(next)
+            {
+                continue;
+            }
+            accepted.push(next);
+            stack.push(next);
+        }
+    }
+
+    fit_cylinder(&facets, &accepted)
+}
+
+/// Least-squares cylinder through the vertices of the given facet groups
+fn fit_cylinder(facets: &Facets, groups: &[usize]) -> Option<MeasureEntity> {
+    let triangles: Vec<usize> = (0..facets.group.len()).filter(|t| groups.contains(&facets.group[*t])).collect();
+
+    // Axis: the direction most orthogonal to every facet normal
+    let mut scatter = DMat3::ZERO;
+    for &t in &triangles {
+        let n = facets.normals[t]?.as_dvec3();
+        scatter += DMat3::from_cols(n * n.x, n * n.y, n * n.z) * facets.area(t) as f64;
+    }
+    let (_, axes) = principal_axes(scatter);
+    let (axis, u, v) = (axes.x_axis, axes.y_axis, axes.z_axis);
+
+    // Kåsa fit of x² + y² + D·x + E·y + F = 0 in the cross-section
+    let points: Vec<DVec3> = triangles.iter().flat_map(|&t| facets.triangles[t]).map(|p| p.as_dvec3()).collect();
+    let mut normal_matrix = DMat3::ZERO;
+    let mut rhs = DVec3::ZERO;
+    for p in &points {
+        let (x, y) = (p.dot(u), p.dot(v));
+        let row = DVec3::new(x, y, 1.0);
+        normal_matrix += DMat3::from_cols(row * x, row * y, row);
+        rhs -= row * (x * x + y * y);
+    }
+    if normal_matrix.determinant().abs() < 1e-12 {
+        return None;
+    }
+    let [d, e, f] = (normal_matrix.inverse() * rhs).to_array();
+    let (cx, cy) = (-d / 2.0, -e / 2.0);
+    let radius = (cx * cx + cy * cy - f).sqrt();
+    if !radius.is_finite() || radius <= 0.0 {
+        return None;
+    }
+    let off_circle = points
+        .iter()
+        .map(|p| ((p.dot(u) - cx).hypot(p.dot(v) - cy) - radius).abs())
+        .fold(0.0, f64::max);
+    if off_circle > CIRCLE_TOLERANCE * radius {
+        return None;
+    }
+
+    let (lo, hi) = points
+        .iter()
+        .map(|p| p.dot(axis))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), h| (lo.min(h), hi.max(h)));
+    let center = u * cx + v * cy + axis * ((lo + hi) / 2.0);
+    Some(MeasureEntity::Cylinder { center: center.as_vec3(), axis: axis.as_vec3(), radius: radius as f32 })
+}
+
+/// Mesh triangles split into connected coplanar groups
+struct Facets {
+    triangles: Vec<[Vec3; 3]>,
+    /// Unit normal, `None` for degenerate triangles
+    normals: Vec<Option<Vec3>>,
+    /// Triangles sharing each edge (by quantised endpoints)
+    adjacency: HashMap<([i64; 3], [i64; 3]), Vec<usize>>,
+    /// Coplanar group of each triangle
+    group: Vec<usize>,
+}
+
+impl Facets {
+    fn new(mesh: &MeshData) -> Self {
+        let position = |i: u32| {
+            let v = &mesh.vertices[i as usize * 9..i as usize * 9 + 3];
+            Vec3::new(v[0], v[1], v[2])
+        };
+        let triangles: Vec<[Vec3; 3]> =
+            mesh.indices.chunks_exact(3).map(|t| [position(t[0]), position(t[1]), position(t[2])]).collect();
+        let normals = triangles.iter().map(|[a, b, c]| (*b - *a).cross(*c - *a).try_normalize()).collect();
+
+        let mut adjacency: HashMap<_, Vec<usize>> = HashMap::new();
+        for (i, tri) in triangles.iter().enumerate() {
+            for j in 0..3 {
+                adjacency.entry(edge_key(tri[j], tri[(j + 1) % 3])).or_default().push(i);
+            }
+        }
+
+        let mut facets = Self { group: vec![usize::MAX; triangles.len()], triangles, normals, adjacency };
+        let mut next_group = 0;
+        for seed in 0..facets.triangles.len() {
+            if facets.group[seed] != usize::MAX {
+                continue;
+            }
+            facets.group[seed] = next_group;
+            let mut stack = vec![seed];
+            while let Some(t) = stack.pop() {
+                for n in facets.neighbours(t) {
+                    let coplanar = match (facets.normals[t], facets.normals[n]) {
+                        (Some(a), Some(b)) => a.dot(b) >= COPLANAR_COS,
+                        _ => false,
+                    };
+                    if coplanar && facets.group[n] == usize::MAX {
+                        facets.group[n] = next_group;
+                        stack.push(n);
+                    }
+                }
+            }
+            next_group += 1;
+        }
+        facets
+    }
+
+    fn neighbours(&self, t: usize) -> Vec<usize> {
+        let tri = &self.triangles[t];
+        (0..3)
+            .flat_map(|j| self.adjacency[&edge_key(tri[j], tri[(j + 1) % 3])].iter().copied())
+            .filter(|&n| n != t)
+            .collect()
+    }
+
+    fn members(&self, group: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.group.len()).filter(move |&t| self.group[t] == group)
+    }
+
+    fn group_normal(&self, group: usize) -> Option<Vec3> {
+        self.members(group).find_map(|t| self.normals[t])
+    }
+
+    fn group_neighbours(&self, group: usize) -> Vec<usize> {
+        let mut out: Vec<usize> =
+            self.members(group).flat_map(|t| self.neighbours(t)).map(|n| self.group[n]).filter(|&g| g != group).collect();
+        out.sort_unstable();
+        out.dedup();
+        out
+    }
+
+    fn area(&self, t: usize) -> f32 {
+        let [a, b, c] = self.triangles[t];
+        (b - a).cross(c - a).length() / 2.0
+    }
+
+    /// Area-weighted centroid of a group
+    fn centroid(&self, group: usize) -> Vec3 {
+        let (sum, area) = self.members(group).fold((Vec3::ZERO, 0.0), |(sum, area), t| {
+            let [a, b, c] = self.triangles[t];
+            let weight = self.area(t);
+            (sum + (a + b + c) / 3.0 * weight, area + weight)
+        });
+        if area > 0.0 { sum / area } else { self.triangles[self.members(group).next().unwrap_or(0)][0] }
+    }
+
+    /// Extent of a group across `axis`, within its own plane
+    fn width(&self, group: usize, axis: Vec3) -> f32 {
+        let Some(normal) = self.group_normal(group) else {
+            return 0.0;
+        };
+        let across = axis.cross(normal);
+        let (lo, hi) = self
+            .members(group)
+            .flat_map(|t| self.triangles[t])
+            .map(|p| p.dot(across))
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), d| (lo.min(d), hi.max(d)));
+        hi - lo
+    }
+}
+
+fn edge_key(a: Vec3, b: Vec3) -> ([i64; 3], [i64; 3]) {
+    let quantize = |p: Vec3| (p * QUANT_SCALE).round().as_i64vec3().to_array();
+    let (qa, qb) = (quantize(a), quantize(b));
+    if qa <= qb { (qa, qb) } else { (qb, qa) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::prism;
+
+    #[test]
+    fn test_cylinder_face_radius_and_axis() {
+        let mesh = prism(32, 5.0, 10.0);
+        let Some(MeasureEntity::Cylinder { center, axis, radius }) = face_entity(&mesh, 8) else {
+            panic!("side face should be cylindrical");
+        };
+        assert!((radius - 5.0).abs() < 1e-3, "radius = {}", radius);
+        assert!(axis.z.abs() > 0.9999);
+        assert!(center.distance(Vec3::new(0.0, 0.0, 5.0)) < 1e-3);
+    }
+
+    #[test]
+    fn test_cap_and_box_faces_are_planes() {
+        let mesh = prism(32, 5.0, 10.0);
+        let Some(MeasureEntity::Plane { normal, point }) = face_entity(&mesh, 3) else {
+            panic!("cap should be planar");
+        };
+        assert!(normal.z > 0.9999);
+        assert!((point.z - 10.0).abs() < 1e-4);
+
+        // Faces of a box meet at 90°, so a side is its own plane
+        let cube = prism(4, 1.0, 1.0);
+        assert!(matches!(face_entity(&cube, 0), Some(MeasureEntity::Plane { .. })));
+    }
+
+    #[test]
+    fn test_parallel_and_perpendicular_faces() {
+        let bottom = MeasureEntity::Plane { point: Vec3::new(1.0, 1.0, 0.0), normal: Vec3::NEG_Z };
+        let top = MeasureEntity::Plane { point: Vec3::new(3.0, 2.0, 7.5), normal: Vec3::Z };
+        let side = MeasureEntity::Plane { point: Vec3::new(0.0, 2.0, 4.0), normal: Vec3::NEG_X };
+
+        let parallel = measure(&bottom, Some(&top));
+        assert_eq!(parallel.len(), 1);
+        let Measurement::Distance { value, .. } = parallel[0] else { panic!("expected distance") };
+        assert!((value - 7.5).abs() < 1e-5);
+
+        let crossing = measure(&top, Some(&side));
+        let [Measurement::Angle { degrees, at }] = crossing[..] else { panic!("expected one angle") };
+        assert!((degrees - 90.0).abs() < 1e-4);
+        // On the shared edge x = 0, z = 7.5
+        assert!(at.x.abs() < 1e-4 && (at.z - 7.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_edges_points_and_cylinders() {
+        let a = MeasureEntity::Edge { start: Vec3::ZERO, end: Vec3::new(4.0, 0.0, 0.0) };
+        let b = MeasureEntity::Edge { start: Vec3::new(1.0, -1.0, 3.0), end: Vec3::new(1.0, 1.0, 3.0) };
+        assert_eq!(measure(&a, None), [Measurement::Distance { value: 4.0, from: Vec3::ZERO, to: Vec3::new(4.0, 0.0, 0.0) }]);
+
+        // Skew edges: 3 apart, at right angles
+        let skew = measure(&a, Some(&b));
+        assert!(matches!(skew[0], Measurement::Distance { value, .. } if (value - 3.0).abs() < 1e-5));
+        assert!(matches!(skew[1], Measurement::Angle { degrees, .. } if (degrees - 90.0).abs() < 1e-4));
+
+        let point = MeasureEntity::Point(Vec3::new(6.0, 0.0, 0.0));
+        assert!(matches!(measure(&a, Some(&point))[0], Measurement::Distance { value, .. } if (value - 2.0).abs() < 1e-5));
+
+        // Point to a cylinder measures to its axis and reports the radius
+        let cylinder = MeasureEntity::Cylinder { center: Vec3::ZERO, axis: Vec3::Z, radius: 2.0 };
+        let result = measure(&MeasureEntity::Point(Vec3::new(0.0, 5.0, 9.0)), Some(&cylinder));
+        assert!(matches!(result[0], Measurement::Distance { value, to, .. } if (value - 5.0).abs() < 1e-5 && to.z == 9.0));
+        assert!(matches!(result[1], Measurement::Radius { value, .. } if value == 2.0));
+    }
+
+    #[test]
+    fn test_edge_and_axis_at_an_angle_to_a_plane() {
+        let floor = MeasureEntity::Plane { point: Vec3::new(5.0, 5.0, 0.0), normal: Vec3::Z };
+
+        // Slanted edge above the plane: distance from its lower end
+        let edge = MeasureEntity::Edge { start: Vec3::new(2.0, 0.0, 3.0), end: Vec3::new(0.0, 0.0, 1.0) };
+        let result = measure(&edge, Some(&floor));
+        let Measurement::Distance { value, from, to } = result[0] else { panic!("expected distance") };
+        assert!((value - 1.0).abs() < 1e-5);
+        assert_eq!((from, to), (Vec3::new(0.0, 0.0, 1.0), Vec3::ZERO));
+        assert!(matches!(result[1], Measurement::Angle { degrees, .. } if (degrees - 45.0).abs() < 1e-3));
+
+        // An edge through the plane and a cylinder axis at an angle only have an angle
+        let crossing = MeasureEntity::Edge { start: Vec3::new(0.0, 0.0, -1.0), end: Vec3::new(0.0, 1.0, 1.0) };
+        assert!(matches!(measure(&crossing, Some(&floor))[..], [Measurement::Angle { .. }]));
+        let cylinder = MeasureEntity::Cylinder { center: Vec3::new(0.0, 0.0, 5.0), axis: Vec3::Z, radius: 1.0 };
+        assert!(!measure(&floor, Some(&cylinder)).iter().any(|m| matches!(m, Measurement::Distance { .. })));
+    }
+}
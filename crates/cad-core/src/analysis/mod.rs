@@ -0,0 +1,14 @@
+//! Analyses of built meshes (mass properties, measurements, interference,
+//! sections, printability).
+
+pub mod interference;
+pub mod mass;
+pub mod measure;
+pub mod printability;
+pub mod section;
+
+pub use interference::{mesh_bounds, scene_interference, Interference};
+pub use mass::{scene_mass_properties, MassMoments};
+pub use measure::{face_entity, measure, MeasureEntity, Measurement};
+pub use printability::{heatmap_mesh, scene_printability, FaceChecks, Heatmap, Printability};
+pub use section::{cap_mesh, section_loops, section_sketch, SectionPlane};
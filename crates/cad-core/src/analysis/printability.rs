@@ -0,0 +1,412 @@
+//! Printability checks for additive manufacturing.
+//!
+//! Every triangle is checked on its own. Its normal is the mean of the vertex
+//! normals of `MeshData` (the geometric normal for degenerate ones):
+//! - the overhang angle is measured from the vertical of the build direction;
+//!   faces steeper than the allowed angle need support, except those lying on
+//!   the build plate;
+//! - the wall thickness is the distance from the centroid to the first
+//!   surface hit by a ray cast inwards along the normal, through a bounding
+//!   volume hierarchy of the mesh. Walls thinner than `min_feature` are
+//!   details below the printer resolution, thinner than `min_wall` are weak.
+
+use std::collections::HashMap;
+
+use glam::Vec3;
+use shared::{BodyPrintability, PrintSettings, PrintabilityReport, SceneDescriptionV2};
+
+use crate::mesh::MeshData;
+
+/// Faces within this height (mm) of the lowest vertex lie on the build plate
+const PLATE_TOLERANCE: f32 = 1e-3;
+/// Hits closer than this (mm) to the ray origin are the face itself
+const RAY_EPSILON: f32 = 1e-4;
+/// Triangles per BVH leaf
+const LEAF_SIZE: usize = 4;
+
+/// Colour of faces without issues in a heatmap
+const FINE_COLOR: [f32; 3] = [0.72, 0.74, 0.76];
+/// Heatmap colours at the threshold and at the worst value
+const WARN_COLOR: [f32; 3] = [1.0, 0.85, 0.1];
+const BAD_COLOR: [f32; 3] = [0.9, 0.1, 0.1];
+
+/// Per-triangle results for one mesh, in index buffer order
+#[derive(Debug, Clone, Default)]
+pub struct FaceChecks {
+    /// Overhang angle from the vertical, degrees; 0 for faces looking up or
+    /// sideways and for faces on the build plate
+    pub overhang: Vec<f32>,
+    /// Wall thickness, mm; `None` when the ray leaves the mesh (open surface)
+    pub thickness: Vec<Option<f32>>,
+}
+
+/// Printability report plus the per-face results of every body by id
+#[derive(Debug, Clone)]
+pub struct Printability {
+    pub report: PrintabilityReport,
+    pub faces: HashMap<String, FaceChecks>,
+}
+
+/// Quantity shown by `heatmap_mesh`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Heatmap {
+    #[default]
+    Overhang,
+    Thickness,
+}
+
+/// Unit build direction of `settings` (+Z when zero)
+pub fn build_direction(settings: &PrintSettings) -> Vec3 {
+    let [x, y, z] = settings.build_direction;
+    Vec3::new(x as f32, y as f32, z as f32).normalize_or(Vec3::Z)
+}
+
+/// Overhang angle and wall thickness of every triangle of `mesh`
+pub fn face_checks(mesh: &MeshData, settings: &PrintSettings) -> FaceChecks {
+    let up = build_direction(settings);
+    let position = |i: u32| Vec3::from_slice(&mesh.vertices[i as usize * 9..i as usize * 9 + 3]);
+    let normal = |i: u32| Vec3::from_slice(&mesh.vertices[i as usize * 9 + 3..i as usize * 9 + 6]);
+
+    let triangles: Vec<[Vec3; 3]> =
+        mesh.indices.chunks_exact(3).map(|t| [position(t[0]), position(t[1]), position(t[2])]).collect();
+    let normals: Vec<Vec3> = mesh
+        .indices
+        .chunks_exact(3)
+        .zip(&triangles)
+        .map(|(t, [a, b, c])| {
+            let mean = normal(t[0]) + normal(t[1]) + normal(t[2]);
+            mean.try_normalize().unwrap_or_else(|| (*b - *a).cross(*c - *a).normalize_or_zero())
+        })
+        .collect();
+
+    let plate = triangles.iter().flatten().map(|p| p.dot(up)).fold(f32::INFINITY, f32::min);
+    let overhang = triangles
+        .iter()
+        .zip(&normals)
+        .map(|(triangle, n)| {
+            let on_plate = triangle.iter().all(|p| p.dot(up) - plate <= PLATE_TOLERANCE);
+            let down = -n.dot(up);
+            if on_plate || down <= 0.0 {
+                0.0
+            } else {
+                down.min(1.0).asin().to_degrees()
+            }
+        })
+        .collect();
+
+    let bvh = Bvh::new(&triangles);
+    let thickness = triangles
+        .iter()
+        .zip(&normals)
+        .enumerate()
+        .map(|(i, ([a, b, c], n))| {
+            if *n == Vec3::ZERO {
+                return None;
+            }
+            bvh.cast((*a + *b + *c) / 3.0, -*n, i)
+        })
+        .collect();
+
+    FaceChecks { overhang, thickness }
+}
+
+/// Summary of the face checks of one body
+pub fn body_printability(
+    id: &str,
+    name: &str,
+    mesh: &MeshData,
+    checks: &FaceChecks,
+    settings: &PrintSettings,
+) -> BodyPrintability {
+    let mut body = BodyPrintability {
+        id: id.to_string(),
+        name: name.to_string(),
+        triangles: mesh.indices.len() / 3,
+        ..Default::default()
+    };
+    let position = |i: u32| Vec3::from_slice(&mesh.vertices[i as usize * 9..i as usize * 9 + 3]);
+
+    for (i, t) in mesh.indices.chunks_exact(3).enumerate() {
+        let [a, b, c] = [position(t[0]), position(t[1]), position(t[2])];
+        let area = (b - a).cross(c - a).length() as f64 * 0.5;
+        body.surface_area += area;
+
+        let overhang = checks.overhang[i] as f64;
+        body.max_overhang_angle = body.max_overhang_angle.max(overhang);
+        if overhang > settings.overhang_angle {
+            body.overhang_faces += 1;
+            body.overhang_area += area;
+        }
+
+        let Some(thickness) = checks.thickness[i].map(f64::from) else {
+            continue;
+        };
+        body.min_thickness = Some(body.min_thickness.map_or(thickness, |min| min.min(thickness)));
+        if thickness < settings.min_feature {
+            body.small_feature_faces += 1;
+            body.small_feature_area += area;
+        } else if thickness < settings.min_wall {
+            body.thin_wall_faces += 1;
+            body.thin_wall_area += area;
+        }
+    }
+    body
+}
+
+/// Printability of every built body (meshes keyed by body id), in scene
+/// order; meshes without a scene body are listed after them by id.
+pub fn scene_printability(
+    scene: &SceneDescriptionV2,
+    meshes: &HashMap<String, MeshData>,
+    settings: &PrintSettings,
+) -> Printability {
+    let mut keys: Vec<&String> = scene.bodies.iter().map(|b| &b.id).filter(|id| meshes.contains_key(*id)).collect();
+    let mut orphans: Vec<&String> = meshes.keys().filter(|id| scene.bodies.iter().all(|b| &b.id != *id)).collect();
+    orphans.sort();
+    keys.extend(orphans);
+
+    let mut faces = HashMap::new();
+    let bodies = keys
+        .into_iter()
+        .map(|id| {
+            let name = scene.bodies.iter().find(|b| &b.id == id).map_or(id.as_str(), |b| b.name.as_str());
+            let checks = face_checks(&meshes[id], settings);
+            let body = body_printability(id, name, &meshes[id], &checks, settings);
+            faces.insert(id.clone(), checks);
+            body
+        })
+        .collect();
+
+    Printability {
+        report: PrintabilityReport { settings: settings.clone(), bodies, errors: HashMap::new() },
+        faces,
+    }
+}
+
+/// Copy of `mesh` coloured per triangle by `heatmap`: faces within the limits
+/// are neutral grey, the others go from yellow at the limit to red at the
+/// worst value (facing straight down, or thinner than `min_feature`).
+/// Triangles get their own vertices so colours do not blend across edges.
+pub fn heatmap_mesh(mesh: &MeshData, checks: &FaceChecks, heatmap: Heatmap, settings: &PrintSettings) -> MeshData {
+    let severity = |i: usize| -> Option<f32> {
+        match heatmap {
+            Heatmap::Overhang => {
+                let (angle, limit) = (checks.overhang[i], settings.overhang_angle as f32);
+                (angle > limit).then(|| (angle - limit) / (90.0 - limit).max(f32::EPSILON))
+            }
+            Heatmap::Thickness => {
+                let (wall, feature) = (settings.min_wall as f32, settings.min_feature as f32);
+                let thickness = checks.thickness[i].filter(|t| *t < wall)?;
+                Some((wall - thickness) / (wall - feature).max(f32::EPSILON))
+            }
+        }
+    };
+
+    let mut vertices = Vec::with_capacity(mesh.indices.len() * 9);
+    for (i, t) in mesh.indices.chunks_exact(3).enumerate() {
+        let color = severity(i).map_or(FINE_COLOR, |x| {
+            let x = x.clamp(0.0, 1.0);
+            std::array::from_fn(|k| WARN_COLOR[k] * (1.0 - x) + BAD_COLOR[k] * x)
+        });
+        for &index in t {
+            let base = index as usize * 9;
+            vertices.extend_from_slice(&mesh.vertices[base..base + 6]);
+            vertices.extend_from_slice(&color);
+        }
+    }
+    let indices = (0..mesh.indices.len() as u32 / 3 * 3).collect();
+    MeshData { vertices, indices }
+}
+
+/// Node of `Bvh`: a leaf holds `count` triangles from `first` in `order`,
+/// an inner node (count 0) has its children at `first` and `first + 1`
+#[derive(Debug, Clone, Copy, Default)]
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    first: usize,
+    count: usize,
+}
+
+/// Bounding volume hierarchy over triangles, split at the centroid median
+struct Bvh<'a> {
+    triangles: &'a [[Vec3; 3]],
+    nodes: Vec<BvhNode>,
+    order: Vec<usize>,
+}
+
+impl<'a> Bvh<'a> {
+    fn new(triangles: &'a [[Vec3; 3]]) -> Self {
+        let mut bvh = Self { triangles, nodes: Vec::new(), order: (0..triangles.len()).collect() };
+        if !triangles.is_empty() {
+            let centroids: Vec<Vec3> = triangles.iter().map(|[a, b, c]| (*a + *b + *c) / 3.0).collect();
+            bvh.nodes.push(BvhNode::default());
+            bvh.build(0, 0, triangles.len(), &centroids);
+        }
+        bvh
+    }
+
+    fn build(&mut self, node: usize, start: usize, end: usize, centroids: &[Vec3]) {
+        let (min, max) = self.order[start..end]
+            .iter()
+            .flat_map(|&t| self.triangles[t])
+            .fold((Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)), |(min, max), p| (min.min(p), max.max(p)));
+        if end - start <= LEAF_SIZE {
+            self.nodes[node] = BvhNode { min, max, first: start, count: end - start };
+            return;
+        }
+
+        let size = max - min;
+        let axis = if size.x >= size.y && size.x >= size.z {
+            0
+        } else if size.y >= size.z {
+            1
+        } else {
+            2
+        };
+        let mid = (start + end) / 2;
+        self.order[start..end]
+            .select_nth_unstable_by(mid - start, |&a, &b| centroids[a][axis].total_cmp(&centroids[b][axis]));
+
+        let left = self.nodes.len();
+        self.nodes.extend([BvhNode::default(); 2]);
+        self.nodes[node] = BvhNode { min, max, first: left, count: 0 };
+        self.build(left, start, mid, centroids);
+        self.build(left + 1, mid, end, centroids);
+    }
+
+    /// Distance to the nearest triangle other than `skip` hit by the ray
+    fn cast(&self, origin: Vec3, dir: Vec3, skip: usize) -> Option<f32> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let inv = dir.recip();
+        let mut best = f32::INFINITY;
+        let mut stack = vec![0];
+        while let Some(i) = stack.pop() {
+            let node = self.nodes[i];
+            // Padded so rays running along a node face do not turn into NaN
+            let (t1, t2) = ((node.min - RAY_EPSILON - origin) * inv, (node.max + RAY_EPSILON - origin) * inv);
+            let (near, far) = (t1.min(t2).max_element(), t1.max(t2).min_element());
+            if near > far || far < 0.0 || near > best {
+                continue;
+            }
+            if node.count == 0 {
+                stack.extend([node.first, node.first + 1]);
+                continue;
+            }
+            for &t in &self.order[node.first..node.first + node.count] {
+                if t == skip {
+                    continue;
+                }
+                if let Some(d) = ray_triangle(origin, dir, &self.triangles[t]) {
+                    if d > RAY_EPSILON && d < best {
+                        best = d;
+                    }
+                }
+            }
+        }
+        best.is_finite().then_some(best)
+    }
+}
+
+/// Möller–Trumbore intersection, both sides; distance along `dir`
+fn ray_triangle(origin: Vec3, dir: Vec3, [a, b, c]: &[Vec3; 3]) -> Option<f32> {
+    let (e1, e2) = (*b - *a, *c - *a);
+    let p = dir.cross(e2);
+    let det = e1.dot(p);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let s = origin - *a;
+    let u = s.dot(p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(e1);
+    let v = dir.dot(q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    Some(e2.dot(q) / det)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::box_mesh;
+
+    #[test]
+    fn test_box_on_plate_has_no_overhang() {
+        let mesh = box_mesh([0.0; 3], [10.0; 3]);
+        let settings = PrintSettings::default();
+        let checks = face_checks(&mesh, &settings);
+        assert!(checks.overhang.iter().all(|a| *a == 0.0));
+        assert!(checks.thickness.iter().all(|t| t.is_some_and(|t| (t - 10.0).abs() < 1e-4)));
+
+        let body = body_printability("body_1", "Box", &mesh, &checks, &settings);
+        assert!(body.is_clean());
+        assert_eq!(body.triangles, 12);
+        assert!((body.surface_area - 600.0).abs() < 1e-3);
+        assert!((body.min_thickness.unwrap() - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_overhang_depends_on_build_direction() {
+        // Raised box: its bottom faces straight down, off the plate
+        let mut mesh = box_mesh([0.0; 3], [1.0; 3]);
+        let floor = box_mesh([5.0, 5.0, -5.0], [6.0, 6.0, -4.0]);
+        let offset = mesh.vertex_count() as u32;
+        mesh.vertices.extend(floor.vertices);
+        mesh.indices.extend(floor.indices.iter().map(|i| i + offset));
+
+        let settings = PrintSettings::default();
+        let body = body_printability("b", "B", &mesh, &face_checks(&mesh, &settings), &settings);
+        assert_eq!(body.overhang_faces, 2);
+        assert!((body.overhang_area - 1.0).abs() < 1e-6);
+        assert!((body.max_overhang_angle - 90.0).abs() < 1e-3);
+
+        // Printed along X, the -X faces of both boxes are on or above the plate
+        let sideways = PrintSettings { build_direction: [2.0, 0.0, 0.0], ..Default::default() };
+        let checks = face_checks(&mesh, &sideways);
+        assert_eq!(checks.overhang[0..2], [0.0, 0.0]);
+        assert!(checks.overhang[12..14].iter().all(|a| (a - 90.0).abs() < 1e-3));
+        let body = body_printability("b", "B", &mesh, &checks, &sideways);
+        assert_eq!(body.overhang_faces, 2);
+    }
+
+    #[test]
+    fn test_thin_walls_and_small_features() {
+        let settings = PrintSettings { min_wall: 1.0, min_feature: 0.4, ..Default::default() };
+
+        let plate = box_mesh([0.0; 3], [20.0, 20.0, 0.6]);
+        let body = body_printability("p", "Plate", &plate, &face_checks(&plate, &settings), &settings);
+        assert_eq!(body.thin_wall_faces, 4);
+        assert!((body.thin_wall_area - 800.0).abs() < 1e-3);
+        assert_eq!(body.small_feature_faces, 0);
+        assert!((body.min_thickness.unwrap() - 0.6).abs() < 1e-5);
+
+        let pin = box_mesh([0.0; 3], [0.2, 0.2, 5.0]);
+        let checks = face_checks(&pin, &settings);
+        let body = body_printability("n", "Pin", &pin, &checks, &settings);
+        assert_eq!(body.small_feature_faces, 8);
+        assert_eq!(body.thin_wall_faces, 0);
+
+        let heat = heatmap_mesh(&pin, &checks, Heatmap::Thickness, &settings);
+        assert_eq!(heat.vertex_count(), pin.indices.len());
+        assert_eq!(heat.indices.len(), pin.indices.len());
+        let colors: Vec<[f32; 3]> = heat.vertices.chunks_exact(9).map(|v| [v[6], v[7], v[8]]).collect();
+        assert_eq!(colors.iter().filter(|c| **c == BAD_COLOR).count(), 24);
+        assert_eq!(colors.iter().filter(|c| **c == FINE_COLOR).count(), 12);
+    }
+
+    #[test]
+    fn test_open_surface_has_no_thickness() {
+        let mut mesh = box_mesh([0.0; 3], [1.0; 3]);
+        // Drop the top face: rays from the bottom leave through the hole
+        mesh.indices.drain(30..36);
+        let checks = face_checks(&mesh, &PrintSettings::default());
+        assert_eq!(checks.thickness[8..10], [None, None]);
+        assert!(checks.thickness[0..8].iter().all(Option::is_some));
+    }
+}
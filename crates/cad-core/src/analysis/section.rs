@@ -0,0 +1,404 @@
+//! Planar cross-sections of closed triangle meshes.
+//!
+//! Every triangle straddling the plane contributes one segment; segments are
+//! chained into closed loops through their edge crossings. A crossing is
+//! computed from the edge endpoints in a fixed order, so both triangles of an
+//! edge produce the same point. Segments run along `normal × face normal`,
+//! which makes outer boundaries counter-clockwise and holes clockwise seen
+//! from the side the plane cuts away. Caps are triangulated by ear clipping
+//! after bridging each hole into its outer loop.
+
+use std::collections::HashMap;
+
+use glam::{IVec3, Vec2, Vec3};
+use shared::{Point2D, Sketch, SketchElement, SketchPlane};
+
+use crate::mesh::MeshData;
+
+/// Grid (mm) on which crossing points are matched when chaining segments
+const WELD_TOLERANCE: f32 = 1e-4;
+/// Sine of the angle below which consecutive loop segments are merged
+const COLLINEAR_SINE: f32 = 1e-4;
+
+/// Cutting plane: material on the `normal` side is cut away
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectionPlane {
+    pub origin: Vec3,
+    /// Unit normal pointing into the removed half-space
+    pub normal: Vec3,
+}
+
+impl SectionPlane {
+    pub fn new(origin: Vec3, normal: Vec3) -> Self {
+        Self { origin, normal: normal.normalize_or(Vec3::Z) }
+    }
+
+    /// Signed distance of `p`, positive on the removed side
+    pub fn distance(&self, p: Vec3) -> f32 {
+        self.normal.dot(p - self.origin)
+    }
+
+    /// In-plane axes (u, v) with u × v = normal
+    pub fn basis(&self) -> (Vec3, Vec3) {
+        let u = self.normal.any_orthonormal_vector();
+        (u, self.normal.cross(u))
+    }
+
+    /// Plane equation `[a, b, c, d]`: points with `a·x + b·y + c·z + d > 0` are cut away
+    pub fn equation(&self) -> [f32; 4] {
+        let n = self.normal;
+        [n.x, n.y, n.z, -n.dot(self.origin)]
+    }
+}
+
+/// Closed section loops of a mesh (first point not repeated). Open chains
+/// from non-manifold meshes are dropped.
+pub fn section_loops(mesh: &MeshData, plane: &SectionPlane) -> Vec<Vec<Vec3>> {
+    let position = |i: u32| Vec3::from_slice(&mesh.vertices[i as usize * 9..i as usize * 9 + 3]);
+
+    let mut segments: Vec<(Vec3, Vec3)> = Vec::new();
+    for triangle in mesh.indices.chunks_exact(3) {
+        let p = [position(triangle[0]), position(triangle[1]), position(triangle[2])];
+        let d = p.map(|q| plane.distance(q));
+        // Vertices on the plane count as cut away, consistently for every triangle
+        let cut = d.map(|x| x >= 0.0);
+        if cut.iter().all(|c| *c) || cut.iter().all(|c| !*c) {
+            continue;
+        }
+
+        let mut crossings = [Vec3::ZERO; 2];
+        let mut n = 0;
+        for k in 0..3 {
+            let (i, j) = (k, (k + 1) % 3);
+            if cut[i] != cut[j] && n < 2 {
+                crossings[n] = edge_crossing((p[i], d[i]), (p[j], d[j]));
+                n += 1;
+            }
+        }
+        let [a, b] = crossings;
+        if weld_key(a) == weld_key(b) {
+            continue;
+        }
+        let direction = plane.normal.cross((p[1] - p[0]).cross(p[2] - p[0]));
+        segments.push(if (b - a).dot(direction) >= 0.0 { (a, b) } else { (b, a) });
+    }
+
+    let mut by_start: HashMap<IVec3, Vec<usize>> = HashMap::new();
+    for (i, (start, _)) in segments.iter().enumerate() {
+        by_start.entry(weld_key(*start)).or_default().push(i);
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut loops = Vec::new();
+    for first in 0..segments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start_key = weld_key(segments[first].0);
+        let mut points = vec![segments[first].0];
+        let mut end = segments[first].1;
+        let closed = loop {
+            let key = weld_key(end);
+            if key == start_key {
+                break true;
+            }
+            points.push(end);
+            let next = by_start.get(&key).and_then(|candidates| candidates.iter().find(|&&i| !used[i]).copied());
+            let Some(next) = next else {
+                break false;
+            };
+            used[next] = true;
+            end = segments[next].1;
+        };
+
+        let points = simplify_loop(points);
+        if closed && points.len() >= 3 {
+            loops.push(points);
+        }
+    }
+    loops
+}
+
+/// Triangulated cap filling `loops` (as from [`section_loops`]), facing along
+/// the plane normal, in a single colour
+pub fn cap_mesh(loops: &[Vec<Vec3>], plane: &SectionPlane, color: [f32; 3]) -> MeshData {
+    let (u, v) = plane.basis();
+    let project = |p: Vec3| Vec2::new((p - plane.origin).dot(u), (p - plane.origin).dot(v));
+    let rings: Vec<Vec<Vec2>> = loops.iter().map(|l| l.iter().map(|p| project(*p)).collect()).collect();
+
+    let (outers, holes): (Vec<usize>, Vec<usize>) = (0..rings.len()).partition(|&i| signed_area(&rings[i]) > 0.0);
+
+    // Each hole belongs to the smallest outer loop around it
+    let mut holes_of: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &hole in &holes {
+        let owner = outers
+            .iter()
+            .filter(|&&outer| contains(&rings[outer], rings[hole][0]))
+            .min_by(|&&a, &&b| signed_area(&rings[a]).total_cmp(&signed_area(&rings[b])));
+        if let Some(&owner) = owner {
+            holes_of.entry(owner).or_default().push(hole);
+        }
+    }
+
+    let mut mesh = MeshData { vertices: Vec::new(), indices: Vec::new() };
+    let normal = plane.normal.to_array();
+    for &outer in &outers {
+        let hole_rings: Vec<&[Vec2]> =
+            holes_of.get(&outer).map_or_else(Vec::new, |h| h.iter().map(|&i| rings[i].as_slice()).collect());
+        let polygon = bridge_holes(&rings[outer], &hole_rings);
+        for triangle in ear_clip(&polygon) {
+            for p in triangle {
+                let world = plane.origin + u * p.x + v * p.y;
+                mesh.indices.push(mesh.vertex_count() as u32);
+                mesh.vertices.extend(world.to_array());
+                mesh.vertices.extend(normal);
+                mesh.vertices.extend(color);
+            }
+        }
+    }
+    mesh
+}
+
+/// Sketch of closed polylines tracing `loops` on a principal plane. Points
+/// are projected onto the plane, so the loops should lie in it.
+pub fn section_sketch(loops: &[Vec<Vec3>], plane: SketchPlane, offset: f64) -> Sketch {
+    let point = |p: &Vec3| {
+        let (x, y) = match plane {
+            SketchPlane::Xy => (p.x, p.y),
+            SketchPlane::Xz => (p.x, p.z),
+            SketchPlane::Yz => (p.y, p.z),
+        };
+        Point2D { x: x as f64, y: y as f64 }
+    };
+
+    let mut sketch = Sketch { plane: plane.clone(), offset, ..Default::default() };
+    for ring in loops {
+        let mut points: Vec<Point2D> = ring.iter().map(point).collect();
+        points.push(point(&ring[0]));
+        sketch.push_element(SketchElement::Polyline { id: None, points });
+    }
+    sketch
+}
+
+/// Point where the plane crosses an edge, given endpoint distances
+fn edge_crossing(a: (Vec3, f32), b: (Vec3, f32)) -> Vec3 {
+    let ((pa, da), (pb, db)) = if a.0.to_array() <= b.0.to_array() { (a, b) } else { (b, a) };
+    pa + (pb - pa) * (da / (da - db))
+}
+
+fn weld_key(p: Vec3) -> IVec3 {
+    (p / WELD_TOLERANCE).round().as_ivec3()
+}
+
+/// Drop repeated points and points between collinear segments
+fn simplify_loop(mut points: Vec<Vec3>) -> Vec<Vec3> {
+    let mut i = 0;
+    while points.len() >= 3 && i < points.len() {
+        let n = points.len();
+        let prev = points[(i + n - 1) % n];
+        let next = points[(i + 1) % n];
+        let (a, b) = (points[i] - prev, next - points[i]);
+        let scale = a.length() * b.length();
+        if a.length() < WELD_TOLERANCE || (a.cross(b).length() <= COLLINEAR_SINE * scale && a.dot(b) > 0.0) {
+            points.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    points
+}
+
+fn signed_area(ring: &[Vec2]) -> f32 {
+    let n = ring.len();
+    (0..n).map(|i| ring[i].perp_dot(ring[(i + 1) % n])).sum::<f32>() / 2.0
+}
+
+/// Even-odd point-in-polygon test
+fn contains(ring: &[Vec2], p: Vec2) -> bool {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Splice clockwise `holes` into a counter-clockwise `outer` ring through
+/// bridge edges that cross no other edge, giving one simple-enough polygon
+fn bridge_holes(outer: &[Vec2], holes: &[&[Vec2]]) -> Vec<Vec2> {
+    let mut polygon = outer.to_vec();
+    let mut holes: Vec<&[Vec2]> = holes.to_vec();
+    // Rightmost holes first, so earlier bridges don't block later ones
+    holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+
+    for (h, hole) in holes.iter().enumerate() {
+        let start = (0..hole.len()).max_by(|&a, &b| hole[a].x.total_cmp(&hole[b].x)).unwrap_or(0);
+        let from = hole[start];
+
+        let blocked = |to: Vec2, polygon: &[Vec2]| {
+            let rings = std::iter::once(polygon).chain(holes[h..].iter().copied());
+            rings.into_iter().any(|ring| {
+                (0..ring.len()).any(|i| segments_cross(from, to, ring[i], ring[(i + 1) % ring.len()]))
+            })
+        };
+        let target = (0..polygon.len())
+            .filter(|&i| !blocked(polygon[i], &polygon))
+            .min_by(|&a, &b| polygon[a].distance_squared(from).total_cmp(&polygon[b].distance_squared(from)));
+        let Some(target) = target else {
+            continue;
+        };
+
+        // outer[..=target], hole from `start` around and back, outer[target..]
+        let mut spliced = polygon[..=target].to_vec();
+        spliced.extend((0..=hole.len()).map(|k| hole[(start + k) % hole.len()]));
+        spliced.extend_from_slice(&polygon[target..]);
+        polygon = spliced;
+    }
+    polygon
+}
+
+fn max_x(ring: &[Vec2]) -> f32 {
+    ring.iter().map(|p| p.x).fold(f32::MIN, f32::max)
+}
+
+/// Proper crossing of segments `ab` and `cd` (shared endpoints don't count)
+fn segments_cross(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let orient = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p);
+    let (d1, d2) = (orient(a, b, c), orient(a, b, d));
+    let (d3, d4) = (orient(c, d, a), orient(c, d, b));
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Ear clipping of a counter-clockwise polygon (may touch itself at bridge vertices)
+fn ear_clip(polygon: &[Vec2]) -> Vec<[Vec2; 3]> {
+    let mut ring: Vec<usize> = (0..polygon.len()).collect();
+    let mut triangles = Vec::new();
+    let mut i = 0;
+    let mut misses = 0;
+    while ring.len() > 3 {
+        let n = ring.len();
+        let (a, b, c) = (ring[(i + n - 1) % n], ring[i % n], ring[(i + 1) % n]);
+        let (pa, pb, pc) = (polygon[a], polygon[b], polygon[c]);
+        let convex = (pb - pa).perp_dot(pc - pb) > 0.0;
+        let is_ear = convex
+            && ring.iter().all(|&k| {
+                let p = polygon[k];
+                k == a || k == b || k == c || p == pa || p == pb || p == pc || !inside_triangle(p, pa, pb, pc)
+            });
+
+        // A degenerate remainder has no proper ear; clip anyway rather than loop forever
+        if is_ear || misses > n {
+            if convex {
+                triangles.push([pa, pb, pc]);
+            }
+            ring.remove(i % n);
+            misses = 0;
+        } else {
+            i = (i + 1) % n;
+            misses += 1;
+        }
+    }
+    if let [a, b, c] = ring[..] {
+        if (polygon[b] - polygon[a]).perp_dot(polygon[c] - polygon[b]) > 0.0 {
+            triangles.push([polygon[a], polygon[b], polygon[c]]);
+        }
+    }
+    triangles
+}
+
+fn inside_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) > 0.0 && (c - b).perp_dot(p - b) > 0.0 && (a - c).perp_dot(p - c) > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::{box_mesh, inverted};
+
+    fn cap_area(mesh: &MeshData) -> f32 {
+        let p = |i: usize| Vec3::from_slice(&mesh.vertices[i * 9..i * 9 + 3]);
+        mesh.indices
+            .chunks_exact(3)
+            .map(|t| {
+                let (a, b, c) = (p(t[0] as usize), p(t[1] as usize), p(t[2] as usize));
+                (b - a).cross(c - a).length() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_box_section_is_counter_clockwise_square() {
+        let mesh = box_mesh([0.0; 3], [2.0, 3.0, 4.0]);
+        let plane = SectionPlane::new(Vec3::new(0.0, 0.0, 1.5), Vec3::Z);
+
+        let loops = section_loops(&mesh, &plane);
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 4, "{:?}", loops[0]);
+        assert!(loops[0].iter().all(|p| (p.z - 1.5).abs() < 1e-6));
+
+        let cap = cap_mesh(&loops, &plane, [1.0, 0.0, 0.0]);
+        assert!((cap_area(&cap) - 6.0).abs() < 1e-4);
+        // Cap faces the removed side
+        let p = |i: usize| Vec3::from_slice(&cap.vertices[i * 9..i * 9 + 3]);
+        assert!((p(1) - p(0)).cross(p(2) - p(0)).z > 0.0);
+        assert_eq!(cap.vertices[3..9], [0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_section_through_vertices_and_misses() {
+        let mesh = box_mesh([0.0; 3], [1.0; 3]);
+        // Plane through the top face: its vertices count as cut away, so the
+        // section is the face outline
+        let plane = SectionPlane::new(Vec3::ONE, Vec3::Z);
+        let loops = section_loops(&mesh, &plane);
+        assert_eq!(loops.len(), 1);
+        assert!((cap_area(&cap_mesh(&loops, &plane, [1.0; 3])) - 1.0).abs() < 1e-4);
+
+        let plane = SectionPlane::new(Vec3::new(0.0, 0.0, 5.0), Vec3::Z);
+        assert!(section_loops(&mesh, &plane).is_empty());
+
+        // Diagonal plane through two opposite vertical edges
+        let plane = SectionPlane::new(Vec3::ZERO, Vec3::new(1.0, -1.0, 0.0));
+        let loops = section_loops(&mesh, &plane);
+        assert_eq!(loops.len(), 1);
+        let cap = cap_mesh(&loops, &plane, [1.0; 3]);
+        assert!((cap_area(&cap) - 2f32.sqrt()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_hollow_box_cap_has_hole() {
+        let mut mesh = box_mesh([0.0; 3], [4.0; 3]);
+        let cavity = inverted(box_mesh([1.0; 3], [3.0; 3]));
+        let offset = mesh.vertex_count() as u32;
+        mesh.vertices.extend(cavity.vertices);
+        mesh.indices.extend(cavity.indices.iter().map(|i| i + offset));
+
+        let plane = SectionPlane::new(Vec3::new(0.0, 2.0, 0.0), Vec3::NEG_Y);
+        let loops = section_loops(&mesh, &plane);
+        assert_eq!(loops.len(), 2);
+        let cap = cap_mesh(&loops, &plane, [1.0; 3]);
+        assert!((cap_area(&cap) - 12.0).abs() < 1e-3, "area = {}", cap_area(&cap));
+    }
+
+    #[test]
+    fn test_section_sketch_polylines() {
+        let mesh = box_mesh([0.0; 3], [2.0, 3.0, 4.0]);
+        let plane = SectionPlane::new(Vec3::new(0.0, 1.0, 0.0), Vec3::Y);
+        let sketch = section_sketch(&section_loops(&mesh, &plane), SketchPlane::Xz, 1.0);
+
+        assert_eq!(sketch.plane, SketchPlane::Xz);
+        assert_eq!(sketch.elements.len(), 1);
+        let SketchElement::Polyline { points, id } = &sketch.elements[0] else {
+            panic!("expected a polyline");
+        };
+        assert!(id.is_some());
+        assert_eq!(points.len(), 5);
+        assert_eq!(points.first(), points.last());
+        assert!(points.iter().all(|p| (0.0..=2.0).contains(&p.x) && (0.0..=4.0).contains(&p.y)));
+    }
+}
@@ -0,0 +1,110 @@
+//! Headless command line front-end for cad-core.
+//!
+//! Usage:
+//!   vcad-cli build <scene.json> <output.glb>
+//!   vcad-cli configs <scene.json> <output_dir> [table.csv|table.json]
+
+use std::process::ExitCode;
+
+use cad_core::build::build_scene_meshes_v2;
+use cad_core::configurations::build_configurations_glb;
+use cad_core::export::{build_scene_glb, unique_file_names, GltfOptions};
+use shared::{DesignTable, SceneDescriptionV2, SceneInput};
+
+const USAGE: &str = "Usage:\n  vcad-cli build <scene.json> <output.glb>\n  \
+                     vcad-cli configs <scene.json> <output_dir> [table.csv|table.json]";
+
+fn load_scene(path: &str) -> Result<SceneDescriptionV2, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let input: SceneInput =
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse {path}: {e}"))?;
+    Ok(input.into_v2())
+}
+
+fn build(scene_path: &str, output_path: &str) -> Result<(), String> {
+    let scene = load_scene(scene_path)?;
+    let (meshes, errors) = build_scene_meshes_v2(&scene, &[]);
+
+    let mut error_ids: Vec<&String> = errors.keys().collect();
+    error_ids.sort();
+    for id in error_ids {
+        eprintln!("warning: body {id}: {}", errors[id]);
+    }
+
+    if meshes.is_empty() {
+        return Err("Scene produced no geometry".to_string());
+    }
+
+    let glb = build_scene_glb(&scene, &meshes, &GltfOptions::default());
+    std::fs::write(output_path, glb).map_err(|e| format!("Failed to write {output_path}: {e}"))?;
+    println!("Wrote {} bodies to {output_path}", meshes.len());
+    Ok(())
+}
+
+/// Design table from a CSV or JSON file (by extension)
+fn load_table(path: &str) -> Result<DesignTable, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let table = if path.to_ascii_lowercase().ends_with(".json") {
+        DesignTable::from_json(&text)
+    } else {
+        DesignTable::from_csv(&text)
+    };
+    table.map_err(|e| format!("{path}: {e}"))
+}
+
+/// Build every configuration of the design table into `<output_dir>/<name>.glb`.
+/// Without a table file the scene's own `design_table` is used.
+fn configs(scene_path: &str, output_dir: &str, table_path: Option<&str>) -> Result<(), String> {
+    let mut scene = load_scene(scene_path)?;
+    if let Some(table_path) = table_path {
+        scene.design_table = Some(load_table(table_path)?);
+    }
+    if scene.design_table.as_ref().is_none_or(|t| t.configurations.is_empty()) {
+        return Err("Scene has no design table configurations".to_string());
+    }
+
+    std::fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create {output_dir}: {e}"))?;
+    let built = build_configurations_glb(&scene).map_err(|e| e.to_string())?;
+
+    let mut failed = 0;
+    let names = unique_file_names(built.iter().map(|config| config.name.as_str()), "glb");
+    for (config, file) in built.into_iter().zip(names) {
+        let mut error_ids: Vec<&String> = config.errors.keys().collect();
+        error_ids.sort();
+        for id in error_ids {
+            eprintln!("warning: {}: body {id}: {}", config.name, config.errors[id]);
+        }
+        if config.glb.is_empty() {
+            eprintln!("error: {}: configuration produced no geometry", config.name);
+            failed += 1;
+            continue;
+        }
+        let path = std::path::Path::new(output_dir).join(file);
+        std::fs::write(&path, &config.glb).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+
+    if failed > 0 {
+        return Err(format!("{failed} configuration(s) produced no geometry"));
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["build", scene, output] => build(scene, output),
+        ["configs", scene, output_dir] => configs(scene, output_dir, None),
+        ["configs", scene, output_dir, table] => configs(scene, output_dir, Some(table)),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
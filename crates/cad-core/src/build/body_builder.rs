@@ -1,33 +1,38 @@
 //! Body mesh building from features
 
-use shared::{Body, BooleanOp, Feature, Transform};
+use shared::{Body, BooleanOp, EdgeRef, Feature, Transform};
 use vcad::Part;
 
-use crate::extrude::{extrude_mesh, revolve_mesh};
-use crate::helpers::{combine_transforms, get_body_base_transform};
-use crate::viewport::mesh::MeshData;
+use crate::extrude::{extrude_mesh, is_fan_extrudable, revolve_mesh};
+use crate::helpers::{combine_transforms, get_body_base_transform, sketch_placement};
+use crate::mesh::MeshData;
 
 use super::extrude_builder::{create_extrude_part_full, create_revolve_part_from_sketch_with_axis};
 use super::fillet_builder::{apply_chamfer, apply_rounded_fillet, FilletEdge};
+use super::mesh_builder::create_mesh_part;
 use super::mesh_extraction::{apply_selection_color, extract_mesh_data};
 use super::primitives::{apply_transform, create_primitive};
+use super::topology::{resolve_edge, resolve_sketch_faces};
 
 /// Build MeshData directly from a body's features
-/// `all_bodies` is needed to resolve BooleanModify references to other bodies
-pub fn build_body_mesh_data(body: &Body, selected: bool, all_bodies: &[Body]) -> Result<Option<MeshData>, String> {
+/// `all_bodies` is needed to resolve BooleanModify references to other bodies.
+/// Non-fatal problems (unresolved edge/face references) are pushed to `errors`;
+/// the affected feature is skipped and the rest of the body still builds.
+pub fn build_body_mesh_data(
+    body: &Body,
+    selected: bool,
+    all_bodies: &[Body],
+    errors: &mut Vec<String>,
+) -> Result<Option<MeshData>, String> {
     if body.features.is_empty() {
         return Err("Body has no features".to_string());
     }
 
+    // Sketches placed on faces follow the current position of those faces
+    let body = &resolve_sketch_faces(body, errors);
+
     // Find the first base feature (can be at any position)
-    let base_feature = body.features.iter().find(|f| {
-        matches!(
-            f,
-            Feature::BasePrimitive { .. }
-                | Feature::BaseExtrude { .. }
-                | Feature::BaseRevolve { .. }
-        )
-    });
+    let base_feature = body.features.iter().find(|f| is_base_feature(f));
 
     // If no base feature found, check if we have only sketches
     let Some(base_feature) = base_feature else {
@@ -40,10 +45,7 @@ pub fn build_body_mesh_data(body: &Body, selected: bool, all_bodies: &[Body]) ->
     };
 
     // Check if there are any modification features
-    let has_modifications = body
-        .features
-        .iter()
-        .any(|f| matches!(f, Feature::Extrude { .. } | Feature::Revolve { .. }));
+    let has_modifications = body.features.iter().any(|f| !is_base_feature(f) && !matches!(f, Feature::Sketch { .. }));
 
     // Build initial geometry from base feature
     let mut current_part: Option<Part> = match base_feature {
@@ -64,8 +66,16 @@ pub fn build_body_mesh_data(body: &Body, selected: bool, all_bodies: &[Body]) ->
             height_backward,
             draft_angle,
         } => {
-            // If no modifications and no draft angle, return mesh directly (faster)
-            if !has_modifications && draft_angle.abs() < 0.01 {
+            // A plain single convex profile is meshed directly (faster);
+            // holes, concave outlines, backward extent and rotated or scaled
+            // sketches need the CSG part
+            if !has_modifications
+                && draft_angle.abs() < 0.01
+                && *height_backward == 0.0
+                && sketch_transform.rotation == [0.0; 3]
+                && sketch_transform.scale == [1.0; 3]
+                && is_fan_extrudable(sketch)
+            {
                 let mut mesh = extrude_mesh(sketch, sketch_transform, *height)?;
                 if selected {
                     apply_selection_color(&mut mesh);
@@ -109,21 +119,38 @@ pub fn build_body_mesh_data(body: &Body, selected: bool, all_bodies: &[Body]) ->
             // Has modifications - use real revolve CSG with axis
             create_revolve_part_from_sketch_with_axis(id, sketch, sketch_transform, *angle, *segments, axis)
         }
+        // A non-manifold import cannot become a solid: fail the whole body
+        Feature::BaseMesh { id, mesh, transform } => Some(create_mesh_part(id, mesh, transform)?),
         _ => {
             return Err("Unexpected feature type".to_string());
         }
     };
 
-    // Process modification features (Extrude, Revolve, etc.) - skip base features
+    apply_modifications(body, &mut current_part, all_bodies, errors);
+
+    // Convert final Part to MeshData
+    match current_part {
+        Some(part) => Ok(extract_mesh_data(&part, selected)),
+        None => Ok(None),
+    }
+}
+
+fn is_base_feature(feature: &Feature) -> bool {
+    matches!(
+        feature,
+        Feature::BasePrimitive { .. }
+            | Feature::BaseExtrude { .. }
+            | Feature::BaseRevolve { .. }
+            | Feature::BaseMesh { .. }
+    )
+}
+
+/// Process modification features (Extrude, Revolve, etc.) - skip base features
+fn apply_modifications(body: &Body, current_part: &mut Option<Part>, all_bodies: &[Body], errors: &mut Vec<String>) {
     tracing::debug!("Processing modification features for body {}", body.id);
     for feature in &body.features {
-        // Skip base features (already processed above)
-        if matches!(
-            feature,
-            Feature::BasePrimitive { .. }
-                | Feature::BaseExtrude { .. }
-                | Feature::BaseRevolve { .. }
-        ) {
+        // Skip base features (already processed)
+        if is_base_feature(feature) {
             tracing::debug!("  Skipping base feature: {:?}", feature.id());
             continue;
         }
@@ -139,7 +166,7 @@ pub fn build_body_mesh_data(body: &Body, selected: bool, all_bodies: &[Body]) ->
             } => {
                 process_extrude_feature(
                     body,
-                    &mut current_part,
+                    current_part,
                     extrude_id,
                     sketch_id,
                     *height,
@@ -153,29 +180,68 @@ pub fn build_body_mesh_data(body: &Body, selected: bool, all_bodies: &[Body]) ->
                     (Some(start), Some(end)) => Some((*start, *end)),
                     _ => None,
                 };
-                process_revolve_feature(body, &mut current_part, sketch_id, *angle, *segments, *cut, axis);
+                process_revolve_feature(body, current_part, sketch_id, *angle, *segments, *cut, axis);
             }
             Feature::Sketch { .. } => {
                 // Sketches are reference geometry, don't modify the part
             }
             Feature::BooleanModify { op, tool_body_id, .. } => {
-                process_boolean_modify(&mut current_part, op, tool_body_id, all_bodies);
+                process_boolean_modify(current_part, op, tool_body_id, all_bodies);
             }
-            Feature::Fillet3D { radius, segments, edges, .. } => {
-                process_fillet_feature(&mut current_part, *radius, *segments, edges);
+            Feature::Fillet3D { id, radius, segments, edges, edge_refs } => {
+                if let Some(edges) = collect_edges(body, current_part, "Fillet", id, edges, edge_refs, errors) {
+                    process_fillet_feature(current_part, *radius, *segments, &edges);
+                }
             }
-            Feature::Chamfer3D { distance, edges, .. } => {
-                process_chamfer_feature(&mut current_part, *distance, edges);
+            Feature::Chamfer3D { id, distance, edges, edge_refs } => {
+                if let Some(edges) = collect_edges(body, current_part, "Chamfer", id, edges, edge_refs, errors) {
+                    process_chamfer_feature(current_part, *distance, &edges);
+                }
             }
             _ => {}
         }
     }
+}
 
-    // Convert final Part to MeshData
-    match current_part {
-        Some(part) => Ok(extract_mesh_data(&part, selected)),
-        None => Ok(None),
+/// Edge stored by coordinates: (start, end, normal1, normal2)
+type CoordEdge = ([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>);
+
+/// Combine coordinate edges with named edges resolved against the current part.
+/// Returns None (feature skipped) if any named edge can't be resolved.
+fn collect_edges(
+    body: &Body,
+    current_part: &Option<Part>,
+    kind: &str,
+    feature_id: &str,
+    edges: &[CoordEdge],
+    edge_refs: &[EdgeRef],
+    errors: &mut Vec<String>,
+) -> Option<Vec<FilletEdge>> {
+    let mut result: Vec<FilletEdge> = edges
+        .iter()
+        .map(|(start, end, normal1, normal2)| FilletEdge {
+            start: *start,
+            end: *end,
+            normal1: *normal1,
+            normal2: *normal2,
+        })
+        .collect();
+
+    if edge_refs.is_empty() {
+        return Some(result);
+    }
+
+    let mesh = current_part.as_ref().and_then(|part| extract_mesh_data(part, false))?;
+    for edge_ref in edge_refs {
+        match resolve_edge(body, &mesh, edge_ref) {
+            Ok(resolved) => result.extend(resolved),
+            Err(e) => {
+                errors.push(format!("{} '{}': {}", kind, feature_id, e));
+                return None;
+            }
+        }
     }
+    Some(result)
 }
 
 /// Process an Extrude feature and update the current part
@@ -198,16 +264,16 @@ fn process_extrude_feature(
     }
 
     if let Some((sketch, sketch_transform)) = sketch_data {
-        // Combine body's base transform with sketch's local transform
+        // Sketch position relative to the body, its own rotation and scale
         let body_transform = get_body_base_transform(body);
-        let combined_transform = combine_transforms(&body_transform, sketch_transform);
+        let placement = sketch_placement(&body_transform, sketch_transform);
 
         if let Some(base_part) = current_part.take() {
             // Create extrusion tool from sketch (respects plane orientation and cut direction)
             if let Some(tool_part) = create_extrude_part_full(
                 "extrude_tool",
                 sketch,
-                &combined_transform,
+                &placement,
                 height,
                 height_backward,
                 cut,
@@ -300,9 +366,9 @@ fn find_sketch_in_body<'a>(
 
 /// Extract revolve axis from sketch's revolve_axis field if set
 fn extract_revolve_axis_from_sketch(sketch: &shared::Sketch) -> Option<([f64; 2], [f64; 2])> {
-    let axis_index = sketch.revolve_axis?;
+    let axis_id = sketch.revolve_axis.as_deref()?;
 
-    if let Some(shared::SketchElement::Line { start, end }) = sketch.elements.get(axis_index) {
+    if let Some(shared::SketchElement::Line { start, end, .. }) = sketch.element_by_id(axis_id) {
         Some(([start.x, start.y], [end.x, end.y]))
     } else {
         None
@@ -326,8 +392,8 @@ fn process_boolean_modify(
 
     let tool_body = tool_body.unwrap();
 
-    // Build the tool body's Part
-    let tool_part = build_body_part(tool_body, all_bodies);
+    // Build the tool body's Part (its own reference errors are reported on its entry)
+    let tool_part = build_body_part(tool_body, all_bodies, &mut Vec::new());
 
     if tool_part.is_none() {
         tracing::warn!("BooleanModify: failed to build tool body {} geometry", tool_body_id);
@@ -349,21 +415,16 @@ fn process_boolean_modify(
 }
 
 /// Build a Part from a body (without converting to MeshData)
-/// Used for BooleanModify to get the tool body's geometry
-fn build_body_part(body: &Body, all_bodies: &[Body]) -> Option<Part> {
+/// Used for BooleanModify to get the tool body's geometry and by headless exporters
+pub fn build_body_part(body: &Body, all_bodies: &[Body], errors: &mut Vec<String>) -> Option<Part> {
     if body.features.is_empty() {
         return None;
     }
 
+    let body = &resolve_sketch_faces(body, errors);
+
     // Find the first base feature
-    let base_feature = body.features.iter().find(|f| {
-        matches!(
-            f,
-            Feature::BasePrimitive { .. }
-                | Feature::BaseExtrude { .. }
-                | Feature::BaseRevolve { .. }
-        )
-    });
+    let base_feature = body.features.iter().find(|f| is_base_feature(f));
 
     let Some(base_feature) = base_feature else {
         return None;
@@ -400,59 +461,17 @@ fn build_body_part(body: &Body, all_bodies: &[Body]) -> Option<Part> {
             let axis = extract_revolve_axis_from_sketch(sketch);
             create_revolve_part_from_sketch_with_axis(id, sketch, sketch_transform, *angle, *segments, axis)
         }
+        Feature::BaseMesh { id, mesh, transform } => match create_mesh_part(id, mesh, transform) {
+            Ok(part) => Some(part),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        },
         _ => None,
     };
 
-    // Process modification features
-    for feature in &body.features {
-        if matches!(
-            feature,
-            Feature::BasePrimitive { .. }
-                | Feature::BaseExtrude { .. }
-                | Feature::BaseRevolve { .. }
-        ) {
-            continue;
-        }
-
-        match feature {
-            Feature::Extrude {
-                id: extrude_id,
-                sketch_id,
-                height,
-                height_backward,
-                cut,
-                draft_angle,
-            } => {
-                process_extrude_feature(
-                    body,
-                    &mut current_part,
-                    extrude_id,
-                    sketch_id,
-                    *height,
-                    *height_backward,
-                    *cut,
-                    *draft_angle,
-                );
-            }
-            Feature::Revolve { sketch_id, angle, segments, cut, axis_start, axis_end, .. } => {
-                let axis = match (axis_start, axis_end) {
-                    (Some(start), Some(end)) => Some((*start, *end)),
-                    _ => None,
-                };
-                process_revolve_feature(body, &mut current_part, sketch_id, *angle, *segments, *cut, axis);
-            }
-            Feature::BooleanModify { op, tool_body_id, .. } => {
-                process_boolean_modify(&mut current_part, op, tool_body_id, all_bodies);
-            }
-            Feature::Fillet3D { radius, segments, edges, .. } => {
-                process_fillet_feature(&mut current_part, *radius, *segments, edges);
-            }
-            Feature::Chamfer3D { distance, edges, .. } => {
-                process_chamfer_feature(&mut current_part, *distance, edges);
-            }
-            _ => {}
-        }
-    }
+    apply_modifications(body, &mut current_part, all_bodies, errors);
 
     current_part
 }
@@ -462,29 +481,18 @@ fn process_fillet_feature(
     current_part: &mut Option<Part>,
     radius: f64,
     segments: u32,
-    edges: &[([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)],
+    fillet_edges: &[FilletEdge],
 ) {
-    if edges.is_empty() {
+    if fillet_edges.is_empty() {
         tracing::warn!("Fillet3D: no edges specified");
         return;
     }
 
-    // Convert edge data to FilletEdge format
-    let fillet_edges: Vec<FilletEdge> = edges
-        .iter()
-        .map(|(start, end, normal1, normal2)| FilletEdge {
-            start: *start,
-            end: *end,
-            normal1: *normal1,
-            normal2: *normal2,
-        })
-        .collect();
-
     if let Some(base_part) = current_part.take() {
         // Use rounded fillet (with arc profile)
-        if let Some(result) = apply_rounded_fillet(&base_part, &fillet_edges, radius, segments) {
+        if let Some(result) = apply_rounded_fillet(&base_part, fillet_edges, radius, segments) {
             *current_part = Some(result);
-            tracing::debug!("Fillet3D: applied rounded fillet with radius {} to {} edges", radius, edges.len());
+            tracing::debug!("Fillet3D: applied rounded fillet with radius {} to {} edges", radius, fillet_edges.len());
         } else {
             tracing::warn!("Fillet3D: failed to create rounded fillet geometry");
             *current_part = Some(base_part);
@@ -496,29 +504,18 @@ fn process_fillet_feature(
 fn process_chamfer_feature(
     current_part: &mut Option<Part>,
     distance: f64,
-    edges: &[([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)],
+    fillet_edges: &[FilletEdge],
 ) {
-    if edges.is_empty() {
+    if fillet_edges.is_empty() {
         tracing::warn!("Chamfer3D: no edges specified");
         return;
     }
 
-    // Convert edge data to FilletEdge format
-    let fillet_edges: Vec<FilletEdge> = edges
-        .iter()
-        .map(|(start, end, normal1, normal2)| FilletEdge {
-            start: *start,
-            end: *end,
-            normal1: *normal1,
-            normal2: *normal2,
-        })
-        .collect();
-
     if let Some(base_part) = current_part.take() {
         // Use chamfer (triangular prism - flat bevel at 45°)
-        if let Some(result) = apply_chamfer(&base_part, &fillet_edges, distance, 1) {
+        if let Some(result) = apply_chamfer(&base_part, fillet_edges, distance, 1) {
             *current_part = Some(result);
-            tracing::debug!("Chamfer3D: applied chamfer with distance {} to {} edges", distance, edges.len());
+            tracing::debug!("Chamfer3D: applied chamfer with distance {} to {} edges", distance, fillet_edges.len());
         } else {
             tracing::warn!("Chamfer3D: failed to create chamfer geometry");
             *current_part = Some(base_part);
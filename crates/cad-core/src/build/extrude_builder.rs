@@ -6,8 +6,8 @@ use manifold_rs::Manifold;
 use shared::{Sketch, SketchElement, SketchPlane, Transform};
 use vcad::Part;
 
-use super::primitives::DEFAULT_SEGMENTS;
-use crate::extrude::extract_2d_profiles;
+use super::primitives::{apply_transform, DEFAULT_SEGMENTS};
+use crate::extrude::{extract_2d_profiles, group_profiles_into_regions};
 use crate::sketch::operations::{validate_sketch_for_extrusion, SketchValidation};
 
 /// Determine if cut direction should be reversed based on face normal.
@@ -79,6 +79,31 @@ pub fn create_extrude_part_full(
     height_backward: f64,
     is_cut: bool,
     draft_angle: f64,
+) -> Option<Part> {
+    let part = create_extrude_part_at(id, sketch, transform, height, height_backward, is_cut, draft_angle)?;
+    Some(orient_about_sketch(part, transform))
+}
+
+/// Apply the rotation and scale of the sketch transform about the sketch
+/// position, in the same order as `apply_transform`: scale → rotate → translate
+fn orient_about_sketch(part: Part, transform: &Transform) -> Part {
+    if transform.rotation == [0.0; 3] && transform.scale == [1.0; 3] {
+        return part;
+    }
+    let [x, y, z] = transform.position;
+    let local = Transform { position: [0.0; 3], ..transform.clone() };
+    apply_transform(part.translate(-x, -y, -z), &local).translate(x, y, z)
+}
+
+/// Extrude tool placed at the sketch position (rotation and scale not applied yet)
+fn create_extrude_part_at(
+    id: &str,
+    sketch: &Sketch,
+    transform: &Transform,
+    height: f64,
+    height_backward: f64,
+    is_cut: bool,
+    draft_angle: f64,
 ) -> Option<Part> {
     if sketch.elements.is_empty() {
         return None;
@@ -183,12 +208,16 @@ fn try_create_profile_extrusion(
         return None;
     }
 
-    // Extrude each profile separately and union them together
-    // (Manifold interprets multiple polygons as outer+holes, not separate shapes)
+    // Extrude each region (outer boundary + its holes) separately and union
+    // them together: Manifold interprets multiple polygons as outer+holes,
+    // so disjoint outlines must not be passed in one call
+    let profile_count = profiles.len();
+    let regions = group_profiles_into_regions(profiles);
     let mut result_manifold: Option<Manifold> = None;
 
-    for (pi, profile) in profiles.iter().enumerate() {
-        // Calculate scale factor for draft angle
+    for (ri, region) in regions.iter().enumerate() {
+        let profile = &region[0];
+        // Calculate scale factor for draft angle from the outer boundary
         // Draft angle: positive = expands outward, negative = tapers inward
         // scale = 1 + height * tan(draft_angle) / r_avg
         let scale_top = if draft_angle.abs() > 0.001 {
@@ -216,36 +245,38 @@ fn try_create_profile_extrusion(
         // Clamp scale to reasonable range (0.1 to 10.0)
         let scale_top = scale_top.clamp(0.1, 10.0);
 
-        // Convert profile to format for Manifold::extrude
-        let polygon_data: Vec<f64> = match sketch.plane {
-            SketchPlane::Xy => {
-                // XY plane: use coordinates as-is
-                profile.iter().flat_map(|p| vec![p[0], p[1]]).collect()
-            }
-            SketchPlane::Xz => {
-                // XZ plane: sketch (x,y) -> world (x, extrude, y)
-                // Use (x, -y) to get correct Z after rotation, reverse for winding
-                let mut pts: Vec<[f64; 2]> = profile.iter().map(|p| [p[0], -p[1]]).collect();
-                pts.reverse(); // Fix winding order after coordinate flip
-                pts.iter().flat_map(|p| vec![p[0], p[1]]).collect()
-            }
-            SketchPlane::Yz => {
-                // YZ plane: sketch (x,y) -> world (extrude, y, z)
-                // Using rotate(0, -90, 0): Manifold X -> world Z, Manifold Y -> world Y, Manifold Z -> world -X
-                // So: Manifold X = world Z = sketch_y, Manifold Y = world Y = sketch_x
-                // Extrusion goes along world -X (into body from positive X)
-                // Swapping x,y reverses winding order, so we need to reverse the points
-                let mut pts: Vec<[f64; 2]> = profile.iter().map(|p| [p[1], p[0]]).collect();
-                pts.reverse(); // Fix winding order after coordinate swap
-                pts.iter().flat_map(|p| vec![p[0], p[1]]).collect()
-            }
-        };
-
-        let polygon_slice: &[f64] = &polygon_data;
+        // Convert polygons to format for Manifold::extrude
+        let polygons: Vec<Vec<f64>> = region
+            .iter()
+            .map(|polygon| match sketch.plane {
+                SketchPlane::Xy => {
+                    // XY plane: use coordinates as-is
+                    polygon.iter().flat_map(|p| vec![p[0], p[1]]).collect()
+                }
+                SketchPlane::Xz => {
+                    // XZ plane: sketch (x,y) -> world (x, extrude, y)
+                    // Use (x, -y) to get correct Z after rotation, reverse for winding
+                    let mut pts: Vec<[f64; 2]> = polygon.iter().map(|p| [p[0], -p[1]]).collect();
+                    pts.reverse(); // Fix winding order after coordinate flip
+                    pts.iter().flat_map(|p| vec![p[0], p[1]]).collect()
+                }
+                SketchPlane::Yz => {
+                    // YZ plane: sketch (x,y) -> world (extrude, y, z)
+                    // Using rotate(0, -90, 0): Manifold X -> world Z, Manifold Y -> world Y, Manifold Z -> world -X
+                    // So: Manifold X = world Z = sketch_y, Manifold Y = world Y = sketch_x
+                    // Extrusion goes along world -X (into body from positive X)
+                    // Swapping x,y reverses winding order, so we need to reverse the points
+                    let mut pts: Vec<[f64; 2]> = polygon.iter().map(|p| [p[1], p[0]]).collect();
+                    pts.reverse(); // Fix winding order after coordinate swap
+                    pts.iter().flat_map(|p| vec![p[0], p[1]]).collect()
+                }
+            })
+            .collect();
+        let polygon_slices: Vec<&[f64]> = polygons.iter().map(Vec::as_slice).collect();
 
-        // Create the manifold extrusion for this profile
+        // Create the manifold extrusion for this region
         let manifold = Manifold::extrude(
-            &[polygon_slice],
+            &polygon_slices,
             height.abs(),
             1,         // n_divisions
             0.0,       // twist_degrees
@@ -254,7 +285,7 @@ fn try_create_profile_extrusion(
         );
 
         if manifold.is_empty() {
-            tracing::warn!("Manifold::extrude returned empty geometry for profile {}", pi);
+            tracing::warn!("Manifold::extrude returned empty geometry for region {}", ri);
             continue;
         }
 
@@ -268,7 +299,7 @@ fn try_create_profile_extrusion(
     let manifold = match result_manifold {
         Some(m) => m,
         None => {
-            tracing::warn!("No valid extrusions created from {} profiles", profiles.len());
+            tracing::warn!("No valid extrusions created from {} profiles", profile_count);
             return None;
         }
     };
@@ -412,7 +443,7 @@ fn try_create_cylinder_from_sketch_full(
     }
 
     let circle = match &sketch.elements[0] {
-        SketchElement::Circle { center, radius } => Some((center, *radius)),
+        SketchElement::Circle { center, radius, .. } => Some((center, *radius)),
         _ => None,
     }?;
 
@@ -851,6 +882,7 @@ mod tests {
     fn test_validate_single_circle() {
         let sketch = Sketch {
             elements: vec![SketchElement::Circle {
+                id: None,
                 center: Point2D { x: 0.0, y: 0.0 },
                 radius: 1.0,
             }],
@@ -866,6 +898,7 @@ mod tests {
     fn test_validate_single_rectangle() {
         let sketch = Sketch {
             elements: vec![SketchElement::Rectangle {
+                id: None,
                 corner: Point2D { x: 0.0, y: 0.0 },
                 width: 2.0,
                 height: 1.0,
@@ -881,6 +914,7 @@ mod tests {
     fn test_extrude_circle_creates_cylinder() {
         let sketch = Sketch {
             elements: vec![SketchElement::Circle {
+                id: None,
                 center: Point2D { x: 0.0, y: 0.0 },
                 radius: 1.0,
             }],
@@ -894,6 +928,7 @@ mod tests {
     fn test_extrude_rectangle_creates_profile() {
         let sketch = Sketch {
             elements: vec![SketchElement::Rectangle {
+                id: None,
                 corner: Point2D { x: -1.0, y: -1.0 },
                 width: 2.0,
                 height: 2.0,
@@ -910,14 +945,17 @@ mod tests {
         let sketch = Sketch {
             elements: vec![
                 SketchElement::Line {
+                    id: None,
                     start: Point2D { x: 0.0, y: 0.0 },
                     end: Point2D { x: 2.0, y: 0.0 },
                 },
                 SketchElement::Line {
+                    id: None,
                     start: Point2D { x: 2.0, y: 0.0 },
                     end: Point2D { x: 1.0, y: 2.0 },
                 },
                 SketchElement::Line {
+                    id: None,
                     start: Point2D { x: 1.0, y: 2.0 },
                     end: Point2D { x: 0.0, y: 0.0 },
                 },
@@ -927,4 +965,36 @@ mod tests {
         let part = create_extrude_part_from_sketch("test", &sketch, &identity(), 1.0);
         assert!(part.is_some());
     }
+
+    #[test]
+    fn test_extrude_imported_dxf_outline() {
+        // 2×1 cm plate with a centreline on a construction layer
+        let dxf = "0\nSECTION\n2\nHEADER\n9\n$INSUNITS\n70\n5\n0\nENDSEC\n\
+                   0\nSECTION\n2\nENTITIES\n\
+                   0\nLWPOLYLINE\n8\nOUTLINE\n90\n4\n70\n1\n\
+                   10\n0\n20\n0\n10\n2\n20\n0\n10\n2\n20\n1\n10\n0\n20\n1\n\
+                   0\nLINE\n8\nCENTER\n10\n1\n20\n-0.5\n11\n1\n21\n1.5\n\
+                   0\nENDSEC\n0\nEOF\n";
+        let options = crate::import::DxfOptions {
+            construction_layers: vec!["center".to_string()],
+            ..Default::default()
+        };
+        let import = crate::import::parse_dxf(dxf, &options).unwrap();
+
+        let part = create_extrude_part_full("plate", &import.sketch, &identity(), 5.0, 0.0, false, 0.0).unwrap();
+        assert!((part.volume() - 20.0 * 10.0 * 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_extrude_imported_svg_with_hole() {
+        // 20×10 mm plate with a Ø4 mm hole drawn as two arcs
+        let svg = r#"<svg width="20mm" height="10mm" viewBox="0 0 20 10">
+            <path d="M0 0H20V10H0Z M8 5A2 2 0 0 1 12 5A2 2 0 0 1 8 5Z"/></svg>"#;
+        let import = crate::import::parse_svg(svg, &Default::default()).unwrap();
+
+        let part = create_extrude_part_full("plate", &import.sketch, &identity(), 2.0, 0.0, false, 0.0).unwrap();
+        // The hole is a 48-gon (two tessellated half circles)
+        let hole = 0.5 * 48.0 * 4.0 * (std::f64::consts::TAU / 48.0).sin();
+        assert!((part.volume() - (200.0 - hole) * 2.0).abs() < 1e-4, "volume {}", part.volume());
+    }
 }
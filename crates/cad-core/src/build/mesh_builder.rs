@@ -0,0 +1,112 @@
+//! Imported meshes (BaseMesh) as solids
+
+use std::collections::HashMap;
+
+use manifold_rs::Mesh;
+use shared::{ImportedMesh, Transform};
+use vcad::Part;
+
+use super::primitives::apply_transform;
+
+/// Check that a mesh is a closed, consistently oriented 2-manifold: every
+/// edge is used by exactly two triangles, once in each direction. The
+/// kernel silently returns an empty solid otherwise, so this gives the user
+/// a reason instead.
+pub fn check_manifold(mesh: &ImportedMesh) -> Result<(), String> {
+    if mesh.indices.is_empty() || !mesh.indices.len().is_multiple_of(3) {
+        return Err("Imported mesh has no triangles".to_string());
+    }
+    let vertex_count = mesh.vertex_count() as u32;
+    if mesh.indices.iter().any(|&i| i >= vertex_count) {
+        return Err("Imported mesh references a missing vertex".to_string());
+    }
+
+    // Directed edge → number of triangles using it
+    let mut edges: HashMap<(u32, u32), u32> = HashMap::new();
+    let mut degenerate = 0;
+    for tri in mesh.indices.chunks_exact(3) {
+        if tri[0] == tri[1] || tri[1] == tri[2] || tri[0] == tri[2] {
+            degenerate += 1;
+            continue;
+        }
+        for k in 0..3 {
+            *edges.entry((tri[k], tri[(k + 1) % 3])).or_default() += 1;
+        }
+    }
+
+    let mut open = 0;
+    let mut invalid = 0;
+    for (&(a, b), &count) in &edges {
+        let reverse = edges.get(&(b, a)).copied().unwrap_or(0);
+        // Visit each undirected edge once
+        if reverse > 0 && a > b {
+            continue;
+        }
+        match (count, reverse) {
+            (1, 1) => {}
+            (1, 0) => open += 1,
+            _ => invalid += 1,
+        }
+    }
+
+    if open == 0 && invalid == 0 && degenerate == 0 {
+        return Ok(());
+    }
+    let problems: Vec<String> = [
+        (open, "open edges"),
+        (invalid, "edges shared by more than two faces or flipped faces"),
+        (degenerate, "degenerate triangles"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, what)| format!("{} {}", count, what))
+    .collect();
+    Err(format!("Imported mesh is not a closed manifold: {}", problems.join(", ")))
+}
+
+/// Create a Part from an imported mesh, placed by `transform`
+pub fn create_mesh_part(id: &str, mesh: &ImportedMesh, transform: &Transform) -> Result<Part, String> {
+    check_manifold(mesh)?;
+
+    let manifold = Mesh::new(&mesh.positions, &mesh.indices).to_manifold();
+    if manifold.is_empty() {
+        return Err("Imported mesh was rejected by the geometry kernel".to_string());
+    }
+    Ok(apply_transform(Part::new(id, manifold), transform))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tetrahedron with outward-facing triangles
+    fn tetrahedron() -> ImportedMesh {
+        ImportedMesh {
+            positions: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+            indices: vec![0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3],
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_check_manifold() {
+        assert_eq!(check_manifold(&tetrahedron()), Ok(()));
+
+        let mut open = tetrahedron();
+        open.indices.truncate(9);
+        assert_eq!(
+            check_manifold(&open).unwrap_err(),
+            "Imported mesh is not a closed manifold: 3 open edges"
+        );
+
+        let mut flipped = tetrahedron();
+        flipped.indices[9..].copy_from_slice(&[1, 3, 2]);
+        assert!(check_manifold(&flipped).unwrap_err().contains("flipped faces"));
+    }
+
+    #[test]
+    fn test_create_mesh_part() {
+        let part = create_mesh_part("tetra", &tetrahedron(), &Transform::new()).unwrap();
+        assert!((part.volume() - 1.0 / 6.0).abs() < 1e-6);
+    }
+}
@@ -2,9 +2,13 @@
 
 use vcad::Part;
 
-use crate::viewport::mesh::MeshData;
+use crate::mesh::MeshData;
 
 const FACE_HIGHLIGHT_COLOR: [f32; 3] = [1.0, 0.6, 0.0];
+const SELECTION_COLOR: [f32; 3] = [0.3, 0.7, 0.9];
+
+/// Colour of bodies without an appearance
+pub const BODY_COLOR: [f32; 3] = [0.6, 0.6, 0.65];
 
 /// Extract MeshData from a vcad Part
 pub fn extract_mesh_data(part: &Part, selected: bool) -> Option<MeshData> {
@@ -24,11 +28,7 @@ pub fn extract_mesh_data(part: &Part, selected: bool) -> Option<MeshData> {
         return None;
     }
 
-    let color = if selected {
-        [0.3_f32, 0.7, 0.9]
-    } else {
-        [0.6, 0.6, 0.65]
-    };
+    let color = if selected { SELECTION_COLOR } else { BODY_COLOR };
 
     let tri_count = indices.len() / 3;
     let mut vertices = Vec::with_capacity(tri_count * 3 * 9);
@@ -76,16 +76,13 @@ pub fn extract_mesh_data(part: &Part, selected: bool) -> Option<MeshData> {
 
 /// Apply selection highlight color to mesh
 pub fn apply_selection_color(mesh: &mut MeshData) {
-    let stride = 9;
-    let selection_color = [0.3_f32, 0.7, 0.9];
-
-    for i in 0..(mesh.vertices.len() / stride) {
-        let color_offset = i * stride + 6;
-        if color_offset + 2 < mesh.vertices.len() {
-            mesh.vertices[color_offset] = selection_color[0];
-            mesh.vertices[color_offset + 1] = selection_color[1];
-            mesh.vertices[color_offset + 2] = selection_color[2];
-        }
+    apply_body_color(mesh, SELECTION_COLOR);
+}
+
+/// Paint every vertex of the mesh with one color (body appearance)
+pub fn apply_body_color(mesh: &mut MeshData, color: [f32; 3]) {
+    for vertex in mesh.vertices.chunks_exact_mut(9) {
+        vertex[6..9].copy_from_slice(&color);
     }
 }
 
@@ -0,0 +1,256 @@
+//! CSG mesh building for V2 Body-based architecture.
+//!
+//! Evaluates body features (primitives, extrudes, revolves, imported meshes,
+//! booleans, fillets, chamfers) into vcad Parts and MeshData. Edge and face references
+//! are resolved by name at rebuild time (see [`topology`]); feature properties
+//! bound to parameters are re-evaluated before every build.
+
+mod body_builder;
+mod extrude_builder;
+mod fillet_builder;
+mod mesh_builder;
+mod mesh_extraction;
+mod primitives;
+mod sketch_geometry;
+pub mod topology;
+
+pub use body_builder::{build_body_mesh_data, build_body_part};
+pub use extrude_builder::{create_extrude_part_full, create_revolve_part_from_sketch_with_axis};
+pub use mesh_builder::{check_manifold, create_mesh_part};
+pub use mesh_extraction::{apply_body_color, apply_face_highlight, apply_selection_color, extract_mesh_data, BODY_COLOR};
+pub use primitives::{apply_transform, create_primitive, DEFAULT_SEGMENTS};
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use shared::SceneDescriptionV2;
+
+use crate::mesh::MeshData;
+
+/// Scene with feature bindings (`Body::bindings`) evaluated, plus binding
+/// errors per body. Borrows the scene unchanged when nothing is bound.
+pub fn evaluate_feature_bindings(
+    scene: &SceneDescriptionV2,
+) -> (Cow<'_, SceneDescriptionV2>, HashMap<String, Vec<String>>) {
+    let mut errors: HashMap<String, Vec<String>> = HashMap::new();
+    if scene.bodies.iter().all(|b| b.bindings.is_empty()) {
+        return (Cow::Borrowed(scene), errors);
+    }
+
+    let mut evaluated = scene.clone();
+    for error in evaluated.apply_feature_bindings() {
+        errors.entry(error.body_id.clone()).or_default().push(error.to_string());
+    }
+    (Cow::Owned(evaluated), errors)
+}
+
+/// Build meshes from V2 scene (body-based)
+pub fn build_scene_meshes_v2(
+    scene: &SceneDescriptionV2,
+    selected_ids: &[String],
+) -> (HashMap<String, MeshData>, HashMap<String, String>) {
+    let mut meshes: HashMap<String, MeshData> = HashMap::new();
+    let mut errors: HashMap<String, String> = HashMap::new();
+
+    let (scene, mut binding_errors) = evaluate_feature_bindings(scene);
+
+    for body in &scene.bodies {
+        if !body.visible {
+            continue;
+        }
+
+        let is_selected = selected_ids.contains(&body.id);
+
+        let mut body_errors = binding_errors.remove(&body.id).unwrap_or_default();
+        match body_builder::build_body_mesh_data(body, is_selected, &scene.bodies, &mut body_errors) {
+            Ok(Some(mut mesh_data)) => {
+                // Selection highlight wins over the body colour
+                if let (false, Some(appearance)) = (is_selected, &body.appearance) {
+                    apply_body_color(&mut mesh_data, appearance.color);
+                }
+                meshes.insert(body.id.clone(), mesh_data);
+            }
+            Ok(None) => {
+                // Body doesn't produce 3D geometry (e.g., sketch-only body)
+            }
+            Err(msg) => {
+                body_errors.insert(0, msg);
+            }
+        }
+
+        if !body_errors.is_empty() {
+            errors.insert(body.id.clone(), body_errors.join("; "));
+        }
+    }
+
+    (meshes, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::*;
+
+    fn empty_scene() -> SceneDescriptionV2 {
+        SceneDescriptionV2 {
+            version: 2,
+            bodies: vec![],
+            body_operations: vec![],
+            parameters: HashMap::new(),
+            design_table: None,
+        }
+    }
+
+    fn scene_with_cube() -> SceneDescriptionV2 {
+        SceneDescriptionV2 {
+            version: 2,
+            bodies: vec![Body {
+                id: "body1".to_string(),
+                name: "Cube".to_string(),
+                features: vec![Feature::BasePrimitive {
+                    id: "feat1".to_string(),
+                    primitive: Primitive::Cube {
+                        width: 1.0,
+                        height: 1.0,
+                        depth: 1.0,
+                    },
+                    transform: Transform::new(),
+                }],
+                visible: true,
+                parameters: HashMap::new(),
+                bindings: HashMap::new(),
+                appearance: None,
+            }],
+            body_operations: vec![],
+            parameters: HashMap::new(),
+            design_table: None,
+        }
+    }
+
+    #[test]
+    fn test_build_empty_scene() {
+        let scene = empty_scene();
+        let (meshes, errors) = build_scene_meshes_v2(&scene, &[]);
+        assert!(meshes.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_build_single_body() {
+        let scene = scene_with_cube();
+        let (meshes, errors) = build_scene_meshes_v2(&scene, &[]);
+        assert!(errors.is_empty());
+        assert_eq!(meshes.len(), 1);
+        assert!(meshes.contains_key("body1"));
+    }
+
+    #[test]
+    fn test_build_hidden_body_excluded() {
+        let mut scene = scene_with_cube();
+        scene.bodies[0].visible = false;
+        let (meshes, _) = build_scene_meshes_v2(&scene, &[]);
+        assert!(meshes.is_empty());
+    }
+
+    #[test]
+    fn test_build_uses_body_color() {
+        let mut scene = scene_with_cube();
+        scene.bodies[0].appearance = Some(Appearance { color: [1.0, 0.0, 0.0], ..Default::default() });
+
+        let (meshes, _) = build_scene_meshes_v2(&scene, &[]);
+        assert!(meshes["body1"].vertices.chunks_exact(9).all(|v| v[6..9] == [1.0, 0.0, 0.0]));
+
+        // Selected bodies keep the highlight colour
+        let (meshes, _) = build_scene_meshes_v2(&scene, &["body1".to_string()]);
+        assert_eq!(meshes["body1"].vertices[6..9], [0.3, 0.7, 0.9]);
+    }
+
+    #[test]
+    fn test_build_applies_feature_bindings() {
+        let mut scene = scene_with_cube();
+        scene.parameters.insert(
+            "size".to_string(),
+            Parameter {
+                name: "size".to_string(),
+                value: ParameterValue::Number { value: 3.0 },
+                unit: None,
+                description: None,
+            },
+        );
+        scene.bodies[0].bindings.insert("feat1.width".to_string(), "size * 2".to_string());
+
+        let (evaluated, binding_errors) = evaluate_feature_bindings(&scene);
+        assert!(binding_errors.is_empty());
+        assert_eq!(evaluated.bodies[0].features[0].numeric_property("width"), Some(6.0));
+
+        let (meshes, errors) = build_scene_meshes_v2(&scene, &[]);
+        assert!(errors.is_empty());
+        let mesh = &meshes["body1"];
+        let xs = mesh.vertices.chunks(9).map(|v| v[0]);
+        let width = xs.clone().fold(f32::MIN, f32::max) - xs.fold(f32::MAX, f32::min);
+        assert!((width - 6.0).abs() < 1e-4);
+    }
+
+    fn mesh_body(id: &str, indices: Vec<u32>) -> Body {
+        Body {
+            id: id.to_string(),
+            name: id.to_string(),
+            features: vec![Feature::BaseMesh {
+                id: format!("{}_mesh", id),
+                mesh: ImportedMesh {
+                    positions: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+                    indices,
+                    source: Some("tetra.stl".to_string()),
+                },
+                transform: Transform::new(),
+            }],
+            visible: false,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        }
+    }
+
+    #[test]
+    fn test_build_base_mesh_as_boolean_tool() {
+        let mut scene = scene_with_cube();
+        scene.bodies.push(mesh_body("tool", vec![0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3]));
+        scene.bodies[0].features.push(Feature::BooleanModify {
+            id: "cut".to_string(),
+            op: BooleanOp::Difference,
+            tool_body_id: "tool".to_string(),
+        });
+
+        let (meshes, errors) = build_scene_meshes_v2(&scene, &[]);
+        assert!(errors.is_empty(), "{:?}", errors);
+        // The tetrahedron's corner is cut out of the cube
+        assert!(meshes["body1"].indices.len() / 3 > 12);
+    }
+
+    #[test]
+    fn test_build_non_manifold_mesh_is_error() {
+        let mut scene = empty_scene();
+        let mut open = mesh_body("open", vec![0, 2, 1, 0, 1, 3, 0, 3, 2]);
+        open.visible = true;
+        scene.bodies.push(open);
+
+        let (meshes, errors) = build_scene_meshes_v2(&scene, &[]);
+        assert!(meshes.is_empty());
+        assert!(errors["open"].contains("not a closed manifold"), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_extract_mesh_data_cube() {
+        let part = primitives::create_primitive(
+            "c",
+            &Primitive::Cube {
+                width: 1.0,
+                height: 1.0,
+                depth: 1.0,
+            },
+        );
+        let mesh = mesh_extraction::extract_mesh_data(&part, false).unwrap();
+        assert_eq!(mesh.vertices.len() % 9, 0);
+        assert_eq!(mesh.indices.len() % 3, 0);
+    }
+}
@@ -15,7 +15,7 @@ pub fn sketch_bounds(sketch: &Sketch) -> Option<(f64, f64, f64, f64)> {
 
     for elem in &sketch.elements {
         match elem {
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 min_x = min_x.min(start.x).min(end.x);
                 min_y = min_y.min(start.y).min(end.y);
                 max_x = max_x.max(start.x).max(end.x);
@@ -25,13 +25,14 @@ pub fn sketch_bounds(sketch: &Sketch) -> Option<(f64, f64, f64, f64)> {
                 corner,
                 width,
                 height,
+                ..
             } => {
                 min_x = min_x.min(corner.x);
                 min_y = min_y.min(corner.y);
                 max_x = max_x.max(corner.x + width);
                 max_y = max_y.max(corner.y + height);
             }
-            SketchElement::Circle { center, radius } => {
+            SketchElement::Circle { center, radius, .. } => {
                 min_x = min_x.min(center.x - radius);
                 min_y = min_y.min(center.y - radius);
                 max_x = max_x.max(center.x + radius);
@@ -0,0 +1,877 @@
+//! Persistent topological naming
+//!
+//! Faces are named after the feature (and sketch element) that generated
+//! them, see `shared::topology`. Their world-space surfaces are recomputed
+//! from the feature's *current* parameters on every rebuild, so references
+//! survive edits to earlier features. Edges are resolved against the mesh of
+//! the part being modified: an edge is every mesh edge shared by a triangle
+//! of one face and a triangle of the other.
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use shared::{Body, CapSide, EdgeRef, FaceRef, Feature, Primitive, PrimitiveFace, Sketch, SketchElement, SketchPlane};
+
+use crate::helpers::{get_body_base_transform, sketch_placement};
+use crate::mesh::MeshData;
+
+use super::fillet_builder::FilletEdge;
+
+/// Distance tolerance for "point lies on surface"
+const SURFACE_TOL: f64 = 1e-3;
+/// Tolerance on `1 - |cos|` for parallel normals
+const PARALLEL_TOL: f64 = 1e-3;
+/// Must match the overshoot used by `create_extrude_part_full` for cuts
+const CUT_OVERSHOOT: f64 = 0.01;
+/// Quantization scale for matching mesh vertices
+const QUANT_SCALE: f64 = 10000.0;
+
+/// Bounded direction on a planar face: `dot(p - origin, dir)` in `[min, max]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extent {
+    pub dir: [f64; 3],
+    pub min: f64,
+    pub max: f64,
+}
+
+/// World-space surface of a named face
+#[derive(Debug, Clone, PartialEq)]
+pub enum Surface {
+    /// Planar face. `extent` limits side and primitive faces; caps are unbounded
+    Plane {
+        origin: [f64; 3],
+        normal: [f64; 3],
+        extent: Option<[Extent; 2]>,
+    },
+    /// Cylindrical side of an extruded circle or arc. `min`/`max` are
+    /// measured along the unit `axis` from `center`
+    Cylinder {
+        axis: [f64; 3],
+        center: [f64; 3],
+        radius: f64,
+        min: f64,
+        max: f64,
+        /// Angular span of an arc; `None` for a full circle
+        sweep: Option<Sweep>,
+    },
+}
+
+/// Angular span of an arc side face, measured from `u` towards `v`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sweep {
+    pub u: [f64; 3],
+    pub v: [f64; 3],
+    pub start: f64,
+    pub span: f64,
+}
+
+impl Sweep {
+    /// Check whether the radial direction `d` lies within the span
+    fn contains(&self, d: [f64; 3], radius: f64) -> bool {
+        let tol = SURFACE_TOL / radius.max(SURFACE_TOL);
+        let from_start = (dot(d, self.v).atan2(dot(d, self.u)) - self.start).rem_euclid(TAU);
+        from_start <= self.span + tol || from_start >= TAU - tol
+    }
+}
+
+impl Surface {
+    /// Check whether a point lies on this (bounded) surface
+    fn contains(&self, p: [f64; 3]) -> bool {
+        match self {
+            Surface::Plane { origin, normal, extent } => {
+                let d = sub(p, *origin);
+                if dot(d, *normal).abs() > SURFACE_TOL {
+                    return false;
+                }
+                extent.as_ref().is_none_or(|ext| {
+                    ext.iter().all(|e| {
+                        let t = dot(d, e.dir);
+                        t >= e.min - SURFACE_TOL && t <= e.max + SURFACE_TOL
+                    })
+                })
+            }
+            Surface::Cylinder { axis, center, radius, min, max, sweep } => {
+                let (d, h) = radial(p, *axis, *center);
+                (length(d) - radius).abs() <= SURFACE_TOL * radius.max(1.0)
+                    && h >= min - SURFACE_TOL
+                    && h <= max + SURFACE_TOL
+                    && sweep.as_ref().is_none_or(|s| s.contains(d, *radius))
+            }
+        }
+    }
+
+    /// Check whether a mesh triangle belongs to this surface
+    fn contains_triangle(&self, tri: &[[f64; 3]; 3], normal: [f64; 3]) -> bool {
+        let parallel = match self {
+            Surface::Plane { normal: n, .. } => 1.0 - dot(normal, *n).abs() <= PARALLEL_TOL,
+            // Cylinder walls have normals perpendicular to the axis; this
+            // rejects cap triangles whose corners all sit on the rim
+            Surface::Cylinder { axis, .. } => dot(normal, *axis).abs() <= 0.01,
+        };
+        if !parallel || !tri.iter().all(|p| self.contains_unbounded(*p)) {
+            return false;
+        }
+        let centroid = [
+            (tri[0][0] + tri[1][0] + tri[2][0]) / 3.0,
+            (tri[0][1] + tri[1][1] + tri[2][1]) / 3.0,
+            (tri[0][2] + tri[1][2] + tri[2][2]) / 3.0,
+        ];
+        self.contains(centroid)
+    }
+
+    fn contains_unbounded(&self, p: [f64; 3]) -> bool {
+        match self {
+            Surface::Plane { origin, normal, .. } => dot(sub(p, *origin), *normal).abs() <= SURFACE_TOL,
+            Surface::Cylinder { axis, center, radius, .. } => {
+                (length(radial(p, *axis, *center).0) - radius).abs() <= SURFACE_TOL * radius.max(1.0)
+            }
+        }
+    }
+
+    /// Same underlying geometry (used to collapse coincident faces when naming)
+    fn coincides(&self, other: &Surface) -> bool {
+        match (self, other) {
+            (Surface::Plane { origin: o1, normal: n1, .. }, Surface::Plane { origin: o2, normal: n2, .. }) => {
+                1.0 - dot(*n1, *n2).abs() <= PARALLEL_TOL && dot(sub(*o2, *o1), *n1).abs() <= SURFACE_TOL
+            }
+            (
+                Surface::Cylinder { axis: a1, center: c1, radius: r1, .. },
+                Surface::Cylinder { axis: a2, center: c2, radius: r2, .. },
+            ) => {
+                1.0 - dot(*a1, *a2).abs() <= PARALLEL_TOL
+                    && (r1 - r2).abs() <= SURFACE_TOL
+                    && length(cross(sub(*c2, *c1), *a1)) <= SURFACE_TOL
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Compute the world-space surface of a named face from current feature parameters
+pub fn face_surface(body: &Body, face: &FaceRef) -> Result<Surface, String> {
+    let feature_id = face.feature_id();
+    let feature = body
+        .features
+        .iter()
+        .find(|f| f.id() == feature_id)
+        .ok_or_else(|| format!("feature '{}' no longer exists", feature_id))?;
+
+    match face {
+        FaceRef::Primitive { face, .. } => {
+            let Feature::BasePrimitive { primitive, transform, .. } = feature else {
+                return Err(format!("feature '{}' is not a primitive", feature_id));
+            };
+            let Primitive::Cube { width, height, depth } = primitive else {
+                return Err(format!("only cube faces of '{}' can be referenced", feature_id));
+            };
+            Ok(cube_face_surface([*width, *height, *depth], transform, *face))
+        }
+        FaceRef::ExtrudeCap { side, .. } => {
+            let span = extrude_span(body, feature)?;
+            let height = match (side, span.cut) {
+                (CapSide::Start, true) => {
+                    return Err(format!("cut '{}' has no start face", feature_id));
+                }
+                (CapSide::Start, false) => span.min,
+                (CapSide::End, false) => span.max,
+                // Floor of the pocket: the end of the span away from the sketch plane
+                (CapSide::End, true) if span.reversed => span.max,
+                (CapSide::End, true) => span.min,
+            };
+            Ok(Surface::Plane { origin: span.point([0.0, 0.0], height), normal: span.axis(), extent: None })
+        }
+        FaceRef::ExtrudeSide { element_id, segment, .. } => {
+            let span = extrude_span(body, feature)?;
+            if span.draft {
+                return Err(format!("side faces of drafted extrude '{}' cannot be referenced", feature_id));
+            }
+            let element = span
+                .sketch
+                .element_by_id(element_id)
+                .ok_or_else(|| format!("sketch element '{}' no longer exists", element_id))?;
+            side_surface(&span, element, *segment)
+                .ok_or_else(|| format!("element '{}' has no side face {}", element_id, segment))
+        }
+    }
+}
+
+/// Span of an extrude feature: heights above the sketch plane, placed in
+/// world space by the sketch transform
+struct ExtrudeSpan {
+    sketch: Sketch,
+    transform: shared::Transform,
+    min: f64,
+    max: f64,
+    cut: bool,
+    reversed: bool,
+    draft: bool,
+}
+
+impl ExtrudeSpan {
+    /// Sketch point at height `h` above the sketch plane → world, in the same
+    /// order as `apply_transform`: scale → rotate → translate
+    fn point(&self, p: [f64; 2], h: f64) -> [f64; 3] {
+        let local = lift(&self.sketch.plane, p, self.sketch.offset + h);
+        add(self.direction(local), self.transform.position)
+    }
+
+    /// Sketch-space vector → world (scale and rotation only)
+    fn direction(&self, v: [f64; 3]) -> [f64; 3] {
+        let s = self.transform.scale;
+        rotate_euler([v[0] * s[0], v[1] * s[1], v[2] * s[2]], self.transform.rotation)
+    }
+
+    /// Unit extrude direction in world space
+    fn axis(&self) -> [f64; 3] {
+        normalize(self.direction(unit(axis_index(&self.sketch.plane))))
+    }
+
+    /// World-space extent along `axis()` from `origin` (at height 0)
+    fn extent_from(&self, p: [f64; 2], origin: [f64; 3]) -> (f64, f64) {
+        let axis = self.axis();
+        let a = dot(sub(self.point(p, self.min), origin), axis);
+        let b = dot(sub(self.point(p, self.max), origin), axis);
+        (a.min(b), a.max(b))
+    }
+}
+
+fn extrude_span(body: &Body, feature: &Feature) -> Result<ExtrudeSpan, String> {
+    let (sketch, transform, height, height_backward, cut, draft_angle) = match feature {
+        Feature::BaseExtrude { sketch, sketch_transform, height, height_backward, draft_angle, .. } => {
+            (sketch.clone(), sketch_transform.clone(), *height, *height_backward, false, *draft_angle)
+        }
+        Feature::Extrude { id, sketch_id, height, height_backward, cut, draft_angle } => {
+            let (sketch, transform) = find_sketch(body, sketch_id)
+                .ok_or_else(|| format!("sketch '{}' of '{}' no longer exists", sketch_id, id))?;
+            let placement = sketch_placement(&get_body_base_transform(body), transform);
+            (sketch.clone(), placement, *height, *height_backward, *cut, *draft_angle)
+        }
+        _ => return Err(format!("feature '{}' is not an extrude", feature.id())),
+    };
+
+    let reversed = cut && should_reverse(&sketch);
+    let (min, max) = if !cut {
+        (-height_backward, height)
+    } else if reversed {
+        (-CUT_OVERSHOOT, height - CUT_OVERSHOOT)
+    } else {
+        (-height + CUT_OVERSHOOT, CUT_OVERSHOOT)
+    };
+
+    Ok(ExtrudeSpan { sketch, transform, min, max, cut, reversed, draft: draft_angle.abs() >= 0.01 })
+}
+
+fn side_surface(span: &ExtrudeSpan, element: &SketchElement, segment: usize) -> Option<Surface> {
+    let (a, b) = match element {
+        SketchElement::Circle { center, radius, .. } if segment == 0 => {
+            return arc_surface(span, [center.x, center.y], *radius, None);
+        }
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } if segment == 0 => {
+            // Counter-clockwise from start to end, as in `tessellate_arc`
+            let sweep = (end_angle - start_angle).rem_euclid(TAU);
+            return arc_surface(span, [center.x, center.y], *radius, Some((*start_angle, sweep)));
+        }
+        SketchElement::Line { start, end, .. } if segment == 0 => ([start.x, start.y], [end.x, end.y]),
+        SketchElement::Rectangle { corner, width, height, .. } if segment < 4 => {
+            let corners = [
+                [corner.x, corner.y],
+                [corner.x + width, corner.y],
+                [corner.x + width, corner.y + height],
+                [corner.x, corner.y + height],
+            ];
+            (corners[segment], corners[(segment + 1) % 4])
+        }
+        SketchElement::Polyline { points, .. } if segment < points.len() && points.len() >= 2 => {
+            let p = &points[segment];
+            let q = &points[(segment + 1) % points.len()];
+            ([p.x, p.y], [q.x, q.y])
+        }
+        _ => return None,
+    };
+
+    let origin = span.point(a, 0.0);
+    let along = sub(span.point(b, 0.0), origin);
+    let length = length(along);
+    if length < 1e-9 {
+        return None;
+    }
+    let dir = scale(along, 1.0 / length);
+    let axis = span.axis();
+    let (min, max) = span.extent_from(a, origin);
+    Some(Surface::Plane {
+        origin,
+        normal: cross(dir, axis),
+        extent: Some([Extent { dir, min: 0.0, max: length }, Extent { dir: axis, min, max }]),
+    })
+}
+
+/// Side of an extruded circle (`sweep: None`) or arc (`(start, span)` in radians)
+fn arc_surface(span: &ExtrudeSpan, center: [f64; 2], radius: f64, sweep: Option<(f64, f64)>) -> Option<Surface> {
+    let plane = &span.sketch.plane;
+    let u = span.direction(lift(plane, [1.0, 0.0], 0.0));
+    let v = span.direction(lift(plane, [0.0, 1.0], 0.0));
+    let (lu, lv) = (length(u), length(v));
+    // Non-uniform in-plane scale turns the circle into an ellipse
+    if (lu - lv).abs() > 1e-9 * lu.max(lv) || lu < 1e-12 {
+        return None;
+    }
+    let origin = span.point(center, 0.0);
+    let (min, max) = span.extent_from(center, origin);
+    Some(Surface::Cylinder {
+        axis: span.axis(),
+        center: origin,
+        radius: radius * lu,
+        min,
+        max,
+        sweep: sweep.map(|(start, span)| Sweep { u: scale(u, 1.0 / lu), v: scale(v, 1.0 / lv), start, span }),
+    })
+}
+
+fn cube_face_surface(size: [f64; 3], transform: &shared::Transform, face: PrimitiveFace) -> Surface {
+    let (axis, sign) = match face {
+        PrimitiveFace::XMin => (0, -1.0),
+        PrimitiveFace::XMax => (0, 1.0),
+        PrimitiveFace::YMin => (1, -1.0),
+        PrimitiveFace::YMax => (1, 1.0),
+        PrimitiveFace::ZMin => (2, -1.0),
+        PrimitiveFace::ZMax => (2, 1.0),
+    };
+    // Same order as `apply_transform`: scale → rotate (X, then Y, then Z) → translate
+    let half = [
+        size[0] * transform.scale[0] / 2.0,
+        size[1] * transform.scale[1] / 2.0,
+        size[2] * transform.scale[2] / 2.0,
+    ];
+    let rotate = |v: [f64; 3]| rotate_euler(v, transform.rotation);
+    let mut local = [0.0; 3];
+    local[axis] = sign * half[axis];
+    let origin = add(rotate(local), transform.position);
+    let normal = rotate(scale(unit(axis), sign * transform.scale[axis].signum()));
+
+    let others: Vec<usize> = (0..3).filter(|i| *i != axis).collect();
+    let extent = |i: usize| Extent { dir: rotate(unit(i)), min: -half[i].abs(), max: half[i].abs() };
+    Surface::Plane { origin, normal, extent: Some([extent(others[0]), extent(others[1])]) }
+}
+
+/// Resolve a named edge against the mesh of the part it is applied to
+pub fn resolve_edge(body: &Body, mesh: &MeshData, edge: &EdgeRef) -> Result<Vec<FilletEdge>, String> {
+    let surfaces = [
+        face_surface(body, &edge.faces[0]).map_err(|e| format!("face {}: {}", edge.faces[0], e))?,
+        face_surface(body, &edge.faces[1]).map_err(|e| format!("face {}: {}", edge.faces[1], e))?,
+    ];
+
+    let triangles = mesh_triangles(mesh);
+    let mut membership = vec![[false; 2]; triangles.len()];
+    for (i, (tri, normal)) in triangles.iter().enumerate() {
+        for (k, surface) in surfaces.iter().enumerate() {
+            membership[i][k] = surface.contains_triangle(tri, *normal);
+        }
+    }
+
+    // Mesh edge (quantized, undirected) → adjacent triangles
+    let mut adjacency: HashMap<([i64; 3], [i64; 3]), Vec<usize>> = HashMap::new();
+    for (i, (tri, _)) in triangles.iter().enumerate() {
+        if !membership[i][0] && !membership[i][1] {
+            continue;
+        }
+        for j in 0..3 {
+            adjacency.entry(edge_key(tri[j], tri[(j + 1) % 3])).or_default().push(i);
+        }
+    }
+
+    let mut pieces = Vec::new();
+    for tris in adjacency.values() {
+        let on_first = tris.iter().find(|t| membership[**t][0]);
+        let on_second = tris.iter().find(|t| membership[**t][1] && Some(*t) != on_first);
+        let (Some(&t1), Some(&t2)) = (on_first, on_second) else {
+            continue;
+        };
+        let (start, end) = shared_edge(&triangles[t1].0, &triangles[t2].0);
+        pieces.push(FilletEdge {
+            start,
+            end,
+            normal1: triangles[t1].1,
+            normal2: Some(triangles[t2].1),
+        });
+    }
+
+    if pieces.is_empty() {
+        return Err(format!("edge {} no longer exists", edge));
+    }
+    Ok(merge_collinear(pieces))
+}
+
+/// Name a picked edge by the two latest-created faces it lies on
+pub fn name_edge(body: &Body, start: [f64; 3], end: [f64; 3]) -> Option<EdgeRef> {
+    let mid = scale(add(start, end), 0.5);
+    let mut found: Vec<(FaceRef, Surface)> = Vec::new();
+    for (face, surface) in body_faces(body).into_iter().rev() {
+        if ![start, mid, end].iter().all(|p| surface.contains(*p)) {
+            continue;
+        }
+        if found.iter().any(|(_, s)| s.coincides(&surface)) {
+            continue;
+        }
+        found.push((face, surface));
+        if found.len() == 2 {
+            let second = found.pop()?.0;
+            let first = found.pop()?.0;
+            return Some(EdgeRef { faces: [first, second] });
+        }
+    }
+    None
+}
+
+/// Name a picked planar face by the latest-created feature that generated it
+pub fn name_face(body: &Body, point: [f64; 3], normal: [f64; 3]) -> Option<FaceRef> {
+    body_faces(body).into_iter().rev().find_map(|(face, surface)| match &surface {
+        Surface::Plane { normal: n, .. }
+            if 1.0 - dot(*n, normal).abs() <= PARALLEL_TOL && surface.contains(point) =>
+        {
+            Some(face)
+        }
+        _ => None,
+    })
+}
+
+/// Re-place sketches created on faces onto the current position of those faces.
+///
+/// Returns a copy of the body with updated sketch offsets; errors are reported
+/// per sketch and leave the stored offset unchanged.
+pub fn resolve_sketch_faces(body: &Body, errors: &mut Vec<String>) -> Body {
+    let mut resolved = body.clone();
+    for i in 0..resolved.features.len() {
+        let Feature::Sketch { id, sketch, transform } = &resolved.features[i] else {
+            continue;
+        };
+        let Some(face) = &sketch.face else {
+            continue;
+        };
+
+        // Offset is measured along the sketch normal, before the sketch's own scale
+        let placement = sketch_placement(&get_body_base_transform(&resolved), transform);
+        let axis = axis_index(&sketch.plane);
+        let s = placement.scale[axis];
+        let sketch_normal = normalize(rotate_euler(scale(unit(axis), s), placement.rotation));
+        let offset = match face_surface(&resolved, face) {
+            Ok(Surface::Plane { origin, normal, .. })
+                if 1.0 - dot(normal, sketch_normal).abs() <= PARALLEL_TOL && s.abs() > 1e-12 =>
+            {
+                Ok(dot(sub(origin, placement.position), sketch_normal) / s.abs())
+            }
+            Ok(_) => Err(format!("face {} is not parallel to the sketch plane", face)),
+            Err(e) => Err(format!("face {}: {}", face, e)),
+        };
+
+        match offset {
+            Ok(offset) => {
+                if let Feature::Sketch { sketch, .. } = &mut resolved.features[i] {
+                    sketch.offset = offset;
+                }
+            }
+            Err(e) => errors.push(format!("Sketch '{}': {}", id, e)),
+        }
+    }
+    resolved
+}
+
+/// Every face a body's features can generate, in feature order
+fn body_faces(body: &Body) -> Vec<(FaceRef, Surface)> {
+    let mut refs = Vec::new();
+    for feature in &body.features {
+        match feature {
+            Feature::BasePrimitive { id, primitive: Primitive::Cube { .. }, .. } => {
+                for face in [
+                    PrimitiveFace::XMin,
+                    PrimitiveFace::XMax,
+                    PrimitiveFace::YMin,
+                    PrimitiveFace::YMax,
+                    PrimitiveFace::ZMin,
+                    PrimitiveFace::ZMax,
+                ] {
+                    refs.push(FaceRef::Primitive { feature_id: id.clone(), face });
+                }
+            }
+            Feature::BaseExtrude { id, .. } | Feature::Extrude { id, .. } => {
+                let Ok(span) = extrude_span(body, feature) else { continue };
+                for side in [CapSide::Start, CapSide::End] {
+                    refs.push(FaceRef::ExtrudeCap { feature_id: id.clone(), side });
+                }
+                for (index, element) in span.sketch.elements.iter().enumerate() {
+                    let Some(element_id) = element.id() else { continue };
+                    if span.sketch.is_construction(index) {
+                        continue;
+                    }
+                    let segments = match element {
+                        SketchElement::Rectangle { .. } => 4,
+                        SketchElement::Polyline { points, .. } => points.len(),
+                        _ => 1,
+                    };
+                    for segment in 0..segments {
+                        refs.push(FaceRef::ExtrudeSide {
+                            feature_id: id.clone(),
+                            element_id: element_id.to_string(),
+                            segment,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    refs.into_iter()
+        .filter_map(|face| face_surface(body, &face).ok().map(|s| (face, s)))
+        .collect()
+}
+
+fn find_sketch<'a>(body: &'a Body, sketch_id: &str) -> Option<(&'a Sketch, &'a shared::Transform)> {
+    body.features.iter().find_map(|f| match f {
+        Feature::Sketch { id, sketch, transform } if id == sketch_id => Some((sketch, transform)),
+        Feature::BaseExtrude { id, sketch, sketch_transform, .. }
+        | Feature::BaseRevolve { id, sketch, sketch_transform, .. }
+            if id == sketch_id =>
+        {
+            Some((sketch, sketch_transform))
+        }
+        _ => None,
+    })
+}
+
+/// Same rule as `extrude_builder::should_reverse_cut_direction`
+fn should_reverse(sketch: &Sketch) -> bool {
+    sketch.face_normal.is_some_and(|n| n[axis_index(&sketch.plane)] < 0.0)
+}
+
+fn axis_index(plane: &SketchPlane) -> usize {
+    match plane {
+        SketchPlane::Xy => 2,
+        SketchPlane::Xz => 1,
+        SketchPlane::Yz => 0,
+    }
+}
+
+/// Sketch point → sketch space, with a coordinate along the extrude axis
+fn lift(plane: &SketchPlane, p: [f64; 2], axis_coord: f64) -> [f64; 3] {
+    match plane {
+        SketchPlane::Xy => [p[0], p[1], axis_coord],
+        SketchPlane::Xz => [p[0], axis_coord, p[1]],
+        SketchPlane::Yz => [axis_coord, p[0], p[1]],
+    }
+}
+
+fn mesh_triangles(mesh: &MeshData) -> Vec<([[f64; 3]; 3], [f64; 3])> {
+    let stride = 9;
+    let vertex = |i: u32| {
+        let o = i as usize * stride;
+        [mesh.vertices[o] as f64, mesh.vertices[o + 1] as f64, mesh.vertices[o + 2] as f64]
+    };
+    mesh.indices
+        .chunks_exact(3)
+        .filter_map(|t| {
+            let tri = [vertex(t[0]), vertex(t[1]), vertex(t[2])];
+            let n = cross(sub(tri[1], tri[0]), sub(tri[2], tri[0]));
+            let len = dot(n, n).sqrt();
+            (len > 1e-12).then(|| (tri, scale(n, 1.0 / len)))
+        })
+        .collect()
+}
+
+fn quantize(p: [f64; 3]) -> [i64; 3] {
+    [
+        (p[0] * QUANT_SCALE).round() as i64,
+        (p[1] * QUANT_SCALE).round() as i64,
+        (p[2] * QUANT_SCALE).round() as i64,
+    ]
+}
+
+fn edge_key(a: [f64; 3], b: [f64; 3]) -> ([i64; 3], [i64; 3]) {
+    let (qa, qb) = (quantize(a), quantize(b));
+    if qa <= qb { (qa, qb) } else { (qb, qa) }
+}
+
+/// The two vertices of `a` that also belong to `b`
+fn shared_edge(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> ([f64; 3], [f64; 3]) {
+    let in_b = |p: &[f64; 3]| b.iter().any(|q| quantize(*q) == quantize(*p));
+    let shared: Vec<[f64; 3]> = a.iter().filter(|p| in_b(p)).copied().collect();
+    (shared[0], shared[shared.len().min(2) - 1])
+}
+
+/// Join consecutive pieces that continue each other in the same direction
+fn merge_collinear(mut pieces: Vec<FilletEdge>) -> Vec<FilletEdge> {
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'outer: for i in 0..pieces.len() {
+            for j in 0..pieces.len() {
+                if i == j || !same_normals(&pieces[i], &pieces[j]) {
+                    continue;
+                }
+                let (a, b) = (&pieces[i], &pieces[j]);
+                let da = sub(a.end, a.start);
+                let db = sub(b.end, b.start);
+                let parallel = 1.0 - dot(da, db).abs() / (dot(da, da).sqrt() * dot(db, db).sqrt()) <= PARALLEL_TOL;
+                if !parallel {
+                    continue;
+                }
+                let joined = if quantize(a.end) == quantize(b.start) {
+                    Some((a.start, b.end))
+                } else if quantize(a.end) == quantize(b.end) {
+                    Some((a.start, b.start))
+                } else {
+                    None
+                };
+                if let Some((start, end)) = joined {
+                    pieces[i].start = start;
+                    pieces[i].end = end;
+                    pieces.remove(j);
+                    merged = true;
+                    break 'outer;
+                }
+            }
+        }
+    }
+    pieces
+}
+
+fn same_normals(a: &FilletEdge, b: &FilletEdge) -> bool {
+    let close = |x: [f64; 3], y: [f64; 3]| 1.0 - dot(x, y) <= PARALLEL_TOL;
+    close(a.normal1, b.normal1)
+        && match (a.normal2, b.normal2) {
+            (Some(x), Some(y)) => close(x, y),
+            _ => false,
+        }
+}
+
+/// Euler rotation in degrees: X, then Y, then Z (Manifold convention)
+fn rotate_euler(v: [f64; 3], degrees: [f64; 3]) -> [f64; 3] {
+    let [rx, ry, rz] = degrees.map(f64::to_radians);
+    let (s, c) = rx.sin_cos();
+    let v = [v[0], c * v[1] - s * v[2], s * v[1] + c * v[2]];
+    let (s, c) = ry.sin_cos();
+    let v = [c * v[0] + s * v[2], v[1], -s * v[0] + c * v[2]];
+    let (s, c) = rz.sin_cos();
+    [c * v[0] - s * v[1], s * v[0] + c * v[1], v[2]]
+}
+
+/// Offset from the axis line through `center` and coordinate along it
+fn radial(p: [f64; 3], axis: [f64; 3], center: [f64; 3]) -> ([f64; 3], f64) {
+    let d = sub(p, center);
+    let h = dot(d, axis);
+    (sub(d, scale(axis, h)), h)
+}
+
+fn unit(axis: usize) -> [f64; 3] {
+    let mut v = [0.0; 3];
+    v[axis] = 1.0;
+    v
+}
+
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: [f64; 3], k: f64) -> [f64; 3] {
+    [a[0] * k, a[1] * k, a[2] * k]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f64; 3]) -> [f64; 3] {
+    scale(a, 1.0 / length(a))
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{Point2D, Transform};
+    use std::collections::HashMap as Map;
+
+    fn cube_body(size: f64) -> Body {
+        Body {
+            id: "b".to_string(),
+            name: "Cube".to_string(),
+            visible: true,
+            features: vec![Feature::BasePrimitive {
+                id: "cube".to_string(),
+                primitive: Primitive::Cube { width: size, height: size, depth: size },
+                transform: Transform::new(),
+            }],
+            parameters: Map::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        }
+    }
+
+    fn with_boss(mut body: Body, height: f64) -> Body {
+        let sketch = Sketch {
+            offset: 1.0,
+            elements: vec![SketchElement::Rectangle {
+                id: Some("r".to_string()),
+                corner: Point2D { x: -0.5, y: -0.5 },
+                width: 1.0,
+                height: 1.0,
+            }],
+            ..Default::default()
+        };
+        body.features.push(Feature::Sketch { id: "s1".to_string(), sketch, transform: Transform::new() });
+        body.features.push(Feature::Extrude {
+            id: "boss".to_string(),
+            sketch_id: "s1".to_string(),
+            height,
+            height_backward: 0.0,
+            cut: false,
+            draft_angle: 0.0,
+        });
+        body
+    }
+
+    fn extruded(elements: Vec<SketchElement>, sketch_transform: Transform, height: f64) -> Body {
+        Body {
+            id: "b".to_string(),
+            name: "Extrude".to_string(),
+            visible: true,
+            features: vec![Feature::BaseExtrude {
+                id: "base".to_string(),
+                sketch: Sketch { elements, ..Default::default() },
+                sketch_transform,
+                height,
+                height_backward: 0.0,
+                draft_angle: 0.0,
+            }],
+            parameters: Map::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        }
+    }
+
+    fn side(element_id: &str) -> FaceRef {
+        FaceRef::ExtrudeSide { feature_id: "base".to_string(), element_id: element_id.to_string(), segment: 0 }
+    }
+
+    #[test]
+    fn test_cube_face_follows_size() {
+        let surface = face_surface(
+            &cube_body(4.0),
+            &FaceRef::Primitive { feature_id: "cube".to_string(), face: PrimitiveFace::XMax },
+        )
+        .unwrap();
+        assert!(surface.contains([2.0, 0.5, -1.0]));
+        assert!(!surface.contains([2.0, 3.0, 0.0]));
+    }
+
+    #[test]
+    fn test_extrude_cap_follows_height() {
+        let body = with_boss(cube_body(2.0), 3.0);
+        let cap = FaceRef::ExtrudeCap { feature_id: "boss".to_string(), side: CapSide::End };
+        match face_surface(&body, &cap).unwrap() {
+            Surface::Plane { origin, .. } => assert!((origin[2] - 4.0).abs() < 1e-9),
+            other => panic!("expected plane, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_feature_is_error() {
+        let face = FaceRef::ExtrudeCap { feature_id: "gone".to_string(), side: CapSide::End };
+        let err = face_surface(&cube_body(1.0), &face).unwrap_err();
+        assert!(err.contains("no longer exists"));
+    }
+
+    #[test]
+    fn test_name_edge_prefers_latest_faces() {
+        let body = with_boss(cube_body(2.0), 1.0);
+        // Top edge of the boss along +X side
+        let edge = name_edge(&body, [0.5, -0.5, 2.0], [0.5, 0.5, 2.0]).unwrap();
+        assert_eq!(
+            edge.faces[0],
+            FaceRef::ExtrudeSide { feature_id: "boss".to_string(), element_id: "r".to_string(), segment: 1 }
+        );
+        assert_eq!(edge.faces[1], FaceRef::ExtrudeCap { feature_id: "boss".to_string(), side: CapSide::End });
+    }
+
+    #[test]
+    fn test_sketch_on_face_follows_boss_height() {
+        let mut body = with_boss(cube_body(2.0), 1.0);
+        let sketch = Sketch {
+            face: Some(FaceRef::ExtrudeCap { feature_id: "boss".to_string(), side: CapSide::End }),
+            face_normal: Some([0.0, 0.0, 1.0]),
+            offset: 2.0,
+            ..Default::default()
+        };
+        body.features.push(Feature::Sketch { id: "s2".to_string(), sketch, transform: Transform::new() });
+        if let Feature::Extrude { height, .. } = &mut body.features[2] {
+            *height = 2.5;
+        }
+
+        let mut errors = Vec::new();
+        let resolved = resolve_sketch_faces(&body, &mut errors);
+        assert!(errors.is_empty());
+        let Feature::Sketch { sketch, .. } = &resolved.features[3] else { panic!() };
+        assert!((sketch.offset - 3.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_sides_are_limited_to_their_span() {
+        let arc = |id: &str, start_angle: f64, end_angle: f64| SketchElement::Arc {
+            id: Some(id.to_string()),
+            center: Point2D { x: 0.0, y: 0.0 },
+            radius: 1.0,
+            start_angle,
+            end_angle,
+        };
+        let pi = std::f64::consts::PI;
+        let body = extruded(vec![arc("upper", 0.0, pi), arc("lower", pi, 0.0)], Transform::new(), 2.0);
+
+        let upper = face_surface(&body, &side("upper")).unwrap();
+        let lower = face_surface(&body, &side("lower")).unwrap();
+        assert!(upper.contains([0.0, 1.0, 1.0]));
+        assert!(!upper.contains([0.0, -1.0, 1.0]));
+        assert!(lower.contains([0.0, -1.0, 1.0]));
+        assert!(!lower.contains([0.0, 1.0, 1.0]));
+
+        let edge = name_edge(&body, [0.0, 1.0, 0.0], [0.0, 1.0, 2.0]);
+        assert!(edge.is_none(), "one arc side alone does not form an edge: {:?}", edge);
+    }
+
+    #[test]
+    fn test_rotated_sketch_moves_faces() {
+        let rectangle = SketchElement::Rectangle {
+            id: Some("r".to_string()),
+            corner: Point2D { x: 0.0, y: 0.0 },
+            width: 1.0,
+            height: 1.0,
+        };
+        // Rotating 90° about X sends the extrude direction +Z to -Y
+        let transform = Transform { position: [0.0, 0.0, 5.0], rotation: [90.0, 0.0, 0.0], ..Transform::new() };
+        let body = extruded(vec![rectangle], transform, 2.0);
+
+        let cap = FaceRef::ExtrudeCap { feature_id: "base".to_string(), side: CapSide::End };
+        let Surface::Plane { origin, normal, .. } = face_surface(&body, &cap).unwrap() else { panic!() };
+        assert!(length(sub(origin, [0.0, -2.0, 5.0])) < 1e-9, "{:?}", origin);
+        assert!(length(sub(normal, [0.0, -1.0, 0.0])) < 1e-9, "{:?}", normal);
+
+        // First rectangle edge (sketch y = 0) now lies in the plane z = 5
+        let bottom = face_surface(&body, &side("r")).unwrap();
+        assert!(bottom.contains([0.5, -1.0, 5.0]));
+        assert!(!bottom.contains([0.5, 1.0, 5.0]));
+        assert!(!bottom.contains([0.5, 0.0, 1.0]));
+    }
+}
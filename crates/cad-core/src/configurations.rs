@@ -0,0 +1,132 @@
+//! Design-table configurations: apply a row of the scene's design table,
+//! re-evaluate everything parameter-driven and build it.
+//!
+//! Used by the parameters panel (active configuration) and by
+//! `vcad-cli configs` for headless batch export of a part family.
+
+use std::collections::HashMap;
+
+use shared::{DesignTableError, SceneDescriptionV2};
+
+use crate::build::build_scene_meshes_v2;
+use crate::export::{build_scene_glb, GltfOptions};
+use crate::sketch::constraints::solve_scene_sketches;
+
+/// One configuration built to GLB
+pub struct ConfigurationGlb {
+    pub name: String,
+    /// Empty when the configuration produced no geometry
+    pub glb: Vec<u8>,
+    /// Per-body build errors (same shape as `build_scene_meshes_v2`)
+    pub errors: HashMap<String, String>,
+}
+
+/// Apply configuration `name` in place: parameter values, bound sketch
+/// dimensions (with re-solve) and bound feature properties.
+pub fn apply_configuration(scene: &mut SceneDescriptionV2, name: &str) -> Result<(), DesignTableError> {
+    scene.apply_configuration(name)?;
+    solve_scene_sketches(scene);
+    // Binding errors are reported again by every build
+    scene.apply_feature_bindings();
+    Ok(())
+}
+
+/// Copy of the scene with configuration `name` applied
+pub fn configure_scene(scene: &SceneDescriptionV2, name: &str) -> Result<SceneDescriptionV2, DesignTableError> {
+    let mut configured = scene.clone();
+    apply_configuration(&mut configured, name)?;
+    Ok(configured)
+}
+
+/// Build every configuration of the scene's design table to GLB, in table order
+pub fn build_configurations_glb(scene: &SceneDescriptionV2) -> Result<Vec<ConfigurationGlb>, DesignTableError> {
+    let names: Vec<String> = scene
+        .design_table
+        .iter()
+        .flat_map(|table| table.configurations.iter().map(|c| c.name.clone()))
+        .collect();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let configured = configure_scene(scene, &name)?;
+            let (meshes, errors) = build_scene_meshes_v2(&configured, &[]);
+            Ok(ConfigurationGlb {
+                name,
+                glb: build_scene_glb(&configured, &meshes, &GltfOptions::default()),
+                errors,
+            })
+        })
+        .collect()
+}
+
+/// File name for a configuration (anything but `[A-Za-z0-9._-]` becomes `_`)
+pub fn configuration_file_name(name: &str, extension: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+        .collect();
+    format!("{}.{}", stem, extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::*;
+
+    fn cube_family() -> SceneDescriptionV2 {
+        let mut scene = SceneDescriptionV2 {
+            bodies: vec![Body {
+                id: "body1".to_string(),
+                name: "Cube".to_string(),
+                features: vec![Feature::BasePrimitive {
+                    id: "feat1".to_string(),
+                    primitive: Primitive::Cube {
+                        width: 1.0,
+                        height: 1.0,
+                        depth: 1.0,
+                    },
+                    transform: Transform::new(),
+                }],
+                visible: true,
+                parameters: HashMap::new(),
+                bindings: HashMap::from([("feat1.width".to_string(), "size".to_string())]),
+                appearance: None,
+            }],
+            ..Default::default()
+        };
+        scene.parameters.insert(
+            "size".to_string(),
+            Parameter {
+                name: "size".to_string(),
+                value: ParameterValue::Number { value: 1.0 },
+                unit: Some("mm".to_string()),
+                description: None,
+            },
+        );
+        scene.design_table = Some(DesignTable::from_csv("name,size\nsmall,2\nlarge,5\n").unwrap());
+        scene
+    }
+
+    #[test]
+    fn test_configure_scene_reflows_bindings() {
+        let scene = cube_family();
+        let large = configure_scene(&scene, "large").unwrap();
+        assert_eq!(large.bodies[0].features[0].numeric_property("width"), Some(5.0));
+        // The source scene is untouched
+        assert_eq!(scene.bodies[0].features[0].numeric_property("width"), Some(1.0));
+    }
+
+    #[test]
+    fn test_build_configurations_glb() {
+        let built = build_configurations_glb(&cube_family()).unwrap();
+        let names: Vec<&str> = built.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["small", "large"]);
+        assert!(built.iter().all(|c| !c.glb.is_empty() && c.errors.is_empty()));
+    }
+
+    #[test]
+    fn test_configuration_file_name() {
+        assert_eq!(configuration_file_name("M8, long", "glb"), "M8__long.glb");
+    }
+}
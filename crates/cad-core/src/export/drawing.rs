@@ -0,0 +1,491 @@
+//! Flat 2D drawings for laser/CNC output: a sketch (without construction
+//! geometry) or the outline of a planar face, with optional dimension
+//! annotations. Written to DXF by `dxf.rs` and to SVG by `svg.rs`.
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{PI, TAU};
+
+use shared::{DimensionType, Sketch, SketchElement};
+
+use crate::mesh::MeshData;
+
+/// Offset of automatically placed dimension lines, as a fraction of the
+/// drawing size
+const DIMENSION_OFFSET: f64 = 0.08;
+
+/// Options for building a drawing
+#[derive(Debug, Clone, Copy)]
+pub struct DrawingOptions {
+    /// Include dimensions as annotations
+    pub dimensions: bool,
+    /// Decimal places of dimension values
+    pub precision: usize,
+}
+
+impl Default for DrawingOptions {
+    fn default() -> Self {
+        Self { dimensions: false, precision: 2 }
+    }
+}
+
+/// Cut geometry, in mm
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Line { start: [f64; 2], end: [f64; 2] },
+    Circle { center: [f64; 2], radius: f64 },
+    /// Counter-clockwise from `start_angle` to `end_angle` (radians)
+    Arc { center: [f64; 2], radius: f64, start_angle: f64, end_angle: f64 },
+    Polyline { points: Vec<[f64; 2]>, closed: bool },
+}
+
+/// Dimension annotation: measured points, dimension line and value text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub from: [f64; 2],
+    pub to: [f64; 2],
+    pub line_start: [f64; 2],
+    pub line_end: [f64; 2],
+    pub text: String,
+}
+
+/// 2D drawing in mm
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Drawing {
+    pub shapes: Vec<Shape>,
+    pub annotations: Vec<Annotation>,
+}
+
+impl Shape {
+    /// Points on the shape, enough to bound it
+    fn sample(&self) -> Vec<[f64; 2]> {
+        let arc = |center: [f64; 2], radius: f64, start: f64, span: f64| -> Vec<[f64; 2]> {
+            (0..=32)
+                .map(|i| {
+                    let angle = start + span * i as f64 / 32.0;
+                    [center[0] + radius * angle.cos(), center[1] + radius * angle.sin()]
+                })
+                .collect()
+        };
+        match self {
+            Shape::Line { start, end } => vec![*start, *end],
+            Shape::Circle { center, radius } => arc(*center, *radius, 0.0, TAU),
+            Shape::Arc { center, radius, start_angle, end_angle } => {
+                arc(*center, *radius, *start_angle, arc_span(*start_angle, *end_angle))
+            }
+            Shape::Polyline { points, .. } => points.clone(),
+        }
+    }
+}
+
+/// Counter-clockwise span of an arc, in (0, 2π]
+pub(crate) fn arc_span(start_angle: f64, end_angle: f64) -> f64 {
+    let span = (end_angle - start_angle).rem_euclid(TAU);
+    if span < 1e-12 {
+        TAU
+    } else {
+        span
+    }
+}
+
+impl Annotation {
+    /// Extension lines, dimension line and arrowheads
+    pub fn lines(&self, text_height: f64) -> Vec<[[f64; 2]; 2]> {
+        let mut lines = vec![[self.from, self.line_start], [self.to, self.line_end], [self.line_start, self.line_end]];
+        let (dx, dy) = (self.line_end[0] - self.line_start[0], self.line_end[1] - self.line_start[1]);
+        let len = dx.hypot(dy);
+        if len > 1e-12 {
+            let arrow = (text_height * 0.8).min(len / 3.0);
+            let dir = [dx / len, dy / len];
+            for (tip, sign) in [(self.line_start, 1.0), (self.line_end, -1.0)] {
+                for side in [-1.0, 1.0] {
+                    // Arrow wings at ±20° from the dimension line
+                    let (sin, cos) = (side * 20f64.to_radians()).sin_cos();
+                    let wing = [
+                        tip[0] + sign * arrow * (dir[0] * cos - dir[1] * sin),
+                        tip[1] + sign * arrow * (dir[0] * sin + dir[1] * cos),
+                    ];
+                    lines.push([tip, wing]);
+                }
+            }
+        }
+        lines.retain(|[a, b]| a != b);
+        lines
+    }
+
+    /// Text insertion point (centre of the text baseline) and rotation in
+    /// degrees, kept readable (between -90° and 90°)
+    pub fn text_placement(&self, text_height: f64) -> ([f64; 2], f64) {
+        let (dx, dy) = (self.line_end[0] - self.line_start[0], self.line_end[1] - self.line_start[1]);
+        let mut angle = dy.atan2(dx);
+        if angle > PI / 2.0 + 1e-9 {
+            angle -= PI;
+        } else if angle <= -PI / 2.0 + 1e-9 {
+            angle += PI;
+        }
+        let mid = [
+            (self.line_start[0] + self.line_end[0]) / 2.0,
+            (self.line_start[1] + self.line_end[1]) / 2.0,
+        ];
+        // Raise the baseline above the dimension line (left of the text direction)
+        let gap = text_height * 0.3;
+        ([mid[0] - angle.sin() * gap, mid[1] + angle.cos() * gap], angle.to_degrees())
+    }
+}
+
+impl Drawing {
+    /// Bounding box (min, max) of the geometry and annotations
+    pub fn bounds(&self) -> Option<([f64; 2], [f64; 2])> {
+        let points = self
+            .shapes
+            .iter()
+            .flat_map(Shape::sample)
+            .chain(self.annotations.iter().flat_map(|a| [a.from, a.to, a.line_start, a.line_end]));
+        points.fold(None, |bounds, [x, y]| {
+            let ([x0, y0], [x1, y1]) = bounds.unwrap_or(([x, y], [x, y]));
+            Some(([x0.min(x), y0.min(y)], [x1.max(x), y1.max(y)]))
+        })
+    }
+
+    /// Height of annotation text, relative to the drawing size
+    pub fn text_height(&self) -> f64 {
+        let size = self.bounds().map(|(min, max)| (max[0] - min[0]).max(max[1] - min[1])).unwrap_or(0.0);
+        (size * 0.03).clamp(0.1, 5.0)
+    }
+}
+
+fn format_value(value: f64, precision: usize) -> String {
+    format!("{:.*}", precision, value)
+}
+
+/// Dimension line through `pos` parallel to from→to, or offset by `offset`
+/// to the left when no position is given (as the sketch renderer does)
+fn annotation(from: [f64; 2], to: [f64; 2], pos: Option<[f64; 2]>, offset: f64, text: String) -> Annotation {
+    let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+    let len = dx.hypot(dy);
+    let perp = if len < 1e-12 {
+        [0.0, 0.0]
+    } else if let Some(pos) = pos {
+        let dir = [dx / len, dy / len];
+        let rel = [pos[0] - from[0], pos[1] - from[1]];
+        let along = rel[0] * dir[0] + rel[1] * dir[1];
+        [rel[0] - along * dir[0], rel[1] - along * dir[1]]
+    } else {
+        [-dy / len * offset, dx / len * offset]
+    };
+    Annotation {
+        from,
+        to,
+        line_start: [from[0] + perp[0], from[1] + perp[1]],
+        line_end: [to[0] + perp[0], to[1] + perp[1]],
+        text,
+    }
+}
+
+/// Drawing of a sketch in its own coordinates. Construction geometry is
+/// left out; dimensions become annotations when requested.
+pub fn sketch_drawing(sketch: &Sketch, options: &DrawingOptions) -> Drawing {
+    let mut drawing = Drawing::default();
+    let mut dimensions = Vec::new();
+
+    for (index, element) in sketch.elements.iter().enumerate() {
+        if sketch.is_construction(index) {
+            continue;
+        }
+        let shape = match element {
+            SketchElement::Line { start, end, .. } => Shape::Line { start: [start.x, start.y], end: [end.x, end.y] },
+            SketchElement::Circle { center, radius, .. } => {
+                Shape::Circle { center: [center.x, center.y], radius: *radius }
+            }
+            SketchElement::Arc { center, radius, start_angle, end_angle, .. } => Shape::Arc {
+                center: [center.x, center.y],
+                radius: *radius,
+                start_angle: *start_angle,
+                end_angle: *end_angle,
+            },
+            SketchElement::Rectangle { corner, width, height, .. } => Shape::Polyline {
+                points: vec![
+                    [corner.x, corner.y],
+                    [corner.x + width, corner.y],
+                    [corner.x + width, corner.y + height],
+                    [corner.x, corner.y + height],
+                ],
+                closed: true,
+            },
+            SketchElement::Polyline { points, .. } | SketchElement::Spline { points, .. } => {
+                let mut points: Vec<[f64; 2]> = points.iter().map(|p| [p.x, p.y]).collect();
+                let closed = points.len() > 2 && points.first() == points.last();
+                if closed {
+                    points.pop();
+                }
+                Shape::Polyline { points, closed }
+            }
+            SketchElement::Dimension { from, to, value, dimension_line_pos, dimension_type, .. } => {
+                dimensions.push((
+                    [from.x, from.y],
+                    [to.x, to.y],
+                    dimension_line_pos.as_ref().map(|p| [p.x, p.y]),
+                    *value,
+                    *dimension_type,
+                ));
+                continue;
+            }
+        };
+        drawing.shapes.push(shape);
+    }
+
+    if options.dimensions {
+        for (from, to, pos, value, dimension_type) in dimensions {
+            let prefix = match dimension_type {
+                DimensionType::Linear => "",
+                DimensionType::Radius => "R",
+                DimensionType::Diameter => "Ø",
+            };
+            let text = format!("{}{}", prefix, format_value(value, options.precision));
+            // Same automatic offset as the sketch renderer
+            drawing.annotations.push(annotation(from, to, pos, 0.5, text));
+        }
+    }
+    drawing
+}
+
+/// Orthonormal (u, v) axes in the plane with normal `n`, with u × v = n.
+/// Faces facing +Z get the world X/Y axes.
+fn plane_axes(n: [f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let cross = |a: [f64; 3], b: [f64; 3]| {
+        [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+    };
+    let helper = if n[2].abs() < 0.9 { [0.0, 0.0, 1.0] } else { [0.0, 1.0, 0.0] };
+    let u = cross(helper, n);
+    let len = (u[0] * u[0] + u[1] * u[1] + u[2] * u[2]).sqrt();
+    let u = u.map(|c| c / len);
+    (u, cross(n, u))
+}
+
+/// Drop points that lie on the straight line between their neighbours
+fn remove_collinear(mut points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    let mut i = 0;
+    while points.len() > 3 && i < points.len() {
+        let n = points.len();
+        let (a, b, c) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let (ab, bc) = ([b[0] - a[0], b[1] - a[1]], [c[0] - b[0], c[1] - b[1]]);
+        let cross = ab[0] * bc[1] - ab[1] * bc[0];
+        let dot = ab[0] * bc[0] + ab[1] * bc[1];
+        if cross.abs() <= 1e-9 * ab[0].hypot(ab[1]) * bc[0].hypot(bc[1]) && dot > 0.0 {
+            points.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    points
+}
+
+/// Drawing of a planar face: the boundary loops of `triangles` (indices of
+/// triangles in `mesh`, as from face picking) projected onto the face
+/// plane. Looking at the face from outside, the projected X axis points
+/// right. Outer and hole loops become closed polylines; with dimensions the
+/// overall width and height are annotated.
+pub fn face_drawing(
+    mesh: &MeshData,
+    triangles: &[usize],
+    normal: [f32; 3],
+    options: &DrawingOptions,
+) -> Result<Drawing, String> {
+    let n = normal.map(f64::from);
+    let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if len < 1e-9 {
+        return Err("Face has no normal".to_string());
+    }
+    let (u, v) = plane_axes(n.map(|c| c / len));
+
+    // Triangles keep their own vertices, so edges are matched by position
+    let key = |p: [f64; 3]| p.map(|c| (c * 1e5).round() as i64);
+    let mut projected: HashMap<[i64; 3], [f64; 2]> = HashMap::new();
+    let mut edges: HashSet<([i64; 3], [i64; 3])> = HashSet::new();
+    for &tri in triangles {
+        let Some(corners) = mesh.indices.get(tri * 3..tri * 3 + 3) else {
+            return Err(format!("Face triangle {} is out of range", tri));
+        };
+        let keys: Vec<[i64; 3]> = corners
+            .iter()
+            .map(|&i| {
+                let base = i as usize * 9;
+                let p: [f64; 3] = std::array::from_fn(|k| mesh.vertices[base + k] as f64);
+                let k = key(p);
+                projected.entry(k).or_insert([
+                    p[0] * u[0] + p[1] * u[1] + p[2] * u[2],
+                    p[0] * v[0] + p[1] * v[1] + p[2] * v[2],
+                ]);
+                k
+            })
+            .collect();
+        for k in 0..3 {
+            edges.insert((keys[k], keys[(k + 1) % 3]));
+        }
+    }
+
+    // Boundary edges are those whose reverse belongs to no face triangle;
+    // loops touching at a vertex give it several outgoing edges
+    let mut next: HashMap<[i64; 3], Vec<[i64; 3]>> = HashMap::new();
+    for (a, b) in edges.iter().filter(|(a, b)| !edges.contains(&(*b, *a))) {
+        next.entry(*a).or_default().push(*b);
+    }
+    if next.is_empty() {
+        return Err("Face has no boundary".to_string());
+    }
+
+    let mut starts: Vec<[i64; 3]> = next.keys().copied().collect();
+    starts.sort();
+    let mut drawing = Drawing::default();
+    for start in starts {
+        while next.contains_key(&start) {
+            let mut points = Vec::new();
+            let (mut previous, mut current) = (None, start);
+            while let Some(following) = take_boundary_edge(&mut next, &projected, previous, current) {
+                points.push(projected[&current]);
+                (previous, current) = (Some(current), following);
+                if current == start {
+                    break;
+                }
+            }
+            if points.len() >= 2 {
+                let closed = current == start;
+                let points = if closed { remove_collinear(points) } else { points };
+                drawing.shapes.push(Shape::Polyline { points, closed });
+            }
+        }
+    }
+
+    if options.dimensions {
+        if let Some((min, max)) = drawing.bounds() {
+            let offset = (max[0] - min[0]).max(max[1] - min[1]) * DIMENSION_OFFSET;
+            // Width below the face, height on its left
+            let width = format_value(max[0] - min[0], options.precision);
+            let height = format_value(max[1] - min[1], options.precision);
+            drawing.annotations.push(annotation([max[0], min[1]], min, None, offset, width));
+            drawing.annotations.push(annotation(min, [min[0], max[1]], None, offset, height));
+        }
+    }
+    Ok(drawing)
+}
+
+/// Remove and return an unused boundary edge leaving `from` (reached from
+/// `previous`). Where loops touch at a vertex, the edge turning most
+/// clockwise from the incoming one keeps the walk on the same loop.
+fn take_boundary_edge(
+    next: &mut HashMap<[i64; 3], Vec<[i64; 3]>>,
+    projected: &HashMap<[i64; 3], [f64; 2]>,
+    previous: Option<[i64; 3]>,
+    from: [i64; 3],
+) -> Option<[i64; 3]> {
+    let outgoing = next.get_mut(&from)?;
+    let direction = |to: [i64; 3]| {
+        let (a, b) = (projected[&from], projected[&to]);
+        (b[1] - a[1]).atan2(b[0] - a[0])
+    };
+    let index = match previous {
+        Some(previous) if outgoing.len() > 1 => {
+            let back = direction(previous);
+            let clockwise = |to: [i64; 3]| (back - direction(to)).rem_euclid(TAU);
+            (0..outgoing.len()).min_by(|&i, &j| clockwise(outgoing[i]).total_cmp(&clockwise(outgoing[j])))?
+        }
+        _ => outgoing.len() - 1,
+    };
+    let to = outgoing.swap_remove(index);
+    if outgoing.is_empty() {
+        next.remove(&from);
+    }
+    Some(to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::{triangles, GREY};
+    use shared::Point2D;
+
+    #[test]
+    fn test_sketch_drawing_skips_construction() {
+        let mut sketch = Sketch::default();
+        sketch.push_element(SketchElement::Rectangle {
+            id: None,
+            corner: Point2D { x: 0.0, y: 0.0 },
+            width: 4.0,
+            height: 2.0,
+        });
+        let axis = sketch.push_element(SketchElement::Line {
+            id: None,
+            start: Point2D { x: 2.0, y: -1.0 },
+            end: Point2D { x: 2.0, y: 3.0 },
+        });
+        sketch.set_construction(axis, true);
+        sketch.push_element(SketchElement::Dimension {
+            from: Point2D { x: 0.0, y: 0.0 },
+            to: Point2D { x: 4.0, y: 0.0 },
+            value: 4.0,
+            parameter_name: None,
+            dimension_line_pos: Some(Point2D { x: 1.0, y: -1.0 }),
+            target_element: None,
+            dimension_type: DimensionType::Linear,
+        });
+
+        let plain = sketch_drawing(&sketch, &DrawingOptions::default());
+        assert_eq!(plain.shapes.len(), 1);
+        assert!(plain.annotations.is_empty());
+
+        let options = DrawingOptions { dimensions: true, precision: 1 };
+        let annotated = sketch_drawing(&sketch, &options);
+        let dim = &annotated.annotations[0];
+        assert_eq!(dim.text, "4.0");
+        assert_eq!((dim.line_start, dim.line_end), ([0.0, -1.0], [4.0, -1.0]));
+        assert_eq!(annotated.bounds(), Some(([0.0, -1.0], [4.0, 2.0])));
+    }
+
+    /// Top face (+Z) of a 3×2 plate with a 1×1 square hole, two triangles per
+    /// quad strip, each triangle with its own vertices like `MeshData`
+    fn plate_with_hole() -> MeshData {
+        let outer = [[0.0, 0.0, 1.0], [3.0, 0.0, 1.0], [3.0, 2.0, 1.0], [0.0, 2.0, 1.0]];
+        let inner = [[1.0, 0.5, 1.0], [2.0, 0.5, 1.0], [2.0, 1.5, 1.0], [1.0, 1.5, 1.0]];
+        let tris: Vec<[[f32; 3]; 3]> = (0..4)
+            .flat_map(|k| {
+                let (o0, o1, i0, i1) = (outer[k], outer[(k + 1) % 4], inner[k], inner[(k + 1) % 4]);
+                [[o0, o1, i1], [o0, i1, i0]]
+            })
+            .collect();
+        triangles(&tris, GREY)
+    }
+
+    #[test]
+    fn test_face_drawing_outer_and_hole_loops() {
+        let mesh = plate_with_hole();
+        let triangles: Vec<usize> = (0..8).collect();
+        let options = DrawingOptions { dimensions: true, precision: 0 };
+        let drawing = face_drawing(&mesh, &triangles, [0.0, 0.0, 1.0], &options).unwrap();
+
+        assert_eq!(drawing.shapes.len(), 2);
+        for shape in &drawing.shapes {
+            let Shape::Polyline { points, closed } = shape else { panic!("expected polylines") };
+            assert!(*closed);
+            assert_eq!(points.len(), 4);
+        }
+        let texts: Vec<&str> = drawing.annotations.iter().map(|a| a.text.as_str()).collect();
+        assert_eq!(texts, ["3", "2"]);
+    }
+
+    #[test]
+    fn test_face_drawing_loops_sharing_a_vertex() {
+        // Two unit squares touching only at (1, 1)
+        let square = |x: f32, y: f32| {
+            let c = [[x, y, 0.0], [x + 1.0, y, 0.0], [x + 1.0, y + 1.0, 0.0], [x, y + 1.0, 0.0]];
+            [[c[0], c[1], c[2]], [c[0], c[2], c[3]]]
+        };
+        let mesh = triangles(&[square(0.0, 0.0), square(1.0, 1.0)].concat(), GREY);
+        let drawing = face_drawing(&mesh, &[0, 1, 2, 3], [0.0, 0.0, 1.0], &DrawingOptions::default()).unwrap();
+
+        assert_eq!(drawing.shapes.len(), 2);
+        for shape in &drawing.shapes {
+            let Shape::Polyline { points, closed } = shape else { panic!("expected polylines") };
+            assert!(*closed);
+            assert_eq!(points.len(), 4, "{:?}", points);
+        }
+    }
+}
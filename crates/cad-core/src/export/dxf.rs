@@ -0,0 +1,198 @@
+//! DXF R12 (AC1009) writer for 2D drawings.
+//!
+//! R12 is the version laser and CNC software reads most reliably: outlines
+//! use LINE, CIRCLE, ARC and POLYLINE/VERTEX (no LWPOLYLINE), and dimension
+//! annotations are plain LINE and TEXT entities, since R12 DIMENSION
+//! entities need anonymous blocks. Geometry goes on layer `OUTLINE`,
+//! annotations on layer `DIMENSIONS`. Coordinates are in mm.
+
+use std::fmt::Write;
+
+use super::drawing::{Drawing, Shape};
+
+const OUTLINE_LAYER: &str = "OUTLINE";
+const DIMENSION_LAYER: &str = "DIMENSIONS";
+
+/// DXF output: group code/value lines
+struct DxfWriter {
+    out: String,
+}
+
+impl DxfWriter {
+    fn pair(&mut self, code: i32, value: impl std::fmt::Display) {
+        let _ = writeln!(self.out, "{:>3}\n{}", code, value);
+    }
+
+    fn point(&mut self, code: i32, [x, y]: [f64; 2]) {
+        self.pair(code, x);
+        self.pair(code + 10, y);
+        self.pair(code + 20, 0.0);
+    }
+
+    fn entity(&mut self, kind: &str, layer: &str) {
+        self.pair(0, kind);
+        self.pair(8, layer);
+    }
+
+    fn layer(&mut self, name: &str, color: i32) {
+        self.pair(0, "LAYER");
+        self.pair(2, name);
+        self.pair(70, 0);
+        self.pair(62, color);
+        self.pair(6, "CONTINUOUS");
+    }
+
+    fn line(&mut self, layer: &str, start: [f64; 2], end: [f64; 2]) {
+        self.entity("LINE", layer);
+        self.point(10, start);
+        self.point(11, end);
+    }
+}
+
+/// R12 text is in the ANSI code page: the diameter sign becomes the `%%c`
+/// control code, other non-ASCII characters `\U+XXXX` escapes
+fn dxf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'Ø' | '⌀' => "%%c".to_string(),
+            c if c.is_ascii() => c.to_string(),
+            c => format!("\\U+{:04X}", c as u32),
+        })
+        .collect()
+}
+
+/// Build a DXF R12 file from a drawing
+pub fn build_dxf(drawing: &Drawing) -> Vec<u8> {
+    let mut w = DxfWriter { out: String::new() };
+    let (min, max) = drawing.bounds().unwrap_or(([0.0, 0.0], [0.0, 0.0]));
+
+    w.pair(0, "SECTION");
+    w.pair(2, "HEADER");
+    w.pair(9, "$ACADVER");
+    w.pair(1, "AC1009");
+    w.pair(9, "$EXTMIN");
+    w.point(10, min);
+    w.pair(9, "$EXTMAX");
+    w.point(10, max);
+    w.pair(0, "ENDSEC");
+
+    w.pair(0, "SECTION");
+    w.pair(2, "TABLES");
+    w.pair(0, "TABLE");
+    w.pair(2, "LTYPE");
+    w.pair(70, 1);
+    w.pair(0, "LTYPE");
+    w.pair(2, "CONTINUOUS");
+    w.pair(70, 0);
+    w.pair(3, "Solid line");
+    w.pair(72, 65);
+    w.pair(73, 0);
+    w.pair(40, 0.0);
+    w.pair(0, "ENDTAB");
+    w.pair(0, "TABLE");
+    w.pair(2, "LAYER");
+    w.pair(70, 2);
+    // Colour 7 is white/black depending on the background, 5 is blue
+    w.layer(OUTLINE_LAYER, 7);
+    w.layer(DIMENSION_LAYER, 5);
+    w.pair(0, "ENDTAB");
+    w.pair(0, "ENDSEC");
+
+    w.pair(0, "SECTION");
+    w.pair(2, "ENTITIES");
+    for shape in &drawing.shapes {
+        match shape {
+            Shape::Line { start, end } => w.line(OUTLINE_LAYER, *start, *end),
+            Shape::Circle { center, radius } => {
+                w.entity("CIRCLE", OUTLINE_LAYER);
+                w.point(10, *center);
+                w.pair(40, radius);
+            }
+            Shape::Arc { center, radius, start_angle, end_angle } => {
+                w.entity("ARC", OUTLINE_LAYER);
+                w.point(10, *center);
+                w.pair(40, radius);
+                w.pair(50, start_angle.to_degrees());
+                w.pair(51, end_angle.to_degrees());
+            }
+            Shape::Polyline { points, closed } => {
+                w.entity("POLYLINE", OUTLINE_LAYER);
+                w.pair(66, 1);
+                w.point(10, [0.0, 0.0]);
+                w.pair(70, i32::from(*closed));
+                for point in points {
+                    w.entity("VERTEX", OUTLINE_LAYER);
+                    w.point(10, *point);
+                }
+                w.entity("SEQEND", OUTLINE_LAYER);
+            }
+        }
+    }
+
+    let text_height = drawing.text_height();
+    for annotation in &drawing.annotations {
+        for [start, end] in annotation.lines(text_height) {
+            w.line(DIMENSION_LAYER, start, end);
+        }
+        let (position, rotation) = annotation.text_placement(text_height);
+        w.entity("TEXT", DIMENSION_LAYER);
+        w.point(10, position);
+        w.pair(40, text_height);
+        w.pair(1, dxf_text(&annotation.text));
+        w.pair(50, rotation);
+        // Centred on the baseline: the alignment point (11) is used
+        w.pair(72, 1);
+        w.point(11, position);
+    }
+    w.pair(0, "ENDSEC");
+    w.pair(0, "EOF");
+
+    w.out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::drawing::Annotation;
+
+    /// Entity types of the ENTITIES section in order
+    fn entity_types(dxf: &str) -> Vec<String> {
+        let lines: Vec<&str> = dxf.lines().map(str::trim).collect();
+        let start = lines.iter().position(|l| *l == "ENTITIES").unwrap();
+        lines[start + 1..]
+            .chunks(2)
+            .filter(|pair| pair[0] == "0" && pair.len() == 2)
+            .map(|pair| pair[1].to_string())
+            .take_while(|kind| kind != "ENDSEC")
+            .collect()
+    }
+
+    #[test]
+    fn test_r12_entities() {
+        let drawing = Drawing {
+            shapes: vec![
+                Shape::Polyline { points: vec![[0.0, 0.0], [4.0, 0.0], [4.0, 2.0]], closed: true },
+                Shape::Arc { center: [1.0, 1.0], radius: 0.5, start_angle: 0.0, end_angle: std::f64::consts::PI },
+            ],
+            annotations: vec![Annotation {
+                from: [0.0, 0.0],
+                to: [4.0, 0.0],
+                line_start: [0.0, -1.0],
+                line_end: [4.0, -1.0],
+                text: "4.00".to_string(),
+            }],
+        };
+        let dxf = String::from_utf8(build_dxf(&drawing)).unwrap();
+
+        assert!(dxf.contains("$ACADVER\n  1\nAC1009\n"));
+        assert!(dxf.ends_with("  0\nEOF\n"));
+        assert!(dxf.contains(" 51\n180\n"));
+        let types = entity_types(&dxf);
+        assert_eq!(&types[..6], ["POLYLINE", "VERTEX", "VERTEX", "VERTEX", "SEQEND", "ARC"]);
+        // Two extension lines, the dimension line and four arrow wings
+        assert_eq!(types.iter().filter(|t| *t == "LINE").count(), 7);
+        assert_eq!(types.last().map(String::as_str), Some("TEXT"));
+        assert!(dxf.contains("  1\n4.00\n"));
+        assert_eq!(dxf_text("Ø5 °"), "%%c5 \\U+00B0");
+    }
+}
@@ -0,0 +1,482 @@
+//! GLB (binary glTF 2.0) writer.
+//!
+//! Every mesh becomes a node under a single `vCAD` root node, with its own
+//! PBR material (from the body appearance when set, else the mesh colour).
+//! With the scene available (`build_scene_glb`) nodes are named after
+//! bodies, carry the rigid placement of the body's base feature as their
+//! translation/rotation (vertices are written relative to it), and hold an
+//! `extras` block with the body id, feature ids and evaluated parameter
+//! values so viewers can map meshes back to the scene.
+
+use std::collections::HashMap;
+
+use glam::{DQuat, DVec3, EulerRot};
+use serde_json::{json, Map, Value};
+use shared::{Appearance, Body, Feature, SceneDescriptionV2};
+
+use crate::mesh::MeshData;
+
+/// GLB magic number: "glTF"
+const GLB_MAGIC: u32 = 0x46546C67;
+/// GLB version 2
+const GLB_VERSION: u32 = 2;
+/// JSON chunk type
+const CHUNK_TYPE_JSON: u32 = 0x4E4F534A;
+/// BIN chunk type
+const CHUNK_TYPE_BIN: u32 = 0x004E4942;
+
+/// glTF component types
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+/// glTF buffer view targets
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Extension for flat-shaded materials
+const KHR_MATERIALS_UNLIT: &str = "KHR_materials_unlit";
+
+/// Options for `build_scene_glb`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GltfOptions {
+    /// Mark materials with `KHR_materials_unlit` (flat colours, e.g. for
+    /// previews); viewers without the extension fall back to PBR
+    pub unlit: bool,
+}
+
+/// PBR metallic-roughness material of one mesh
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PbrMaterial {
+    base_color: [f32; 4],
+    metallic: f32,
+    roughness: f32,
+}
+
+impl PbrMaterial {
+    /// Material in the mesh colour (the colour of its first vertex)
+    fn from_mesh(mesh: &MeshData) -> Self {
+        let rgb = mesh.vertices.get(6..9).unwrap_or(&[0.6, 0.6, 0.65]);
+        Self { base_color: [rgb[0], rgb[1], rgb[2], 1.0], metallic: 0.3, roughness: 0.5 }
+    }
+
+    /// Material of a body with an appearance: its colour and opacity, PBR
+    /// factors of its named material
+    fn from_appearance(appearance: &Appearance) -> Self {
+        let [r, g, b] = appearance.color;
+        let (metallic, roughness) = appearance.material.as_ref().map_or((0.3, 0.5), |m| (m.metallic, m.roughness));
+        Self { base_color: [r, g, b, appearance.opacity.clamp(0.0, 1.0)], metallic, roughness }
+    }
+}
+
+/// One mesh node of the output
+struct GlbNode<'a> {
+    name: String,
+    mesh: &'a MeshData,
+    material: PbrMaterial,
+    /// Node placement; vertices are written in node coordinates
+    translation: DVec3,
+    rotation: DQuat,
+    extras: Option<Value>,
+}
+
+/// Build a complete GLB (binary glTF) file from mesh data.
+///
+/// Each entry in `meshes` becomes a separate node/mesh in the glTF scene,
+/// named after its key, with vertices as given.
+pub fn build_glb(meshes: &HashMap<String, MeshData>) -> Vec<u8> {
+    // Sort mesh keys for deterministic output
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+
+    let nodes: Vec<GlbNode> = keys
+        .into_iter()
+        .map(|key| GlbNode {
+            name: key.clone(),
+            mesh: &meshes[key],
+            material: PbrMaterial::from_mesh(&meshes[key]),
+            translation: DVec3::ZERO,
+            rotation: DQuat::IDENTITY,
+            extras: None,
+        })
+        .collect();
+    write_glb(&nodes, None, &GltfOptions::default())
+}
+
+/// Build a GLB of scene meshes keyed by body id (as from
+/// `build_scene_meshes_v2`): one node per body, in scene order, named after
+/// the body and placed at its base feature. Node `extras` carry `body_id`,
+/// `features` (ids) and `parameters` (evaluated values); the root node's
+/// extras carry the global parameters.
+pub fn build_scene_glb(
+    scene: &SceneDescriptionV2,
+    meshes: &HashMap<String, MeshData>,
+    options: &GltfOptions,
+) -> Vec<u8> {
+    // Scene order first, then meshes without a body (sorted)
+    let mut keys: Vec<&String> = scene.bodies.iter().map(|b| &b.id).filter(|id| meshes.contains_key(*id)).collect();
+    let mut orphans: Vec<&String> = meshes.keys().filter(|id| scene.bodies.iter().all(|b| &b.id != *id)).collect();
+    orphans.sort();
+    keys.extend(orphans);
+
+    let nodes: Vec<GlbNode> = keys
+        .into_iter()
+        .map(|id| {
+            let mesh = &meshes[id];
+            let body = scene.bodies.iter().find(|b| &b.id == id);
+            let (translation, rotation) = body.map_or((DVec3::ZERO, DQuat::IDENTITY), body_placement);
+            GlbNode {
+                name: body.map(|b| b.name.trim()).filter(|n| !n.is_empty()).unwrap_or(id).to_string(),
+                mesh,
+                material: match body.and_then(|b| b.appearance.as_ref()) {
+                    Some(appearance) => PbrMaterial::from_appearance(appearance),
+                    None => PbrMaterial::from_mesh(mesh),
+                },
+                translation,
+                rotation,
+                extras: body.map(|b| body_extras(scene, b)),
+            }
+        })
+        .collect();
+
+    let globals = parameter_values(scene.parameters.keys(), |name| scene.evaluate_parameter(None, name).ok());
+    write_glb(&nodes, Some(json!({ "parameters": globals })), options)
+}
+
+/// Rigid placement of a body: position and rotation of its base feature
+/// (scale stays in the vertices). Rotation is applied about X, then Y, then
+/// Z, as when building.
+fn body_placement(body: &Body) -> (DVec3, DQuat) {
+    match body.features.first() {
+        Some(
+            Feature::BasePrimitive { transform, .. }
+            | Feature::BaseMesh { transform, .. }
+            | Feature::BaseExtrude { sketch_transform: transform, .. },
+        ) => {
+            let [rx, ry, rz] = transform.rotation.map(f64::to_radians);
+            (DVec3::from(transform.position), DQuat::from_euler(EulerRot::ZYX, rz, ry, rx))
+        }
+        Some(Feature::BaseRevolve { sketch_transform, .. }) => (DVec3::from(sketch_transform.position), DQuat::IDENTITY),
+        _ => (DVec3::ZERO, DQuat::IDENTITY),
+    }
+}
+
+/// Evaluated parameters as a JSON object sorted by name; failing formulas
+/// are left out
+fn parameter_values<'a>(names: impl Iterator<Item = &'a String>, value: impl Fn(&str) -> Option<f64>) -> Value {
+    let mut names: Vec<&String> = names.collect();
+    names.sort();
+    let values: Map<String, Value> =
+        names.into_iter().filter_map(|name| Some((name.clone(), json!(value(name)?)))).collect();
+    Value::Object(values)
+}
+
+fn body_extras(scene: &SceneDescriptionV2, body: &Body) -> Value {
+    let value = |name: &str| scene.evaluate_parameter(Some(&body.id), name).ok();
+    let parameters = parameter_values(body.parameters.keys(), value);
+    json!({
+        "body_id": body.id,
+        "features": body.features.iter().map(Feature::id).collect::<Vec<_>>(),
+        "parameters": parameters,
+    })
+}
+
+/// Write nodes as GLB: 3 buffer views/accessors per mesh (positions,
+/// normals, indices), one material per mesh, all under a root node
+fn write_glb(nodes: &[GlbNode], root_extras: Option<Value>, options: &GltfOptions) -> Vec<u8> {
+    // ── Phase 1: Build binary buffer ─────────────────────────
+    let mut bin_data: Vec<u8> = Vec::new();
+    let mut accessors = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut gltf_meshes = Vec::new();
+    let mut materials = Vec::new();
+    let mut gltf_nodes = Vec::new();
+    let mut children: Vec<usize> = Vec::new();
+
+    for node in nodes {
+        let mesh = node.mesh;
+        let vertex_count = mesh.vertex_count();
+        let index_count = mesh.indices.len();
+        if vertex_count == 0 || index_count == 0 {
+            continue;
+        }
+
+        // Interleaved vertices [px, py, pz, nx, ny, nz, r, g, b] → node
+        // coordinates, positions and normals separately
+        let inverse = node.rotation.inverse();
+        let mut positions: Vec<f32> = Vec::with_capacity(vertex_count * 3);
+        let mut normals: Vec<f32> = Vec::with_capacity(vertex_count * 3);
+        let mut pos_min = [f32::MAX; 3];
+        let mut pos_max = [f32::MIN; 3];
+        for v in mesh.vertices.chunks_exact(9) {
+            let world = DVec3::new(v[0] as f64, v[1] as f64, v[2] as f64);
+            let p = (inverse * (world - node.translation)).as_vec3().to_array();
+            let n = (inverse * DVec3::new(v[3] as f64, v[4] as f64, v[5] as f64)).as_vec3().to_array();
+            positions.extend_from_slice(&p);
+            normals.extend_from_slice(&n);
+            for k in 0..3 {
+                pos_min[k] = pos_min[k].min(p[k]);
+                pos_max[k] = pos_max[k].max(p[k]);
+            }
+        }
+
+        let buffer_view = buffer_views.len();
+        for (data, target) in [
+            (floats_to_bytes(&positions), ARRAY_BUFFER),
+            (floats_to_bytes(&normals), ARRAY_BUFFER),
+            (u32s_to_bytes(&mesh.indices), ELEMENT_ARRAY_BUFFER),
+        ] {
+            buffer_views.push(json!({
+                "buffer": 0,
+                "byteOffset": bin_data.len(),
+                "byteLength": data.len(),
+                "target": target
+            }));
+            bin_data.extend_from_slice(&data);
+        }
+
+        let accessor = accessors.len();
+        accessors.push(json!({
+            "bufferView": buffer_view,
+            "byteOffset": 0,
+            "componentType": FLOAT,
+            "count": vertex_count,
+            "type": "VEC3",
+            "min": pos_min,
+            "max": pos_max
+        }));
+        accessors.push(json!({
+            "bufferView": buffer_view + 1,
+            "byteOffset": 0,
+            "componentType": FLOAT,
+            "count": vertex_count,
+            "type": "VEC3"
+        }));
+        accessors.push(json!({
+            "bufferView": buffer_view + 2,
+            "byteOffset": 0,
+            "componentType": UNSIGNED_INT,
+            "count": index_count,
+            "type": "SCALAR"
+        }));
+
+        let mut material = json!({
+            "name": node.name,
+            "pbrMetallicRoughness": {
+                "baseColorFactor": node.material.base_color,
+                "metallicFactor": node.material.metallic,
+                "roughnessFactor": node.material.roughness
+            }
+        });
+        if node.material.base_color[3] < 1.0 {
+            material["alphaMode"] = json!("BLEND");
+        }
+        if options.unlit {
+            material["extensions"] = json!({ KHR_MATERIALS_UNLIT: {} });
+        }
+        materials.push(material);
+
+        gltf_meshes.push(json!({
+            "name": node.name,
+            "primitives": [{
+                "attributes": {
+                    "POSITION": accessor,
+                    "NORMAL": accessor + 1
+                },
+                "indices": accessor + 2,
+                "material": materials.len() - 1
+            }]
+        }));
+
+        let mut gltf_node = json!({ "name": node.name, "mesh": gltf_meshes.len() - 1 });
+        if node.translation != DVec3::ZERO {
+            gltf_node["translation"] = json!(node.translation.to_array());
+        }
+        if node.rotation != DQuat::IDENTITY {
+            gltf_node["rotation"] = json!(node.rotation.to_array());
+        }
+        if let Some(extras) = &node.extras {
+            gltf_node["extras"] = extras.clone();
+        }
+        // Node 0 is the root
+        children.push(gltf_nodes.len() + 1);
+        gltf_nodes.push(gltf_node);
+    }
+
+    if gltf_nodes.is_empty() {
+        return Vec::new();
+    }
+
+    // ── Phase 2: Build glTF JSON ─────────────────────────────
+    let mut root = json!({ "name": "vCAD", "children": children });
+    if let Some(extras) = root_extras {
+        root["extras"] = extras;
+    }
+    gltf_nodes.insert(0, root);
+
+    let mut gltf_json = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": "vCAD v0.1"
+        },
+        "scene": 0,
+        "scenes": [{
+            "name": "Scene",
+            "nodes": [0]
+        }],
+        "nodes": gltf_nodes,
+        "meshes": gltf_meshes,
+        "materials": materials,
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+        "buffers": [{
+            "byteLength": bin_data.len()
+        }]
+    });
+    if options.unlit {
+        gltf_json["extensionsUsed"] = json!([KHR_MATERIALS_UNLIT]);
+    }
+
+    let json_str = serde_json::to_string(&gltf_json).unwrap_or_default();
+    let mut json_bytes = json_str.into_bytes();
+
+    // Pad JSON to 4-byte alignment with spaces (per GLB spec)
+    while json_bytes.len() % 4 != 0 {
+        json_bytes.push(b' ');
+    }
+
+    // Pad BIN to 4-byte alignment with zeros (per GLB spec)
+    while bin_data.len() % 4 != 0 {
+        bin_data.push(0);
+    }
+
+    // ── Phase 3: Assemble GLB ────────────────────────────────
+    let json_chunk_length = json_bytes.len() as u32;
+    let bin_chunk_length = bin_data.len() as u32;
+
+    let total_length: u32 = 12 // header
+        + 8 + json_chunk_length  // JSON chunk header + data
+        + 8 + bin_chunk_length;  // BIN chunk header + data
+
+    let mut glb = Vec::with_capacity(total_length as usize);
+
+    // Header
+    glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+    glb.extend_from_slice(&GLB_VERSION.to_le_bytes());
+    glb.extend_from_slice(&total_length.to_le_bytes());
+
+    // JSON chunk
+    glb.extend_from_slice(&json_chunk_length.to_le_bytes());
+    glb.extend_from_slice(&CHUNK_TYPE_JSON.to_le_bytes());
+    glb.extend_from_slice(&json_bytes);
+
+    // BIN chunk
+    glb.extend_from_slice(&bin_chunk_length.to_le_bytes());
+    glb.extend_from_slice(&CHUNK_TYPE_BIN.to_le_bytes());
+    glb.extend_from_slice(&bin_data);
+
+    glb
+}
+
+fn floats_to_bytes(data: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.len() * 4);
+    for &f in data {
+        bytes.extend_from_slice(&f.to_le_bytes());
+    }
+    bytes
+}
+
+fn u32s_to_bytes(data: &[u32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.len() * 4);
+    for &v in data {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::triangle;
+    use shared::{Parameter, ParameterValue, Primitive, Transform};
+
+    /// JSON chunk of a GLB file
+    fn gltf_json(glb: &[u8]) -> Value {
+        let length = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        serde_json::from_slice(&glb[20..20 + length]).unwrap()
+    }
+
+    fn parameter(name: &str, value: ParameterValue) -> Parameter {
+        Parameter { name: name.to_string(), value, unit: None, description: None }
+    }
+
+    #[test]
+    fn test_scene_glb_nodes_materials_extras() {
+        let mut transform = Transform::new();
+        transform.position = [10.0, 0.0, 0.0];
+        let mut body = Body {
+            id: "body_1".to_string(),
+            name: "Bracket".to_string(),
+            features: vec![Feature::BasePrimitive {
+                id: "primitive_1".to_string(),
+                primitive: Primitive::Cube { width: 1.0, height: 1.0, depth: 1.0 },
+                transform,
+            }],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        };
+        let width = parameter("width", ParameterValue::Number { value: 2.5 });
+        body.parameters.insert("width".to_string(), width);
+        let mut glass = body.clone();
+        glass.id = "body_2".to_string();
+        glass.appearance = Some(Appearance {
+            color: [0.0, 0.0, 1.0],
+            opacity: 0.25,
+            material: shared::Material::preset("Steel"),
+        });
+        let mut scene = SceneDescriptionV2 { bodies: vec![body, glass], ..Default::default() };
+        let gap = parameter("gap", ParameterValue::Formula { expression: "width*2".to_string() });
+        scene.parameters.insert("gap".to_string(), gap);
+        let meshes = HashMap::from([("body_1".to_string(), triangle([10.0, 0.0, 0.0], [1.0, 0.0, 0.0])), ("body_2".to_string(), triangle([10.0, 0.0, 0.0], [1.0, 0.0, 0.0]))]);
+
+        let glb = build_scene_glb(&scene, &meshes, &GltfOptions { unlit: true });
+        let gltf = gltf_json(&glb);
+
+        let node = &gltf["nodes"][1];
+        assert_eq!(gltf["nodes"][0]["children"], json!([1, 2]));
+        assert_eq!(node["name"], "Bracket");
+        assert_eq!(node["translation"], json!([10.0, 0.0, 0.0]));
+        assert!(node.get("rotation").is_none());
+        assert_eq!(node["extras"]["body_id"], "body_1");
+        assert_eq!(node["extras"]["features"], json!(["primitive_1"]));
+        assert_eq!(node["extras"]["parameters"], json!({ "width": 2.5 }));
+        // `width` is a body parameter: the global formula doesn't resolve
+        assert_eq!(gltf["nodes"][0]["extras"]["parameters"], json!({}));
+        // Vertices are relative to the node
+        assert_eq!(gltf["accessors"][0]["min"], json!([0.0, 0.0, 0.0]));
+
+        let material = &gltf["materials"][0];
+        assert_eq!(material["pbrMetallicRoughness"]["baseColorFactor"], json!([1.0, 0.0, 0.0, 1.0]));
+        assert!(material["extensions"].get(KHR_MATERIALS_UNLIT).is_some());
+        assert_eq!(gltf["extensionsUsed"], json!([KHR_MATERIALS_UNLIT]));
+
+        let glass = &gltf["materials"][1];
+        assert_eq!(glass["pbrMetallicRoughness"]["baseColorFactor"], json!([0.0, 0.0, 1.0, 0.25]));
+        assert_eq!(glass["pbrMetallicRoughness"]["metallicFactor"], json!(0.9f32));
+        assert_eq!(glass["alphaMode"], "BLEND");
+    }
+
+    #[test]
+    fn test_plain_glb_keeps_world_coordinates() {
+        let meshes = HashMap::from([("b".to_string(), triangle([10.0, 0.0, 0.0], [1.0, 0.0, 0.0])), ("a".to_string(), triangle([10.0, 0.0, 0.0], [1.0, 0.0, 0.0]))]);
+        let gltf = gltf_json(&build_glb(&meshes));
+
+        let names: Vec<&str> = (1..3).map(|i| gltf["nodes"][i]["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(gltf["materials"].as_array().unwrap().len(), 2);
+        assert_eq!(gltf["accessors"][0]["min"], json!([10.0, 0.0, 0.0]));
+        assert!(gltf.get("extensionsUsed").is_none());
+        assert!(build_glb(&HashMap::new()).is_empty());
+    }
+}
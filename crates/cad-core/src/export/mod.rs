@@ -0,0 +1,148 @@
+//! Exporters. Mesh writers take built `MeshData` keyed by body (as produced
+//! by `build_scene_meshes_v2` / `CsgCache`) and return file bytes; DXF and SVG
+//! writers take a flat `Drawing` of a sketch or planar face.
+
+mod drawing;
+mod dxf;
+mod glb;
+mod obj;
+mod ply;
+mod stl;
+mod svg;
+mod threemf;
+mod zip;
+
+pub use drawing::{face_drawing, sketch_drawing, Annotation, Drawing, DrawingOptions, Shape};
+pub use dxf::build_dxf;
+pub use glb::{build_glb, build_scene_glb, GltfOptions};
+pub use obj::build_obj;
+pub use ply::{build_ply, build_ply_per_body, PlyFormat};
+pub use stl::{build_stl, build_stl_per_body, StlFormat};
+pub use svg::build_svg;
+pub use threemf::{build_3mf, ModelUnit};
+
+use std::collections::{HashMap, HashSet};
+
+use shared::SceneDescriptionV2;
+
+use crate::build::{apply_body_color, BODY_COLOR};
+use crate::configurations::configuration_file_name;
+use crate::mesh::MeshData;
+
+/// Meshes in their body colours (appearance or default), without the
+/// selection and face highlights of display meshes
+pub fn meshes_with_body_colors(scene: &SceneDescriptionV2, meshes: &HashMap<String, MeshData>) -> HashMap<String, MeshData> {
+    meshes
+        .iter()
+        .map(|(id, mesh)| {
+            let color = scene
+                .bodies
+                .iter()
+                .find(|b| &b.id == id)
+                .and_then(|b| b.appearance.as_ref())
+                .map_or(BODY_COLOR, |appearance| appearance.color);
+            let mut mesh = mesh.clone();
+            apply_body_color(&mut mesh, color);
+            (id.clone(), mesh)
+        })
+        .collect()
+}
+
+/// Meshes keyed by body name instead of body id, for exporters that label
+/// objects. Bodies with an empty or duplicated name keep their id.
+pub fn meshes_by_body_name(scene: &SceneDescriptionV2, meshes: &HashMap<String, MeshData>) -> HashMap<String, MeshData> {
+    meshes_by_name(meshes, |id| scene.bodies.iter().find(|b| b.id == id).map(|b| b.name.as_str()))
+}
+
+/// Same as `meshes_by_body_name` with names from `name_of` (e.g. result
+/// bodies of operations, which have no scene entry)
+pub fn meshes_by_name<'a>(
+    meshes: &HashMap<String, MeshData>,
+    name_of: impl Fn(&str) -> Option<&'a str>,
+) -> HashMap<String, MeshData> {
+    let mut named = HashMap::new();
+    let mut ids: Vec<&String> = meshes.keys().collect();
+    ids.sort();
+    for id in ids {
+        let name = name_of(id)
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && !named.contains_key(*name) && !meshes.contains_key(*name));
+        let key = name.map(str::to_string).unwrap_or_else(|| id.clone());
+        named.insert(key, meshes[id].clone());
+    }
+    named
+}
+
+/// Text escaped for XML content and attribute values (SVG, 3MF)
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// File name for an exported object, by the same rule as configuration
+/// exports: anything but `[A-Za-z0-9._-]` becomes `_`
+pub fn sanitize_file_name(name: &str, extension: &str) -> String {
+    configuration_file_name(name, extension)
+}
+
+/// File names for a batch of exported objects: sanitized like
+/// `sanitize_file_name`, with `_2`, `_3`, … appended to names that collide
+/// (case-insensitively) with an earlier one
+pub fn unique_file_names<'a>(names: impl IntoIterator<Item = &'a str>, extension: &str) -> Vec<String> {
+    let mut used = HashSet::new();
+    names
+        .into_iter()
+        .map(|name| {
+            let mut file = sanitize_file_name(name, extension);
+            let stem = file[..file.len() - extension.len() - 1].to_string();
+            let mut n = 2;
+            while !used.insert(file.to_lowercase()) {
+                file = format!("{}_{}.{}", stem, n, extension);
+                n += 1;
+            }
+            file
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meshes_with_body_colors() {
+        let body = |id: &str, appearance| shared::Body {
+            id: id.to_string(),
+            name: id.to_string(),
+            features: vec![],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance,
+        };
+        let red = shared::Appearance { color: [1.0, 0.0, 0.0], ..Default::default() };
+        let scene = SceneDescriptionV2 { bodies: vec![body("a", None), body("b", Some(red))], ..Default::default() };
+        let highlighted = MeshData { vertices: vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.3, 0.7, 0.9], indices: vec![] };
+        let meshes = HashMap::from([("a".to_string(), highlighted.clone()), ("b".to_string(), highlighted)]);
+
+        let colored = meshes_with_body_colors(&scene, &meshes);
+        assert_eq!(colored["a"].vertices[6..9], BODY_COLOR);
+        assert_eq!(colored["b"].vertices[6..9], [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_unique_file_names() {
+        let files = unique_file_names(["A/B", "A:B", "a_b", "A_B_2", "Plate"], "stl");
+        assert_eq!(files, vec!["A_B.stl", "A_B_2.stl", "a_b_3.stl", "A_B_2_2.stl", "Plate.stl"]);
+    }
+}
@@ -0,0 +1,93 @@
+//! Wavefront OBJ writer.
+//!
+//! One `o`/`g` block per mesh, named after its key (body name). Positions
+//! and normals are deduplicated separately, so a vertex shared by faces with
+//! different normals is written once. Vertex colours use the common
+//! `v x y z r g b` extension (read by Blender, MeshLab and most renderers).
+//! Coordinates are in model units (mm); OBJ has no unit field.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::mesh::{MeshData, Welder};
+
+/// Build one OBJ file with an object/group per mesh.
+///
+/// Meshes are written in key order so the output is deterministic.
+pub fn build_obj(meshes: &HashMap<String, MeshData>) -> Vec<u8> {
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+
+    let mut out = String::from("# vCAD OBJ export\n");
+    // OBJ indices are global to the file and 1-based
+    let (mut position_base, mut normal_base) = (1, 1);
+
+    for key in keys {
+        let mesh = &meshes[key];
+        // Group names end at whitespace
+        let name: String = key.chars().map(|c| if c.is_whitespace() { '_' } else { c }).collect();
+        let _ = writeln!(out, "o {}\ng {}", name, name);
+
+        // Positions carry their colour; normals are welded separately
+        let mut positions = Welder::default();
+        let mut normals = Welder::default();
+        let mut faces = String::new();
+        for tri in mesh.indices.chunks_exact(3) {
+            faces.push('f');
+            for &i in tri {
+                let v = &mesh.vertices[i as usize * 9..i as usize * 9 + 9];
+                let (p, new_p) = positions.insert([v[0], v[1], v[2], v[6], v[7], v[8]]);
+                if new_p {
+                    let _ = writeln!(out, "v {} {} {} {} {} {}", v[0], v[1], v[2], v[6], v[7], v[8]);
+                }
+                let (n, new_n) = normals.insert([v[3], v[4], v[5]]);
+                if new_n {
+                    let _ = writeln!(out, "vn {} {} {}", v[3], v[4], v[5]);
+                }
+                let _ = write!(faces, " {}//{}", position_base + p as usize, normal_base + n as usize);
+            }
+            faces.push('\n');
+        }
+        out.push_str(&faces);
+        position_base += positions.len();
+        normal_base += normals.len();
+    }
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::triangles;
+
+    /// Two triangles of a unit cube corner: one facing +Z, one facing +X,
+    /// sharing the edge (1,0,0)-(1,1,0) but not its normals
+    fn corner_mesh() -> MeshData {
+        let tris = [
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+            [[1.0, 0.0, 0.0], [1.0, 0.0, -1.0], [1.0, 1.0, 0.0]],
+        ];
+        triangles(&tris, [0.5, 0.25, 1.0])
+    }
+
+    #[test]
+    fn test_obj_dedupes_positions_and_normals() {
+        let text = String::from_utf8(build_obj(&HashMap::from([("Corner part".to_string(), corner_mesh())]))).unwrap();
+
+        assert!(text.contains("o Corner_part\ng Corner_part\n"));
+        assert_eq!(text.matches("\nv ").count(), 4);
+        assert_eq!(text.matches("\nvn ").count(), 2);
+        assert!(text.contains("v 1 1 0 0.5 0.25 1\n"));
+        assert!(text.contains("f 1//1 2//1 3//1\nf 2//2 4//2 3//2\n"));
+    }
+
+    #[test]
+    fn test_obj_indices_are_global() {
+        let meshes = HashMap::from([("A".to_string(), corner_mesh()), ("B".to_string(), corner_mesh())]);
+        let text = String::from_utf8(build_obj(&meshes)).unwrap();
+
+        let b = text.split("o B\n").nth(1).unwrap();
+        assert!(b.contains("f 5//3 6//3 7//3\n"));
+        assert_eq!(text.matches("\nv ").count(), 8);
+    }
+}
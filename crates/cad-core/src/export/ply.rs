@@ -0,0 +1,153 @@
+//! PLY writer (binary little-endian and ASCII).
+//!
+//! Vertices carry position, normal and an 8-bit colour and are deduplicated
+//! on all three, so faces with different normals keep separate vertices.
+//! PLY has no notion of objects: use `build_ply_per_body` for a file per
+//! body; the merged file lists the bodies in `comment` lines.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mesh::MeshData;
+
+/// PLY flavour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlyFormat {
+    #[default]
+    Binary,
+    Ascii,
+}
+
+fn color_byte(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Build one PLY file containing every mesh.
+///
+/// Meshes are written in key order so the output is deterministic.
+pub fn build_ply(meshes: &HashMap<String, MeshData>, format: PlyFormat) -> Vec<u8> {
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+    let parts: Vec<(&str, &MeshData)> = keys.iter().map(|k| (k.as_str(), &meshes[*k])).collect();
+    write_ply(&parts, format)
+}
+
+/// Build one PLY file per mesh, named after its key (e.g. body name)
+pub fn build_ply_per_body(meshes: &HashMap<String, MeshData>, format: PlyFormat) -> Vec<(String, Vec<u8>)> {
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| (key.clone(), write_ply(&[(key.as_str(), &meshes[key])], format)))
+        .collect()
+}
+
+fn write_ply(parts: &[(&str, &MeshData)], format: PlyFormat) -> Vec<u8> {
+    let welded: Vec<MeshData> = parts.iter().map(|(_, mesh)| mesh.deduplicated()).collect();
+    let vertex_count: usize = welded.iter().map(MeshData::vertex_count).sum();
+    let face_count: usize = welded.iter().map(|m| m.indices.len() / 3).sum();
+
+    let mut header = String::from("ply\n");
+    header.push_str(match format {
+        PlyFormat::Binary => "format binary_little_endian 1.0\n",
+        PlyFormat::Ascii => "format ascii 1.0\n",
+    });
+    header.push_str("comment vCAD export, units: mm\n");
+    for (name, _) in parts {
+        // Comments end at the line break
+        let _ = writeln!(header, "comment object {}", name.replace(['\r', '\n'], " "));
+    }
+    let _ = writeln!(header, "element vertex {}", vertex_count);
+    for property in ["x", "y", "z", "nx", "ny", "nz"] {
+        let _ = writeln!(header, "property float {}", property);
+    }
+    header.push_str("property uchar red\nproperty uchar green\nproperty uchar blue\n");
+    let _ = writeln!(header, "element face {}", face_count);
+    header.push_str("property list uchar uint vertex_indices\nend_header\n");
+
+    let mut out = header.into_bytes();
+    match format {
+        PlyFormat::Binary => {
+            for mesh in &welded {
+                for v in mesh.vertices.chunks_exact(9) {
+                    for x in &v[..6] {
+                        out.extend_from_slice(&x.to_le_bytes());
+                    }
+                    out.extend(v[6..].iter().map(|&c| color_byte(c)));
+                }
+            }
+            let mut offset = 0u32;
+            for mesh in &welded {
+                for tri in mesh.indices.chunks_exact(3) {
+                    out.push(3);
+                    for i in tri {
+                        out.extend_from_slice(&(i + offset).to_le_bytes());
+                    }
+                }
+                offset += mesh.vertex_count() as u32;
+            }
+        }
+        PlyFormat::Ascii => {
+            let mut body = String::new();
+            for mesh in &welded {
+                for v in mesh.vertices.chunks_exact(9) {
+                    let _ = writeln!(
+                        body,
+                        "{} {} {} {} {} {} {} {} {}",
+                        v[0],
+                        v[1],
+                        v[2],
+                        v[3],
+                        v[4],
+                        v[5],
+                        color_byte(v[6]),
+                        color_byte(v[7]),
+                        color_byte(v[8])
+                    );
+                }
+            }
+            let mut offset = 0u32;
+            for mesh in &welded {
+                for tri in mesh.indices.chunks_exact(3) {
+                    let _ = writeln!(body, "3 {} {} {}", tri[0] + offset, tri[1] + offset, tri[2] + offset);
+                }
+                offset += mesh.vertex_count() as u32;
+            }
+            out.extend_from_slice(body.as_bytes());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::square;
+
+    #[test]
+    fn test_ascii_merged() {
+        let meshes = HashMap::from([("A".to_string(), square(1.0, [1.0, 0.5, 0.0])), ("B".to_string(), square(1.0, [1.0, 0.5, 0.0]))]);
+        let text = String::from_utf8(build_ply(&meshes, PlyFormat::Ascii)).unwrap();
+
+        assert!(text.contains("comment object A\ncomment object B\n"));
+        assert!(text.contains("element vertex 8\n"));
+        assert!(text.contains("element face 4\n"));
+        assert!(text.contains("\n1 1 0 0 0 1 255 128 0\n"));
+        // Second body's faces are offset past the first body's vertices
+        assert!(text.ends_with("3 4 5 6\n3 4 6 7\n"));
+    }
+
+    #[test]
+    fn test_binary_per_body_size() {
+        let files = build_ply_per_body(&HashMap::from([("Quad".to_string(), square(1.0, [1.0, 0.5, 0.0]))]), PlyFormat::Binary);
+        assert_eq!(files.len(), 1);
+
+        let bytes = &files[0].1;
+        let marker = b"end_header\n";
+        let body = bytes.windows(marker.len()).position(|w| w == marker).unwrap() + marker.len();
+        // 4 vertices * (6 floats + 3 bytes) + 2 faces * (1 + 3 * 4 bytes)
+        assert_eq!(bytes.len() - body, 4 * 27 + 2 * 13);
+    }
+}
@@ -0,0 +1,156 @@
+//! STL writer (binary and ASCII).
+//!
+//! STL has no units; coordinates are written in model units (mm). Facet
+//! normals are recomputed from the triangle winding rather than taken from
+//! the (possibly smoothed) vertex normals.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mesh::MeshData;
+
+/// Binary STL header (80 bytes). Must not start with "solid", otherwise some
+/// readers mistake the file for ASCII.
+const BINARY_HEADER: &[u8] = b"vCAD binary STL";
+
+/// STL flavour
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StlFormat {
+    #[default]
+    Binary,
+    Ascii,
+}
+
+type Triangle = [[f32; 3]; 4];
+
+/// Triangles of a mesh as [normal, a, b, c]
+fn triangles(mesh: &MeshData) -> impl Iterator<Item = Triangle> + '_ {
+    let position = |i: u32| {
+        let base = i as usize * 9;
+        [mesh.vertices[base], mesh.vertices[base + 1], mesh.vertices[base + 2]]
+    };
+    mesh.indices.chunks_exact(3).map(move |tri| {
+        let (a, b, c) = (position(tri[0]), position(tri[1]), position(tri[2]));
+        [facet_normal(a, b, c), a, b, c]
+    })
+}
+
+fn facet_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
+    let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let n = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+    let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+    if len > f32::EPSILON {
+        n.map(|x| x / len)
+    } else {
+        // Degenerate triangle: readers accept a zero normal
+        [0.0; 3]
+    }
+}
+
+/// Build one STL file containing every mesh (a single merged solid).
+///
+/// Meshes are written in key order so the output is deterministic.
+pub fn build_stl(meshes: &HashMap<String, MeshData>, format: StlFormat) -> Vec<u8> {
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+    let parts: Vec<&MeshData> = keys.iter().map(|k| &meshes[*k]).collect();
+
+    match format {
+        StlFormat::Binary => write_binary(&parts),
+        StlFormat::Ascii => write_ascii("vcad", &parts),
+    }
+}
+
+/// Build one STL file per mesh, named after its key (e.g. body name)
+pub fn build_stl_per_body(meshes: &HashMap<String, MeshData>, format: StlFormat) -> Vec<(String, Vec<u8>)> {
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| {
+            let mesh = &meshes[key];
+            let bytes = match format {
+                StlFormat::Binary => write_binary(&[mesh]),
+                StlFormat::Ascii => write_ascii(key, &[mesh]),
+            };
+            (key.clone(), bytes)
+        })
+        .collect()
+}
+
+fn write_binary(parts: &[&MeshData]) -> Vec<u8> {
+    let count: usize = parts.iter().map(|m| m.indices.len() / 3).sum();
+
+    let mut bytes = Vec::with_capacity(84 + count * 50);
+    let mut header = [0u8; 80];
+    header[..BINARY_HEADER.len()].copy_from_slice(BINARY_HEADER);
+    bytes.extend_from_slice(&header);
+    bytes.extend_from_slice(&(count as u32).to_le_bytes());
+
+    for mesh in parts {
+        for triangle in triangles(mesh) {
+            for v in triangle.iter().flatten() {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+            // Attribute byte count
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+fn write_ascii(name: &str, parts: &[&MeshData]) -> Vec<u8> {
+    // The solid name runs to the end of the line
+    let name: String = name.chars().map(|c| if c.is_whitespace() { '_' } else { c }).collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "solid {}", name);
+    for mesh in parts {
+        for [n, a, b, c] in triangles(mesh) {
+            let _ = writeln!(out, "  facet normal {:e} {:e} {:e}", n[0], n[1], n[2]);
+            out.push_str("    outer loop\n");
+            for v in [a, b, c] {
+                let _ = writeln!(out, "      vertex {:e} {:e} {:e}", v[0], v[1], v[2]);
+            }
+            out.push_str("    endloop\n  endfacet\n");
+        }
+    }
+    let _ = writeln!(out, "endsolid {}", name);
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::{triangle, GREY};
+
+    #[test]
+    fn test_binary_layout() {
+        let meshes = HashMap::from([("a".to_string(), triangle([0.0; 3], GREY)), ("b".to_string(), triangle([0.0; 3], GREY))]);
+        let stl = build_stl(&meshes, StlFormat::Binary);
+
+        assert_eq!(stl.len(), 84 + 2 * 50);
+        assert!(!stl.starts_with(b"solid"));
+        assert_eq!(u32::from_le_bytes(stl[80..84].try_into().unwrap()), 2);
+        // Facet normal of the first triangle
+        let nz = f32::from_le_bytes(stl[92..96].try_into().unwrap());
+        assert_eq!(nz, 1.0);
+    }
+
+    #[test]
+    fn test_ascii_per_body() {
+        let meshes = HashMap::from([("Bracket left".to_string(), triangle([0.0; 3], GREY))]);
+        let files = build_stl_per_body(&meshes, StlFormat::Ascii);
+
+        assert_eq!(files.len(), 1);
+        let text = String::from_utf8(files[0].1.clone()).unwrap();
+        assert!(text.starts_with("solid Bracket_left\n"));
+        assert!(text.trim_end().ends_with("endsolid Bracket_left"));
+        assert_eq!(text.matches("facet normal").count(), 1);
+        assert!(text.contains("facet normal 0e0 0e0 1e0"));
+        assert_eq!(text.matches("vertex").count(), 3);
+    }
+}
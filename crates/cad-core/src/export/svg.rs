@@ -0,0 +1,159 @@
+//! SVG writer for 2D drawings.
+//!
+//! One SVG user unit is one mm, and the document size is set in mm so that
+//! laser software imports it at scale. The Y axis is flipped (SVG points
+//! down). Outlines are in group `outline` (black hairline strokes, no
+//! fill), annotations in group `dimensions` (blue).
+
+use std::fmt::Write;
+
+use super::drawing::{arc_span, Drawing, Shape};
+use super::escape_xml;
+
+/// Stroke width of outlines, mm
+const STROKE_WIDTH: f64 = 0.1;
+
+/// Coordinate rounded to 1 nm, without trailing zeros or negative zero
+fn num(value: f64) -> String {
+    format!("{}", (value * 1e6).round() / 1e6 + 0.0)
+}
+
+/// Point in SVG coordinates
+fn svg_point([x, y]: [f64; 2]) -> String {
+    format!("{} {}", num(x), num(-y))
+}
+
+fn shape_element(shape: &Shape) -> String {
+    match shape {
+        Shape::Line { start, end } => {
+            let [x1, y1, x2, y2] = [start[0], -start[1], end[0], -end[1]].map(num);
+            format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#, x1, y1, x2, y2)
+        }
+        Shape::Circle { center, radius } => {
+            format!(r#"<circle cx="{}" cy="{}" r="{}"/>"#, num(center[0]), num(-center[1]), num(*radius))
+        }
+        Shape::Arc { center, radius, start_angle, end_angle } => {
+            let span = arc_span(*start_angle, *end_angle);
+            if span >= std::f64::consts::TAU - 1e-9 {
+                return shape_element(&Shape::Circle { center: *center, radius: *radius });
+            }
+            let at = |angle: f64| [center[0] + radius * angle.cos(), center[1] + radius * angle.sin()];
+            // Counter-clockwise in drawing coordinates is clockwise after the
+            // Y flip: sweep flag 0
+            format!(
+                r#"<path d="M {} A {} {} 0 {} 0 {}"/>"#,
+                svg_point(at(*start_angle)),
+                num(*radius),
+                num(*radius),
+                i32::from(span > std::f64::consts::PI),
+                svg_point(at(start_angle + span)),
+            )
+        }
+        Shape::Polyline { points, closed } => {
+            let points: Vec<String> = points.iter().map(|p| svg_point(*p).replace(' ', ",")).collect();
+            let tag = if *closed { "polygon" } else { "polyline" };
+            format!(r#"<{} points="{}"/>"#, tag, points.join(" "))
+        }
+    }
+}
+
+/// Build an SVG document from a drawing
+pub fn build_svg(drawing: &Drawing) -> Vec<u8> {
+    let (min, max) = drawing.bounds().unwrap_or(([0.0, 0.0], [0.0, 0.0]));
+    let text_height = drawing.text_height();
+    // Room for strokes and annotation text at the border
+    let margin = if drawing.annotations.is_empty() { STROKE_WIDTH } else { text_height * 1.5 };
+    let (width, height) = (max[0] - min[0] + 2.0 * margin, max[1] - min[1] + 2.0 * margin);
+
+    let mut out = String::new();
+    let _ = writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}mm" height="{h}mm" viewBox="{} {} {w} {h}">"#,
+        num(min[0] - margin),
+        num(-max[1] - margin),
+        w = num(width),
+        h = num(height),
+    );
+
+    let _ = writeln!(
+        out,
+        r##"<g id="outline" fill="none" stroke="#000000" stroke-width="{}">"##,
+        STROKE_WIDTH
+    );
+    for shape in &drawing.shapes {
+        let _ = writeln!(out, "  {}", shape_element(shape));
+    }
+    let _ = writeln!(out, "</g>");
+
+    if !drawing.annotations.is_empty() {
+        let _ = write!(out, r##"<g id="dimensions" fill="none" stroke="#0000ff" stroke-width="{}""##, STROKE_WIDTH / 2.0);
+        let _ = writeln!(out, r#" font-family="sans-serif" text-anchor="middle">"#);
+        for annotation in &drawing.annotations {
+            for [start, end] in annotation.lines(text_height) {
+                let _ = writeln!(out, "  {}", shape_element(&Shape::Line { start, end }));
+            }
+            let ([x, y], rotation) = annotation.text_placement(text_height);
+            let (x, y) = (num(x), num(-y));
+            let transform = format!("rotate({} {} {})", num(-rotation), x, y);
+            let _ = writeln!(
+                out,
+                r##"  <text x="{}" y="{}" font-size="{}" fill="#0000ff" stroke="none" transform="{}">{}</text>"##,
+                x,
+                y,
+                num(text_height),
+                transform,
+                escape_xml(&annotation.text),
+            );
+        }
+        let _ = writeln!(out, "</g>");
+    }
+    let _ = writeln!(out, "</svg>");
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+    use crate::export::drawing::Annotation;
+
+    #[test]
+    fn test_svg_document() {
+        let drawing = Drawing {
+            shapes: vec![
+                Shape::Polyline { points: vec![[0.0, 0.0], [4.0, 0.0], [4.0, 2.0]], closed: true },
+                Shape::Arc { center: [0.0, 0.0], radius: 1.0, start_angle: 0.0, end_angle: FRAC_PI_2 },
+            ],
+            annotations: Vec::new(),
+        };
+        let svg = String::from_utf8(build_svg(&drawing)).unwrap();
+
+        assert!(svg.contains(r#"width="4.2mm" height="2.2mm" viewBox="-0.1 -2.1 4.2 2.2""#), "{}", svg);
+        assert!(svg.contains(r#"<polygon points="0,0 4,0 4,-2"/>"#));
+        // Quarter arc from (1, 0) to (0, 1), drawn with the Y axis flipped
+        assert!(svg.contains(r#"<path d="M 1 0 A 1 1 0 0 0 0 -1"/>"#));
+        assert!(!svg.contains(r#"id="dimensions""#));
+    }
+
+    #[test]
+    fn test_svg_annotations() {
+        let drawing = Drawing {
+            shapes: vec![Shape::Circle { center: [0.0, 0.0], radius: 2.0 }],
+            annotations: vec![Annotation {
+                from: [-2.0, 0.0],
+                to: [2.0, 0.0],
+                line_start: [-2.0, -3.0],
+                line_end: [2.0, -3.0],
+                text: "Ø4 <max>".to_string(),
+            }],
+        };
+        let svg = String::from_utf8(build_svg(&drawing)).unwrap();
+
+        assert!(svg.contains(r#"<circle cx="0" cy="0" r="2"/>"#));
+        assert!(svg.contains(r#"id="dimensions""#));
+        assert!(svg.contains(">Ø4 &lt;max&gt;</text>"));
+        assert!(svg.contains(r#"transform="rotate(0 0 "#));
+    }
+}
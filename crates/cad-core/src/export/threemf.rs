@@ -0,0 +1,206 @@
+//! 3MF writer (3MF Core Specification 1.x).
+//!
+//! Every mesh becomes a separate `<object>` named after its key (body name)
+//! with a display colour from `<basematerials>`. Vertices are welded by exact
+//! position, since 3MF meshes share vertices between triangles while
+//! `MeshData` keeps three per triangle. Model coordinates are in mm and are
+//! scaled to the requested unit, which is written to `<model unit="...">`.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::escape_xml;
+use super::zip::write_zip;
+use crate::mesh::{MeshData, Welder};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+ <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+ <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+ <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+const MODEL_PATH: &str = "3D/3dmodel.model";
+
+/// Length unit of a 3MF model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelUnit {
+    #[default]
+    Millimeter,
+    Centimeter,
+    Meter,
+    Inch,
+}
+
+impl ModelUnit {
+    /// Value of the `unit` attribute
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelUnit::Millimeter => "millimeter",
+            ModelUnit::Centimeter => "centimeter",
+            ModelUnit::Meter => "meter",
+            ModelUnit::Inch => "inch",
+        }
+    }
+
+    /// Millimeters per unit
+    pub fn to_mm(&self) -> f64 {
+        match self {
+            ModelUnit::Millimeter => 1.0,
+            ModelUnit::Centimeter => 10.0,
+            ModelUnit::Meter => 1000.0,
+            ModelUnit::Inch => 25.4,
+        }
+    }
+}
+
+/// Mesh with welded vertices
+struct IndexedMesh {
+    positions: Vec<[f32; 3]>,
+    triangles: Vec<[u32; 3]>,
+}
+
+/// Weld identical positions (after scaling) and drop triangles that
+/// collapse, which 3MF consumers reject.
+fn weld(mesh: &MeshData, scale: f32) -> IndexedMesh {
+    let mut welder = Welder::default();
+    let mut index_of = |i: u32| {
+        let base = i as usize * 9;
+        welder.insert([0, 1, 2].map(|k| mesh.vertices[base + k] * scale)).0
+    };
+
+    let mut triangles = Vec::with_capacity(mesh.indices.len() / 3);
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [index_of(tri[0]), index_of(tri[1]), index_of(tri[2])];
+        if a != b && b != c && a != c {
+            triangles.push([a, b, c]);
+        }
+    }
+    IndexedMesh { positions: welder.into_values(), triangles }
+}
+
+/// Display colour of a mesh: the colour of its first vertex as `#RRGGBB`
+fn display_color(mesh: &MeshData) -> String {
+    let rgb = mesh.vertices.get(6..9).unwrap_or(&[0.6, 0.6, 0.65]);
+    let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}", byte(rgb[0]), byte(rgb[1]), byte(rgb[2]))
+}
+
+/// The `3D/3dmodel.model` document
+fn model_xml(meshes: &HashMap<String, MeshData>, unit: ModelUnit) -> String {
+    let scale = (1.0 / unit.to_mm()) as f32;
+    let mut keys: Vec<&String> = meshes.keys().collect();
+    keys.sort();
+    let objects: Vec<(&String, &MeshData, IndexedMesh)> = keys
+        .into_iter()
+        .map(|name| (name, &meshes[name], weld(&meshes[name], scale)))
+        .filter(|(_, _, welded)| !welded.triangles.is_empty())
+        .collect();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<model unit=\"{}\" xml:lang=\"en-US\" xmlns=\"http://schemas.microsoft.com/3dmanufacturing/core/2015/02\">",
+        unit.as_str()
+    );
+    xml.push_str(" <metadata name=\"Application\">vCAD</metadata>\n");
+    xml.push_str(" <resources>\n");
+
+    // Resource 1 holds the colours, objects start at 2
+    xml.push_str("  <basematerials id=\"1\">\n");
+    for (name, mesh, _) in &objects {
+        let _ = writeln!(xml, "   <base name=\"{}\" displaycolor=\"{}\"/>", escape_xml(name), display_color(mesh));
+    }
+    xml.push_str("  </basematerials>\n");
+
+    for (index, (name, _, welded)) in objects.iter().enumerate() {
+        let _ = writeln!(
+            xml,
+            "  <object id=\"{}\" type=\"model\" name=\"{}\" pid=\"1\" pindex=\"{}\">",
+            index + 2,
+            escape_xml(name),
+            index
+        );
+        xml.push_str("   <mesh>\n    <vertices>\n");
+        for [x, y, z] in &welded.positions {
+            let _ = writeln!(xml, "     <vertex x=\"{}\" y=\"{}\" z=\"{}\"/>", x, y, z);
+        }
+        xml.push_str("    </vertices>\n    <triangles>\n");
+        for [a, b, c] in &welded.triangles {
+            let _ = writeln!(xml, "     <triangle v1=\"{}\" v2=\"{}\" v3=\"{}\"/>", a, b, c);
+        }
+        xml.push_str("    </triangles>\n   </mesh>\n  </object>\n");
+    }
+    xml.push_str(" </resources>\n <build>\n");
+    for index in 0..objects.len() {
+        let _ = writeln!(xml, "  <item objectid=\"{}\"/>", index + 2);
+    }
+    xml.push_str(" </build>\n</model>\n");
+    xml
+}
+
+/// Build a 3MF package with one object per mesh, named after its key.
+///
+/// Objects are written in key order so the output is deterministic.
+pub fn build_3mf(meshes: &HashMap<String, MeshData>, unit: ModelUnit) -> Vec<u8> {
+    let model = model_xml(meshes, unit);
+    write_zip(&[
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+        ("_rels/.rels", RELATIONSHIPS.as_bytes()),
+        (MODEL_PATH, model.as_bytes()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh_fixtures::square;
+    use crate::export::zip::read_zip;
+
+    fn model_of(package: &[u8]) -> String {
+        let entries = read_zip(package);
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["[Content_Types].xml", "_rels/.rels", MODEL_PATH]);
+        String::from_utf8(entries[2].1.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_objects_named_and_welded() {
+        let meshes = HashMap::from([
+            ("Plate <A>".to_string(), square(10.0, [1.0, 0.0, 0.0])),
+            ("Lid".to_string(), square(10.0, [0.6, 0.6, 0.65])),
+        ]);
+        let model = model_of(&build_3mf(&meshes, ModelUnit::Millimeter));
+
+        assert!(model.contains("<model unit=\"millimeter\""));
+        // Key order: Lid first
+        assert!(model.contains("<object id=\"2\" type=\"model\" name=\"Lid\" pid=\"1\" pindex=\"0\">"));
+        assert!(model.contains("name=\"Plate &lt;A&gt;\" pid=\"1\" pindex=\"1\">"));
+        assert!(model.contains("<base name=\"Plate &lt;A&gt;\" displaycolor=\"#FF0000\"/>"));
+        assert!(model.contains("displaycolor=\"#9999A6\""));
+        // Six corners per square welded into four
+        assert_eq!(model.matches("<vertex ").count(), 8);
+        assert_eq!(model.matches("<triangle ").count(), 4);
+        assert_eq!(model.matches("<item objectid=").count(), 2);
+    }
+
+    #[test]
+    fn test_unit_scaling() {
+        let meshes = HashMap::from([("Plate".to_string(), square(10.0, [0.5; 3]))]);
+        let model = model_of(&build_3mf(&meshes, ModelUnit::Centimeter));
+
+        assert!(model.contains("<model unit=\"centimeter\""));
+        assert!(model.contains("<vertex x=\"1\" y=\"1\" z=\"0\"/>"));
+        assert!(!model.contains("x=\"10\""));
+    }
+}
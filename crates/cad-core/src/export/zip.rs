@@ -0,0 +1,161 @@
+//! Minimal zip writer for package formats (3MF).
+//!
+//! Entries are deflated in memory; no zip64, no extra fields. Timestamps are
+//! fixed to 1980-01-01 so identical scenes produce identical files.
+
+use std::io::Write;
+
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+/// Version 2.0: deflate
+const VERSION: u16 = 20;
+const METHOD_DEFLATE: u16 = 8;
+/// MS-DOS date of 1980-01-01
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+struct CentralEntry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    offset: u32,
+}
+
+fn put_u16(out: &mut Vec<u8>, v: u16) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+/// Build a zip archive from `(path, contents)` entries, in the given order
+pub(crate) fn write_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut central = Vec::with_capacity(entries.len());
+
+    for (name, data) in entries {
+        let mut crc = Crc::new();
+        crc.update(data);
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        // Writing into a Vec cannot fail
+        let _ = encoder.write_all(data);
+        let compressed = encoder.finish().unwrap_or_default();
+
+        let entry = CentralEntry {
+            name: name.to_string(),
+            crc: crc.sum(),
+            compressed: compressed.len() as u32,
+            size: data.len() as u32,
+            offset: out.len() as u32,
+        };
+
+        put_u32(&mut out, LOCAL_HEADER);
+        put_u16(&mut out, VERSION);
+        put_u16(&mut out, 0); // flags
+        put_u16(&mut out, METHOD_DEFLATE);
+        put_u16(&mut out, 0); // time
+        put_u16(&mut out, DOS_DATE);
+        put_u32(&mut out, entry.crc);
+        put_u32(&mut out, entry.compressed);
+        put_u32(&mut out, entry.size);
+        put_u16(&mut out, entry.name.len() as u16);
+        put_u16(&mut out, 0); // extra field length
+        out.extend_from_slice(entry.name.as_bytes());
+        out.extend_from_slice(&compressed);
+
+        central.push(entry);
+    }
+
+    let directory_offset = out.len() as u32;
+    for entry in &central {
+        put_u32(&mut out, CENTRAL_HEADER);
+        put_u16(&mut out, VERSION); // made by
+        put_u16(&mut out, VERSION); // needed to extract
+        put_u16(&mut out, 0); // flags
+        put_u16(&mut out, METHOD_DEFLATE);
+        put_u16(&mut out, 0); // time
+        put_u16(&mut out, DOS_DATE);
+        put_u32(&mut out, entry.crc);
+        put_u32(&mut out, entry.compressed);
+        put_u32(&mut out, entry.size);
+        put_u16(&mut out, entry.name.len() as u16);
+        put_u16(&mut out, 0); // extra field length
+        put_u16(&mut out, 0); // comment length
+        put_u16(&mut out, 0); // disk number
+        put_u16(&mut out, 0); // internal attributes
+        put_u32(&mut out, 0); // external attributes
+        put_u32(&mut out, entry.offset);
+        out.extend_from_slice(entry.name.as_bytes());
+    }
+    let directory_size = out.len() as u32 - directory_offset;
+
+    put_u32(&mut out, END_OF_CENTRAL_DIRECTORY);
+    put_u16(&mut out, 0); // this disk
+    put_u16(&mut out, 0); // disk with the central directory
+    put_u16(&mut out, central.len() as u16);
+    put_u16(&mut out, central.len() as u16);
+    put_u32(&mut out, directory_size);
+    put_u32(&mut out, directory_offset);
+    put_u16(&mut out, 0); // comment length
+    out
+}
+
+/// Read back the entries of an archive produced by `write_zip`
+#[cfg(test)]
+pub(crate) fn read_zip(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+    use std::io::Read;
+
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]) as usize;
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+
+    let end = bytes.len() - 22;
+    assert_eq!(u32_at(end), END_OF_CENTRAL_DIRECTORY);
+    let count = u16_at(end + 10);
+    let mut at = u32_at(end + 16) as usize;
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        assert_eq!(u32_at(at), CENTRAL_HEADER);
+        let crc = u32_at(at + 16);
+        let compressed = u32_at(at + 20) as usize;
+        let name_len = u16_at(at + 28);
+        let offset = u32_at(at + 42) as usize;
+        let name = String::from_utf8(bytes[at + 46..at + 46 + name_len].to_vec()).unwrap();
+        at += 46 + name_len;
+
+        assert_eq!(u32_at(offset), LOCAL_HEADER);
+        let data_start = offset + 30 + u16_at(offset + 26) + u16_at(offset + 28);
+        let mut data = Vec::new();
+        flate2::read::DeflateDecoder::new(&bytes[data_start..data_start + compressed])
+            .read_to_end(&mut data)
+            .unwrap();
+        let mut check = Crc::new();
+        check.update(&data);
+        assert_eq!(check.sum(), crc, "CRC mismatch in {}", name);
+        entries.push((name, data));
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip_roundtrip() {
+        let text = "hello 3MF ".repeat(100);
+        let zip = write_zip(&[("a.txt", text.as_bytes()), ("dir/empty", b"")]);
+
+        let entries = read_zip(&zip);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], ("a.txt".to_string(), text.into_bytes()));
+        assert_eq!(entries[1], ("dir/empty".to_string(), Vec::new()));
+        // Repetitive text is actually compressed
+        assert!(zip.len() < 500);
+    }
+}
@@ -1,7 +1,7 @@
 use glam::Vec3;
 use shared::{Sketch, SketchElement, SketchPlane, Transform};
 
-use crate::viewport::mesh::MeshData;
+use crate::mesh::MeshData;
 
 const DEFAULT_COLOR: [f32; 3] = [0.6, 0.6, 0.65];
 const CIRCLE_SEGMENTS: usize = 32;
@@ -16,15 +16,7 @@ pub fn extrude_mesh(
     transform: &Transform,
     height: f64,
 ) -> Result<MeshData, String> {
-    // Filter out construction geometry
-    let geometry_elements: Vec<&SketchElement> = sketch
-        .elements
-        .iter()
-        .enumerate()
-        .filter(|(i, _)| !sketch.is_construction(*i))
-        .map(|(_, e)| e)
-        .collect();
-
+    let geometry_elements = geometry_elements(sketch);
     if geometry_elements.is_empty() {
         return Err("No geometry elements to extrude (all elements are construction)".to_string());
     }
@@ -151,6 +143,45 @@ pub fn extrude_mesh(
     Ok(MeshData { vertices, indices })
 }
 
+/// Whether `extrude_mesh` builds the sketch correctly: its caps are
+/// fan-triangulated per profile, so only a single convex profile qualifies.
+/// Holes, several profiles and concave outlines need the CSG extrusion.
+pub fn is_fan_extrudable(sketch: &Sketch) -> bool {
+    let Ok(profiles) = extract_2d_profiles_ref(&geometry_elements(sketch)) else {
+        return false;
+    };
+    let [profile] = profiles.as_slice() else {
+        return false;
+    };
+    let mut profile = profile.clone();
+    if profile.len() > 3 && dist_sq(&profile[0], profile.last().unwrap()) <= CHAIN_TOLERANCE_SQ {
+        profile.pop();
+    }
+    let n = profile.len();
+    if n < 3 {
+        return false;
+    }
+
+    // Convex: every turn goes the same way as the whole outline
+    let orientation = signed_area(&profile).signum();
+    (0..n).all(|i| {
+        let (a, b, c) = (profile[i], profile[(i + 1) % n], profile[(i + 2) % n]);
+        let turn = (b[0] - a[0]) * (c[1] - b[1]) - (b[1] - a[1]) * (c[0] - b[0]);
+        turn * orientation >= -1e-9
+    })
+}
+
+/// Sketch elements without construction geometry
+fn geometry_elements(sketch: &Sketch) -> Vec<&SketchElement> {
+    sketch
+        .elements
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !sketch.is_construction(*i))
+        .map(|(_, e)| e)
+        .collect()
+}
+
 // ── Revolve ─────────────────────────────────────────────────
 
 /// Generate revolved mesh from a sketch (supports multiple profiles).
@@ -294,10 +325,10 @@ fn extract_2d_profiles_ref(elements: &[&SketchElement]) -> Result<Vec<Vec<[f64;
     for elem in elements.iter() {
         match elem {
             // Self-contained closed shapes → add directly as profile
-            SketchElement::Circle { center, radius } => {
+            SketchElement::Circle { center, radius, .. } => {
                 profiles.push(tessellate_circle(center.x, center.y, *radius));
             }
-            SketchElement::Rectangle { corner, width, height } => {
+            SketchElement::Rectangle { corner, width, height, .. } => {
                 profiles.push(vec![
                     [corner.x, corner.y],
                     [corner.x + width, corner.y],
@@ -306,24 +337,24 @@ fn extract_2d_profiles_ref(elements: &[&SketchElement]) -> Result<Vec<Vec<[f64;
                 ]);
             }
             // Chainable elements - collect for ordered chaining
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 chainable_segments.push(ChainableSegment {
                     points: vec![[start.x, start.y], [end.x, end.y]],
                 });
             }
-            SketchElement::Arc { center, radius, start_angle, end_angle } => {
+            SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
                 let arc = tessellate_arc(center.x, center.y, *radius, *start_angle, *end_angle);
                 if !arc.is_empty() {
                     chainable_segments.push(ChainableSegment { points: arc });
                 }
             }
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 let pts: Vec<[f64; 2]> = points.iter().map(|p| [p.x, p.y]).collect();
                 if pts.len() >= 2 {
                     chainable_segments.push(ChainableSegment { points: pts });
                 }
             }
-            SketchElement::Spline { points } => {
+            SketchElement::Spline { points, .. } => {
                 let pts: Vec<[f64; 2]> = points.iter().map(|p| [p.x, p.y]).collect();
                 if pts.len() >= 2 {
                     chainable_segments.push(ChainableSegment { points: pts });
@@ -394,10 +425,10 @@ pub fn extract_2d_profiles(elements: &[SketchElement]) -> Result<Vec<Vec<[f64; 2
     for elem in elements.iter() {
         match elem {
             // Self-contained closed shapes → add directly as profile
-            SketchElement::Circle { center, radius } => {
+            SketchElement::Circle { center, radius, .. } => {
                 profiles.push(tessellate_circle(center.x, center.y, *radius));
             }
-            SketchElement::Rectangle { corner, width, height } => {
+            SketchElement::Rectangle { corner, width, height, .. } => {
                 profiles.push(vec![
                     [corner.x, corner.y],
                     [corner.x + width, corner.y],
@@ -406,24 +437,24 @@ pub fn extract_2d_profiles(elements: &[SketchElement]) -> Result<Vec<Vec<[f64; 2
                 ]);
             }
             // Chainable elements - collect for ordered chaining
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 chainable_segments.push(ChainableSegment {
                     points: vec![[start.x, start.y], [end.x, end.y]],
                 });
             }
-            SketchElement::Arc { center, radius, start_angle, end_angle } => {
+            SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
                 let arc = tessellate_arc(center.x, center.y, *radius, *start_angle, *end_angle);
                 if !arc.is_empty() {
                     chainable_segments.push(ChainableSegment { points: arc });
                 }
             }
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 let pts: Vec<[f64; 2]> = points.iter().map(|p| [p.x, p.y]).collect();
                 if pts.len() >= 2 {
                     chainable_segments.push(ChainableSegment { points: pts });
                 }
             }
-            SketchElement::Spline { points } => {
+            SketchElement::Spline { points, .. } => {
                 let pts: Vec<[f64; 2]> = points.iter().map(|p| [p.x, p.y]).collect();
                 if pts.len() >= 2 {
                     chainable_segments.push(ChainableSegment { points: pts });
@@ -461,6 +492,11 @@ fn chain_segments_by_proximity(mut segments: Vec<ChainableSegment>) -> Vec<Vec<[
             let chain_start = chain[0];
             let chain_end = *chain.last().unwrap();
 
+            // A closed loop is complete: touching loops stay separate profiles
+            if chain.len() > 3 && dist_sq(&chain_start, &chain_end) <= CHAIN_TOLERANCE_SQ {
+                break;
+            }
+
             // Find best match for chain end (extend forward)
             let mut best_match: Option<(usize, bool, f64)> = None; // (index, reversed, dist_sq)
 
@@ -540,20 +576,21 @@ fn chain_segments_by_proximity(mut segments: Vec<ChainableSegment>) -> Vec<Vec<[
 
 fn extract_single_element(elem: &SketchElement) -> Result<Vec<[f64; 2]>, String> {
     match elem {
-        SketchElement::Circle { center, radius } => {
+        SketchElement::Circle { center, radius, .. } => {
             Ok(tessellate_circle(center.x, center.y, *radius))
         }
         SketchElement::Rectangle {
             corner,
             width,
             height,
+            ..
         } => Ok(vec![
             [corner.x, corner.y],
             [corner.x + width, corner.y],
             [corner.x + width, corner.y + height],
             [corner.x, corner.y + height],
         ]),
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             if points.len() < 3 {
                 return Err("Polyline needs >= 3 points for extrusion".to_string());
             }
@@ -564,6 +601,7 @@ fn extract_single_element(elem: &SketchElement) -> Result<Vec<[f64; 2]>, String>
             radius,
             start_angle,
             end_angle,
+            ..
         } => {
             let pts = tessellate_arc(center.x, center.y, *radius, *start_angle, *end_angle);
             if pts.len() < 3 {
@@ -571,7 +609,7 @@ fn extract_single_element(elem: &SketchElement) -> Result<Vec<[f64; 2]>, String>
             }
             Ok(pts)
         }
-        SketchElement::Spline { points } => {
+        SketchElement::Spline { points, .. } => {
             if points.len() < 3 {
                 return Err("Spline needs >= 3 points for extrusion".to_string());
             }
@@ -607,6 +645,90 @@ pub fn tessellate_arc(cx: f64, cy: f64, radius: f64, start: f64, end: f64) -> Ve
         .collect()
 }
 
+// ── Regions (outer boundary + holes) ────────────────────────
+
+/// Signed area of a closed polygon (positive = counter-clockwise)
+pub fn signed_area(polygon: &[[f64; 2]]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Even-odd point-in-polygon test
+fn point_in_polygon(point: [f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + n - 1) % n]);
+        if (a[1] > point[1]) != (b[1] > point[1])
+            && point[0] < (b[0] - a[0]) * (point[1] - a[1]) / (b[1] - a[1]) + a[0]
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Group closed profiles into regions by nesting depth.
+///
+/// Each region is an outer boundary (counter-clockwise) followed by its
+/// holes (clockwise): a profile nested in an odd number of others is a hole
+/// of the smallest profile containing it, one nested in an even number
+/// (an island inside a hole) starts a new region. The duplicated closing
+/// point of a chained loop is dropped.
+pub fn group_profiles_into_regions(profiles: Vec<Vec<[f64; 2]>>) -> Vec<Vec<Vec<[f64; 2]>>> {
+    let mut profiles: Vec<Vec<[f64; 2]>> = profiles
+        .into_iter()
+        .map(|mut profile| {
+            if profile.len() > 3 && dist_sq(&profile[0], profile.last().unwrap()) <= CHAIN_TOLERANCE_SQ {
+                profile.pop();
+            }
+            profile
+        })
+        .collect();
+    // Largest first: a container always precedes the profiles inside it
+    profiles.sort_by(|a, b| signed_area(b).abs().total_cmp(&signed_area(a).abs()));
+
+    // Index of the smallest containing profile and nesting depth
+    let mut parent: Vec<Option<usize>> = vec![None; profiles.len()];
+    let mut depth = vec![0usize; profiles.len()];
+    for i in 0..profiles.len() {
+        for j in (0..i).rev() {
+            if point_in_polygon(profiles[i][0], &profiles[j]) {
+                parent[i] = Some(j);
+                depth[i] = depth[j] + 1;
+                break;
+            }
+        }
+    }
+
+    let mut regions: Vec<Vec<Vec<[f64; 2]>>> = Vec::new();
+    let mut region_of = vec![usize::MAX; profiles.len()];
+    for (i, mut profile) in profiles.into_iter().enumerate() {
+        let area = signed_area(&profile);
+        if depth[i].is_multiple_of(2) {
+            if area < 0.0 {
+                profile.reverse();
+            }
+            region_of[i] = regions.len();
+            regions.push(vec![profile]);
+        } else {
+            if area > 0.0 {
+                profile.reverse();
+            }
+            let outer = parent[i].expect("hole has a parent");
+            region_of[i] = region_of[outer];
+            regions[region_of[outer]].push(profile);
+        }
+    }
+    regions
+}
+
 fn dist_sq(a: &[f64; 2], b: &[f64; 2]) -> f64 {
     let dx = a[0] - b[0];
     let dy = a[1] - b[1];
@@ -736,6 +858,7 @@ mod tests {
 
     fn rect_element(x: f64, y: f64, w: f64, h: f64) -> SketchElement {
         SketchElement::Rectangle {
+            id: None,
             corner: Point2D { x, y },
             width: w,
             height: h,
@@ -744,6 +867,7 @@ mod tests {
 
     fn circle_element(cx: f64, cy: f64, r: f64) -> SketchElement {
         SketchElement::Circle {
+            id: None,
             center: Point2D { x: cx, y: cy },
             radius: r,
         }
@@ -798,6 +922,7 @@ mod tests {
     #[test]
     fn test_extract_2d_profile_single_line_error() {
         let result = extract_2d_profile(&[SketchElement::Line {
+            id: None,
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 1.0, y: 0.0 },
         }]);
@@ -821,6 +946,7 @@ mod tests {
     #[test]
     fn test_extract_2d_profile_polyline() {
         let profile = extract_2d_profile(&[SketchElement::Polyline {
+            id: None,
             points: vec![
                 Point2D { x: 0.0, y: 0.0 },
                 Point2D { x: 1.0, y: 0.0 },
@@ -952,6 +1078,24 @@ mod tests {
         assert!(max[2].abs() < 0.01);
     }
 
+    #[test]
+    fn test_is_fan_extrudable() {
+        assert!(is_fan_extrudable(&xy_sketch(vec![rect_element(0.0, 0.0, 2.0, 1.0)])));
+        assert!(is_fan_extrudable(&xy_sketch(vec![circle_element(0.0, 0.0, 1.0)])));
+
+        // A plate with a hole
+        let plate = xy_sketch(vec![rect_element(0.0, 0.0, 30.0, 20.0), circle_element(15.0, 10.0, 4.0)]);
+        assert!(!is_fan_extrudable(&plate));
+
+        // Concave L outline
+        let l_shape = [[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 2.0], [0.0, 2.0], [0.0, 0.0]];
+        let polyline = SketchElement::Polyline {
+            id: None,
+            points: l_shape.iter().map(|[x, y]| Point2D { x: *x, y: *y }).collect(),
+        };
+        assert!(!is_fan_extrudable(&xy_sketch(vec![polyline])));
+    }
+
     #[test]
     fn test_extrude_zero_height_error() {
         // Zero height is caught in build.rs, not in extrude_mesh itself
@@ -1031,6 +1175,7 @@ mod tests {
     fn test_revolve_too_few_points_error() {
         // Single line can't form a revolve profile
         let sketch = xy_sketch(vec![SketchElement::Line {
+            id: None,
             start: Point2D { x: 1.0, y: 0.0 },
             end: Point2D { x: 2.0, y: 0.0 },
         }]);
@@ -1089,4 +1234,22 @@ mod tests {
         // YZ plane: extrudes along X
         assert!((max[0] - 3.0).abs() < 0.01);
     }
+
+    // --- Regions ---
+
+    #[test]
+    fn test_group_profiles_into_regions() {
+        let square = |x: f64, size: f64| vec![[x, x], [x + size, x], [x + size, x + size], [x, x + size]];
+        // Plate with a hole, an island inside the hole and a separate square,
+        // all counter-clockwise
+        let profiles = vec![square(3.0, 1.0), square(2.0, 6.0), square(0.0, 10.0), square(20.0, 1.0)];
+        let regions = group_profiles_into_regions(profiles);
+
+        assert_eq!(regions.len(), 3);
+        let plate = regions.iter().find(|r| r.len() == 2).expect("plate with a hole");
+        assert!((signed_area(&plate[0]) - 100.0).abs() < 1e-9);
+        assert!((signed_area(&plate[1]) + 36.0).abs() < 1e-9);
+        // The island and the separate square are outer boundaries
+        assert!(regions.iter().filter(|r| r.len() == 1).all(|r| signed_area(&r[0]) > 0.0));
+    }
 }
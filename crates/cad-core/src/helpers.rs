@@ -0,0 +1,62 @@
+//! Body/feature helpers needed by feature evaluation
+
+use shared::{Body, Feature, Transform};
+
+/// Check if body has base geometry (primitive, extrude, revolve or imported mesh)
+pub fn has_base_geometry(body: &Body) -> bool {
+    body.features.iter().any(|f| {
+        matches!(
+            f,
+            Feature::BasePrimitive { .. }
+                | Feature::BaseExtrude { .. }
+                | Feature::BaseRevolve { .. }
+                | Feature::BaseMesh { .. }
+        )
+    })
+}
+
+/// Get the base transform of a body (from BasePrimitive, BaseExtrude, BaseRevolve or BaseMesh)
+/// Returns Transform::new() if no base feature found
+pub fn get_body_base_transform(body: &Body) -> Transform {
+    for feature in &body.features {
+        match feature {
+            Feature::BasePrimitive { transform, .. } => return transform.clone(),
+            Feature::BaseExtrude { sketch_transform, .. } => return sketch_transform.clone(),
+            Feature::BaseRevolve { sketch_transform, .. } => return sketch_transform.clone(),
+            Feature::BaseMesh { transform, .. } => return transform.clone(),
+            _ => continue,
+        }
+    }
+    Transform::new()
+}
+
+/// Placement of a body's sketch for extrusion: the sketch's own rotation and
+/// scale, at its position combined with the body base transform
+pub fn sketch_placement(body_transform: &Transform, sketch_transform: &Transform) -> Transform {
+    Transform {
+        position: combine_transforms(body_transform, sketch_transform).position,
+        ..sketch_transform.clone()
+    }
+}
+
+/// Combine two transforms (parent + child)
+/// Result position = parent.position + child.position * parent.scale
+pub fn combine_transforms(parent: &Transform, child: &Transform) -> Transform {
+    Transform {
+        position: [
+            parent.position[0] + child.position[0] * parent.scale[0],
+            parent.position[1] + child.position[1] * parent.scale[1],
+            parent.position[2] + child.position[2] * parent.scale[2],
+        ],
+        rotation: [
+            parent.rotation[0] + child.rotation[0],
+            parent.rotation[1] + child.rotation[1],
+            parent.rotation[2] + child.rotation[2],
+        ],
+        scale: [
+            parent.scale[0] * child.scale[0],
+            parent.scale[1] * child.scale[1],
+            parent.scale[2] * child.scale[2],
+        ],
+    }
+}
@@ -0,0 +1,549 @@
+//! DXF reader for 2D outlines (laser/waterjet drawings) into sketches.
+//!
+//! Reads the ENTITIES section: LINE, CIRCLE, ARC, LWPOLYLINE and SPLINE.
+//! Polyline bulges and NURBS splines are tessellated, since sketch polylines
+//! and splines are point lists. Coordinates are scaled to mm from
+//! `$INSUNITS` (unitless drawings are taken as mm) unless a scale is given;
+//! unknown unit codes are reported as a warning and also taken as mm.
+//! Other entity types (TEXT, INSERT, HATCH, ...) are skipped and reported.
+
+use std::f64::consts::PI;
+
+use shared::{Point2D, Sketch, SketchElement, SketchPlane};
+
+/// Maximum angle between tessellated points of a bulge arc
+const BULGE_STEP: f64 = PI / 16.0;
+/// Samples per control point of a NURBS spline
+const SPLINE_SAMPLES_PER_CONTROL: usize = 8;
+
+/// How to place an imported drawing
+#[derive(Debug, Clone)]
+pub struct DxfOptions {
+    pub plane: SketchPlane,
+    pub offset: f64,
+    /// Entities on these layers (case-insensitive) become construction geometry
+    pub construction_layers: Vec<String>,
+    /// Millimetres per drawing unit; `None` reads `$INSUNITS`
+    pub scale: Option<f64>,
+}
+
+impl Default for DxfOptions {
+    fn default() -> Self {
+        Self {
+            plane: SketchPlane::Xy,
+            offset: 0.0,
+            construction_layers: Vec::new(),
+            scale: None,
+        }
+    }
+}
+
+/// Result of a DXF import
+#[derive(Debug, Clone)]
+pub struct DxfImport {
+    pub sketch: Sketch,
+    /// Layers of the imported entities, sorted
+    pub layers: Vec<String>,
+    /// Types of skipped entities, sorted
+    pub skipped: Vec<String>,
+    /// Millimetres per drawing unit that was applied
+    pub scale: f64,
+    /// Problems that did not stop the import (e.g. unknown units)
+    pub warnings: Vec<String>,
+}
+
+/// Millimetres per unit of a `$INSUNITS` code (0 = unitless → mm); `None`
+/// for codes outside the DXF table
+fn insunits_scale(code: i64) -> Option<f64> {
+    Some(match code {
+        0 | 4 => 1.0,
+        1 => 25.4, // inch
+        2 => 304.8, // foot
+        3 => 1_609_344.0, // mile
+        5 => 10.0, // centimetre
+        6 => 1e3, // metre
+        7 => 1e6, // kilometre
+        8 => 2.54e-5, // microinch
+        9 => 0.0254, // mil
+        10 => 914.4, // yard
+        11 => 1e-7, // ångström
+        12 => 1e-6, // nanometre
+        13 => 1e-3, // micron
+        14 => 100.0, // decimetre
+        15 => 1e4, // decametre
+        16 => 1e5, // hectometre
+        17 => 1e12, // gigametre
+        18 => 1.495_978_707e14, // astronomical unit
+        19 => 9.460_730_472_580_8e18, // light year
+        20 => 3.085_677_581_491_367e19, // parsec
+        21 => 1_200_000.0 / 3937.0, // US survey foot
+        22 => 100_000.0 / 3937.0, // US survey inch
+        23 => 3_600_000.0 / 3937.0, // US survey yard
+        24 => 6_336_000_000.0 / 3937.0, // US survey mile
+        _ => return None,
+    })
+}
+
+/// One entity: its type and group code/value pairs in file order
+struct Entity {
+    kind: String,
+    codes: Vec<(i32, String)>,
+}
+
+impl Entity {
+    fn text(&self, code: i32) -> Option<&str> {
+        self.codes.iter().find(|(c, _)| *c == code).map(|(_, v)| v.as_str())
+    }
+
+    fn float(&self, code: i32) -> Option<f64> {
+        self.text(code)?.parse().ok()
+    }
+
+    fn floats(&self, code: i32) -> Vec<f64> {
+        self.codes.iter().filter(|(c, _)| *c == code).filter_map(|(_, v)| v.parse().ok()).collect()
+    }
+
+    fn point(&self, x: i32, y: i32) -> Result<[f64; 2], String> {
+        match (self.float(x), self.float(y)) {
+            (Some(x), Some(y)) => Ok([x, y]),
+            _ => Err(format!("{} without coordinates (group {}/{})", self.kind, x, y)),
+        }
+    }
+
+    /// Points from repeated x/y group codes (`10`/`20`, `11`/`21`)
+    fn points(&self, x: i32, y: i32) -> Vec<[f64; 2]> {
+        self.floats(x).into_iter().zip(self.floats(y)).map(|(x, y)| [x, y]).collect()
+    }
+
+    /// The extrusion direction points down the Z axis: the object coordinate
+    /// system is the world mirrored in X (common in CAM-exported files)
+    fn mirrored(&self) -> bool {
+        self.float(230).is_some_and(|z| z < 0.0)
+    }
+}
+
+/// Split DXF text into group code/value pairs
+fn group_pairs(text: &str) -> Result<Vec<(i32, String)>, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    lines
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| pair.len() == 2)
+        .map(|(i, pair)| {
+            let code = pair[0]
+                .trim()
+                .parse()
+                .map_err(|_| format!("DXF line {}: expected a group code, got '{}'", i * 2 + 1, pair[0].trim()))?;
+            Ok((code, pair[1].trim().to_string()))
+        })
+        .collect()
+}
+
+/// `$INSUNITS` from the HEADER section and the entities of the ENTITIES section
+fn read_entities(pairs: &[(i32, String)]) -> (Option<i64>, Vec<Entity>) {
+    let mut insunits = None;
+    let mut section = "";
+    let mut entities: Vec<Entity> = Vec::new();
+
+    let mut i = 0;
+    while i < pairs.len() {
+        let (code, value) = (&pairs[i].0, pairs[i].1.as_str());
+        match (*code, value) {
+            (0, "SECTION") => {
+                section = pairs.get(i + 1).map(|(_, name)| name.as_str()).unwrap_or_default();
+                i += 1;
+            }
+            (0, "ENDSEC") => section = "",
+            (9, "$INSUNITS") if section == "HEADER" => {
+                insunits = pairs.get(i + 1).and_then(|(_, v)| v.parse().ok());
+                i += 1;
+            }
+            (0, kind) if section == "ENTITIES" => entities.push(Entity {
+                kind: kind.to_string(),
+                codes: Vec::new(),
+            }),
+            _ if section == "ENTITIES" => {
+                if let Some(entity) = entities.last_mut() {
+                    entity.codes.push((*code, value.to_string()));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    (insunits, entities)
+}
+
+/// Parse a DXF drawing into a sketch
+pub fn parse_dxf(text: &str, options: &DxfOptions) -> Result<DxfImport, String> {
+    let pairs = group_pairs(text)?;
+    let (insunits, entities) = read_entities(&pairs);
+    let mut warnings = Vec::new();
+    let scale = options.scale.unwrap_or_else(|| {
+        let code = insunits.unwrap_or(0);
+        insunits_scale(code).unwrap_or_else(|| {
+            warnings.push(format!("Unknown $INSUNITS code {}: drawing taken as mm, choose the units explicitly", code));
+            1.0
+        })
+    });
+
+    let mut sketch = Sketch {
+        plane: options.plane.clone(),
+        offset: options.offset,
+        ..Default::default()
+    };
+    let mut layers: Vec<String> = Vec::new();
+    let mut skipped: Vec<String> = Vec::new();
+
+    for entity in &entities {
+        let element = match entity.kind.as_str() {
+            "LINE" => line(entity)?,
+            "CIRCLE" => circle(entity)?,
+            "ARC" => arc(entity)?,
+            "LWPOLYLINE" => lwpolyline(entity)?,
+            "SPLINE" => spline(entity)?,
+            "ENDSEC" | "EOF" => continue,
+            other => {
+                skipped.push(other.to_string());
+                continue;
+            }
+        };
+        let Some(element) = element else { continue };
+
+        let index = sketch.push_element(scale_element(element, scale));
+        let layer = entity.text(8).unwrap_or("0");
+        if options.construction_layers.iter().any(|l| l.eq_ignore_ascii_case(layer)) {
+            sketch.set_construction(index, true);
+        }
+        if !layers.iter().any(|l| l == layer) {
+            layers.push(layer.to_string());
+        }
+    }
+
+    if sketch.elements.is_empty() {
+        return Err("DXF has no LINE, CIRCLE, ARC, LWPOLYLINE or SPLINE entities".to_string());
+    }
+    layers.sort();
+    skipped.sort();
+    skipped.dedup();
+    Ok(DxfImport { sketch, layers, skipped, scale, warnings })
+}
+
+fn point2d([x, y]: [f64; 2]) -> Point2D {
+    Point2D { x, y }
+}
+
+/// Mirror an OCS point into world coordinates when the entity is mirrored
+fn world(entity: &Entity, [x, y]: [f64; 2]) -> [f64; 2] {
+    if entity.mirrored() {
+        [-x, y]
+    } else {
+        [x, y]
+    }
+}
+
+fn line(entity: &Entity) -> Result<Option<SketchElement>, String> {
+    // LINE is given in world coordinates
+    Ok(Some(SketchElement::Line {
+        id: None,
+        start: point2d(entity.point(10, 20)?),
+        end: point2d(entity.point(11, 21)?),
+    }))
+}
+
+fn circle(entity: &Entity) -> Result<Option<SketchElement>, String> {
+    let radius = entity.float(40).ok_or("CIRCLE without radius")?;
+    Ok(Some(SketchElement::Circle {
+        id: None,
+        center: point2d(world(entity, entity.point(10, 20)?)),
+        radius,
+    }))
+}
+
+fn arc(entity: &Entity) -> Result<Option<SketchElement>, String> {
+    let radius = entity.float(40).ok_or("ARC without radius")?;
+    let start = entity.float(50).unwrap_or(0.0).to_radians();
+    let end = entity.float(51).unwrap_or(360.0).to_radians();
+    // Mirroring reverses the direction: the counter-clockwise arc a→b
+    // becomes the counter-clockwise arc (π−b)→(π−a)
+    let (start_angle, end_angle) = if entity.mirrored() { (PI - end, PI - start) } else { (start, end) };
+    Ok(Some(SketchElement::Arc {
+        id: None,
+        center: point2d(world(entity, entity.point(10, 20)?)),
+        radius,
+        start_angle,
+        end_angle,
+    }))
+}
+
+fn lwpolyline(entity: &Entity) -> Result<Option<SketchElement>, String> {
+    // Vertices with the bulge of the segment that starts at them
+    let mut vertices: Vec<([f64; 2], f64)> = Vec::new();
+    for (code, value) in &entity.codes {
+        let Ok(v) = value.parse::<f64>() else { continue };
+        match code {
+            10 => vertices.push(([v, 0.0], 0.0)),
+            20 => {
+                if let Some(last) = vertices.last_mut() {
+                    last.0[1] = v;
+                }
+            }
+            42 => {
+                if let Some(last) = vertices.last_mut() {
+                    last.1 = v;
+                }
+            }
+            _ => {}
+        }
+    }
+    if vertices.len() < 2 {
+        return Ok(None);
+    }
+
+    let closed = entity.text(70).and_then(|f| f.parse::<i64>().ok()).is_some_and(|flags| flags & 1 != 0);
+    let segments = if closed { vertices.len() } else { vertices.len() - 1 };
+
+    let mut points = vec![vertices[0].0];
+    for i in 0..segments {
+        let (from, bulge) = vertices[i];
+        let to = vertices[(i + 1) % vertices.len()].0;
+        if bulge.abs() > 1e-12 {
+            points.extend(bulge_points(from, to, bulge));
+        }
+        points.push(to);
+    }
+
+    Ok(Some(SketchElement::Polyline {
+        id: None,
+        points: points.into_iter().map(|p| point2d(world(entity, p))).collect(),
+    }))
+}
+
+/// Intermediate points of a bulge arc from `from` to `to` (endpoints
+/// excluded). The bulge is tan(θ/4); positive bulges turn counter-clockwise.
+fn bulge_points(from: [f64; 2], to: [f64; 2], bulge: f64) -> Vec<[f64; 2]> {
+    let sweep = 4.0 * bulge.atan();
+    let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+    let chord = dx.hypot(dy);
+    if chord < 1e-12 {
+        return Vec::new();
+    }
+    // Centre lies on the chord's perpendicular bisector (signed by the sweep)
+    let distance = chord / 2.0 / (sweep / 2.0).tan();
+    let center = [
+        (from[0] + to[0]) / 2.0 - dy / chord * distance,
+        (from[1] + to[1]) / 2.0 + dx / chord * distance,
+    ];
+    let radius = (from[0] - center[0]).hypot(from[1] - center[1]);
+    let start = (from[1] - center[1]).atan2(from[0] - center[0]);
+
+    let steps = (sweep.abs() / BULGE_STEP).ceil().max(1.0) as usize;
+    (1..steps)
+        .map(|k| {
+            let angle = start + sweep * k as f64 / steps as f64;
+            [center[0] + radius * angle.cos(), center[1] + radius * angle.sin()]
+        })
+        .collect()
+}
+
+fn spline(entity: &Entity) -> Result<Option<SketchElement>, String> {
+    let degree = entity.float(71).unwrap_or(3.0) as usize;
+    let closed = entity.text(70).and_then(|f| f.parse::<i64>().ok()).is_some_and(|flags| flags & 1 != 0);
+    let knots = entity.floats(40);
+    let control = entity.points(10, 20);
+    let fit = entity.points(11, 21);
+
+    let mut points = if !control.is_empty() && knots.len() == control.len() + degree + 1 {
+        let mut weights = entity.floats(41);
+        weights.resize(control.len(), 1.0);
+        sample_nurbs(degree, &knots, &control, &weights)
+    } else if fit.len() >= 2 {
+        fit
+    } else if control.len() >= 2 {
+        // Inconsistent knot vector: fall back to the control polygon
+        control
+    } else {
+        return Ok(None);
+    };
+
+    if closed && points.first() != points.last() {
+        points.push(points[0]);
+    }
+    Ok(Some(SketchElement::Spline {
+        id: None,
+        points: points.into_iter().map(point2d).collect(),
+    }))
+}
+
+/// Evenly sample a NURBS curve over its knot domain (de Boor's algorithm in
+/// homogeneous coordinates)
+fn sample_nurbs(degree: usize, knots: &[f64], control: &[[f64; 2]], weights: &[f64]) -> Vec<[f64; 2]> {
+    let n = control.len();
+    let (t0, t1) = (knots[degree], knots[n]);
+    let samples = (n * SPLINE_SAMPLES_PER_CONTROL).max(16);
+
+    (0..=samples)
+        .map(|s| {
+            let t = t0 + (t1 - t0) * s as f64 / samples as f64;
+            // Knot span k with knots[k] <= t < knots[k + 1], within the domain
+            let k = (degree..n).rev().find(|&k| knots[k] <= t).unwrap_or(degree);
+            let mut d: Vec<[f64; 3]> = (0..=degree)
+                .map(|j| {
+                    let [x, y] = control[j + k - degree];
+                    let w = weights[j + k - degree];
+                    [x * w, y * w, w]
+                })
+                .collect();
+            for r in 1..=degree {
+                for j in (r..=degree).rev() {
+                    let left = knots[j + k - degree];
+                    let right = knots[j + 1 + k - r];
+                    let alpha = if right > left { (t - left) / (right - left) } else { 0.0 };
+                    d[j] = [0, 1, 2].map(|c| (1.0 - alpha) * d[j - 1][c] + alpha * d[j][c]);
+                }
+            }
+            let [x, y, w] = d[degree];
+            [x / w, y / w]
+        })
+        .collect()
+}
+
+fn scale_element(element: SketchElement, scale: f64) -> SketchElement {
+    if (scale - 1.0).abs() < f64::EPSILON {
+        return element;
+    }
+    let p = |p: Point2D| Point2D { x: p.x * scale, y: p.y * scale };
+    match element {
+        SketchElement::Line { id, start, end } => SketchElement::Line { id, start: p(start), end: p(end) },
+        SketchElement::Circle { id, center, radius } => SketchElement::Circle {
+            id,
+            center: p(center),
+            radius: radius * scale,
+        },
+        SketchElement::Arc { id, center, radius, start_angle, end_angle } => SketchElement::Arc {
+            id,
+            center: p(center),
+            radius: radius * scale,
+            start_angle,
+            end_angle,
+        },
+        SketchElement::Polyline { id, points } => SketchElement::Polyline {
+            id,
+            points: points.into_iter().map(p).collect(),
+        },
+        SketchElement::Spline { id, points } => SketchElement::Spline {
+            id,
+            points: points.into_iter().map(p).collect(),
+        },
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wrap entity group codes into a minimal DXF with an optional `$INSUNITS`
+    fn dxf(insunits: Option<i64>, entities: &str) -> String {
+        let header = insunits
+            .map(|u| format!("0\nSECTION\n2\nHEADER\n9\n$INSUNITS\n70\n{}\n0\nENDSEC\n", u))
+            .unwrap_or_default();
+        format!("{}0\nSECTION\n2\nENTITIES\n{}0\nENDSEC\n0\nEOF\n", header, entities)
+    }
+
+    #[test]
+    fn test_entities_layers_and_units() {
+        let text = dxf(
+            Some(1),
+            "0\nLINE\n8\nCUT\n10\n0\n20\n0\n11\n2\n21\n0\n\
+             0\nCIRCLE\n8\nCUT\n10\n1\n20\n1\n40\n0.5\n\
+             0\nARC\n8\nCenterlines\n10\n0\n20\n0\n40\n1\n50\n0\n51\n90\n\
+             0\nTEXT\n8\nCUT\n1\nPart 42\n",
+        );
+        let options = DxfOptions {
+            construction_layers: vec!["CENTERLINES".to_string()],
+            ..Default::default()
+        };
+        let import = parse_dxf(&text, &options).unwrap();
+
+        assert_eq!(import.scale, 25.4);
+        assert_eq!(import.layers, ["CUT", "Centerlines"]);
+        assert_eq!(import.skipped, ["TEXT"]);
+        let sketch = &import.sketch;
+        assert_eq!(sketch.elements.len(), 3);
+        assert!(matches!(&sketch.elements[0], SketchElement::Line { end, .. } if end.x == 50.8));
+        assert!(matches!(&sketch.elements[1], SketchElement::Circle { radius, .. } if *radius == 12.7));
+        assert!(
+            matches!(&sketch.elements[2], SketchElement::Arc { end_angle, .. } if (end_angle - PI / 2.0).abs() < 1e-12)
+        );
+        assert!(sketch.is_construction(2) && !sketch.is_construction(0));
+    }
+
+    #[test]
+    fn test_insunits_table_and_unknown_code() {
+        let line = |code: i64| dxf(Some(code), "0\nLINE\n8\n0\n10\n0\n20\n0\n11\n1\n21\n0\n");
+        let import = |code: i64, scale: Option<f64>| {
+            parse_dxf(&line(code), &DxfOptions { scale, ..Default::default() }).unwrap()
+        };
+
+        for (code, scale) in [(3, 1_609_344.0), (7, 1e6), (8, 2.54e-5), (11, 1e-7), (12, 1e-6)] {
+            let imported = import(code, None);
+            assert_eq!(imported.scale, scale, "$INSUNITS {}", code);
+            assert!(imported.warnings.is_empty());
+        }
+
+        let unknown = import(99, None);
+        assert_eq!(unknown.scale, 1.0);
+        assert_eq!(unknown.warnings.len(), 1);
+        assert!(unknown.warnings[0].contains("99"), "{}", unknown.warnings[0]);
+        // A chosen scale does not depend on the header
+        assert!(import(99, Some(25.4)).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_closed_lwpolyline_with_bulge() {
+        // 2×1 slot-like outline: straight bottom, semicircular right end (bulge 1 = 180°)
+        let text = dxf(
+            None,
+            "0\nLWPOLYLINE\n8\n0\n90\n3\n70\n1\n10\n0\n20\n0\n10\n2\n20\n0\n42\n1\n10\n2\n20\n1\n",
+        );
+        let import = parse_dxf(&text, &DxfOptions::default()).unwrap();
+        let SketchElement::Polyline { points, .. } = &import.sketch.elements[0] else {
+            panic!("expected a polyline");
+        };
+
+        assert_eq!(import.scale, 1.0);
+        assert_eq!(points.first(), points.last());
+        // The arc bulges out to x = 2.5 (centre (2, 0.5), radius 0.5)
+        let max_x = points.iter().map(|p| p.x).fold(f64::MIN, f64::max);
+        assert!((max_x - 2.5).abs() < 1e-9, "max x = {}", max_x);
+    }
+
+    #[test]
+    fn test_spline_and_mirrored_arc() {
+        // Quadratic Bézier as a clamped NURBS: (0,0) → (1,2) → (2,0), peak y = 1
+        let text = dxf(
+            Some(4),
+            "0\nSPLINE\n8\n0\n70\n8\n71\n2\n72\n6\n73\n3\n\
+             40\n0\n40\n0\n40\n0\n40\n1\n40\n1\n40\n1\n\
+             10\n0\n20\n0\n10\n1\n20\n2\n10\n2\n20\n0\n\
+             0\nARC\n8\n0\n10\n5\n20\n0\n40\n1\n50\n0\n51\n90\n210\n0\n220\n0\n230\n-1\n",
+        );
+        let import = parse_dxf(&text, &DxfOptions::default()).unwrap();
+
+        let SketchElement::Spline { points, .. } = &import.sketch.elements[0] else {
+            panic!("expected a spline");
+        };
+        assert_eq!(points.first(), Some(&Point2D { x: 0.0, y: 0.0 }));
+        assert!((points.last().unwrap().x - 2.0).abs() < 1e-12);
+        let peak = points.iter().map(|p| p.y).fold(f64::MIN, f64::max);
+        assert!((peak - 1.0).abs() < 1e-9, "peak = {}", peak);
+
+        // Mirrored OCS: centre x flips, the 0°..90° arc becomes 90°..180°
+        let SketchElement::Arc { center, start_angle, end_angle, .. } = &import.sketch.elements[1] else {
+            panic!("expected an arc");
+        };
+        assert_eq!(center.x, -5.0);
+        assert!((start_angle - PI / 2.0).abs() < 1e-12 && (end_angle - PI).abs() < 1e-12);
+    }
+}
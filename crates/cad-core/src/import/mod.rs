@@ -0,0 +1,80 @@
+//! Importers. Mesh files (STL/OBJ) are read into `shared::ImportedMesh` for
+//! `Feature::BaseMesh`: vertices are welded by exact position so that a
+//! closed part yields a closed (manifold) mesh; whether it really is
+//! manifold is checked at build time. DXF and SVG drawings are read into
+//! sketches.
+
+mod dxf;
+mod obj;
+mod stl;
+mod svg;
+
+pub use dxf::{parse_dxf, DxfImport, DxfOptions};
+pub use obj::parse_obj;
+pub use stl::parse_stl;
+pub use svg::{parse_svg, SvgImport, SvgOptions};
+
+use std::path::Path;
+
+use shared::ImportedMesh;
+
+use crate::mesh::Welder;
+
+/// Read an STL or OBJ file (by extension). The file name is kept as
+/// `ImportedMesh::source`.
+pub fn import_mesh_file(path: &Path) -> Result<ImportedMesh, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+    let mut mesh = match extension.as_str() {
+        "stl" => parse_stl(&bytes)?,
+        "obj" => parse_obj(&String::from_utf8_lossy(&bytes))?,
+        _ => return Err(format!("Unsupported mesh format '{}': expected .stl or .obj", extension)),
+    };
+    mesh.source = path.file_name().map(|name| name.to_string_lossy().into_owned());
+    Ok(mesh)
+}
+
+/// Read a DXF file into a sketch
+pub fn import_dxf_file(path: &Path, options: &DxfOptions) -> Result<DxfImport, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    // Pre-2007 files are in the ANSI code page; only layer names can be affected
+    parse_dxf(&String::from_utf8_lossy(&bytes), options)
+}
+
+/// Read an SVG file into a sketch
+pub fn import_svg_file(path: &Path, options: &SvgOptions) -> Result<SvgImport, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_svg(&text, options)
+}
+
+/// Collects triangles, welding vertices with identical positions and
+/// dropping triangles that collapse to a line or point
+#[derive(Default)]
+struct MeshWelder {
+    positions: Welder<3>,
+    indices: Vec<u32>,
+}
+
+impl MeshWelder {
+    fn vertex(&mut self, p: [f32; 3]) -> u32 {
+        self.positions.insert(p).0
+    }
+
+    fn triangle(&mut self, a: [f32; 3], b: [f32; 3], c: [f32; 3]) {
+        let [a, b, c] = [self.vertex(a), self.vertex(b), self.vertex(c)];
+        if a != b && b != c && a != c {
+            self.indices.extend_from_slice(&[a, b, c]);
+        }
+    }
+
+    fn finish(self) -> Result<ImportedMesh, String> {
+        if self.indices.is_empty() {
+            return Err("Mesh has no triangles".to_string());
+        }
+        Ok(ImportedMesh {
+            positions: self.positions.into_values().concat(),
+            indices: self.indices,
+            source: None,
+        })
+    }
+}
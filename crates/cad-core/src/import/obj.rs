@@ -0,0 +1,81 @@
+//! Wavefront OBJ reader (geometry only).
+//!
+//! All objects and groups are merged into one mesh; polygons are
+//! triangulated as fans. Texture coordinates, normals and materials are
+//! ignored.
+
+use shared::ImportedMesh;
+
+use super::MeshWelder;
+
+/// Parse OBJ text
+pub fn parse_obj(text: &str) -> Result<ImportedMesh, String> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut welder = MeshWelder::default();
+
+    for (number, line) in text.lines().enumerate() {
+        let line_error = |message: &str| format!("OBJ line {}: {}", number + 1, message);
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                // Extra values (vertex colours, w) are ignored
+                let coords: Vec<f32> = tokens.take(3).filter_map(|t| t.parse().ok()).collect();
+                let [x, y, z] = coords[..] else {
+                    return Err(line_error("expected 'v x y z'"));
+                };
+                positions.push([x, y, z]);
+            }
+            Some("f") => {
+                let corners = tokens
+                    .map(|token| {
+                        // `v`, `v/vt`, `v//vn` or `v/vt/vn`; negative indices count from the end
+                        let index: i64 = token.split('/').next().unwrap_or_default().parse().ok()?;
+                        let resolved = if index < 0 { positions.len() as i64 + index } else { index - 1 };
+                        positions.get(usize::try_from(resolved).ok()?).copied()
+                    })
+                    .collect::<Option<Vec<[f32; 3]>>>()
+                    .ok_or_else(|| line_error("face references a missing vertex"))?;
+                if corners.len() < 3 {
+                    return Err(line_error("face has fewer than 3 vertices"));
+                }
+                for pair in corners[1..].windows(2) {
+                    welder.triangle(corners[0], pair[0], pair[1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    welder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quads_and_negative_indices() {
+        let text = "# unit square as a quad and a duplicate-vertex triangle\n\
+                    o Square\n\
+                    v 0 0 0 1 0 0\n\
+                    v 1 0 0\n\
+                    v 1 1 0\n\
+                    v 0 1 0\n\
+                    vn 0 0 1\n\
+                    f 1//1 2//1 3//1 4//1\n\
+                    g other\n\
+                    v 1 1 0\n\
+                    f -5 -4 -1\n";
+        let mesh = parse_obj(text).unwrap();
+
+        // The duplicate of (1, 1, 0) is welded
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_obj("v 0 0 0\nf 1 2 3\n").unwrap_err(), "OBJ line 2: face references a missing vertex");
+        assert!(parse_obj("v 0 0\n").is_err());
+        assert!(parse_obj("# nothing\n").is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! STL reader (binary and ASCII).
+
+use shared::ImportedMesh;
+
+use super::MeshWelder;
+
+/// Parse an STL file. Binary and ASCII are told apart by the size a binary
+/// file with the declared triangle count would have, since binary headers
+/// may also start with "solid".
+pub fn parse_stl(bytes: &[u8]) -> Result<ImportedMesh, String> {
+    let binary_size = bytes
+        .get(80..84)
+        .map(|count| 84 + 50 * u32::from_le_bytes(count.try_into().unwrap_or_default()) as usize);
+    if binary_size == Some(bytes.len()) {
+        parse_binary(bytes)
+    } else if bytes.trim_ascii_start().starts_with(b"solid") {
+        parse_ascii(&String::from_utf8_lossy(bytes))
+    } else {
+        Err("Not an STL file: size does not match the triangle count".to_string())
+    }
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<ImportedMesh, String> {
+    let mut welder = MeshWelder::default();
+    for record in bytes[84..].chunks_exact(50) {
+        let float = |at: usize| f32::from_le_bytes(record[at..at + 4].try_into().unwrap_or_default());
+        // The facet normal (first 12 bytes) is ignored: winding defines orientation
+        let point = |k: usize| [float(12 + k * 12), float(16 + k * 12), float(20 + k * 12)];
+        welder.triangle(point(0), point(1), point(2));
+    }
+    welder.finish()
+}
+
+fn parse_ascii(text: &str) -> Result<ImportedMesh, String> {
+    let mut welder = MeshWelder::default();
+    let mut facet: Vec<[f32; 3]> = Vec::with_capacity(3);
+
+    for (number, line) in text.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => {
+                let coords: Vec<f32> = tokens.filter_map(|t| t.parse().ok()).collect();
+                let [x, y, z] = coords[..] else {
+                    return Err(format!("STL line {}: expected 'vertex x y z'", number + 1));
+                };
+                facet.push([x, y, z]);
+            }
+            Some("endloop") => {
+                let [a, b, c] = facet[..] else {
+                    return Err(format!("STL line {}: facet has {} vertices, expected 3", number + 1, facet.len()));
+                };
+                welder.triangle(a, b, c);
+                facet.clear();
+            }
+            _ => {}
+        }
+    }
+    welder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TETRAHEDRON: [[[f32; 3]; 3]; 4] = [
+        [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0]],
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+        [[0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+        [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    ];
+
+    #[test]
+    fn test_binary_welds_vertices() {
+        // Header starting with "solid" must still be read as binary
+        let mut bytes = b"solid but binary".to_vec();
+        bytes.resize(80, 0);
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        for tri in TETRAHEDRON {
+            bytes.extend_from_slice(&[0; 12]);
+            for v in tri.iter().flatten() {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+            bytes.extend_from_slice(&[0; 2]);
+        }
+
+        let mesh = parse_stl(&bytes).unwrap();
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.triangle_count(), 4);
+    }
+
+    #[test]
+    fn test_ascii() {
+        let mut text = String::from("solid tetra\n");
+        for tri in TETRAHEDRON {
+            text.push_str("  facet normal 0 0 0\n    outer loop\n");
+            for [x, y, z] in tri {
+                text.push_str(&format!("      vertex {:e} {} {}\n", x, y, z));
+            }
+            text.push_str("    endloop\n  endfacet\n");
+        }
+        text.push_str("endsolid tetra\n");
+
+        let mesh = parse_stl(text.as_bytes()).unwrap();
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(&mesh.indices[..3], &[0, 1, 2]);
+
+        assert!(parse_stl(b"solid empty\nendsolid empty\n").is_err());
+        assert!(parse_stl(b"garbage").is_err());
+    }
+}
@@ -0,0 +1,985 @@
+//! SVG reader for logos and artwork into sketches.
+//!
+//! Reads `path` (all commands), `rect` (with rounded corners), `circle`,
+//! `ellipse`, `line`, `polyline` and `polygon` through nested groups,
+//! applying `transform` attributes and the root `viewBox`. Straight runs
+//! become lines/polylines, circular arcs become `Arc` elements when the
+//! transform keeps them circular, and Béziers and elliptical arcs are
+//! flattened into `Spline` points within a tolerance. Every subpath ends
+//! exactly where it started when closed, so closed outlines chain into
+//! profiles and nested ones become holes on extrusion.
+//!
+//! The document is sized in mm (unitless lengths are CSS px, 96 per inch)
+//! and the Y axis is flipped so the artwork keeps its orientation with the
+//! bottom-left corner of the page at the sketch origin. Text, images and
+//! `use` references are skipped and reported; definitions, masks and
+//! hidden (`display="none"`) content are ignored.
+
+use std::f64::consts::TAU;
+
+use shared::{Point2D, Sketch, SketchElement, SketchPlane};
+
+/// Millimetres per CSS pixel
+const PX_MM: f64 = 25.4 / 96.0;
+/// Subdivision limit for Bézier flattening (2^12 segments per curve)
+const MAX_FLATTEN_DEPTH: u32 = 12;
+/// Upper bound of points per flattened elliptical arc
+const MAX_ARC_POINTS: usize = 1024;
+
+/// Elements whose content is not drawn directly
+const HIDDEN_CONTAINERS: [&str; 12] = [
+    "defs",
+    "clipPath",
+    "mask",
+    "symbol",
+    "pattern",
+    "marker",
+    "metadata",
+    "title",
+    "desc",
+    "style",
+    "script",
+    "filter",
+];
+/// Drawn content that can't be imported as sketch geometry
+const UNSUPPORTED: [&str; 4] = ["text", "image", "use", "foreignObject"];
+
+/// How to place an imported drawing
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    pub plane: SketchPlane,
+    pub offset: f64,
+    /// Maximum distance between a curve and its flattened points, mm
+    pub tolerance: f64,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            plane: SketchPlane::Xy,
+            offset: 0.0,
+            tolerance: 0.05,
+        }
+    }
+}
+
+/// Result of an SVG import
+#[derive(Debug, Clone)]
+pub struct SvgImport {
+    pub sketch: Sketch,
+    /// Skipped element names, sorted
+    pub skipped: Vec<String>,
+    /// Page size in mm, if the document has one
+    pub size: Option<[f64; 2]>,
+}
+
+// ── Affine transforms ───────────────────────────────────────
+
+/// SVG transform matrix `[a b c d e f]`: x' = a·x + c·y + e, y' = b·x + d·y + f
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Affine([f64; 6]);
+
+impl Affine {
+    const IDENTITY: Affine = Affine([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    fn translate(x: f64, y: f64) -> Self {
+        Affine([1.0, 0.0, 0.0, 1.0, x, y])
+    }
+
+    fn scale(x: f64, y: f64) -> Self {
+        Affine([x, 0.0, 0.0, y, 0.0, 0.0])
+    }
+
+    fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Affine([cos, sin, -sin, cos, 0.0, 0.0])
+    }
+
+    /// `self · other`: `other` is applied first
+    fn then_apply(self, other: Affine) -> Self {
+        let [a1, b1, c1, d1, e1, f1] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = other.0;
+        Affine([
+            a1 * a2 + c1 * b2,
+            b1 * a2 + d1 * b2,
+            a1 * c2 + c1 * d2,
+            b1 * c2 + d1 * d2,
+            a1 * e2 + c1 * f2 + e1,
+            b1 * e2 + d1 * f2 + f1,
+        ])
+    }
+
+    fn apply(&self, [x, y]: [f64; 2]) -> [f64; 2] {
+        let [a, b, c, d, e, f] = self.0;
+        [a * x + c * y + e, b * x + d * y + f]
+    }
+
+    fn determinant(&self) -> f64 {
+        let [a, b, c, d, ..] = self.0;
+        a * d - b * c
+    }
+
+    /// Upper bound of the length scale (Frobenius norm)
+    fn max_scale(&self) -> f64 {
+        let [a, b, c, d, ..] = self.0;
+        (a * a + b * b + c * c + d * d).sqrt()
+    }
+
+    /// Uniform scale with rotation and/or reflection: circles stay circles
+    fn is_similarity(&self) -> bool {
+        let [a, b, c, d, ..] = self.0;
+        let (x_len, y_len) = (a * a + b * b, c * c + d * d);
+        let eps = 1e-9 * x_len.max(y_len);
+        (a * c + b * d).abs() <= eps && (x_len - y_len).abs() <= eps
+    }
+
+    /// No rotation or skew: axis-aligned rectangles stay axis-aligned
+    fn is_axis_aligned(&self) -> bool {
+        let [a, b, c, d, ..] = self.0;
+        b.abs() <= 1e-12 * a.abs() && c.abs() <= 1e-12 * d.abs()
+    }
+}
+
+// ── Tokenizers ──────────────────────────────────────────────
+
+/// Numbers of path data, point lists and transforms: separators are
+/// whitespace and commas, and "1.5.5" or "1-2" are two numbers
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { bytes: text.as_bytes(), pos: 0 }
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(c) if c.is_ascii_whitespace() || *c == b',') {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.bytes.len()
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |scanner: &mut Self| {
+            let from = scanner.pos;
+            while scanner.pos < scanner.bytes.len() && scanner.bytes[scanner.pos].is_ascii_digit() {
+                scanner.pos += 1;
+            }
+            scanner.pos > from
+        };
+        if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut any = digits(self);
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            any |= digits(self);
+        }
+        if !any {
+            self.pos = start;
+            return None;
+        }
+        // Exponent, but not the "e" of a unit such as "em"
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mark;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()
+    }
+
+    /// Arc flag: a single "0" or "1", which may be written without separator
+    fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+
+    fn rest(&self) -> &'a str {
+        std::str::from_utf8(&self.bytes[self.pos..]).unwrap_or_default().trim()
+    }
+}
+
+fn numbers(text: &str) -> Vec<f64> {
+    let mut scanner = Scanner::new(text);
+    std::iter::from_fn(|| scanner.number()).collect()
+}
+
+/// Length in mm; `None` for percentages and unknown units
+fn length_mm(text: &str) -> Option<f64> {
+    let mut scanner = Scanner::new(text);
+    let value = scanner.number()?;
+    let unit = match scanner.rest() {
+        "" | "px" => PX_MM,
+        "mm" => 1.0,
+        "cm" => 10.0,
+        "in" => 25.4,
+        "pt" => 25.4 / 72.0,
+        "pc" => 25.4 / 6.0,
+        _ => return None,
+    };
+    Some(value * unit)
+}
+
+/// Parse a `transform` attribute
+fn parse_transform(text: &str) -> Affine {
+    let mut result = Affine::IDENTITY;
+    let mut rest = text;
+    while let Some(open) = rest.find('(') {
+        let Some(close) = rest[open..].find(')').map(|c| open + c) else { break };
+        let name = rest[..open].trim_matches(|c: char| c.is_whitespace() || c == ',');
+        let transform = match (name, numbers(&rest[open + 1..close]).as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Affine([a, b, c, d, e, f]),
+            ("translate", &[x]) => Affine::translate(x, 0.0),
+            ("translate", &[x, y]) => Affine::translate(x, y),
+            ("scale", &[s]) => Affine::scale(s, s),
+            ("scale", &[x, y]) => Affine::scale(x, y),
+            ("rotate", &[angle]) => Affine::rotate(angle),
+            ("rotate", &[angle, x, y]) => Affine::translate(x, y)
+                .then_apply(Affine::rotate(angle))
+                .then_apply(Affine::translate(-x, -y)),
+            ("skewX", &[angle]) => Affine([1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0]),
+            ("skewY", &[angle]) => Affine([1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+            _ => Affine::IDENTITY,
+        };
+        result = result.then_apply(transform);
+        rest = &rest[close + 1..];
+    }
+    result
+}
+
+/// Start or end tag of the document
+enum Tag<'a> {
+    Open {
+        name: &'a str,
+        attributes: Vec<(&'a str, String)>,
+        /// `<... />`: no children and no end tag
+        empty: bool,
+    },
+    Close,
+}
+
+impl Tag<'_> {
+    fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            Tag::Open { attributes, .. } => attributes.iter().find(|(k, _)| *k == key).map(|(_, v)| v.as_str()),
+            Tag::Close => None,
+        }
+    }
+
+    fn number(&self, key: &str) -> f64 {
+        self.attribute(key).and_then(|v| Scanner::new(v).number()).unwrap_or(0.0)
+    }
+
+    fn hidden(&self) -> bool {
+        let style_hidden = self
+            .attribute("style")
+            .is_some_and(|style| style.replace(' ', "").contains("display:none"));
+        self.attribute("display") == Some("none") || style_hidden
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Element name without a namespace prefix
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Split the document into tags, skipping text, comments, CDATA,
+/// processing instructions and the DOCTYPE
+fn tags(text: &str) -> Result<Vec<Tag<'_>>, String> {
+    let mut tags = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            let skip_to = |end: &str| rest.find(end).map(|i| i + end.len());
+            let end = if rest.starts_with("<!--") {
+                skip_to("-->")
+            } else if rest.starts_with("<![CDATA[") {
+                skip_to("]]>")
+            } else if rest.starts_with("<?") {
+                skip_to("?>")
+            } else {
+                // DOCTYPE, possibly with an internal subset in brackets
+                match rest.find('[') {
+                    Some(bracket) if rest.find('>').is_some_and(|gt| bracket < gt) => skip_to("]>"),
+                    _ => skip_to(">"),
+                }
+            };
+            rest = &rest[end.ok_or("SVG: unterminated comment or declaration")?..];
+            continue;
+        }
+
+        // End of the tag, ignoring ">" inside quoted attribute values
+        let mut quote = None;
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| match quote {
+                Some(q) if c == q => {
+                    quote = None;
+                    false
+                }
+                Some(_) => false,
+                None if c == '"' || c == '\'' => {
+                    quote = Some(c);
+                    false
+                }
+                None => c == '>',
+            })
+            .map(|(i, _)| i)
+            .ok_or("SVG: unterminated tag")?;
+        let body = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if body.starts_with('/') {
+            tags.push(Tag::Close);
+            continue;
+        }
+        let empty = body.ends_with('/');
+        let body = body.trim_end_matches('/');
+        let name_end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+        let name = local_name(&body[..name_end]);
+
+        let mut attributes = Vec::new();
+        let mut attrs = &body[name_end..];
+        while let Some(eq) = attrs.find('=') {
+            let key = local_name(attrs[..eq].trim());
+            let value = attrs[eq + 1..].trim_start();
+            let Some(q) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else { break };
+            let Some(close) = value[1..].find(q) else { break };
+            attributes.push((key, unescape(&value[1..close + 1])));
+            attrs = &value[close + 2..];
+        }
+        tags.push(Tag::Open { name, attributes, empty });
+    }
+    Ok(tags)
+}
+
+// ── Geometry ────────────────────────────────────────────────
+
+fn point2d([x, y]: [f64; 2]) -> Point2D {
+    Point2D { x, y }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (a[0] - b[0]).hypot(a[1] - b[1])
+}
+
+/// Distance from a point to the segment a–b
+fn segment_distance(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let len_sq = dx * dx + dy * dy;
+    if len_sq == 0.0 {
+        return distance(p, a);
+    }
+    let t = (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / len_sq).clamp(0.0, 1.0);
+    distance(p, [a[0] + t * dx, a[1] + t * dy])
+}
+
+fn lerp(a: [f64; 2], b: [f64; 2], t: f64) -> [f64; 2] {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+/// Append the points of a cubic Bézier after its start point, subdividing
+/// until the control points are within the tolerance of the chord
+fn flatten_cubic(p: [[f64; 2]; 4], tolerance: f64, depth: u32, out: &mut Vec<[f64; 2]>) {
+    let flat = segment_distance(p[1], p[0], p[3]).max(segment_distance(p[2], p[0], p[3])) <= tolerance;
+    if flat || depth >= MAX_FLATTEN_DEPTH {
+        out.push(p[3]);
+        return;
+    }
+    let (p01, p12, p23) = (lerp(p[0], p[1], 0.5), lerp(p[1], p[2], 0.5), lerp(p[2], p[3], 0.5));
+    let (p012, p123) = (lerp(p01, p12, 0.5), lerp(p12, p23, 0.5));
+    let mid = lerp(p012, p123, 0.5);
+    flatten_cubic([p[0], p01, p012, mid], tolerance, depth + 1, out);
+    flatten_cubic([mid, p123, p23, p[3]], tolerance, depth + 1, out);
+}
+
+/// Elliptical arc in centre parameterisation (SVG implementation notes F.6.5)
+struct CenterArc {
+    center: [f64; 2],
+    rx: f64,
+    ry: f64,
+    /// x-axis rotation, radians
+    phi: f64,
+    theta: f64,
+    /// Signed sweep, radians (positive = increasing angle in user space)
+    delta: f64,
+}
+
+impl CenterArc {
+    /// `None` when the arc degenerates to a straight line
+    fn from_endpoints(
+        from: [f64; 2],
+        to: [f64; 2],
+        rx: f64,
+        ry: f64,
+        rotation: f64,
+        large: bool,
+        sweep: bool,
+    ) -> Option<Self> {
+        let (mut rx, mut ry) = (rx.abs(), ry.abs());
+        if rx == 0.0 || ry == 0.0 || from == to {
+            return None;
+        }
+        let phi = rotation.to_radians();
+        let (sin, cos) = phi.sin_cos();
+        let (hx, hy) = ((from[0] - to[0]) / 2.0, (from[1] - to[1]) / 2.0);
+        let x1 = cos * hx + sin * hy;
+        let y1 = -sin * hx + cos * hy;
+
+        // Radii too small to reach the end point are scaled up
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (num / den).max(0.0).sqrt();
+        if large == sweep {
+            coef = -coef;
+        }
+        let (cx1, cy1) = (coef * rx * y1 / ry, -coef * ry * x1 / rx);
+        let center = [
+            cos * cx1 - sin * cy1 + (from[0] + to[0]) / 2.0,
+            sin * cx1 + cos * cy1 + (from[1] + to[1]) / 2.0,
+        ];
+
+        let angle = |ux: f64, uy: f64| uy.atan2(ux);
+        let theta = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - theta;
+        if sweep && delta < 0.0 {
+            delta += TAU;
+        } else if !sweep && delta > 0.0 {
+            delta -= TAU;
+        }
+        Some(Self { center, rx, ry, phi, theta, delta })
+    }
+
+    fn point(&self, angle: f64) -> [f64; 2] {
+        let (sin, cos) = self.phi.sin_cos();
+        let (x, y) = (self.rx * angle.cos(), self.ry * angle.sin());
+        [self.center[0] + cos * x - sin * y, self.center[1] + sin * x + cos * y]
+    }
+}
+
+/// Collects the elements of one shape. Points are tracked in user space
+/// and transformed once, so consecutive elements share exact end points.
+struct PathBuilder<'a> {
+    transform: Affine,
+    tolerance: f64,
+    elements: &'a mut Vec<SketchElement>,
+    /// Straight segments not yet emitted, mm
+    run: Vec<[f64; 2]>,
+    current: [f64; 2],
+    start: [f64; 2],
+}
+
+impl<'a> PathBuilder<'a> {
+    fn new(transform: Affine, tolerance: f64, elements: &'a mut Vec<SketchElement>) -> Self {
+        Self {
+            transform,
+            tolerance,
+            elements,
+            run: Vec::new(),
+            current: [0.0, 0.0],
+            start: [0.0, 0.0],
+        }
+    }
+
+    fn flush(&mut self) {
+        match self.run.len() {
+            0 | 1 => {}
+            2 => self.elements.push(SketchElement::Line {
+                id: None,
+                start: point2d(self.run[0]),
+                end: point2d(self.run[1]),
+            }),
+            _ => self.elements.push(SketchElement::Polyline {
+                id: None,
+                points: self.run.iter().copied().map(point2d).collect(),
+            }),
+        }
+        self.run.clear();
+    }
+
+    fn move_to(&mut self, p: [f64; 2]) {
+        self.flush();
+        self.current = p;
+        self.start = p;
+    }
+
+    fn line_to(&mut self, p: [f64; 2]) {
+        if p == self.current {
+            return;
+        }
+        if self.run.is_empty() {
+            self.run.push(self.transform.apply(self.current));
+        }
+        self.run.push(self.transform.apply(p));
+        self.current = p;
+    }
+
+    fn spline(&mut self, points: Vec<[f64; 2]>) {
+        self.flush();
+        self.elements.push(SketchElement::Spline {
+            id: None,
+            points: points.into_iter().map(point2d).collect(),
+        });
+    }
+
+    fn cubic_to(&mut self, c1: [f64; 2], c2: [f64; 2], p: [f64; 2]) {
+        let control = [self.current, c1, c2, p].map(|q| self.transform.apply(q));
+        let mut points = vec![control[0]];
+        flatten_cubic(control, self.tolerance, 0, &mut points);
+        self.spline(points);
+        self.current = p;
+    }
+
+    fn arc_to(&mut self, rx: f64, ry: f64, rotation: f64, large: bool, sweep: bool, p: [f64; 2]) {
+        let Some(arc) = CenterArc::from_endpoints(self.current, p, rx, ry, rotation, large, sweep) else {
+            self.line_to(p);
+            return;
+        };
+        let (from, to) = (self.transform.apply(self.current), self.transform.apply(p));
+        self.current = p;
+
+        if (arc.rx - arc.ry).abs() <= 1e-9 * arc.rx && self.transform.is_similarity() {
+            self.flush();
+            let det = self.transform.determinant();
+            let center = self.transform.apply(arc.center);
+            let radius = arc.rx * det.abs().sqrt();
+            // A reflection reverses the direction; sketch arcs run
+            // counter-clockwise, so a clockwise arc starts at its end
+            let first = if (arc.delta > 0.0) == (det > 0.0) { from } else { to };
+            let start_angle = (first[1] - center[1]).atan2(first[0] - center[0]);
+            self.elements.push(SketchElement::Arc {
+                id: None,
+                center: point2d(center),
+                radius,
+                start_angle,
+                end_angle: start_angle + arc.delta.abs(),
+            });
+            return;
+        }
+
+        let radius = arc.rx.max(arc.ry) * self.transform.max_scale();
+        let step = 2.0 * (1.0 - (self.tolerance / radius).min(1.0)).acos();
+        let count = ((arc.delta.abs() / step.max(1e-6)).ceil() as usize).clamp(2, MAX_ARC_POINTS);
+        let mut points = vec![from];
+        for i in 1..count {
+            let angle = arc.theta + arc.delta * i as f64 / count as f64;
+            points.push(self.transform.apply(arc.point(angle)));
+        }
+        points.push(to);
+        self.spline(points);
+    }
+
+    fn close(&mut self) {
+        self.line_to(self.start);
+        self.flush();
+        self.current = self.start;
+    }
+
+    fn finish(mut self) {
+        self.flush();
+    }
+}
+
+/// Draw path data until the end or the first error (as SVG renderers do)
+fn draw_path(data: &str, builder: &mut PathBuilder) {
+    let mut s = Scanner::new(data);
+    let mut command = 0u8;
+    // Second control point of the previous C/S or control point of Q/T,
+    // for reflection by S and T
+    let mut last_cubic: Option<[f64; 2]> = None;
+    let mut last_quad: Option<[f64; 2]> = None;
+
+    while !s.at_end() {
+        if let Some(c) = s.peek().filter(u8::is_ascii_alphabetic) {
+            s.pos += 1;
+            command = c;
+        } else if command == 0 {
+            return;
+        }
+        let relative = command.is_ascii_lowercase();
+        let base = if relative { builder.current } else { [0.0, 0.0] };
+        let point = |s: &mut Scanner| -> Option<[f64; 2]> { Some([base[0] + s.number()?, base[1] + s.number()?]) };
+        let current = builder.current;
+        let reflect = |c: Option<[f64; 2]>| c.map_or(current, |c| [2.0 * current[0] - c[0], 2.0 * current[1] - c[1]]);
+        let (mut cubic, mut quad) = (None, None);
+
+        let ok = match command.to_ascii_uppercase() {
+            b'M' => point(&mut s).map(|p| {
+                builder.move_to(p);
+                // Further pairs are implicit line commands
+                command = if relative { b'l' } else { b'L' };
+            }),
+            b'L' => point(&mut s).map(|p| builder.line_to(p)),
+            b'H' => s.number().map(|x| builder.line_to([base[0] + x, current[1]])),
+            b'V' => s.number().map(|y| builder.line_to([current[0], base[1] + y])),
+            b'C' => match (point(&mut s), point(&mut s), point(&mut s)) {
+                (Some(c1), Some(c2), Some(p)) => {
+                    builder.cubic_to(c1, c2, p);
+                    cubic = Some(c2);
+                    Some(())
+                }
+                _ => None,
+            },
+            b'S' => match (point(&mut s), point(&mut s)) {
+                (Some(c2), Some(p)) => {
+                    builder.cubic_to(reflect(last_cubic), c2, p);
+                    cubic = Some(c2);
+                    Some(())
+                }
+                _ => None,
+            },
+            b'Q' | b'T' => {
+                let control = if command.eq_ignore_ascii_case(&b'Q') {
+                    point(&mut s)
+                } else {
+                    Some(reflect(last_quad))
+                };
+                control.zip(point(&mut s)).map(|(q, p)| {
+                    // Degree elevation: the same curve as a cubic
+                    builder.cubic_to(lerp(current, q, 2.0 / 3.0), lerp(p, q, 2.0 / 3.0), p);
+                    quad = Some(q);
+                })
+            }
+            b'A' => {
+                let parameters = (s.number(), s.number(), s.number(), s.flag(), s.flag());
+                match (parameters, point(&mut s)) {
+                    ((Some(rx), Some(ry), Some(rotation), Some(large), Some(sweep)), Some(p)) => {
+                        builder.arc_to(rx, ry, rotation, large, sweep, p);
+                        Some(())
+                    }
+                    _ => None,
+                }
+            }
+            b'Z' => {
+                builder.close();
+                // A new command letter must follow
+                command = 0;
+                Some(())
+            }
+            _ => None,
+        };
+        if ok.is_none() {
+            return;
+        }
+        last_cubic = cubic;
+        last_quad = quad;
+    }
+}
+
+/// Page size in mm and the transform from root user units to page mm
+fn root_viewport(root: &Tag) -> (Option<[f64; 2]>, Affine) {
+    let width = root.attribute("width").and_then(length_mm);
+    let height = root.attribute("height").and_then(length_mm);
+    let view_box = root.attribute("viewBox").map(numbers).filter(|v| v.len() == 4 && v[2] > 0.0 && v[3] > 0.0);
+
+    let Some(vb) = view_box else {
+        let size = width.zip(height).map(|(w, h)| [w, h]);
+        return (size, Affine::scale(PX_MM, PX_MM));
+    };
+    let width = width.unwrap_or(vb[2] * PX_MM);
+    let height = height.unwrap_or(vb[3] * PX_MM);
+    let (sx, sy) = (width / vb[2], height / vb[3]);
+    let stretch = root.attribute("preserveAspectRatio").is_some_and(|p| p.trim() == "none");
+    let transform = if stretch {
+        Affine([sx, 0.0, 0.0, sy, -vb[0] * sx, -vb[1] * sy])
+    } else {
+        // xMidYMid meet: uniform scale, centred on the page
+        let s = sx.min(sy);
+        let (tx, ty) = ((width - vb[2] * s) / 2.0, (height - vb[3] * s) / 2.0);
+        Affine([s, 0.0, 0.0, s, tx - vb[0] * s, ty - vb[1] * s])
+    };
+    (Some([width, height]), transform)
+}
+
+/// Add the geometry of one shape element
+fn draw_shape(tag: &Tag, name: &str, transform: Affine, tolerance: f64, elements: &mut Vec<SketchElement>) {
+    let mut builder = PathBuilder::new(transform, tolerance, elements);
+    let n = |key: &str| tag.number(key);
+    match name {
+        "path" => draw_path(tag.attribute("d").unwrap_or_default(), &mut builder),
+        "rect" => {
+            let (x, y, w, h) = (n("x"), n("y"), n("width"), n("height"));
+            if w <= 0.0 || h <= 0.0 {
+                return;
+            }
+            let (rx, ry) = match (tag.attribute("rx").map(|_| n("rx")), tag.attribute("ry").map(|_| n("ry"))) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(r), None) | (None, Some(r)) => (r, r),
+                (None, None) => (0.0, 0.0),
+            };
+            let (rx, ry) = (rx.clamp(0.0, w / 2.0), ry.clamp(0.0, h / 2.0));
+            if rx == 0.0 || ry == 0.0 {
+                if transform.is_axis_aligned() {
+                    let [a, b] = [transform.apply([x, y]), transform.apply([x + w, y + h])];
+                    builder.elements.push(SketchElement::Rectangle {
+                        id: None,
+                        corner: Point2D { x: a[0].min(b[0]), y: a[1].min(b[1]) },
+                        width: (b[0] - a[0]).abs(),
+                        height: (b[1] - a[1]).abs(),
+                    });
+                    return;
+                }
+                builder.move_to([x, y]);
+                builder.line_to([x + w, y]);
+                builder.line_to([x + w, y + h]);
+                builder.line_to([x, y + h]);
+            } else {
+                builder.move_to([x + rx, y]);
+                builder.line_to([x + w - rx, y]);
+                builder.arc_to(rx, ry, 0.0, false, true, [x + w, y + ry]);
+                builder.line_to([x + w, y + h - ry]);
+                builder.arc_to(rx, ry, 0.0, false, true, [x + w - rx, y + h]);
+                builder.line_to([x + rx, y + h]);
+                builder.arc_to(rx, ry, 0.0, false, true, [x, y + h - ry]);
+                builder.line_to([x, y + ry]);
+                builder.arc_to(rx, ry, 0.0, false, true, [x + rx, y]);
+            }
+            builder.close();
+        }
+        "circle" | "ellipse" => {
+            let (cx, cy) = (n("cx"), n("cy"));
+            let (rx, ry) = if name == "circle" { (n("r"), n("r")) } else { (n("rx"), n("ry")) };
+            if rx <= 0.0 || ry <= 0.0 {
+                return;
+            }
+            if rx == ry && transform.is_similarity() {
+                builder.elements.push(SketchElement::Circle {
+                    id: None,
+                    center: point2d(transform.apply([cx, cy])),
+                    radius: rx * transform.determinant().abs().sqrt(),
+                });
+                return;
+            }
+            builder.move_to([cx + rx, cy]);
+            builder.arc_to(rx, ry, 0.0, false, true, [cx - rx, cy]);
+            builder.arc_to(rx, ry, 0.0, false, true, [cx + rx, cy]);
+            builder.close();
+        }
+        "line" => {
+            builder.move_to([n("x1"), n("y1")]);
+            builder.line_to([n("x2"), n("y2")]);
+        }
+        "polyline" | "polygon" => {
+            let values = numbers(tag.attribute("points").unwrap_or_default());
+            let mut points = values.chunks_exact(2).map(|p| [p[0], p[1]]);
+            let Some(first) = points.next() else { return };
+            builder.move_to(first);
+            points.for_each(|p| builder.line_to(p));
+            if name == "polygon" {
+                builder.close();
+            }
+        }
+        _ => {}
+    }
+    builder.finish();
+}
+
+/// Parse an SVG document into a sketch
+pub fn parse_svg(text: &str, options: &SvgOptions) -> Result<SvgImport, String> {
+    let tags = tags(text)?;
+    let root = tags
+        .iter()
+        .find(|tag| matches!(tag, Tag::Open { .. }))
+        .filter(|tag| matches!(tag, Tag::Open { name: "svg", .. }))
+        .ok_or("Not an SVG document")?;
+    let (size, viewport) = root_viewport(root);
+    // Flip Y: SVG points down, the sketch up
+    let page_height = size.map_or(0.0, |[_, h]| h);
+    let flip = Affine([1.0, 0.0, 0.0, -1.0, 0.0, page_height]);
+    let tolerance = options.tolerance.max(1e-6);
+
+    let mut elements: Vec<SketchElement> = Vec::new();
+    let mut skipped: Vec<String> = Vec::new();
+    // Transforms of the open elements (the root viewport below the first)
+    let mut stack: Vec<Affine> = vec![flip.then_apply(viewport)];
+    // Depth inside a subtree that is not drawn
+    let mut ignored_depth = 0usize;
+    let mut root_seen = false;
+
+    for tag in &tags {
+        let Tag::Open { name, empty, .. } = tag else {
+            if ignored_depth > 0 {
+                ignored_depth -= 1;
+            } else {
+                stack.pop();
+            }
+            continue;
+        };
+        if ignored_depth > 0 {
+            if !empty {
+                ignored_depth += 1;
+            }
+            continue;
+        }
+
+        let parent = *stack.last().unwrap_or(&Affine::IDENTITY);
+        let mut transform = parent.then_apply(tag.attribute("transform").map_or(Affine::IDENTITY, parse_transform));
+        if *name == "svg" {
+            if root_seen {
+                // Nested viewport: only its position is applied
+                transform = transform.then_apply(Affine::translate(tag.number("x"), tag.number("y")));
+            }
+            root_seen = true;
+        }
+
+        let not_drawn = HIDDEN_CONTAINERS.contains(name) || UNSUPPORTED.contains(name) || tag.hidden();
+        if UNSUPPORTED.contains(name) && !tag.hidden() {
+            skipped.push(name.to_string());
+        }
+        if not_drawn {
+            if !empty {
+                ignored_depth = 1;
+            }
+            continue;
+        }
+
+        draw_shape(tag, name, transform, tolerance, &mut elements);
+        if !empty {
+            stack.push(transform);
+        }
+    }
+
+    if elements.is_empty() {
+        return Err("SVG has no paths or shapes".to_string());
+    }
+    let mut sketch = Sketch {
+        plane: options.plane.clone(),
+        offset: options.offset,
+        ..Default::default()
+    };
+    for element in elements {
+        sketch.push_element(element);
+    }
+    skipped.sort();
+    skipped.dedup();
+    Ok(SvgImport { sketch, skipped, size })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn parse(body: &str) -> SvgImport {
+        let text = format!(
+            r#"<?xml version="1.0"?>
+<!-- test -->
+<svg xmlns="http://www.w3.org/2000/svg" width="100mm" height="50mm" viewBox="0 0 200 100">{}</svg>"#,
+            body
+        );
+        parse_svg(&text, &SvgOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_viewbox_transforms_and_skipped() {
+        let import = parse(
+            r#"<defs><rect width="5" height="5"/></defs>
+            <g transform="translate(10 10)"><rect x="0" y="0" width="20" height="10"/>
+            <circle cx="50" cy="20" r="5"/></g>
+            <text x="0" y="0">Logo</text><g style="display: none"><line x2="5"/></g>"#,
+        );
+
+        assert_eq!(import.size, Some([100.0, 50.0]));
+        assert_eq!(import.skipped, ["text"]);
+        let elements = &import.sketch.elements;
+        assert_eq!(elements.len(), 2);
+        // 0.5 mm per user unit, Y flipped from the 50 mm page height
+        assert!(matches!(
+            &elements[0],
+            SketchElement::Rectangle { corner, width, height, .. }
+                if *corner == Point2D { x: 5.0, y: 40.0 } && *width == 10.0 && *height == 5.0
+        ));
+        assert!(matches!(
+            &elements[1],
+            SketchElement::Circle { center, radius, .. } if *center == Point2D { x: 30.0, y: 35.0 } && *radius == 2.5
+        ));
+    }
+
+    #[test]
+    fn test_path_commands_arcs_and_holes() {
+        // Square with a circular hole drawn as two relative arcs
+        let import = parse(r#"<path d="M0,0h20v20H0z M6 10a4 4 0 0 0 8 0a4,4 0 00-8 0z"/>"#);
+        let elements = &import.sketch.elements;
+        assert_eq!(elements.len(), 3, "{:?}", elements);
+        let SketchElement::Polyline { points, .. } = &elements[0] else {
+            panic!("expected a polyline");
+        };
+        assert_eq!(points.len(), 5);
+        assert_eq!(points.first(), points.last());
+        for arc in &elements[1..] {
+            let SketchElement::Arc { center, radius, start_angle, end_angle, .. } = arc else {
+                panic!("expected an arc");
+            };
+            assert!((center.x - 5.0).abs() < 1e-9 && (center.y - 45.0).abs() < 1e-9);
+            assert!((radius - 2.0).abs() < 1e-9);
+            assert!((end_angle - start_angle - PI).abs() < 1e-9);
+        }
+
+        let profiles = crate::extrude::extract_2d_profiles(elements).unwrap();
+        let regions = crate::extrude::group_profiles_into_regions(profiles);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].len(), 2);
+        let hole = crate::extrude::signed_area(&regions[0][1]);
+        assert!(hole < 0.0 && (hole.abs() - PI * 4.0).abs() < 0.2, "hole area {}", hole);
+    }
+
+    #[test]
+    fn test_curves_flattened_within_tolerance() {
+        let text = r#"<svg width="20" height="20" viewBox="0 0 20 20">
+            <path d="M0 20C0 10 10 10 10 20Q15 10 20 20"/>
+            <ellipse cx="10" cy="5" rx="4" ry="2" transform="skewX(10)"/></svg>"#;
+        let options = SvgOptions { tolerance: 0.01, ..Default::default() };
+        let import = parse_svg(text, &options).unwrap();
+        let elements = &import.sketch.elements;
+
+        // Cubic and quadratic segments, then the skewed ellipse as two arcs
+        assert_eq!(elements.len(), 4);
+        assert!(elements.iter().all(|e| matches!(e, SketchElement::Spline { .. })));
+        let SketchElement::Spline { points, .. } = &elements[0] else { unreachable!() };
+        // The cubic peaks 7.5 units (× 25.4/96 mm) above the page bottom
+        let peak = points.iter().map(|p| p.y).fold(f64::MIN, f64::max);
+        assert!((peak - 7.5 * PX_MM).abs() < 0.01, "peak {}", peak);
+        // Consecutive curves share their end point exactly
+        let SketchElement::Spline { points: next, .. } = &elements[1] else { unreachable!() };
+        assert_eq!(points.last(), next.first());
+    }
+}
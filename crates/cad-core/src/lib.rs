@@ -0,0 +1,16 @@
+//! Headless CAD core: evaluates `SceneDescriptionV2` bodies into solids and meshes.
+//!
+//! Shared by the GUI, the server, the test harness and the `vcad-cli` binary so
+//! that every front-end builds a scene identically. No egui/glow dependencies.
+
+pub mod analysis;
+pub mod build;
+pub mod configurations;
+pub mod export;
+pub mod extrude;
+pub mod helpers;
+pub mod import;
+pub mod mesh;
+#[cfg(test)]
+mod mesh_fixtures;
+pub mod sketch;
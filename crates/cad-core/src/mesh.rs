@@ -0,0 +1,67 @@
+//! CPU-side mesh representation produced by the feature builder
+
+use std::collections::HashMap;
+
+/// CPU-side mesh data: interleaved [pos.x, pos.y, pos.z, norm.x, norm.y, norm.z, r, g, b]
+#[derive(Clone)]
+pub struct MeshData {
+    /// 9 floats per vertex: position(3) + normal(3) + color(3)
+    pub vertices: Vec<f32>,
+    pub indices: Vec<u32>,
+}
+
+impl MeshData {
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len() / 9
+    }
+
+    /// Copy with bit-identical vertices (position, normal and colour) merged
+    /// and indices remapped. Vertices are kept in first-use order.
+    pub fn deduplicated(&self) -> MeshData {
+        let mut welder = Welder::default();
+        let indices = self
+            .indices
+            .iter()
+            .map(|&i| {
+                let base = i as usize * 9;
+                welder.insert(std::array::from_fn::<f32, 9, _>(|k| self.vertices[base + k])).0
+            })
+            .collect();
+        MeshData { vertices: welder.into_values().concat(), indices }
+    }
+}
+
+/// Table of bit-identical float tuples (positions, normals or whole
+/// vertices): equal values share one index, assigned in first-use order.
+/// -0.0 and 0.0 count as equal.
+#[derive(Default)]
+pub struct Welder<const N: usize> {
+    lookup: HashMap<[u32; N], u32>,
+    values: Vec<[f32; N]>,
+}
+
+impl<const N: usize> Welder<N> {
+    /// Index of `value`, and whether this call added it
+    pub fn insert(&mut self, value: [f32; N]) -> (u32, bool) {
+        // `+ 0.0` turns -0.0 into 0.0
+        let value = value.map(|v| v + 0.0);
+        let next = self.values.len() as u32;
+        let index = *self.lookup.entry(value.map(f32::to_bits)).or_insert(next);
+        if index == next {
+            self.values.push(value);
+        }
+        (index, index == next)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn into_values(self) -> Vec<[f32; N]> {
+        self.values
+    }
+}
@@ -0,0 +1,93 @@
+//! Small meshes shared by the unit tests of the exporters and analyses.
+//!
+//! Triangles are wound counter-clockwise seen from outside and every vertex
+//! carries the flat normal of its face.
+
+use glam::Vec3;
+
+use crate::mesh::MeshData;
+
+/// Colour of fixture vertices unless a test picks its own
+pub const GREY: [f32; 3] = [0.5, 0.5, 0.5];
+
+/// Separate triangles (no shared vertices) in the given order, one colour
+pub fn triangles(tris: &[[[f32; 3]; 3]], color: [f32; 3]) -> MeshData {
+    let mut mesh = MeshData { vertices: Vec::new(), indices: Vec::new() };
+    for tri in tris {
+        let [a, b, c] = tri.map(Vec3::from);
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        for p in [a, b, c] {
+            mesh.indices.push(mesh.vertex_count() as u32);
+            mesh.vertices.extend(p.to_array());
+            mesh.vertices.extend(normal.to_array());
+            mesh.vertices.extend(color);
+        }
+    }
+    mesh
+}
+
+/// Right triangle with unit legs along +X and +Y from `origin`, facing +Z
+pub fn triangle(origin: [f32; 3], color: [f32; 3]) -> MeshData {
+    let [x, y, z] = origin;
+    triangles(&[[[x, y, z], [x + 1.0, y, z], [x, y + 1.0, z]]], color)
+}
+
+/// Square of side `size` in the XY plane from the origin, facing +Z: two
+/// triangles with unshared vertices
+pub fn square(size: f32, color: [f32; 3]) -> MeshData {
+    let c = [[0.0, 0.0, 0.0], [size, 0.0, 0.0], [size, size, 0.0], [0.0, size, 0.0]];
+    triangles(&[[c[0], c[1], c[2]], [c[0], c[2], c[3]]], color)
+}
+
+/// Closed axis-aligned box `[min, max]`, four vertices per face
+pub fn box_mesh(min: [f32; 3], max: [f32; 3]) -> MeshData {
+    let mut mesh = MeshData { vertices: Vec::new(), indices: Vec::new() };
+    for axis in 0..3 {
+        for (sign, value) in [(-1.0, min[axis]), (1.0, max[axis])] {
+            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+            let mut normal = [0.0; 3];
+            normal[axis] = sign;
+            let base = mesh.vertex_count() as u32;
+            for (du, dv) in [(0, 0), (1, 0), (1, 1), (0, 1)] {
+                let mut p = [0.0; 3];
+                p[axis] = value;
+                p[u] = if du == 0 { min[u] } else { max[u] };
+                p[v] = if dv == 0 { min[v] } else { max[v] };
+                mesh.vertices.extend(p);
+                mesh.vertices.extend(normal);
+                mesh.vertices.extend(GREY);
+            }
+            let quad = if sign > 0.0 { [0, 1, 2, 0, 2, 3] } else { [0, 2, 1, 0, 3, 2] };
+            mesh.indices.extend(quad.map(|k| base + k));
+        }
+    }
+    mesh
+}
+
+/// Closed prism over a regular `sides`-gon of circumradius `radius`, from
+/// z = 0 to `height`. Per side: two wall triangles, then bottom and top.
+pub fn prism(sides: usize, radius: f32, height: f32) -> MeshData {
+    let corner = |i: usize, z: f32| {
+        let a = std::f32::consts::TAU * i as f32 / sides as f32;
+        [radius * a.cos(), radius * a.sin(), z]
+    };
+    let mut tris = Vec::new();
+    for i in 0..sides {
+        let (a0, b0, a1, b1) = (corner(i, 0.0), corner(i + 1, 0.0), corner(i, height), corner(i + 1, height));
+        tris.extend([[a0, b0, b1], [a0, b1, a1], [[0.0; 3], b0, a0], [[0.0, 0.0, height], a1, b1]]);
+    }
+    triangles(&tris, GREY)
+}
+
+/// Same mesh facing the other way (e.g. the walls of a cavity)
+pub fn inverted(mut mesh: MeshData) -> MeshData {
+    for tri in mesh.indices.chunks_exact_mut(3) {
+        tri.swap(1, 2);
+    }
+    for vertex in mesh.vertices.chunks_exact_mut(9) {
+        for n in &mut vertex[3..6] {
+            *n = -*n;
+        }
+    }
+    mesh
+}
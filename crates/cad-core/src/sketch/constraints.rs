@@ -0,0 +1,1126 @@
+//! Constraint solver for sketch elements
+//!
+//! Every constraint is expressed as a set of residual equations over the
+//! element parameters (point coordinates, radii, arc angles). All equations
+//! are solved simultaneously with a damped Newton (Levenberg–Marquardt)
+//! iteration; elements with a `Fixed` constraint are excluded from the
+//! unknowns. After solving, the rank of the Jacobian gives the remaining
+//! degrees of freedom per element and exposes redundant or conflicting
+//! constraints.
+
+use shared::{Body, Feature, PointRef, SceneDescriptionV2, Sketch, SketchConstraint, SketchElement};
+
+use super::solver::{lm_step, RowBasis};
+
+/// Maximum number of solver iterations
+const MAX_ITERATIONS: usize = 100;
+
+/// Tolerance for constraint satisfaction
+const TOLERANCE: f64 = 1e-6;
+
+/// Residual level at which the iteration stops early
+const CONVERGED: f64 = 1e-10;
+
+/// Definition state of a sketch or of a single element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintStatus {
+    /// No degrees of freedom left
+    FullyDefined,
+    /// Geometry can still move in `dof` independent ways
+    UnderDefined { dof: usize },
+    /// Redundant or conflicting constraints are present
+    OverConstrained,
+}
+
+/// Outcome of a solver run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveResult {
+    /// All constraints are satisfied within tolerance
+    pub converged: bool,
+    /// Number of iterations performed
+    pub iterations: usize,
+    /// Largest absolute residual after solving
+    pub max_residual: f64,
+    /// Remaining degrees of freedom of the whole sketch
+    pub dof: usize,
+    /// Remaining degrees of freedom per element (parallel to `sketch.elements`)
+    pub element_dof: Vec<usize>,
+    /// Constraints whose equations duplicate earlier constraints (indices)
+    pub redundant: Vec<usize>,
+    /// Constraints that could not be satisfied (indices)
+    pub conflicting: Vec<usize>,
+}
+
+impl SolveResult {
+    /// Definition state of the whole sketch
+    pub fn status(&self) -> ConstraintStatus {
+        if !self.redundant.is_empty() || !self.conflicting.is_empty() {
+            ConstraintStatus::OverConstrained
+        } else if self.dof == 0 {
+            ConstraintStatus::FullyDefined
+        } else {
+            ConstraintStatus::UnderDefined { dof: self.dof }
+        }
+    }
+
+    /// Definition state of a single element
+    pub fn element_status(&self, sketch: &Sketch, element_idx: usize) -> ConstraintStatus {
+        let over = self
+            .redundant
+            .iter()
+            .chain(&self.conflicting)
+            .filter_map(|&ci| sketch.constraints.get(ci))
+            .any(|c| constraint_elements(sketch, c).contains(&element_idx));
+        if over {
+            return ConstraintStatus::OverConstrained;
+        }
+        match self.element_dof.get(element_idx).copied().unwrap_or(0) {
+            0 => ConstraintStatus::FullyDefined,
+            dof => ConstraintStatus::UnderDefined { dof },
+        }
+    }
+}
+
+/// Solve all constraints in the sketch
+/// Returns true if all constraints were satisfied
+pub fn solve_constraints(sketch: &mut Sketch) -> bool {
+    solve_sketch(sketch).converged
+}
+
+/// Analyze the sketch without modifying it (DOF, redundant/conflicting constraints)
+pub fn analyze_constraints(sketch: &Sketch) -> SolveResult {
+    let mut copy = sketch.clone();
+    solve_sketch(&mut copy)
+}
+
+/// Solve all constraints simultaneously and report the definition state
+pub fn solve_sketch(sketch: &mut Sketch) -> SolveResult {
+    let system = System::new(sketch);
+
+    let mut residuals = system.residuals(&sketch.elements);
+    let mut cost = sum_squares(&residuals);
+    let mut lambda = 1e-3;
+    let mut iterations = 0;
+
+    while iterations < MAX_ITERATIONS && max_abs(&residuals) > CONVERGED && system.num_vars > 0 {
+        iterations += 1;
+        let jacobian = system.jacobian(&sketch.elements);
+
+        let mut improved = false;
+        // Increase damping until the step reduces the error
+        while lambda < 1e10 {
+            let Some(dx) = lm_step(&jacobian, &residuals, lambda) else {
+                lambda *= 10.0;
+                continue;
+            };
+            let mut trial = sketch.elements.clone();
+            system.apply_step(&mut trial, &dx);
+            let trial_residuals = system.residuals(&trial);
+            let trial_cost = sum_squares(&trial_residuals);
+            if trial_cost < cost {
+                sketch.elements = trial;
+                residuals = trial_residuals;
+                cost = trial_cost;
+                lambda = (lambda * 0.1).max(1e-12);
+                improved = true;
+                break;
+            }
+            lambda *= 10.0;
+        }
+
+        if !improved {
+            break;
+        }
+    }
+
+    system.analyze(sketch, &residuals, iterations)
+}
+
+/// Constraint equations of a sketch over its free variables
+struct System {
+    /// Offset of each element's parameters in the variable vector (None = not solved for)
+    offsets: Vec<Option<usize>>,
+    num_vars: usize,
+    /// Residual row range of each constraint
+    rows: Vec<std::ops::Range<usize>>,
+    num_rows: usize,
+    /// Constraints touching each element
+    element_constraints: Vec<Vec<usize>>,
+    constraints: Vec<SketchConstraint>,
+    fixed: Vec<bool>,
+}
+
+impl System {
+    fn new(sketch: &Sketch) -> Self {
+        let count = sketch.elements.len();
+        let fixed: Vec<bool> = (0..count).map(|i| is_element_fixed(sketch, i)).collect();
+
+        let mut element_constraints = vec![Vec::new(); count];
+        let mut rows = Vec::with_capacity(sketch.constraints.len());
+        let mut num_rows = 0;
+        let mut out = Vec::new();
+        for (ci, constraint) in sketch.constraints.iter().enumerate() {
+            out.clear();
+            constraint_residuals(&sketch.elements, constraint, &mut out);
+            rows.push(num_rows..num_rows + out.len());
+            num_rows += out.len();
+            if !out.is_empty() {
+                for ei in constraint_elements(sketch, constraint) {
+                    if let Some(list) = element_constraints.get_mut(ei) {
+                        if !list.contains(&ci) {
+                            list.push(ci);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Only elements that take part in equations are unknowns
+        let mut offsets = vec![None; count];
+        let mut num_vars = 0;
+        for (i, elem) in sketch.elements.iter().enumerate() {
+            if !fixed[i] && !element_constraints[i].is_empty() {
+                offsets[i] = Some(num_vars);
+                num_vars += element_params(elem).len();
+            }
+        }
+
+        Self {
+            offsets,
+            num_vars,
+            rows,
+            num_rows,
+            element_constraints,
+            constraints: sketch.constraints.clone(),
+            fixed,
+        }
+    }
+
+    fn residuals(&self, elements: &[SketchElement]) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.num_rows);
+        for constraint in &self.constraints {
+            constraint_residuals(elements, constraint, &mut out);
+        }
+        out
+    }
+
+    /// Central-difference Jacobian (rows = residuals, columns = variables)
+    fn jacobian(&self, elements: &[SketchElement]) -> Vec<Vec<f64>> {
+        let mut jac = vec![vec![0.0; self.num_vars]; self.num_rows];
+        let mut work = elements.to_vec();
+        let mut plus = Vec::new();
+        let mut minus = Vec::new();
+
+        for (ei, offset) in self.offsets.iter().enumerate() {
+            let Some(offset) = *offset else { continue };
+            let params = element_params(&elements[ei]);
+
+            for k in 0..params.len() {
+                let h = 1e-6 * (1.0 + params[k].abs());
+                let mut p = params.clone();
+
+                p[k] = params[k] + h;
+                set_element_params(&mut work[ei], &p);
+                p[k] = params[k] - h;
+                let mut work_minus = None;
+
+                for &ci in &self.element_constraints[ei] {
+                    plus.clear();
+                    constraint_residuals(&work, &self.constraints[ci], &mut plus);
+
+                    let wm = work_minus.get_or_insert_with(|| {
+                        let mut w = work.clone();
+                        set_element_params(&mut w[ei], &p);
+                        w
+                    });
+                    minus.clear();
+                    constraint_residuals(wm, &self.constraints[ci], &mut minus);
+
+                    for (row, (a, b)) in self.rows[ci].clone().zip(plus.iter().zip(&minus)) {
+                        jac[row][offset + k] = (a - b) / (2.0 * h);
+                    }
+                }
+
+                set_element_params(&mut work[ei], &params);
+            }
+        }
+
+        jac
+    }
+
+    fn apply_step(&self, elements: &mut [SketchElement], dx: &[f64]) {
+        for (ei, offset) in self.offsets.iter().enumerate() {
+            let Some(offset) = *offset else { continue };
+            let mut params = element_params(&elements[ei]);
+            for (k, p) in params.iter_mut().enumerate() {
+                *p += dx[offset + k];
+            }
+            set_element_params(&mut elements[ei], &params);
+        }
+    }
+
+    fn analyze(&self, sketch: &Sketch, residuals: &[f64], iterations: usize) -> SolveResult {
+        let jacobian = self.jacobian(&sketch.elements);
+
+        // Walk constraints in order: rows that add no rank are redundant
+        let mut basis = RowBasis::new();
+        let mut redundant = Vec::new();
+        let mut conflicting = Vec::new();
+        for (ci, range) in self.rows.iter().enumerate() {
+            if range.is_empty() {
+                continue;
+            }
+            let mut independent = true;
+            for row in range.clone() {
+                if !basis.try_add(&jacobian[row]) {
+                    independent = false;
+                }
+            }
+            if !independent {
+                redundant.push(ci);
+            }
+            if residuals[range.clone()].iter().any(|r| r.abs() > TOLERANCE) {
+                conflicting.push(ci);
+            }
+        }
+        // A conflict is reported once, not also as a redundancy
+        redundant.retain(|ci| !conflicting.contains(ci));
+
+        let element_dof: Vec<usize> = sketch
+            .elements
+            .iter()
+            .enumerate()
+            .map(|(ei, elem)| {
+                let count = element_params(elem).len();
+                if self.fixed[ei] {
+                    0
+                } else if let Some(offset) = self.offsets[ei] {
+                    let columns: Vec<usize> = (offset..offset + count).collect();
+                    basis.free_motions(&columns)
+                } else {
+                    count
+                }
+            })
+            .collect();
+
+        let unconstrained: usize = sketch
+            .elements
+            .iter()
+            .enumerate()
+            .filter(|(ei, _)| !self.fixed[*ei] && self.offsets[*ei].is_none())
+            .map(|(_, elem)| element_params(elem).len())
+            .sum();
+
+        let max_residual = max_abs(residuals);
+
+        SolveResult {
+            converged: max_residual <= TOLERANCE,
+            iterations,
+            max_residual,
+            dof: self.num_vars.saturating_sub(basis.rank()) + unconstrained,
+            element_dof,
+            redundant,
+            conflicting,
+        }
+    }
+}
+
+fn sum_squares(values: &[f64]) -> f64 {
+    values.iter().map(|v| v * v).sum()
+}
+
+fn max_abs(values: &[f64]) -> f64 {
+    values.iter().fold(0.0, |m, v| m.max(v.abs()))
+}
+
+/// Solver parameters of an element
+fn element_params(elem: &SketchElement) -> Vec<f64> {
+    match elem {
+        SketchElement::Line { start, end, .. } => vec![start.x, start.y, end.x, end.y],
+        SketchElement::Circle { center, radius, .. } => vec![center.x, center.y, *radius],
+        SketchElement::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            ..
+        } => vec![center.x, center.y, *radius, *start_angle, *end_angle],
+        SketchElement::Rectangle {
+            corner,
+            width,
+            height,
+            ..
+        } => vec![corner.x, corner.y, *width, *height],
+        SketchElement::Polyline { points, .. } | SketchElement::Spline { points, .. } => {
+            points.iter().flat_map(|p| [p.x, p.y]).collect()
+        }
+        SketchElement::Dimension { .. } => Vec::new(),
+    }
+}
+
+/// Write solver parameters back into an element (inverse of `element_params`)
+fn set_element_params(elem: &mut SketchElement, p: &[f64]) {
+    match elem {
+        SketchElement::Line { start, end, .. } => {
+            start.x = p[0];
+            start.y = p[1];
+            end.x = p[2];
+            end.y = p[3];
+        }
+        SketchElement::Circle { center, radius, .. } => {
+            center.x = p[0];
+            center.y = p[1];
+            *radius = p[2];
+        }
+        SketchElement::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+            ..
+        } => {
+            center.x = p[0];
+            center.y = p[1];
+            *radius = p[2];
+            *start_angle = p[3];
+            *end_angle = p[4];
+        }
+        SketchElement::Rectangle {
+            corner,
+            width,
+            height,
+            ..
+        } => {
+            corner.x = p[0];
+            corner.y = p[1];
+            *width = p[2];
+            *height = p[3];
+        }
+        SketchElement::Polyline { points, .. } | SketchElement::Spline { points, .. } => {
+            for (point, xy) in points.iter_mut().zip(p.chunks(2)) {
+                point.x = xy[0];
+                point.y = xy[1];
+            }
+        }
+        SketchElement::Dimension { .. } => {}
+    }
+}
+
+/// Indices of the elements referenced by a constraint
+pub fn constraint_elements(sketch: &Sketch, constraint: &SketchConstraint) -> Vec<usize> {
+    constraint
+        .element_refs()
+        .into_iter()
+        .filter_map(|id| sketch.element_index(id))
+        .collect()
+}
+
+/// Current measured value of a dimensional constraint (angles in degrees).
+///
+/// Used to create a new dimension without moving the geometry.
+pub fn measure_constraint_value(sketch: &Sketch, constraint: &SketchConstraint) -> Option<f64> {
+    constraint.dimension_value()?;
+    // With a zero target the residual is exactly the measured value
+    let mut zero = constraint.clone();
+    zero.set_dimension_value(0.0);
+    let mut out = Vec::new();
+    constraint_residuals(&sketch.elements, &zero, &mut out);
+    let measured = *out.first()?;
+    if matches!(constraint, SketchConstraint::Angle { .. }) {
+        Some(measured.to_degrees())
+    } else {
+        Some(measured)
+    }
+}
+
+/// Push parameter values into bound dimensions and re-solve every sketch of the body
+pub fn solve_body_sketches(body: &mut Body) {
+    body.update_dimensions_from_parameters();
+    solve_sketches(body);
+}
+
+/// Same as `solve_body_sketches` for every body, with parameters evaluated
+/// in scene context (global parameters, references to other bodies)
+pub fn solve_scene_sketches(scene: &mut SceneDescriptionV2) {
+    scene.update_dimensions_from_parameters();
+    for body in &mut scene.bodies {
+        solve_sketches(body);
+    }
+}
+
+fn solve_sketches(body: &mut Body) {
+    for feature in &mut body.features {
+        let sketch = match feature {
+            Feature::Sketch { sketch, .. }
+            | Feature::BaseExtrude { sketch, .. }
+            | Feature::BaseRevolve { sketch, .. } => sketch,
+            _ => continue,
+        };
+        if !sketch.constraints.is_empty() {
+            solve_constraints(sketch);
+        }
+    }
+}
+
+type Vec2 = (f64, f64);
+
+/// Element by id (sketches are small, a linear scan is enough)
+fn element<'a>(elements: &'a [SketchElement], id: &str) -> Option<&'a SketchElement> {
+    elements.iter().find(|e| e.id() == Some(id))
+}
+
+fn line_points(elements: &[SketchElement], id: &str) -> Option<(Vec2, Vec2)> {
+    match element(elements, id) {
+        Some(SketchElement::Line { start, end, .. }) => Some(((start.x, start.y), (end.x, end.y))),
+        _ => None,
+    }
+}
+
+fn circle_like(elements: &[SketchElement], id: &str) -> Option<(Vec2, f64)> {
+    match element(elements, id) {
+        Some(SketchElement::Circle { center, radius, .. })
+        | Some(SketchElement::Arc { center, radius, .. }) => Some(((center.x, center.y), *radius)),
+        _ => None,
+    }
+}
+
+fn sub(a: Vec2, b: Vec2) -> Vec2 {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn cross(a: Vec2, b: Vec2) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn dot(a: Vec2, b: Vec2) -> f64 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+fn length(a: Vec2) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Residuals of a point pair mirrored about an axis: midpoint lies on the axis,
+/// connecting segment is perpendicular to it
+fn push_mirrored(p1: Vec2, p2: Vec2, axis: (Vec2, Vec2), out: &mut Vec<f64>) {
+    let dir = sub(axis.1, axis.0);
+    let len = length(dir).max(TOLERANCE);
+    let mid = ((p1.0 + p2.0) / 2.0, (p1.1 + p2.1) / 2.0);
+    out.push(cross(dir, sub(mid, axis.0)) / len);
+    out.push(dot(dir, sub(p1, p2)) / len);
+}
+
+/// Push the residual equations of a constraint.
+///
+/// The number of pushed values depends only on the element types, so it stays
+/// constant while the solver moves geometry. Inapplicable constraints push nothing.
+fn constraint_residuals(elements: &[SketchElement], constraint: &SketchConstraint, out: &mut Vec<f64>) {
+    match constraint {
+        SketchConstraint::Horizontal { element } => {
+            if let Some((s, e)) = line_points(elements, element) {
+                out.push(e.1 - s.1);
+            }
+        }
+        SketchConstraint::Vertical { element } => {
+            if let Some((s, e)) = line_points(elements, element) {
+                out.push(e.0 - s.0);
+            }
+        }
+        SketchConstraint::Parallel { element1, element2 }
+        | SketchConstraint::Perpendicular { element1, element2 } => {
+            if let (Some(l1), Some(l2)) = (line_points(elements, element1), line_points(elements, element2)) {
+                let d1 = sub(l1.1, l1.0);
+                let d2 = sub(l2.1, l2.0);
+                // Normalized so the residual is the sine/cosine of the angle between lines
+                let norm = (length(d1) * length(d2)).max(TOLERANCE);
+                if matches!(constraint, SketchConstraint::Parallel { .. }) {
+                    out.push(cross(d1, d2) / norm);
+                } else {
+                    out.push(dot(d1, d2) / norm);
+                }
+            }
+        }
+        SketchConstraint::Coincident { point1, point2 } => {
+            if let (Some(p1), Some(p2)) = (point_position(elements, point1), point_position(elements, point2)) {
+                out.push(p1.0 - p2.0);
+                out.push(p1.1 - p2.1);
+            }
+        }
+        SketchConstraint::Fixed { .. } => {
+            // Fixed elements are excluded from the unknowns instead
+        }
+        SketchConstraint::Equal { element1, element2 } => {
+            if let (Some(l1), Some(l2)) = (line_points(elements, element1), line_points(elements, element2)) {
+                out.push(length(sub(l1.1, l1.0)) - length(sub(l2.1, l2.0)));
+            } else if let (Some((_, r1)), Some((_, r2))) =
+                (circle_like(elements, element1), circle_like(elements, element2))
+            {
+                out.push(r1 - r2);
+            }
+        }
+        SketchConstraint::Tangent { element1, element2 } => {
+            let pair = match (line_points(elements, element1), circle_like(elements, element2)) {
+                (Some(line), Some(circle)) => Some((line, circle)),
+                _ => match (line_points(elements, element2), circle_like(elements, element1)) {
+                    (Some(line), Some(circle)) => Some((line, circle)),
+                    _ => None,
+                },
+            };
+            if let Some(((s, e), (center, radius))) = pair {
+                let dir = sub(e, s);
+                let len = length(dir).max(TOLERANCE);
+                // Distance from center to the line equals the radius
+                out.push(cross(dir, sub(center, s)).abs() / len - radius);
+            }
+        }
+        SketchConstraint::Concentric { element1, element2 } => {
+            if let (Some((c1, _)), Some((c2, _))) = (circle_like(elements, element1), circle_like(elements, element2)) {
+                out.push(c1.0 - c2.0);
+                out.push(c1.1 - c2.1);
+            }
+        }
+        SketchConstraint::Symmetric { element1, element2, axis } => {
+            let Some(axis) = line_points(elements, axis) else { return };
+            if let (Some(l1), Some(l2)) = (line_points(elements, element1), line_points(elements, element2)) {
+                push_mirrored(l1.0, l2.0, axis, out);
+                push_mirrored(l1.1, l2.1, axis, out);
+            } else if let (
+                Some(SketchElement::Circle { center: c1, radius: r1, .. }),
+                Some(SketchElement::Circle { center: c2, radius: r2, .. }),
+            ) = (element(elements, element1), element(elements, element2))
+            {
+                push_mirrored((c1.x, c1.y), (c2.x, c2.y), axis, out);
+                out.push(r1 - r2);
+            }
+        }
+        SketchConstraint::Distance { point1, point2, value, .. } => {
+            if let (Some(p1), Some(p2)) = (point_position(elements, point1), point_position(elements, point2)) {
+                out.push(length(sub(p2, p1)) - value);
+            }
+        }
+        SketchConstraint::HorizontalDistance { point1, point2, value, .. } => {
+            if let (Some(p1), Some(p2)) = (point_position(elements, point1), point_position(elements, point2)) {
+                out.push((p2.0 - p1.0).abs() - value);
+            }
+        }
+        SketchConstraint::VerticalDistance { point1, point2, value, .. } => {
+            if let (Some(p1), Some(p2)) = (point_position(elements, point1), point_position(elements, point2)) {
+                out.push((p2.1 - p1.1).abs() - value);
+            }
+        }
+        SketchConstraint::DistancePointLine { point, line, value, .. } => {
+            if let (Some(p), Some((s, e))) = (point_position(elements, point), line_points(elements, line)) {
+                let dir = sub(e, s);
+                out.push(cross(dir, sub(p, s)).abs() / length(dir).max(TOLERANCE) - value);
+            }
+        }
+        SketchConstraint::PointOnLine { point, line } => {
+            if let (Some(p), Some((s, e))) = (point_position(elements, point), line_points(elements, line)) {
+                let dir = sub(e, s);
+                out.push(cross(dir, sub(p, s)) / length(dir).max(TOLERANCE));
+            }
+        }
+        SketchConstraint::Midpoint { point, line } => {
+            if let (Some(p), Some((s, e))) = (point_position(elements, point), line_points(elements, line)) {
+                out.push(p.0 - (s.0 + e.0) / 2.0);
+                out.push(p.1 - (s.1 + e.1) / 2.0);
+            }
+        }
+        SketchConstraint::PointOnCircle { point, circle } => {
+            if let (Some(p), Some((center, radius))) = (point_position(elements, point), circle_like(elements, circle)) {
+                out.push(length(sub(p, center)) - radius);
+            }
+        }
+        SketchConstraint::Angle { element1, element2, value, .. } => {
+            if let (Some(l1), Some(l2)) = (line_points(elements, element1), line_points(elements, element2)) {
+                let d1 = sub(l1.1, l1.0);
+                let d2 = sub(l2.1, l2.0);
+                // Unsigned angle between directions, 0..180°
+                let angle = cross(d1, d2).atan2(dot(d1, d2)).abs();
+                out.push(angle - value.to_radians());
+            }
+        }
+        SketchConstraint::Radius { element, value, .. } => {
+            if let Some((_, radius)) = circle_like(elements, element) {
+                out.push(radius - value);
+            }
+        }
+        SketchConstraint::Diameter { element, value, .. } => {
+            if let Some((_, radius)) = circle_like(elements, element) {
+                out.push(2.0 * radius - value);
+            }
+        }
+    }
+}
+
+/// Get the position of a point reference
+pub fn point_position(elements: &[SketchElement], point_ref: &PointRef) -> Option<(f64, f64)> {
+    element(elements, &point_ref.element_id)?.point(point_ref.point_index)
+}
+
+/// Check if a constraint can be applied to the given elements
+pub fn can_apply_constraint(sketch: &Sketch, constraint: &SketchConstraint) -> bool {
+    match constraint {
+        SketchConstraint::Horizontal { element } | SketchConstraint::Vertical { element } => {
+            matches!(
+                sketch.element_by_id(element),
+                Some(SketchElement::Line { .. })
+            )
+        }
+        SketchConstraint::Parallel { element1, element2 }
+        | SketchConstraint::Perpendicular { element1, element2 } => {
+            matches!(
+                sketch.element_by_id(element1),
+                Some(SketchElement::Line { .. })
+            ) && matches!(
+                sketch.element_by_id(element2),
+                Some(SketchElement::Line { .. })
+            )
+        }
+        SketchConstraint::Coincident { point1, point2 } => {
+            point_position(&sketch.elements, point1).is_some()
+                && point_position(&sketch.elements, point2).is_some()
+        }
+        SketchConstraint::Fixed { element } => {
+            // Fixed can be applied to any element
+            sketch.element_by_id(element).is_some()
+        }
+        SketchConstraint::Equal { element1, element2 } => {
+            // Equal can be applied to two lines or two circles/arcs
+            let e1 = sketch.element_by_id(element1);
+            let e2 = sketch.element_by_id(element2);
+            matches!(
+                (e1, e2),
+                (Some(SketchElement::Line { .. }), Some(SketchElement::Line { .. }))
+                    | (Some(SketchElement::Circle { .. }), Some(SketchElement::Circle { .. }))
+                    | (Some(SketchElement::Arc { .. }), Some(SketchElement::Arc { .. }))
+            )
+        }
+        SketchConstraint::Tangent { element1, element2 } => {
+            // Tangent: one line and one circle/arc
+            let e1 = sketch.element_by_id(element1);
+            let e2 = sketch.element_by_id(element2);
+            matches!(
+                (e1, e2),
+                (Some(SketchElement::Line { .. }), Some(SketchElement::Circle { .. }))
+                    | (Some(SketchElement::Circle { .. }), Some(SketchElement::Line { .. }))
+                    | (Some(SketchElement::Line { .. }), Some(SketchElement::Arc { .. }))
+                    | (Some(SketchElement::Arc { .. }), Some(SketchElement::Line { .. }))
+            )
+        }
+        SketchConstraint::Concentric { element1, element2 } => {
+            // Concentric: two circles or arcs
+            let e1 = sketch.element_by_id(element1);
+            let e2 = sketch.element_by_id(element2);
+            matches!(
+                (e1, e2),
+                (Some(SketchElement::Circle { .. }), Some(SketchElement::Circle { .. }))
+                    | (Some(SketchElement::Arc { .. }), Some(SketchElement::Arc { .. }))
+                    | (Some(SketchElement::Circle { .. }), Some(SketchElement::Arc { .. }))
+                    | (Some(SketchElement::Arc { .. }), Some(SketchElement::Circle { .. }))
+            )
+        }
+        SketchConstraint::Symmetric { element1, element2, axis } => {
+            // Symmetric: two similar elements and an axis line
+            let e1 = sketch.element_by_id(element1);
+            let e2 = sketch.element_by_id(element2);
+            let ax = sketch.element_by_id(axis);
+
+            matches!(ax, Some(SketchElement::Line { .. })) && matches!(
+                (e1, e2),
+                (Some(SketchElement::Line { .. }), Some(SketchElement::Line { .. }))
+                    | (Some(SketchElement::Circle { .. }), Some(SketchElement::Circle { .. }))
+            )
+        }
+        SketchConstraint::Distance { point1, point2, .. }
+        | SketchConstraint::HorizontalDistance { point1, point2, .. }
+        | SketchConstraint::VerticalDistance { point1, point2, .. } => {
+            point_position(&sketch.elements, point1).is_some()
+                && point_position(&sketch.elements, point2).is_some()
+        }
+        SketchConstraint::DistancePointLine { point, line, .. }
+        | SketchConstraint::PointOnLine { point, line }
+        | SketchConstraint::Midpoint { point, line } => {
+            // The point must not belong to the line itself
+            point.element_id != *line
+                && point_position(&sketch.elements, point).is_some()
+                && matches!(sketch.element_by_id(line), Some(SketchElement::Line { .. }))
+        }
+        SketchConstraint::PointOnCircle { point, circle } => {
+            point.element_id != *circle
+                && point_position(&sketch.elements, point).is_some()
+                && matches!(
+                    sketch.element_by_id(circle),
+                    Some(SketchElement::Circle { .. }) | Some(SketchElement::Arc { .. })
+                )
+        }
+        SketchConstraint::Angle { element1, element2, .. } => {
+            matches!(sketch.element_by_id(element1), Some(SketchElement::Line { .. }))
+                && matches!(sketch.element_by_id(element2), Some(SketchElement::Line { .. }))
+        }
+        SketchConstraint::Radius { element, .. } | SketchConstraint::Diameter { element, .. } => {
+            matches!(
+                sketch.element_by_id(element),
+                Some(SketchElement::Circle { .. }) | Some(SketchElement::Arc { .. })
+            )
+        }
+    }
+}
+
+/// Check if an element is fixed (has a Fixed constraint)
+pub fn is_element_fixed(sketch: &Sketch, element_idx: usize) -> bool {
+    let Some(id) = sketch.element_id(element_idx) else { return false };
+    sketch.constraints.iter().any(|c| {
+        matches!(c, SketchConstraint::Fixed { element } if element == id)
+    })
+}
+
+/// Get icons for constraints on an element
+pub fn get_element_constraint_icons(sketch: &Sketch, element_idx: usize) -> Vec<&'static str> {
+    let mut icons = Vec::new();
+    let Some(id) = sketch.element_id(element_idx) else { return icons };
+
+    for constraint in &sketch.constraints {
+        match constraint {
+            SketchConstraint::Horizontal { element } if element == id => {
+                icons.push("H");
+            }
+            SketchConstraint::Vertical { element } if element == id => {
+                icons.push("V");
+            }
+            SketchConstraint::Fixed { element } if element == id => {
+                icons.push("F");
+            }
+            SketchConstraint::Parallel { element1, element2 }
+                if element1 == id || element2 == id =>
+            {
+                icons.push("//");
+            }
+            SketchConstraint::Perpendicular { element1, element2 }
+                if element1 == id || element2 == id =>
+            {
+                icons.push("T");
+            }
+            SketchConstraint::Coincident { point1, point2 }
+                if point1.element_id == id || point2.element_id == id =>
+            {
+                icons.push("C");
+            }
+            SketchConstraint::Equal { element1, element2 }
+                if element1 == id || element2 == id =>
+            {
+                icons.push("=");
+            }
+            SketchConstraint::Tangent { element1, element2 }
+                if element1 == id || element2 == id =>
+            {
+                icons.push("TG");
+            }
+            SketchConstraint::Concentric { element1, element2 }
+                if element1 == id || element2 == id =>
+            {
+                icons.push("O");
+            }
+            SketchConstraint::Symmetric { element1, element2, axis }
+                if element1 == id || element2 == id || axis == id =>
+            {
+                icons.push("S");
+            }
+            SketchConstraint::Midpoint { .. }
+                if constraint.references(id) =>
+            {
+                icons.push("M");
+            }
+            SketchConstraint::PointOnLine { .. } | SketchConstraint::PointOnCircle { .. }
+                if constraint.references(id) =>
+            {
+                icons.push("On");
+            }
+            other
+                if other.dimension_value().is_some()
+                    && other.references(id) =>
+            {
+                icons.push(dimension_icon(other));
+            }
+            _ => {}
+        }
+    }
+
+    icons
+}
+
+/// Short label of a dimensional constraint
+fn dimension_icon(constraint: &SketchConstraint) -> &'static str {
+    match constraint {
+        SketchConstraint::Angle { .. } => "A",
+        SketchConstraint::Radius { .. } => "R",
+        SketchConstraint::Diameter { .. } => "Ø",
+        SketchConstraint::HorizontalDistance { .. } => "DX",
+        SketchConstraint::VerticalDistance { .. } => "DY",
+        _ => "D",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Point2D;
+
+    fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> SketchElement {
+        SketchElement::Line {
+            id: None,
+            start: Point2D { x: x1, y: y1 },
+            end: Point2D { x: x2, y: y2 },
+        }
+    }
+
+    fn circle(x: f64, y: f64, r: f64) -> SketchElement {
+        SketchElement::Circle {
+            id: None,
+            center: Point2D { x, y },
+            radius: r,
+        }
+    }
+
+    /// Sketch whose elements get ids `e1`, `e2`, ... in order
+    fn sketch(elements: Vec<SketchElement>, constraints: Vec<SketchConstraint>) -> Sketch {
+        let mut sketch = Sketch {
+            elements,
+            constraints,
+            ..Default::default()
+        };
+        sketch.ensure_element_ids();
+        sketch
+    }
+
+    /// Id of the element at `index` in sketches built by `sketch()`
+    fn id(index: usize) -> String {
+        format!("e{}", index + 1)
+    }
+
+    fn coincident(e1: usize, p1: usize, e2: usize, p2: usize) -> SketchConstraint {
+        SketchConstraint::Coincident {
+            point1: PointRef { element_id: id(e1), point_index: p1 },
+            point2: PointRef { element_id: id(e2), point_index: p2 },
+        }
+    }
+
+    /// Four lines closed into a rectangle by coincident corners + H/V constraints
+    fn rectangle_sketch() -> Sketch {
+        sketch(
+            vec![
+                line(0.0, 0.0, 10.0, 0.3),
+                line(10.2, 0.1, 9.8, 5.0),
+                line(10.0, 5.2, 0.1, 4.9),
+                line(0.0, 5.0, 0.2, 0.0),
+            ],
+            vec![
+                coincident(0, 1, 1, 0),
+                coincident(1, 1, 2, 0),
+                coincident(2, 1, 3, 0),
+                coincident(3, 1, 0, 0),
+                SketchConstraint::Horizontal { element: id(0) },
+                SketchConstraint::Vertical { element: id(1) },
+                SketchConstraint::Horizontal { element: id(2) },
+                SketchConstraint::Vertical { element: id(3) },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_rectangle_has_four_dof() {
+        let mut s = rectangle_sketch();
+        let result = solve_sketch(&mut s);
+        assert!(result.converged, "{:?}", result);
+        // Position (2) + width + height
+        assert_eq!(result.dof, 4);
+        assert_eq!(result.status(), ConstraintStatus::UnderDefined { dof: 4 });
+        if let SketchElement::Line { start, end, .. } = &s.elements[0] {
+            assert!((start.y - end.y).abs() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_fixed_elements_are_not_moved() {
+        let mut s = sketch(
+            vec![line(0.0, 0.0, 10.0, 0.0), line(10.0, 0.0, 12.0, 8.0)],
+            vec![
+                SketchConstraint::Fixed { element: id(0) },
+                coincident(0, 1, 1, 0),
+                SketchConstraint::Perpendicular { element1: id(0), element2: id(1) },
+                SketchConstraint::Equal { element1: id(0), element2: id(1) },
+            ],
+        );
+        let result = solve_sketch(&mut s);
+        assert!(result.converged);
+        assert_eq!(line_points(&s.elements, "e1"), Some(((0.0, 0.0), (10.0, 0.0))));
+        if let SketchElement::Line { start, end, .. } = &s.elements[1] {
+            assert!((start.x - 10.0).abs() < 1e-6 && start.y.abs() < 1e-6);
+            assert!((end.x - 10.0).abs() < 1e-6);
+            assert!((end.y.abs() - 10.0).abs() < 1e-6);
+        }
+        assert_eq!(result.dof, 0);
+        assert_eq!(result.status(), ConstraintStatus::FullyDefined);
+        assert_eq!(result.element_status(&s, 1), ConstraintStatus::FullyDefined);
+    }
+
+    #[test]
+    fn test_coupled_tangent_perpendicular_equal() {
+        let mut s = sketch(
+            vec![
+                line(0.0, 0.0, 10.0, 1.0),
+                line(0.5, 0.0, 1.0, 9.0),
+                circle(5.0, 4.0, 2.0),
+                circle(20.0, 20.0, 3.0),
+            ],
+            vec![
+                SketchConstraint::Perpendicular { element1: id(0), element2: id(1) },
+                SketchConstraint::Tangent { element1: id(0), element2: id(2) },
+                SketchConstraint::Tangent { element1: id(1), element2: id(2) },
+                SketchConstraint::Equal { element1: id(2), element2: id(3) },
+            ],
+        );
+        let result = solve_sketch(&mut s);
+        assert!(result.converged, "{:?}", result);
+        assert!(result.redundant.is_empty() && result.conflicting.is_empty());
+        // Untouched elements keep their parameters as DOF
+        assert_eq!(result.element_dof.len(), 4);
+    }
+
+    #[test]
+    fn test_redundant_constraint_detected() {
+        let mut s = sketch(
+            vec![line(0.0, 0.0, 10.0, 1.0)],
+            vec![
+                SketchConstraint::Horizontal { element: id(0) },
+                SketchConstraint::Horizontal { element: id(0) },
+            ],
+        );
+        let result = solve_sketch(&mut s);
+        assert!(result.converged);
+        assert_eq!(result.redundant, vec![1]);
+        assert!(result.conflicting.is_empty());
+        assert_eq!(result.status(), ConstraintStatus::OverConstrained);
+        assert_eq!(result.element_status(&s, 0), ConstraintStatus::OverConstrained);
+    }
+
+    #[test]
+    fn test_conflicting_constraints_detected() {
+        let mut s = sketch(
+            vec![line(0.0, 0.0, 10.0, 1.0)],
+            vec![
+                SketchConstraint::Horizontal { element: id(0) },
+                SketchConstraint::Vertical { element: id(0) },
+                SketchConstraint::Fixed { element: id(0) },
+            ],
+        );
+        let result = solve_sketch(&mut s);
+        assert!(!result.converged);
+        assert_eq!(result.conflicting, vec![0, 1]);
+        assert_eq!(result.status(), ConstraintStatus::OverConstrained);
+    }
+
+    fn point(element_index: usize, point_index: usize) -> PointRef {
+        PointRef { element_id: id(element_index), point_index }
+    }
+
+    #[test]
+    fn test_dimensional_constraints_drive_geometry() {
+        let mut s = sketch(
+            vec![
+                line(0.0, 0.0, 8.0, 1.0),
+                line(8.0, 1.0, 9.0, 6.0),
+                circle(3.0, 3.0, 1.0),
+            ],
+            vec![
+                SketchConstraint::Fixed { element: id(0) },
+                coincident(0, 1, 1, 0),
+                SketchConstraint::Angle { element1: id(0), element2: id(1), value: 90.0, parameter: None },
+                SketchConstraint::Distance { point1: point(1, 0), point2: point(1, 1), value: 4.0, parameter: None },
+                SketchConstraint::Diameter { element: id(2), value: 3.0, parameter: None },
+                SketchConstraint::Midpoint { point: point(2, 0), line: id(0) },
+            ],
+        );
+        let result = solve_sketch(&mut s);
+        assert!(result.converged, "{:?}", result);
+
+        let angle = measure_constraint_value(&s, &s.constraints[2]).unwrap();
+        assert!((angle - 90.0).abs() < 1e-4, "angle = {}", angle);
+        let dist = measure_constraint_value(&s, &s.constraints[3]).unwrap();
+        assert!((dist - 4.0).abs() < 1e-6);
+        if let SketchElement::Circle { center, radius, .. } = &s.elements[2] {
+            assert!((radius - 1.5).abs() < 1e-6);
+            assert!((center.x - 4.0).abs() < 1e-6 && (center.y - 0.5).abs() < 1e-6);
+        }
+        // Circle: fully placed by diameter + midpoint; second line fully defined
+        assert_eq!(result.element_dof, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_point_on_curve_and_axis_distances() {
+        let mut s = sketch(
+            vec![line(0.0, 0.0, 10.0, 0.0), circle(0.0, 0.0, 5.0), line(1.0, 1.0, 2.0, 7.0)],
+            vec![
+                SketchConstraint::Fixed { element: id(0) },
+                SketchConstraint::Fixed { element: id(1) },
+                SketchConstraint::PointOnLine { point: point(2, 0), line: id(0) },
+                SketchConstraint::PointOnCircle { point: point(2, 1), circle: id(1) },
+                SketchConstraint::HorizontalDistance { point1: point(0, 0), point2: point(2, 0), value: 2.0, parameter: None },
+                SketchConstraint::VerticalDistance { point1: point(2, 0), point2: point(2, 1), value: 3.0, parameter: None },
+            ],
+        );
+        let result = solve_sketch(&mut s);
+        assert!(result.converged, "{:?}", result);
+        if let SketchElement::Line { start, end, .. } = &s.elements[2] {
+            assert!(start.y.abs() < 1e-6 && (start.x - 2.0).abs() < 1e-6);
+            assert!(((end.x * end.x + end.y * end.y).sqrt() - 5.0).abs() < 1e-6);
+            assert!((end.y.abs() - 3.0).abs() < 1e-6);
+        }
+        assert_eq!(result.dof, 0);
+    }
+
+    #[test]
+    fn test_parameter_change_resolves_body_sketch() {
+        use shared::{Parameter, ParameterValue, Transform};
+        use std::collections::HashMap;
+
+        let mut body = Body {
+            id: "b".to_string(),
+            name: "B".to_string(),
+            features: vec![Feature::Sketch {
+                id: "s".to_string(),
+                sketch: sketch(
+                    vec![circle(0.0, 0.0, 1.0)],
+                    vec![SketchConstraint::Radius { element: id(0), value: 1.0, parameter: Some("r".to_string()) }],
+                ),
+                transform: Transform::new(),
+            }],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        };
+        body.parameters.insert(
+            "r".to_string(),
+            Parameter {
+                name: "r".to_string(),
+                value: ParameterValue::Number { value: 2.5 },
+                unit: None,
+                description: None,
+            },
+        );
+
+        solve_body_sketches(&mut body);
+
+        let Feature::Sketch { sketch, .. } = &body.features[0] else { unreachable!() };
+        assert!(matches!(sketch.elements[0], SketchElement::Circle { radius, .. } if (radius - 2.5).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_unconstrained_element_dof() {
+        let s = sketch(vec![line(0.0, 0.0, 1.0, 0.0), circle(0.0, 0.0, 1.0)], vec![]);
+        let result = analyze_constraints(&s);
+        assert_eq!(result.element_dof, vec![4, 3]);
+        assert_eq!(result.dof, 7);
+    }
+}
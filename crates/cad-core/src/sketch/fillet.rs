@@ -23,12 +23,12 @@ pub fn fillet_lines(
     let elem2 = sketch.elements.get(line2_idx)?;
 
     let (l1_start, l1_end) = match elem1 {
-        SketchElement::Line { start, end } => (Point::new(start.x, start.y), Point::new(end.x, end.y)),
+        SketchElement::Line { start, end, .. } => (Point::new(start.x, start.y), Point::new(end.x, end.y)),
         _ => return None,
     };
 
     let (l2_start, l2_end) = match elem2 {
-        SketchElement::Line { start, end } => (Point::new(start.x, start.y), Point::new(end.x, end.y)),
+        SketchElement::Line { start, end, .. } => (Point::new(start.x, start.y), Point::new(end.x, end.y)),
         _ => return None,
     };
 
@@ -69,6 +69,7 @@ pub fn fillet_lines(
     }
 
     let fillet_arc = SketchElement::Arc {
+        id: None,
         center: Point2D { x: center.x, y: center.y },
         radius,
         start_angle,
@@ -77,11 +78,13 @@ pub fn fillet_lines(
 
     let new_line1 = if t1 < 0.5 {
         SketchElement::Line {
+            id: None,
             start: Point2D { x: tangent1.x, y: tangent1.y },
             end: Point2D { x: l1_end.x, y: l1_end.y },
         }
     } else {
         SketchElement::Line {
+            id: None,
             start: Point2D { x: l1_start.x, y: l1_start.y },
             end: Point2D { x: tangent1.x, y: tangent1.y },
         }
@@ -89,11 +92,13 @@ pub fn fillet_lines(
 
     let new_line2 = if t2 < 0.5 {
         SketchElement::Line {
+            id: None,
             start: Point2D { x: tangent2.x, y: tangent2.y },
             end: Point2D { x: l2_end.x, y: l2_end.y },
         }
     } else {
         SketchElement::Line {
+            id: None,
             start: Point2D { x: l2_start.x, y: l2_start.y },
             end: Point2D { x: tangent2.x, y: tangent2.y },
         }
@@ -240,19 +240,19 @@ pub fn arc_circle_intersection(
 /// Get all endpoints from a sketch element
 pub fn get_element_endpoints_for_trim(elem: &SketchElement) -> Vec<Point> {
     match elem {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             vec![Point::new(start.x, start.y), Point::new(end.x, end.y)]
         }
-        SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             vec![
                 Point::new(center.x + radius * start_angle.cos(), center.y + radius * start_angle.sin()),
                 Point::new(center.x + radius * end_angle.cos(), center.y + radius * end_angle.sin()),
             ]
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             points.iter().map(|p| Point::new(p.x, p.y)).collect()
         }
-        SketchElement::Rectangle { corner, width, height } => {
+        SketchElement::Rectangle { corner, width, height, .. } => {
             vec![
                 Point::new(corner.x, corner.y),
                 Point::new(corner.x + width, corner.y),
@@ -320,7 +320,7 @@ pub fn find_line_intersections(idx: usize, line: KLine, sketch: &Sketch) -> Vec<
 
         // First, check geometric intersections
         match elem {
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 let other = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
                 if let Some((t, u, pt)) = line_line_intersection(line, other) {
                     // Allow intersection if it's inside our line, regardless of where it is on the other line
@@ -333,7 +333,7 @@ pub fn find_line_intersections(idx: usize, line: KLine, sketch: &Sketch) -> Vec<
                     }
                 }
             }
-            SketchElement::Circle { center, radius } => {
+            SketchElement::Circle { center, radius, .. } => {
                 let circle = KCircle::new(Point::new(center.x, center.y), *radius);
                 for (t, pt) in line_circle_intersection(line, circle) {
                     if t > 1e-6 && t < 1.0 - 1e-6 {
@@ -341,7 +341,7 @@ pub fn find_line_intersections(idx: usize, line: KLine, sketch: &Sketch) -> Vec<
                     }
                 }
             }
-            SketchElement::Arc { center, radius, start_angle, end_angle } => {
+            SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
                 let c = Point::new(center.x, center.y);
                 for (t, pt) in line_arc_intersection(line, c, *radius, *start_angle, *end_angle) {
                     if t > 1e-6 && t < 1.0 - 1e-6 {
@@ -349,7 +349,7 @@ pub fn find_line_intersections(idx: usize, line: KLine, sketch: &Sketch) -> Vec<
                     }
                 }
             }
-            SketchElement::Rectangle { corner, width, height } => {
+            SketchElement::Rectangle { corner, width, height, .. } => {
                 let corners = [
                     Point::new(corner.x, corner.y),
                     Point::new(corner.x + width, corner.y),
@@ -365,7 +365,7 @@ pub fn find_line_intersections(idx: usize, line: KLine, sketch: &Sketch) -> Vec<
                     }
                 }
             }
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 // Intersect with each segment of the polyline
                 for j in 0..(points.len().saturating_sub(1)) {
                     let seg = KLine::new(
@@ -413,14 +413,14 @@ pub fn find_arc_intersections(
         }
 
         let points: Vec<Point> = match elem {
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 let line = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
                 line_arc_intersection(line, center, radius, start_angle, end_angle)
                     .into_iter()
                     .map(|(_, pt)| pt)
                     .collect()
             }
-            SketchElement::Circle { center: c, radius: r } => {
+            SketchElement::Circle { center: c, radius: r, .. } => {
                 arc_circle_intersection(
                     center,
                     radius,
@@ -429,13 +429,13 @@ pub fn find_arc_intersections(
                     KCircle::new(Point::new(c.x, c.y), *r),
                 )
             }
-            SketchElement::Arc { center: c, radius: r, start_angle: s, end_angle: e } => {
+            SketchElement::Arc { center: c, radius: r, start_angle: s, end_angle: e, .. } => {
                 arc_arc_intersection(
                     center, radius, start_angle, end_angle,
                     Point::new(c.x, c.y), *r, *s, *e,
                 )
             }
-            SketchElement::Rectangle { corner, width, height } => {
+            SketchElement::Rectangle { corner, width, height, .. } => {
                 let corners = [
                     Point::new(corner.x, corner.y),
                     Point::new(corner.x + width, corner.y),
@@ -453,7 +453,7 @@ pub fn find_arc_intersections(
                 }
                 pts
             }
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 let mut pts = Vec::new();
                 for j in 0..(points.len().saturating_sub(1)) {
                     let seg = KLine::new(
@@ -529,7 +529,7 @@ pub fn find_circle_intersections(
         tracing::info!("  Element[{}] type: {}", i, elem_type);
 
         let points: Vec<Point> = match elem {
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 let line = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
                 let all_ints = line_circle_intersection(line, circle);
                 tracing::info!("    Line ({:.2},{:.2})->({:.2},{:.2}): {} raw intersections, t values: {:?}",
@@ -542,15 +542,15 @@ pub fn find_circle_intersections(
                     .map(|(_, pt)| pt)
                     .collect()
             }
-            SketchElement::Circle { center: c, radius: r } => {
+            SketchElement::Circle { center: c, radius: r, .. } => {
                 circle_circle_intersection(circle, KCircle::new(Point::new(c.x, c.y), *r))
             }
-            SketchElement::Arc { center: c, radius: r, start_angle: s, end_angle: e } => {
+            SketchElement::Arc { center: c, radius: r, start_angle: s, end_angle: e, .. } => {
                 arc_circle_intersection(
                     Point::new(c.x, c.y), *r, *s, *e, circle,
                 )
             }
-            SketchElement::Rectangle { corner, width, height } => {
+            SketchElement::Rectangle { corner, width, height, .. } => {
                 let corners = [
                     Point::new(corner.x, corner.y),
                     Point::new(corner.x + width, corner.y),
@@ -569,7 +569,7 @@ pub fn find_circle_intersections(
                 }
                 pts
             }
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 let mut pts = Vec::new();
                 for j in 0..(points.len().saturating_sub(1)) {
                     let seg = KLine::new(
@@ -633,7 +633,7 @@ pub fn find_polyline_intersections(
             }
 
             match elem {
-                SketchElement::Line { start, end } => {
+                SketchElement::Line { start, end, .. } => {
                     let other = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
                     if let Some((t, u, pt)) = line_line_intersection(seg_line, other) {
                         if t > 1e-6 && t < 1.0 - 1e-6 && u > 1e-6 && u < 1.0 - 1e-6 {
@@ -645,7 +645,7 @@ pub fn find_polyline_intersections(
                         }
                     }
                 }
-                SketchElement::Circle { center, radius } => {
+                SketchElement::Circle { center, radius, .. } => {
                     let circle = KCircle::new(Point::new(center.x, center.y), *radius);
                     for (t, pt) in line_circle_intersection(seg_line, circle) {
                         if t > 1e-6 && t < 1.0 - 1e-6 {
@@ -657,7 +657,7 @@ pub fn find_polyline_intersections(
                         }
                     }
                 }
-                SketchElement::Arc { center, radius, start_angle, end_angle } => {
+                SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
                     let c = Point::new(center.x, center.y);
                     for (t, pt) in line_arc_intersection(seg_line, c, *radius, *start_angle, *end_angle) {
                         if t > 1e-6 && t < 1.0 - 1e-6 {
@@ -669,7 +669,7 @@ pub fn find_polyline_intersections(
                         }
                     }
                 }
-                SketchElement::Rectangle { corner, width, height } => {
+                SketchElement::Rectangle { corner, width, height, .. } => {
                     let corners = [
                         Point::new(corner.x, corner.y),
                         Point::new(corner.x + width, corner.y),
@@ -689,7 +689,7 @@ pub fn find_polyline_intersections(
                         }
                     }
                 }
-                SketchElement::Polyline { points: other_points } => {
+                SketchElement::Polyline { points: other_points, .. } => {
                     // Intersect with each segment of the other polyline
                     for j in 0..(other_points.len().saturating_sub(1)) {
                         let other_seg = KLine::new(
@@ -747,7 +747,7 @@ pub fn find_polyline_intersections(
 /// Find intersection points between two sketch elements
 pub fn find_element_intersections(elem1: &SketchElement, elem2: &SketchElement) -> Vec<Point> {
     match (elem1, elem2) {
-        (SketchElement::Line { start: s1, end: e1 }, SketchElement::Line { start: s2, end: e2 }) => {
+        (SketchElement::Line { start: s1, end: e1, .. }, SketchElement::Line { start: s2, end: e2, .. }) => {
             let l1 = KLine::new(Point::new(s1.x, s1.y), Point::new(e1.x, e1.y));
             let l2 = KLine::new(Point::new(s2.x, s2.y), Point::new(e2.x, e2.y));
             if let Some((t, u, pt)) = line_line_intersection(l1, l2) {
@@ -758,8 +758,8 @@ pub fn find_element_intersections(elem1: &SketchElement, elem2: &SketchElement)
             }
             Vec::new()
         }
-        (SketchElement::Line { start, end }, SketchElement::Circle { center, radius }) |
-        (SketchElement::Circle { center, radius }, SketchElement::Line { start, end }) => {
+        (SketchElement::Line { start, end, .. }, SketchElement::Circle { center, radius, .. }) |
+        (SketchElement::Circle { center, radius, .. }, SketchElement::Line { start, end, .. }) => {
             let line = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
             let circle = KCircle::new(Point::new(center.x, center.y), *radius);
             line_circle_intersection(line, circle)
@@ -768,8 +768,8 @@ pub fn find_element_intersections(elem1: &SketchElement, elem2: &SketchElement)
                 .map(|(_, pt)| pt)
                 .collect()
         }
-        (SketchElement::Line { start, end }, SketchElement::Arc { center, radius, start_angle, end_angle }) |
-        (SketchElement::Arc { center, radius, start_angle, end_angle }, SketchElement::Line { start, end }) => {
+        (SketchElement::Line { start, end, .. }, SketchElement::Arc { center, radius, start_angle, end_angle, .. }) |
+        (SketchElement::Arc { center, radius, start_angle, end_angle, .. }, SketchElement::Line { start, end, .. }) => {
             let line = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
             let c = Point::new(center.x, center.y);
             line_arc_intersection(line, c, *radius, *start_angle, *end_angle)
@@ -778,21 +778,21 @@ pub fn find_element_intersections(elem1: &SketchElement, elem2: &SketchElement)
                 .map(|(_, pt)| pt)
                 .collect()
         }
-        (SketchElement::Circle { center: c1, radius: r1 }, SketchElement::Circle { center: c2, radius: r2 }) => {
+        (SketchElement::Circle { center: c1, radius: r1, .. }, SketchElement::Circle { center: c2, radius: r2, .. }) => {
             circle_circle_intersection(
                 KCircle::new(Point::new(c1.x, c1.y), *r1),
                 KCircle::new(Point::new(c2.x, c2.y), *r2),
             )
         }
-        (SketchElement::Arc { center: c1, radius: r1, start_angle: s1, end_angle: e1 },
-         SketchElement::Arc { center: c2, radius: r2, start_angle: s2, end_angle: e2 }) => {
+        (SketchElement::Arc { center: c1, radius: r1, start_angle: s1, end_angle: e1, .. },
+         SketchElement::Arc { center: c2, radius: r2, start_angle: s2, end_angle: e2, .. }) => {
             arc_arc_intersection(
                 Point::new(c1.x, c1.y), *r1, *s1, *e1,
                 Point::new(c2.x, c2.y), *r2, *s2, *e2,
             )
         }
-        (SketchElement::Circle { center, radius }, SketchElement::Arc { center: ac, radius: ar, start_angle, end_angle }) |
-        (SketchElement::Arc { center: ac, radius: ar, start_angle, end_angle }, SketchElement::Circle { center, radius }) => {
+        (SketchElement::Circle { center, radius, .. }, SketchElement::Arc { center: ac, radius: ar, start_angle, end_angle, .. }) |
+        (SketchElement::Arc { center: ac, radius: ar, start_angle, end_angle, .. }, SketchElement::Circle { center, radius, .. }) => {
             arc_circle_intersection(
                 Point::new(ac.x, ac.y), *ar, *start_angle, *end_angle,
                 KCircle::new(Point::new(center.x, center.y), *radius),
@@ -858,10 +858,11 @@ pub fn reflect_point_about_line(point: (f64, f64), axis: ((f64, f64), (f64, f64)
 /// Reflect a sketch element about a line (axis)
 pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f64, f64))) -> SketchElement {
     match element {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             let reflected_start = reflect_point_about_line((start.x, start.y), axis);
             let reflected_end = reflect_point_about_line((end.x, end.y), axis);
             SketchElement::Line {
+                id: None,
                 start: Point2D {
                     x: reflected_start.0,
                     y: reflected_start.1,
@@ -872,9 +873,10 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 },
             }
         }
-        SketchElement::Circle { center, radius } => {
+        SketchElement::Circle { center, radius, .. } => {
             let reflected_center = reflect_point_about_line((center.x, center.y), axis);
             SketchElement::Circle {
+                id: None,
                 center: Point2D {
                     x: reflected_center.0,
                     y: reflected_center.1,
@@ -887,6 +889,7 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
             radius,
             start_angle,
             end_angle,
+            ..
         } => {
             let reflected_center = reflect_point_about_line((center.x, center.y), axis);
 
@@ -901,6 +904,7 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
             let new_end = 2.0 * axis_angle - start_angle;
 
             SketchElement::Arc {
+                id: None,
                 center: Point2D {
                     x: reflected_center.0,
                     y: reflected_center.1,
@@ -910,9 +914,10 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 end_angle: new_end,
             }
         }
-        SketchElement::Rectangle { corner, width, height } => {
+        SketchElement::Rectangle { corner, width, height, .. } => {
             let reflected_corner = reflect_point_about_line((corner.x, corner.y), axis);
             SketchElement::Rectangle {
+                id: None,
                 corner: Point2D {
                     x: reflected_corner.0,
                     y: reflected_corner.1,
@@ -921,7 +926,7 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 height: *height,
             }
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             let reflected_points: Vec<Point2D> = points
                 .iter()
                 .map(|p| {
@@ -933,10 +938,11 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 })
                 .collect();
             SketchElement::Polyline {
+                id: None,
                 points: reflected_points,
             }
         }
-        SketchElement::Spline { points } => {
+        SketchElement::Spline { points, .. } => {
             let reflected_points: Vec<Point2D> = points
                 .iter()
                 .map(|p| {
@@ -948,6 +954,7 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 })
                 .collect();
             SketchElement::Spline {
+                id: None,
                 points: reflected_points,
             }
         }
@@ -973,7 +980,7 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 value: *value,
                 parameter_name: parameter_name.clone(),
                 dimension_line_pos: reflected_dim_line_pos,
-                target_element: *target_element,
+                target_element: target_element.clone(),
                 dimension_type: *dimension_type,
             }
         }
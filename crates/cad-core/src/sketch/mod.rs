@@ -6,7 +6,7 @@
 //! - Offset: Create parallel copies of elements
 //! - Pattern: Linear and circular arrays of elements
 //! - Validation: Check sketch integrity for extrusion
-//! - Constraints: Geometric constraints solver
+//! - Constraints: Geometric constraints solver (Levenberg–Marquardt, DOF analysis)
 
 mod types;
 pub mod geometry;
@@ -15,6 +15,7 @@ mod fillet;
 mod offset;
 pub mod pattern;
 mod validation;
+mod solver;
 pub mod constraints;
 
 // Re-export the old operations module for backward compatibility
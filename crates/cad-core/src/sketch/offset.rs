@@ -15,7 +15,7 @@ use super::geometry::to_point;
 #[allow(dead_code)]
 pub fn offset_line(line: &SketchElement, distance: f64, click_side: [f64; 2]) -> Option<SketchElement> {
     let (start, end) = match line {
-        SketchElement::Line { start, end } => (Point::new(start.x, start.y), Point::new(end.x, end.y)),
+        SketchElement::Line { start, end, .. } => (Point::new(start.x, start.y), Point::new(end.x, end.y)),
         _ => return None,
     };
 
@@ -33,6 +33,7 @@ pub fn offset_line(line: &SketchElement, distance: f64, click_side: [f64; 2]) ->
     let offset = perp * distance * side;
 
     Some(SketchElement::Line {
+        id: None,
         start: Point2D { x: start.x + offset.x, y: start.y + offset.y },
         end: Point2D { x: end.x + offset.x, y: end.y + offset.y },
     })
@@ -42,7 +43,7 @@ pub fn offset_line(line: &SketchElement, distance: f64, click_side: [f64; 2]) ->
 #[allow(dead_code)]
 pub fn offset_circle(circle: &SketchElement, distance: f64, click_point: [f64; 2]) -> Option<SketchElement> {
     let (center, radius) = match circle {
-        SketchElement::Circle { center, radius } => (Point::new(center.x, center.y), *radius),
+        SketchElement::Circle { center, radius, .. } => (Point::new(center.x, center.y), *radius),
         _ => return None,
     };
 
@@ -56,6 +57,7 @@ pub fn offset_circle(circle: &SketchElement, distance: f64, click_point: [f64; 2
     };
 
     Some(SketchElement::Circle {
+        id: None,
         center: Point2D { x: center.x, y: center.y },
         radius: new_radius,
     })
@@ -65,7 +67,7 @@ pub fn offset_circle(circle: &SketchElement, distance: f64, click_point: [f64; 2
 #[allow(dead_code)]
 pub fn offset_arc(arc: &SketchElement, distance: f64, click_point: [f64; 2]) -> Option<SketchElement> {
     let (center, radius, start_angle, end_angle) = match arc {
-        SketchElement::Arc { center, radius, start_angle, end_angle } =>
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } =>
             (Point::new(center.x, center.y), *radius, *start_angle, *end_angle),
         _ => return None,
     };
@@ -80,6 +82,7 @@ pub fn offset_arc(arc: &SketchElement, distance: f64, click_point: [f64; 2]) ->
     };
 
     Some(SketchElement::Arc {
+        id: None,
         center: Point2D { x: center.x, y: center.y },
         radius: new_radius,
         start_angle,
@@ -91,7 +94,7 @@ pub fn offset_arc(arc: &SketchElement, distance: f64, click_point: [f64; 2]) ->
 #[allow(dead_code)]
 pub fn offset_polyline(polyline: &SketchElement, distance: f64, click_point: [f64; 2]) -> Option<Vec<SketchElement>> {
     let points = match polyline {
-        SketchElement::Polyline { points } => points,
+        SketchElement::Polyline { points, .. } => points,
         _ => return None,
     };
 
@@ -138,14 +141,14 @@ pub fn offset_polyline(polyline: &SketchElement, distance: f64, click_point: [f6
         return None;
     }
 
-    Some(vec![SketchElement::Polyline { points: offset_points }])
+    Some(vec![SketchElement::Polyline { id: None, points: offset_points }])
 }
 
 /// Create an offset copy of a rectangle (simple implementation)
 #[allow(dead_code)]
 pub fn offset_rectangle(rect: &SketchElement, distance: f64, click_point: [f64; 2]) -> Option<Vec<SketchElement>> {
     let (corner, width, height) = match rect {
-        SketchElement::Rectangle { corner, width, height } => (corner, *width, *height),
+        SketchElement::Rectangle { corner, width, height, .. } => (corner, *width, *height),
         _ => return None,
     };
 
@@ -178,14 +181,14 @@ pub fn offset_rectangle(rect: &SketchElement, distance: f64, click_point: [f64;
         Point2D { x: new_corner_x, y: new_corner_y }, // Close the path
     ];
 
-    Some(vec![SketchElement::Polyline { points }])
+    Some(vec![SketchElement::Polyline { id: None, points }])
 }
 
 /// Create an offset copy of a spline (approximate with polyline)
 #[allow(dead_code)]
 pub fn offset_spline(spline: &SketchElement, distance: f64, click_point: [f64; 2]) -> Option<Vec<SketchElement>> {
     let control_points = match spline {
-        SketchElement::Spline { points } => points,
+        SketchElement::Spline { points, .. } => points,
         _ => return None,
     };
 
@@ -194,7 +197,7 @@ pub fn offset_spline(spline: &SketchElement, distance: f64, click_point: [f64; 2
     }
 
     // Use control points as approximation and offset as polyline
-    let approximation = SketchElement::Polyline { points: control_points.clone() };
+    let approximation = SketchElement::Polyline { id: None, points: control_points.clone() };
     offset_polyline(&approximation, distance, click_point)
 }
 
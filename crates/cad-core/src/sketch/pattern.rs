@@ -62,38 +62,42 @@ pub fn circular_pattern(
 /// Translate an element by (dx, dy)
 fn translate_element(element: &SketchElement, dx: f64, dy: f64) -> Option<SketchElement> {
     match element {
-        SketchElement::Line { start, end } => Some(SketchElement::Line {
+        SketchElement::Line { start, end, .. } => Some(SketchElement::Line {
+            id: None,
             start: Point2D { x: start.x + dx, y: start.y + dy },
             end: Point2D { x: end.x + dx, y: end.y + dy },
         }),
-        SketchElement::Circle { center, radius } => Some(SketchElement::Circle {
+        SketchElement::Circle { center, radius, .. } => Some(SketchElement::Circle {
+            id: None,
             center: Point2D { x: center.x + dx, y: center.y + dy },
             radius: *radius,
         }),
-        SketchElement::Arc { center, radius, start_angle, end_angle } => Some(SketchElement::Arc {
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } => Some(SketchElement::Arc {
+            id: None,
             center: Point2D { x: center.x + dx, y: center.y + dy },
             radius: *radius,
             start_angle: *start_angle,
             end_angle: *end_angle,
         }),
-        SketchElement::Rectangle { corner, width, height } => Some(SketchElement::Rectangle {
+        SketchElement::Rectangle { corner, width, height, .. } => Some(SketchElement::Rectangle {
+            id: None,
             corner: Point2D { x: corner.x + dx, y: corner.y + dy },
             width: *width,
             height: *height,
         }),
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             let translated_points: Vec<Point2D> = points
                 .iter()
                 .map(|p| Point2D { x: p.x + dx, y: p.y + dy })
                 .collect();
-            Some(SketchElement::Polyline { points: translated_points })
+            Some(SketchElement::Polyline { id: None, points: translated_points })
         }
-        SketchElement::Spline { points } => {
+        SketchElement::Spline { points, .. } => {
             let translated_points: Vec<Point2D> = points
                 .iter()
                 .map(|p| Point2D { x: p.x + dx, y: p.y + dy })
                 .collect();
-            Some(SketchElement::Spline { points: translated_points })
+            Some(SketchElement::Spline { id: None, points: translated_points })
         }
         SketchElement::Dimension { .. } => None, // Don't copy dimensions
     }
@@ -113,21 +117,24 @@ fn rotate_element(element: &SketchElement, center: [f64; 2], angle: f64) -> Opti
     };
 
     match element {
-        SketchElement::Line { start, end } => Some(SketchElement::Line {
+        SketchElement::Line { start, end, .. } => Some(SketchElement::Line {
+            id: None,
             start: rotate_point(start),
             end: rotate_point(end),
         }),
-        SketchElement::Circle { center: c, radius } => Some(SketchElement::Circle {
+        SketchElement::Circle { center: c, radius, .. } => Some(SketchElement::Circle {
+            id: None,
             center: rotate_point(c),
             radius: *radius,
         }),
-        SketchElement::Arc { center: c, radius, start_angle, end_angle } => Some(SketchElement::Arc {
+        SketchElement::Arc { center: c, radius, start_angle, end_angle, .. } => Some(SketchElement::Arc {
+            id: None,
             center: rotate_point(c),
             radius: *radius,
             start_angle: start_angle + angle,
             end_angle: end_angle + angle,
         }),
-        SketchElement::Rectangle { corner, width, height } => {
+        SketchElement::Rectangle { corner, width, height, .. } => {
             // For rectangles, we rotate all 4 corners and create a polyline
             // (rotated rectangle is no longer axis-aligned)
             let corners = [
@@ -138,15 +145,15 @@ fn rotate_element(element: &SketchElement, center: [f64; 2], angle: f64) -> Opti
                 Point2D { x: corner.x, y: corner.y }, // close the shape
             ];
             let rotated_corners: Vec<Point2D> = corners.iter().map(|p| rotate_point(p)).collect();
-            Some(SketchElement::Polyline { points: rotated_corners })
+            Some(SketchElement::Polyline { id: None, points: rotated_corners })
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             let rotated_points: Vec<Point2D> = points.iter().map(|p| rotate_point(p)).collect();
-            Some(SketchElement::Polyline { points: rotated_points })
+            Some(SketchElement::Polyline { id: None, points: rotated_points })
         }
-        SketchElement::Spline { points } => {
+        SketchElement::Spline { points, .. } => {
             let rotated_points: Vec<Point2D> = points.iter().map(|p| rotate_point(p)).collect();
-            Some(SketchElement::Spline { points: rotated_points })
+            Some(SketchElement::Spline { id: None, points: rotated_points })
         }
         SketchElement::Dimension { .. } => None, // Don't copy dimensions
     }
@@ -159,6 +166,7 @@ mod tests {
     #[test]
     fn test_linear_pattern_line() {
         let line = SketchElement::Line {
+            id: None,
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 1.0, y: 0.0 },
         };
@@ -166,14 +174,14 @@ mod tests {
         let copies = linear_pattern(&line, 3, 2.0, 0.0); // 3 copies along X
         assert_eq!(copies.len(), 2);
 
-        if let SketchElement::Line { start, end } = &copies[0] {
+        if let SketchElement::Line { start, end, .. } = &copies[0] {
             assert!((start.x - 2.0).abs() < 1e-10);
             assert!((end.x - 3.0).abs() < 1e-10);
         } else {
             panic!("Expected Line");
         }
 
-        if let SketchElement::Line { start, end } = &copies[1] {
+        if let SketchElement::Line { start, end, .. } = &copies[1] {
             assert!((start.x - 4.0).abs() < 1e-10);
             assert!((end.x - 5.0).abs() < 1e-10);
         } else {
@@ -184,6 +192,7 @@ mod tests {
     #[test]
     fn test_circular_pattern_line() {
         let line = SketchElement::Line {
+            id: None,
             start: Point2D { x: 1.0, y: 0.0 },
             end: Point2D { x: 2.0, y: 0.0 },
         };
@@ -192,7 +201,7 @@ mod tests {
         assert_eq!(copies.len(), 3);
 
         // First copy should be at 90 degrees
-        if let SketchElement::Line { start, end } = &copies[0] {
+        if let SketchElement::Line { start, end, .. } = &copies[0] {
             assert!((start.x).abs() < 1e-10);
             assert!((start.y - 1.0).abs() < 1e-10);
         } else {
@@ -203,6 +212,7 @@ mod tests {
     #[test]
     fn test_linear_pattern_count_one() {
         let line = SketchElement::Line {
+            id: None,
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 1.0, y: 0.0 },
         };
@@ -0,0 +1,171 @@
+//! Dense linear algebra used by the constraint solver
+//!
+//! Sketches are small (tens to a few hundred variables), so plain dense
+//! matrices stored row-major in `Vec<f64>` are more than enough.
+
+/// Relative tolerance used to decide that a row is linearly dependent
+const RANK_TOLERANCE: f64 = 1e-7;
+
+/// Solve the square system `a * x = b` (row-major `n x n`) with partial pivoting.
+/// Returns None if the matrix is singular.
+pub(crate) fn solve_dense(mut a: Vec<f64>, mut b: Vec<f64>, n: usize) -> Option<Vec<f64>> {
+    for col in 0..n {
+        // Pick the largest pivot in this column
+        let pivot = (col..n).max_by(|&i, &j| {
+            a[i * n + col]
+                .abs()
+                .partial_cmp(&a[j * n + col].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        if a[pivot * n + col].abs() < 1e-14 {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(pivot * n + k, col * n + k);
+            }
+            b.swap(pivot, col);
+        }
+
+        let diag = a[col * n + col];
+        for row in (col + 1)..n {
+            let factor = a[row * n + col] / diag;
+            if factor == 0.0 {
+                continue;
+            }
+            for k in col..n {
+                a[row * n + k] -= factor * a[col * n + k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let mut sum = b[row];
+        for k in (row + 1)..n {
+            sum -= a[row * n + k] * x[k];
+        }
+        x[row] = sum / a[row * n + row];
+    }
+    Some(x)
+}
+
+/// Levenberg–Marquardt step in its minimum-norm form:
+/// `dx = -J^T (J J^T + lambda I)^-1 r`.
+///
+/// The minimum-norm form keeps geometry that is not pinned by constraints
+/// as close as possible to where the user left it.
+pub(crate) fn lm_step(jacobian: &[Vec<f64>], residuals: &[f64], lambda: f64) -> Option<Vec<f64>> {
+    let m = residuals.len();
+    let n = jacobian.first().map(|r| r.len()).unwrap_or(0);
+
+    let mut jjt = vec![0.0; m * m];
+    for i in 0..m {
+        for j in i..m {
+            let dot: f64 = jacobian[i].iter().zip(&jacobian[j]).map(|(a, b)| a * b).sum();
+            jjt[i * m + j] = dot;
+            jjt[j * m + i] = dot;
+        }
+        jjt[i * m + i] += lambda;
+    }
+
+    let y = solve_dense(jjt, residuals.to_vec(), m)?;
+
+    let mut dx = vec![0.0; n];
+    for (row, yi) in jacobian.iter().zip(&y) {
+        for (d, j) in dx.iter_mut().zip(row) {
+            *d -= j * yi;
+        }
+    }
+    Some(dx)
+}
+
+/// Orthonormal basis of a row space, built incrementally (Gram–Schmidt)
+pub(crate) struct RowBasis {
+    pub(crate) rows: Vec<Vec<f64>>,
+}
+
+impl RowBasis {
+    pub(crate) fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    /// Add a row to the basis. Returns false if it is linearly dependent
+    /// on the rows added before.
+    pub(crate) fn try_add(&mut self, row: &[f64]) -> bool {
+        let norm = row.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm < 1e-12 {
+            return false;
+        }
+
+        let mut v: Vec<f64> = row.iter().map(|x| x / norm).collect();
+        // Two passes of modified Gram–Schmidt for numerical stability
+        for _ in 0..2 {
+            for q in &self.rows {
+                let dot: f64 = v.iter().zip(q).map(|(a, b)| a * b).sum();
+                for (vi, qi) in v.iter_mut().zip(q) {
+                    *vi -= dot * qi;
+                }
+            }
+        }
+
+        let rest = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if rest < RANK_TOLERANCE {
+            return false;
+        }
+        for x in &mut v {
+            *x /= rest;
+        }
+        self.rows.push(v);
+        true
+    }
+
+    pub(crate) fn rank(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of independent motions left for the given subset of columns.
+    ///
+    /// The null space projector is `P = I - Q^T Q`; the motions available to
+    /// a subset of variables are spanned by the matching block of `P`.
+    pub(crate) fn free_motions(&self, columns: &[usize]) -> usize {
+        let mut basis = RowBasis::new();
+        for &a in columns {
+            let row: Vec<f64> = columns
+                .iter()
+                .map(|&b| {
+                    let identity = if a == b { 1.0 } else { 0.0 };
+                    identity - self.rows.iter().map(|q| q[a] * q[b]).sum::<f64>()
+                })
+                .collect();
+            basis.try_add(&row);
+        }
+        basis.rank()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_dense() {
+        // 2x + y = 5, x - y = 1
+        let x = solve_dense(vec![2.0, 1.0, 1.0, -1.0], vec![5.0, 1.0], 2).unwrap();
+        assert!((x[0] - 2.0).abs() < 1e-12);
+        assert!((x[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_row_basis_rank_and_free_motions() {
+        let mut basis = RowBasis::new();
+        assert!(basis.try_add(&[1.0, 0.0, 0.0]));
+        assert!(basis.try_add(&[1.0, 1.0, 0.0]));
+        assert!(!basis.try_add(&[2.0, 3.0, 0.0]));
+        assert_eq!(basis.rank(), 2);
+        // Only the third variable is still free
+        assert_eq!(basis.free_motions(&[0, 1, 2]), 1);
+        assert_eq!(basis.free_motions(&[0, 1]), 0);
+    }
+}
@@ -41,6 +41,7 @@ pub fn trim_line(
             if i == 0 {
                 // Remove from start to first intersection
                 return TrimResult::Replaced(vec![SketchElement::Line {
+                    id: None,
                     start: Point2D { x: int.point.x, y: int.point.y },
                     end: Point2D { x: end[0], y: end[1] },
                 }]);
@@ -49,10 +50,12 @@ pub fn trim_line(
                 let prev = &ints[i - 1];
                 return TrimResult::Replaced(vec![
                     SketchElement::Line {
+                        id: None,
                         start: Point2D { x: start[0], y: start[1] },
                         end: Point2D { x: prev.point.x, y: prev.point.y },
                     },
                     SketchElement::Line {
+                        id: None,
                         start: Point2D { x: int.point.x, y: int.point.y },
                         end: Point2D { x: end[0], y: end[1] },
                     },
@@ -64,6 +67,7 @@ pub fn trim_line(
     // Remove from last intersection to end
     let last = ints.last().unwrap();
     TrimResult::Replaced(vec![SketchElement::Line {
+        id: None,
         start: Point2D { x: start[0], y: start[1] },
         end: Point2D { x: last.point.x, y: last.point.y },
     }])
@@ -99,6 +103,7 @@ pub fn trim_arc(
             if i == 0 {
                 // Remove from start to first intersection
                 return TrimResult::Replaced(vec![SketchElement::Arc {
+                    id: None,
                     center: Point2D { x: center[0], y: center[1] },
                     radius,
                     start_angle: new_angle,
@@ -109,12 +114,14 @@ pub fn trim_arc(
                 let prev_angle = param_to_angle(ints[i - 1].param, start_angle, end_angle);
                 return TrimResult::Replaced(vec![
                     SketchElement::Arc {
+                        id: None,
                         center: Point2D { x: center[0], y: center[1] },
                         radius,
                         start_angle,
                         end_angle: prev_angle,
                     },
                     SketchElement::Arc {
+                        id: None,
                         center: Point2D { x: center[0], y: center[1] },
                         radius,
                         start_angle: new_angle,
@@ -128,6 +135,7 @@ pub fn trim_arc(
     // Remove from last intersection to end
     let last_angle = param_to_angle(ints.last().unwrap().param, start_angle, end_angle);
     TrimResult::Replaced(vec![SketchElement::Arc {
+        id: None,
         center: Point2D { x: center[0], y: center[1] },
         radius,
         start_angle,
@@ -231,7 +239,7 @@ pub fn trim_polyline(
         });
 
         if new_points.len() >= 2 {
-            result.push(SketchElement::Polyline { points: new_points });
+            result.push(SketchElement::Polyline { id: None, points: new_points });
         }
     }
 
@@ -251,7 +259,7 @@ pub fn trim_polyline(
         }
 
         if new_points.len() >= 2 {
-            result.push(SketchElement::Polyline { points: new_points });
+            result.push(SketchElement::Polyline { id: None, points: new_points });
         }
     }
 
@@ -269,7 +277,7 @@ pub fn trim_polyline(
                 y: prev.point.y,
             });
             if new_points.len() >= 2 {
-                return TrimResult::Replaced(vec![SketchElement::Polyline { points: new_points }]);
+                return TrimResult::Replaced(vec![SketchElement::Polyline { id: None, points: new_points }]);
             }
         } else if let Some(next) = next_int {
             // Keep the part from intersection to end
@@ -282,7 +290,7 @@ pub fn trim_polyline(
                 new_points.push(points[i].clone());
             }
             if new_points.len() >= 2 {
-                return TrimResult::Replaced(vec![SketchElement::Polyline { points: new_points }]);
+                return TrimResult::Replaced(vec![SketchElement::Polyline { id: None, points: new_points }]);
             }
         }
         return TrimResult::NoChange;
@@ -360,7 +368,7 @@ pub fn trim_rectangle(
         }
 
         match elem {
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 let other = KLine::new(Point::new(start.x, start.y), Point::new(end.x, end.y));
                 if let Some((t, u, pt)) = line_line_intersection(side_line, other) {
                     if t > 1e-6 && t < 1.0 - 1e-6 && u > 1e-6 && u < 1.0 - 1e-6 {
@@ -368,7 +376,7 @@ pub fn trim_rectangle(
                     }
                 }
             }
-            SketchElement::Circle { center: c, radius: r } => {
+            SketchElement::Circle { center: c, radius: r, .. } => {
                 let circle = KCircle::new(Point::new(c.x, c.y), *r);
                 for (t, pt) in line_circle_intersection(side_line, circle) {
                     if t > 1e-6 && t < 1.0 - 1e-6 {
@@ -376,7 +384,7 @@ pub fn trim_rectangle(
                     }
                 }
             }
-            SketchElement::Arc { center: c, radius: r, start_angle, end_angle } => {
+            SketchElement::Arc { center: c, radius: r, start_angle, end_angle, .. } => {
                 let cp = Point::new(c.x, c.y);
                 for (t, pt) in line_arc_intersection(side_line, cp, *r, *start_angle, *end_angle) {
                     if t > 1e-6 && t < 1.0 - 1e-6 {
@@ -384,7 +392,7 @@ pub fn trim_rectangle(
                     }
                 }
             }
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 for j in 0..(points.len().saturating_sub(1)) {
                     let seg = KLine::new(
                         Point::new(points[j].x, points[j].y),
@@ -427,6 +435,7 @@ pub fn trim_rectangle(
             let p0 = &corners[i];
             let p1 = &corners[(i + 1) % 4];
             result.push(SketchElement::Line {
+                id: None,
                 start: Point2D { x: p0[0], y: p0[1] },
                 end: Point2D { x: p1[0], y: p1[1] },
             });
@@ -440,6 +449,7 @@ pub fn trim_rectangle(
             if i == 0 {
                 // Keep from first intersection to end
                 result.push(SketchElement::Line {
+                    id: None,
                     start: Point2D { x: int.point.x, y: int.point.y },
                     end: Point2D { x: side_end[0], y: side_end[1] },
                 });
@@ -447,10 +457,12 @@ pub fn trim_rectangle(
                 // Keep start to prev intersection and from this intersection to end
                 let prev = &side_ints[i - 1];
                 result.push(SketchElement::Line {
+                    id: None,
                     start: Point2D { x: side_start[0], y: side_start[1] },
                     end: Point2D { x: prev.point.x, y: prev.point.y },
                 });
                 result.push(SketchElement::Line {
+                    id: None,
                     start: Point2D { x: int.point.x, y: int.point.y },
                     end: Point2D { x: side_end[0], y: side_end[1] },
                 });
@@ -462,6 +474,7 @@ pub fn trim_rectangle(
     // Click is after all intersections - keep from start to last intersection
     let last = side_ints.last().unwrap();
     result.push(SketchElement::Line {
+        id: None,
         start: Point2D { x: side_start[0], y: side_start[1] },
         end: Point2D { x: last.point.x, y: last.point.y },
     });
@@ -511,6 +524,7 @@ pub fn trim_circle(
                 // Simple case: 2 intersections, create single arc for the other segment
                 tracing::info!("trim_circle: 2 intersections, creating single arc {:.2} to {:.2}", end_angle, start_angle);
                 return TrimResult::Replaced(vec![SketchElement::Arc {
+                    id: None,
                     center: Point2D { x: center[0], y: center[1] },
                     radius,
                     start_angle: end_angle,
@@ -527,6 +541,7 @@ pub fn trim_circle(
                     let arc_end = ints[(j + 1) % n].param;
                     tracing::info!("trim_circle: keeping segment {} as arc {:.2} to {:.2}", j, arc_start, arc_end);
                     arcs.push(SketchElement::Arc {
+                        id: None,
                         center: Point2D { x: center[0], y: center[1] },
                         radius,
                         start_angle: arc_start,
@@ -552,12 +567,14 @@ mod tests {
         let sketch = Sketch {
             elements: vec![
                 SketchElement::Arc {
+                    id: None,
                     center: Point2D { x: 0.0, y: 0.0 },
                     radius: 1.0,
                     start_angle: 0.0,
                     end_angle: PI,
                 },
                 SketchElement::Line {
+                    id: None,
                     start: Point2D { x: 0.0, y: -2.0 },
                     end: Point2D { x: 0.0, y: 2.0 },
                 },
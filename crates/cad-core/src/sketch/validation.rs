@@ -173,10 +173,10 @@ pub fn check_self_intersections(sketch: &Sketch) -> bool {
 #[allow(dead_code)]
 fn get_element_endpoints(elem: &SketchElement) -> Option<([f64; 2], [f64; 2])> {
     match elem {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             Some(([start.x, start.y], [end.x, end.y]))
         }
-        SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             let start = [
                 center.x + radius * start_angle.cos(),
                 center.y + radius * start_angle.sin(),
@@ -187,7 +187,7 @@ fn get_element_endpoints(elem: &SketchElement) -> Option<([f64; 2], [f64; 2])> {
             ];
             Some((start, end))
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             if points.len() >= 2 {
                 let first = &points[0];
                 let last = &points[points.len() - 1];
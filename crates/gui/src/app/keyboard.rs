@@ -52,6 +52,10 @@ pub fn handle_keyboard(
         if i.key_pressed(egui::Key::E) && !i.modifiers.command {
             toolbar::action_extrude(state);
         }
+        // M — toggle measure tool
+        if i.key_pressed(egui::Key::M) && !i.modifiers.command {
+            toolbar::action_measure(state);
+        }
         // F — focus camera on selected object
         if i.key_pressed(egui::Key::F) && !i.modifiers.command {
             if let Some(id) = state.selection.primary() {
@@ -64,7 +68,11 @@ pub fn handle_keyboard(
 }
 
 fn handle_escape(state: &mut AppState) {
-    if state.sketch.is_editing() {
+    if state.measure.is_active() {
+        state.measure.deactivate();
+    } else if state.section.is_active() {
+        state.section.deactivate();
+    } else if state.sketch.is_editing() {
         if !state.sketch.drawing_points.is_empty() {
             // Cancel current drawing operation
             state.sketch.clear_drawing();
@@ -2,6 +2,11 @@
 
 use eframe::egui;
 
+use std::collections::HashMap;
+
+use crate::export::{Drawing, DrawingOptions, PlyFormat, StlFormat};
+use crate::helpers::find_feature_sketch;
+use crate::viewport::mesh::MeshData;
 use crate::i18n::{lang, set_lang, t, Lang};
 use crate::state::{AppState, Units};
 use crate::ui::toolbar;
@@ -38,6 +43,52 @@ pub fn file_menu(ui: &mut egui::Ui, state: &mut AppState, viewport: &ViewportPan
                 }
             }
         }
+        if ui.button(t("menu.import_mesh")).clicked() {
+            ui.close_menu();
+            if let Some(path) = rfd::FileDialog::new()
+                .set_title(t("menu.import_mesh_title"))
+                .add_filter("STL / OBJ", &["stl", "obj"])
+                .pick_file()
+            {
+                match cad_core::import::import_mesh_file(&path) {
+                    Ok(mesh) => {
+                        let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                        let id = state.scene.create_body_with_mesh(name, mesh);
+                        state.selection.select(id);
+                    }
+                    Err(e) => tracing::error!("Failed to import mesh: {e}"),
+                }
+            }
+        }
+        if ui.button(t("menu.import_dxf")).clicked() {
+            ui.close_menu();
+            if let Some(path) = rfd::FileDialog::new()
+                .set_title(t("menu.import_dxf_title"))
+                .add_filter("DXF", &["dxf"])
+                .pick_file()
+            {
+                if let Err(e) = state.dxf_import.open_file(path) {
+                    tracing::error!("Failed to import DXF: {e}");
+                }
+            }
+        }
+        if ui.button(t("menu.import_svg")).clicked() {
+            ui.close_menu();
+            if let Some(path) = rfd::FileDialog::new()
+                .set_title(t("menu.import_svg_title"))
+                .add_filter("SVG", &["svg"])
+                .pick_file()
+            {
+                match cad_core::import::import_svg_file(&path, &Default::default()) {
+                    Ok(import) => {
+                        let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+                        let id = state.scene.create_body_with_sketch(name, import.sketch, shared::Transform::new());
+                        state.selection.select(id);
+                    }
+                    Err(e) => tracing::error!("Failed to import SVG: {e}"),
+                }
+            }
+        }
         if ui.button(t("menu.save")).clicked() {
             ui.close_menu();
             if let Some(path) = rfd::FileDialog::new()
@@ -59,23 +110,42 @@ pub fn file_menu(ui: &mut egui::Ui, state: &mut AppState, viewport: &ViewportPan
             }
         }
         ui.separator();
-        if ui.button(t("menu.export_glb")).clicked() {
-            ui.close_menu();
-            let meshes = viewport.export_meshes();
-            if !meshes.is_empty() {
-                let glb_data = crate::export::build_glb(&meshes);
-                if let Some(path) = rfd::FileDialog::new()
-                    .set_title(t("menu.export_glb_title"))
-                    .add_filter("GLB", &["glb"])
-                    .set_file_name("scene.glb")
-                    .save_file()
-                {
-                    if let Err(e) = std::fs::write(&path, &glb_data) {
-                        tracing::error!("Failed to write GLB: {e}");
-                    }
+        ui.menu_button(t("menu.export_glb"), |ui| {
+            for (unlit, label) in [(false, "menu.glb_pbr"), (true, "menu.glb_unlit")] {
+                if ui.button(t(label)).clicked() {
+                    ui.close_menu();
+                    export_glb(state, viewport, unlit);
                 }
             }
+        });
+        ui.menu_button(t("menu.export_stl"), |ui| {
+            let [binary, ascii] = [StlFormat::Binary, StlFormat::Ascii].map(MeshExport::Stl);
+            mesh_export_buttons(ui, state, viewport, binary, ascii);
+        });
+        if ui.button(t("menu.export_obj")).clicked() {
+            ui.close_menu();
+            export_mesh_files(state, viewport, MeshExport::Obj, false);
         }
+        ui.menu_button(t("menu.export_ply"), |ui| {
+            let [binary, ascii] = [PlyFormat::Binary, PlyFormat::Ascii].map(MeshExport::Ply);
+            mesh_export_buttons(ui, state, viewport, binary, ascii);
+        });
+        if ui.button(t("menu.export_3mf")).clicked() {
+            ui.close_menu();
+            export_3mf(state, viewport);
+        }
+        ui.menu_button(t("menu.export_drawing"), |ui| {
+            ui.checkbox(&mut state.settings.dimensions.export_dimensions, t("menu.drawing_dimensions"));
+            ui.separator();
+            if ui.button("DXF (R12)...").clicked() {
+                ui.close_menu();
+                export_drawing(state, viewport, "dxf");
+            }
+            if ui.button("SVG...").clicked() {
+                ui.close_menu();
+                export_drawing(state, viewport, "svg");
+            }
+        });
         ui.separator();
         if ui.button(t("menu.quit")).clicked() {
             std::process::exit(0);
@@ -83,6 +153,202 @@ pub fn file_menu(ui: &mut egui::Ui, state: &mut AppState, viewport: &ViewportPan
     });
 }
 
+/// Mesh file formats of the File menu
+#[derive(Clone, Copy)]
+enum MeshExport {
+    Stl(StlFormat),
+    Obj,
+    Ply(PlyFormat),
+}
+
+impl MeshExport {
+    fn extension(self) -> &'static str {
+        match self {
+            MeshExport::Stl(_) => "stl",
+            MeshExport::Obj => "obj",
+            MeshExport::Ply(_) => "ply",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            MeshExport::Stl(_) => t("menu.export_stl_title"),
+            MeshExport::Obj => t("menu.export_obj_title"),
+            MeshExport::Ply(_) => t("menu.export_ply_title"),
+        }
+    }
+
+    /// One file with every mesh
+    fn build(self, meshes: &HashMap<String, MeshData>) -> Vec<u8> {
+        match self {
+            MeshExport::Stl(format) => crate::export::build_stl(meshes, format),
+            MeshExport::Obj => crate::export::build_obj(meshes),
+            MeshExport::Ply(format) => crate::export::build_ply(meshes, format),
+        }
+    }
+
+    /// One file per mesh, named after its key
+    fn build_per_body(self, meshes: &HashMap<String, MeshData>) -> Vec<(String, Vec<u8>)> {
+        match self {
+            MeshExport::Stl(format) => crate::export::build_stl_per_body(meshes, format),
+            MeshExport::Obj => meshes
+                .iter()
+                .map(|(name, mesh)| (name.clone(), self.build(&HashMap::from([(name.clone(), mesh.clone())]))))
+                .collect(),
+            MeshExport::Ply(format) => crate::export::build_ply_per_body(meshes, format),
+        }
+    }
+}
+
+/// Binary/ASCII, single file/file per body buttons of an export submenu
+fn mesh_export_buttons(
+    ui: &mut egui::Ui,
+    state: &AppState,
+    viewport: &ViewportPanel,
+    binary: MeshExport,
+    ascii: MeshExport,
+) {
+    for (per_body, buttons) in [
+        (false, [(binary, "menu.mesh_binary"), (ascii, "menu.mesh_ascii")]),
+        (true, [(binary, "menu.mesh_binary_per_body"), (ascii, "menu.mesh_ascii_per_body")]),
+    ] {
+        if per_body {
+            ui.separator();
+        }
+        for (export, label) in buttons {
+            if ui.button(t(label)).clicked() {
+                ui.close_menu();
+                export_mesh_files(state, viewport, export, per_body);
+            }
+        }
+    }
+}
+
+/// Export visible bodies to a mesh format: one merged file, or one file per body into a folder
+fn export_mesh_files(state: &AppState, viewport: &ViewportPanel, export: MeshExport, per_body: bool) {
+    let meshes = crate::export::meshes_by_body_name(&state.scene.scene, &viewport.export_meshes(&state.scene.scene));
+    if meshes.is_empty() {
+        return;
+    }
+
+    let extension = export.extension();
+    if per_body {
+        let Some(dir) = rfd::FileDialog::new().set_title(export.title()).pick_folder() else {
+            return;
+        };
+        let files = export.build_per_body(&meshes);
+        let names = crate::export::unique_file_names(files.iter().map(|(name, _)| name.as_str()), extension);
+        for ((_, bytes), file) in files.iter().zip(names) {
+            let path = dir.join(file);
+            if let Err(e) = std::fs::write(&path, bytes) {
+                tracing::error!("Failed to write {}: {e}", path.display());
+            }
+        }
+    } else if let Some(path) = rfd::FileDialog::new()
+        .set_title(export.title())
+        .add_filter(extension.to_uppercase(), &[extension])
+        .set_file_name(format!("scene.{extension}"))
+        .save_file()
+    {
+        if let Err(e) = std::fs::write(&path, export.build(&meshes)) {
+            tracing::error!("Failed to write {}: {e}", path.display());
+        }
+    }
+}
+
+/// Export visible bodies to GLB: a node and material per body, with the
+/// body id and parameter values in node extras
+fn export_glb(state: &AppState, viewport: &ViewportPanel, unlit: bool) {
+    let meshes = viewport.export_meshes(&state.scene.scene);
+    if meshes.is_empty() {
+        return;
+    }
+    if let Some(path) = rfd::FileDialog::new()
+        .set_title(t("menu.export_glb_title"))
+        .add_filter("GLB", &["glb"])
+        .set_file_name("scene.glb")
+        .save_file()
+    {
+        let options = crate::export::GltfOptions { unlit };
+        let glb = crate::export::build_scene_glb(&state.scene.scene, &meshes, &options);
+        if let Err(e) = std::fs::write(&path, glb) {
+            tracing::error!("Failed to write GLB: {e}");
+        }
+    }
+}
+
+/// Export visible bodies to 3MF, one object per body, in the display units
+fn export_3mf(state: &AppState, viewport: &ViewportPanel) {
+    let meshes = crate::export::meshes_by_body_name(&state.scene.scene, &viewport.export_meshes(&state.scene.scene));
+    if meshes.is_empty() {
+        return;
+    }
+    if let Some(path) = rfd::FileDialog::new()
+        .set_title(t("menu.export_3mf_title"))
+        .add_filter("3MF", &["3mf"])
+        .set_file_name("scene.3mf")
+        .save_file()
+    {
+        let package = crate::export::build_3mf(&meshes, state.settings.units.model_unit());
+        if let Err(e) = std::fs::write(&path, package) {
+            tracing::error!("Failed to write 3MF: {e}");
+        }
+    }
+}
+
+/// Drawing of the selected planar face, the sketch being edited or the last
+/// sketch of the selected body, with a name for the file
+fn selected_drawing(
+    state: &AppState,
+    viewport: &ViewportPanel,
+    options: &DrawingOptions,
+) -> Result<(String, Drawing), String> {
+    if let Some(face) = &state.selection.selected_face {
+        let meshes = viewport.export_meshes(&state.scene.scene);
+        let mesh = meshes.get(&face.object_id).ok_or("Selected face has no mesh")?;
+        let drawing = crate::export::face_drawing(mesh, &face.triangle_indices, face.normal, options)?;
+        let name = state.scene.get_body(&face.object_id).map(|b| b.name.as_str()).unwrap_or("face");
+        return Ok((format!("{name}_face"), drawing));
+    }
+
+    let (body_id, feature_id) = match state.sketch.editing_body_id() {
+        Some(body_id) => (body_id, state.sketch.active_feature_id().map(String::as_str)),
+        None => (state.selection.primary().ok_or("Select a sketch or a planar face")?, None),
+    };
+    let body = state.scene.get_body(body_id).ok_or("Body not found")?;
+    let (_, sketch) = find_feature_sketch(body, feature_id).ok_or("Body has no sketch")?;
+    Ok((body.name.clone(), crate::export::sketch_drawing(sketch, options)))
+}
+
+/// Export a flat drawing (see `selected_drawing`) to DXF R12 or SVG
+fn export_drawing(state: &AppState, viewport: &ViewportPanel, extension: &str) {
+    let options = DrawingOptions {
+        dimensions: state.settings.dimensions.export_dimensions,
+        precision: state.settings.dimensions.precision,
+    };
+    let (name, drawing) = match selected_drawing(state, viewport, &options) {
+        Ok(selected) => selected,
+        Err(e) => {
+            tracing::warn!("Export drawing: {e}");
+            return;
+        }
+    };
+    if let Some(path) = rfd::FileDialog::new()
+        .set_title(t("menu.export_drawing_title"))
+        .add_filter(extension.to_uppercase(), &[extension])
+        .set_file_name(crate::export::sanitize_file_name(&name, extension))
+        .save_file()
+    {
+        let bytes = match extension {
+            "dxf" => crate::export::build_dxf(&drawing),
+            _ => crate::export::build_svg(&drawing),
+        };
+        if let Err(e) = std::fs::write(&path, bytes) {
+            tracing::error!("Failed to write {}: {e}", path.display());
+        }
+    }
+}
+
 /// Show the edit menu
 pub fn edit_menu(ui: &mut egui::Ui, state: &mut AppState) {
     ui.menu_button(t("menu.edit"), |ui| {
@@ -147,6 +413,9 @@ pub fn view_menu(ui: &mut egui::Ui, state: &mut AppState, viewport: &mut Viewpor
         ui.checkbox(&mut state.panels.properties, t("menu.properties"));
         ui.checkbox(&mut state.panels.parameters, t("menu.parameters"));
         ui.checkbox(&mut state.panels.chat, t("menu.ai_chat"));
+        ui.checkbox(&mut state.panels.mass_properties, t("menu.mass_properties"));
+        ui.checkbox(&mut state.panels.interference, t("menu.interference"));
+        ui.checkbox(&mut state.panels.printability, t("menu.printability"));
         ui.separator();
         if ui.button(t("menu.reset_camera")).clicked() {
             viewport.reset_camera();
@@ -422,6 +691,7 @@ fn show_dimension_settings(ui: &mut egui::Ui, state: &mut AppState) {
     });
 
     ui.checkbox(&mut state.settings.dimensions.show_units, t("settings.dim_show_units"));
+    ui.checkbox(&mut state.settings.dimensions.export_dimensions, t("settings.dim_export"));
     ui.add_space(10.0);
 }
 
@@ -7,8 +7,12 @@ mod styles;
 use eframe::egui;
 
 use crate::state::AppState;
+use crate::ui::dxf_import::DxfImportDialogUi;
 use crate::ui::operation_dialog::OperationDialogUi;
-use crate::ui::{chamfer3d_panel, chat_panel, fillet3d_panel, parameters, properties, scene_tree, sketch_toolbar, status_bar, toolbar};
+use crate::ui::{
+    chamfer3d_panel, chat_panel, fillet3d_panel, interference, mass_properties, measure_panel, parameters, printability,
+    properties, scene_tree, section_panel, sketch_toolbar, status_bar, toolbar,
+};
 use crate::viewport::ViewportPanel;
 
 /// Main application
@@ -100,6 +104,28 @@ impl eframe::App for CadApp {
         // ── Operation dialog (extrude/cut params) ────────────
         self.handle_operation_dialog(ctx);
 
+        // ── DXF import dialog ────────────────────────────────
+        self.handle_dxf_import_dialog(ctx);
+
+        // ── Mass properties window ───────────────────────────
+        if self.state.panels.mass_properties {
+            let report = self.viewport.mass_properties(&self.state.scene.scene);
+            mass_properties::show(ctx, &mut self.state, report);
+        }
+
+        // ── Interference window ──────────────────────────────
+        if self.state.panels.interference {
+            let interference = self.viewport.interference(&self.state.scene.scene);
+            interference::show(ctx, &mut self.state, &interference.report);
+        }
+
+        // ── Printability window ──────────────────────────────
+        if self.state.panels.printability {
+            let settings = &self.state.printability.settings;
+            let printability = self.viewport.printability(&self.state.scene.scene, settings);
+            printability::show(ctx, &mut self.state, &printability.report);
+        }
+
         // ── Toolbar ───────────────────────────────────────────
         egui::TopBottomPanel::top("toolbar")
             .frame(
@@ -148,6 +174,35 @@ impl eframe::App for CadApp {
                 });
         }
 
+        // ── Measure toolbar (only in measure mode) ─────────────
+        if self.state.measure.is_active() {
+            egui::TopBottomPanel::top("measure_toolbar")
+                .frame(
+                    egui::Frame::side_top_panel(&ctx.style())
+                        .inner_margin(egui::Margin::symmetric(8, 3))
+                        .fill(egui::Color32::from_rgb(40, 50, 58)),
+                )
+                .show(ctx, |ui| {
+                    measure_panel::show(ui, &mut self.state);
+                });
+        }
+
+        // ── Section toolbar (only in section view) ─────────────
+        if self.state.section.is_active() {
+            let has_section = self.viewport.has_section();
+            let export = egui::TopBottomPanel::top("section_toolbar")
+                .frame(
+                    egui::Frame::side_top_panel(&ctx.style())
+                        .inner_margin(egui::Margin::symmetric(8, 3))
+                        .fill(egui::Color32::from_rgb(52, 48, 38)),
+                )
+                .show(ctx, |ui| section_panel::show(ui, &mut self.state, has_section))
+                .inner;
+            if export {
+                self.viewport.export_section_sketch(&mut self.state);
+            }
+        }
+
         // ── Status bar ───────────────────────────────────────
         egui::TopBottomPanel::bottom("status_bar")
             .exact_height(22.0)
@@ -221,6 +276,22 @@ impl CadApp {
         }
     }
 
+    fn handle_dxf_import_dialog(&mut self, ctx: &egui::Context) {
+        if self.state.dxf_import.show(ctx) != Some(true) {
+            return;
+        }
+        match self.state.dxf_import.import() {
+            Ok(import) => {
+                let name = self.state.dxf_import.body_name();
+                let id = self.state.scene.create_body_with_sketch(name, import.sketch, shared::Transform::new());
+                self.state.selection.select(id);
+                tracing::info!("Imported DXF at {} mm per unit", import.scale);
+            }
+            Err(e) => tracing::error!("Failed to import DXF: {e}"),
+        }
+        self.state.dxf_import.close();
+    }
+
     fn show_right_panel(&mut self, ctx: &egui::Context) {
         let show_right = self.state.panels.properties || self.state.panels.parameters || self.state.panels.chat;
         if !show_right {
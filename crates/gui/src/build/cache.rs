@@ -4,7 +4,7 @@ use std::collections::{HashMap, HashSet};
 
 use shared::SceneDescriptionV2;
 
-use super::{build_scene_meshes_v2, mesh_extraction::apply_face_highlight};
+use super::{apply_face_highlight, build_scene_meshes_v2};
 use crate::state::selection::FaceSelection;
 use crate::viewport::mesh::MeshData;
 use crate::viewport::picking::Aabb;
@@ -84,11 +84,21 @@ impl CsgCache {
         self.face_selection_version = face_selection_version;
     }
 
+    /// Cached meshes keyed by body ID
+    pub fn meshes(&self) -> &HashMap<String, MeshData> {
+        &self.meshes
+    }
+
     /// Clone the cached mesh map (for passing into PaintCallback)
     pub fn meshes_clone(&self) -> HashMap<String, MeshData> {
         self.meshes.clone()
     }
 
+    /// Scene version the cached meshes were built from
+    pub fn scene_version(&self) -> u64 {
+        self.version
+    }
+
     /// Rebuild counter
     pub fn rebuild_count(&self) -> u64 {
         self.rebuild_count
@@ -2,10 +2,18 @@
 //!
 //! Simplified for V2 Body-based architecture.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
-use shared::{Primitive, Transform};
+use shared::{PrintSettings, Primitive, SketchPlane, Transform};
+
+use crate::analysis::{mesh_bounds, scene_interference, scene_mass_properties, section_loops, section_sketch, SectionPlane};
+use crate::export::{self, ModelUnit, PlyFormat, StlFormat};
 
 use crate::harness::TestHarness;
+use crate::helpers::find_feature_sketch;
+use crate::validation::validated_printability;
+use crate::viewport::mesh::MeshData;
 
 /// A command the AI agent can execute (V2 body-based).
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,6 +30,23 @@ pub enum AgentCommand {
     DeleteBody {
         id: String,
     },
+    /// Change the look of a body. Only given fields change: `color` (RGB,
+    /// 0..1), `opacity` (0..1), `material` (preset name such as "Steel" or
+    /// "PLA") and `density` (g/cm³, overrides the material density).
+    /// `reset` restores the default look first.
+    SetAppearance {
+        body_id: String,
+        #[serde(default)]
+        color: Option<[f32; 3]>,
+        #[serde(default)]
+        opacity: Option<f32>,
+        #[serde(default)]
+        material: Option<String>,
+        #[serde(default)]
+        density: Option<f64>,
+        #[serde(default)]
+        reset: bool,
+    },
     /// Undo the last operation.
     Undo,
     /// Redo the last undone operation.
@@ -36,8 +61,125 @@ pub enum AgentCommand {
     ClearSelection,
     /// Inspect the scene: list all bodies.
     Inspect,
+    /// Mass, centre of mass, inertia and principal axes of built bodies
+    /// (all visible ones, or only `body_ids`) and their total.
+    MassProperties {
+        #[serde(default)]
+        body_ids: Vec<String>,
+    },
+    /// Clashes between visible bodies: pairs with overlapping bounds are
+    /// intersected and non-empty common volumes reported.
+    Interference,
+    /// Printability of built bodies (all visible ones, or only `body_ids`):
+    /// overhangs relative to the build direction, thin walls and features
+    /// below the minimum size. Omitted `settings` fields keep their defaults.
+    Printability {
+        #[serde(default)]
+        settings: PrintSettings,
+        #[serde(default)]
+        body_ids: Vec<String>,
+    },
+    /// Cut visible bodies with the principal `plane` at `offset` and create a
+    /// new body whose sketch holds the section outlines (closed polylines).
+    SectionSketch {
+        plane: SketchPlane,
+        #[serde(default)]
+        offset: f64,
+        #[serde(default)]
+        name: Option<String>,
+    },
     /// Export the scene as JSON.
     ExportScene,
+    /// Build visible bodies and write them to STL.
+    /// `path` is a file, or a directory when `per_body` is set.
+    ExportStl {
+        path: String,
+        #[serde(default)]
+        format: StlFormat,
+        #[serde(default)]
+        per_body: bool,
+    },
+    /// Import an STL/OBJ file as a new body with a `BaseMesh` feature.
+    /// The body is named after the file unless `name` is given.
+    ImportMesh {
+        path: String,
+        #[serde(default)]
+        name: Option<String>,
+    },
+    /// Import a DXF drawing as a new body with a sketch on `plane`
+    /// (XY by default). Entities on `construction_layers` become construction
+    /// geometry; `scale` (mm per drawing unit) overrides `$INSUNITS`.
+    /// With `height` the sketch is extruded right away.
+    ImportDxf {
+        path: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        plane: Option<SketchPlane>,
+        #[serde(default)]
+        offset: f64,
+        #[serde(default)]
+        construction_layers: Vec<String>,
+        #[serde(default)]
+        scale: Option<f64>,
+        #[serde(default)]
+        height: Option<f64>,
+    },
+    /// Import SVG artwork as a new body with a sketch on `plane` (XY by
+    /// default). Curves are flattened within `tolerance` mm (0.05 by default).
+    /// With `height` the sketch is extruded right away.
+    ImportSvg {
+        path: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        plane: Option<SketchPlane>,
+        #[serde(default)]
+        offset: f64,
+        #[serde(default)]
+        tolerance: Option<f64>,
+        #[serde(default)]
+        height: Option<f64>,
+    },
+    /// Write a sketch of a body as a flat DXF R12 or SVG drawing (by the
+    /// extension of `path`), without construction geometry. `feature_id`
+    /// picks a Sketch/BaseExtrude/BaseRevolve feature, default is the last
+    /// sketch. With `dimensions` the sketch dimensions are annotated.
+    ExportSketch {
+        path: String,
+        body_id: String,
+        #[serde(default)]
+        feature_id: Option<String>,
+        #[serde(default)]
+        dimensions: bool,
+    },
+    /// Build visible bodies and write them to OBJ, one object per body.
+    ExportObj { path: String },
+    /// Build visible bodies and write them to PLY.
+    /// `path` is a file, or a directory when `per_body` is set.
+    ExportPly {
+        path: String,
+        #[serde(default)]
+        format: PlyFormat,
+        #[serde(default)]
+        per_body: bool,
+    },
+    /// Build visible bodies and write them to a 3MF package,
+    /// one object per body. Coordinates are written in `unit`.
+    #[serde(rename = "export_3mf")]
+    Export3mf {
+        path: String,
+        #[serde(default)]
+        unit: ModelUnit,
+    },
+    /// Build visible bodies and write them to GLB: a node and PBR material
+    /// per body, body id and parameter values in node extras.
+    /// `unlit` marks materials with KHR_materials_unlit.
+    ExportGlb {
+        path: String,
+        #[serde(default)]
+        unlit: bool,
+    },
 }
 
 /// Response from executing a command.
@@ -67,7 +209,6 @@ impl CommandResponse {
         }
     }
 
-    #[allow(dead_code)]
     fn err(msg: impl Into<String>) -> Self {
         Self {
             success: false,
@@ -96,6 +237,50 @@ pub fn execute_command(harness: &mut TestHarness, cmd: AgentCommand) -> CommandR
             CommandResponse::ok_with_data(serde_json::json!({ "removed": removed }))
         }
 
+        AgentCommand::SetAppearance { body_id, color, opacity, material, density, reset } => {
+            let Some(body) = harness.scene.get_body(&body_id) else {
+                return CommandResponse::err(format!("Body {} not found", body_id));
+            };
+            if color.is_some_and(|c| c.iter().any(|v| !(0.0..=1.0).contains(v))) {
+                return CommandResponse::err("color components must be between 0 and 1");
+            }
+            if opacity.is_some_and(|o| !(0.0..=1.0).contains(&o)) {
+                return CommandResponse::err("opacity must be between 0 and 1");
+            }
+            if density.is_some_and(|d| d <= 0.0) {
+                return CommandResponse::err("density must be positive");
+            }
+
+            let mut appearance = if reset { None } else { body.appearance.clone() };
+            if color.is_some() || opacity.is_some() || material.is_some() || density.is_some() {
+                let edited = appearance.get_or_insert_with(Default::default);
+                if let Some(color) = color {
+                    edited.color = color;
+                }
+                if let Some(opacity) = opacity {
+                    edited.opacity = opacity;
+                }
+                if let Some(name) = material {
+                    let Some(preset) = shared::Material::preset(&name) else {
+                        let names: Vec<String> = shared::Material::presets().into_iter().map(|m| m.name).collect();
+                        return CommandResponse::err(format!("Unknown material {name}; known: {}", names.join(", ")));
+                    };
+                    edited.material = Some(preset);
+                }
+                if let Some(density) = density {
+                    let material = edited.material.get_or_insert_with(|| shared::Material {
+                        name: "Custom".to_string(),
+                        density,
+                        metallic: 0.0,
+                        roughness: 0.5,
+                    });
+                    material.density = density;
+                }
+            }
+            harness.scene.set_body_appearance(&body_id, appearance.clone(), true);
+            CommandResponse::ok_with_data(serde_json::json!({ "appearance": appearance }))
+        }
+
         AgentCommand::Undo => {
             let success = harness.undo();
             CommandResponse::ok_with_data(serde_json::json!({ "undone": success }))
@@ -137,6 +322,7 @@ pub fn execute_command(harness: &mut TestHarness, cmd: AgentCommand) -> CommandR
                         "name": body.name,
                         "visible": body.visible,
                         "feature_count": body.features.len(),
+                        "appearance": body.appearance,
                     })
                 })
                 .collect();
@@ -146,11 +332,288 @@ pub fn execute_command(harness: &mut TestHarness, cmd: AgentCommand) -> CommandR
             }))
         }
 
+        AgentCommand::MassProperties { body_ids } => {
+            harness.build();
+            if let Some(missing) = body_ids.iter().find(|id| !harness.meshes().contains_key(*id)) {
+                return CommandResponse::err(format!("Body {} not found or has no geometry", missing));
+            }
+            let meshes: HashMap<String, MeshData> = harness
+                .meshes()
+                .iter()
+                .filter(|(id, _)| body_ids.is_empty() || body_ids.contains(id))
+                .map(|(id, mesh)| (id.clone(), mesh.clone()))
+                .collect();
+            let mut report = scene_mass_properties(&harness.scene.scene, &meshes);
+            report.errors = harness.errors().clone();
+            CommandResponse::ok_with_data(serde_json::json!(report))
+        }
+
+        AgentCommand::Interference => {
+            harness.build();
+            let bounds = harness.meshes().iter().map(|(id, mesh)| (id.clone(), mesh_bounds(mesh))).collect();
+            let interference = scene_interference(&harness.scene.scene, &bounds);
+            CommandResponse::ok_with_data(serde_json::json!(interference.report))
+        }
+
+        AgentCommand::Printability { settings, body_ids } => {
+            harness.build();
+            if let Some(missing) = body_ids.iter().find(|id| !harness.meshes().contains_key(*id)) {
+                return CommandResponse::err(format!("Body {} not found or has no geometry", missing));
+            }
+            let meshes: HashMap<String, MeshData> = harness
+                .meshes()
+                .iter()
+                .filter(|(id, _)| body_ids.is_empty() || body_ids.contains(id))
+                .map(|(id, mesh)| (id.clone(), mesh.clone()))
+                .collect();
+            let printability = validated_printability(&harness.scene.scene, &meshes, &settings);
+            CommandResponse::ok_with_data(serde_json::json!(printability.report))
+        }
+
+        AgentCommand::SectionSketch { plane, offset, name } => {
+            harness.build();
+            let normal = match plane {
+                SketchPlane::Xy => glam::Vec3::Z,
+                SketchPlane::Xz => glam::Vec3::Y,
+                SketchPlane::Yz => glam::Vec3::X,
+            };
+            let section = SectionPlane::new(normal * offset as f32, normal);
+            let mut ids: Vec<&String> = harness.meshes().keys().collect();
+            ids.sort();
+            let loops: Vec<_> = ids.into_iter().flat_map(|id| section_loops(&harness.meshes()[id], &section)).collect();
+            if loops.is_empty() {
+                return CommandResponse::err("Section plane does not cross any body".to_string());
+            }
+            let sketch = section_sketch(&loops, plane, offset);
+            let name = name.unwrap_or_else(|| "Section".to_string());
+            let id = harness.scene.create_body_with_sketch(name, sketch, Transform::new());
+            CommandResponse::ok_with_data(serde_json::json!({ "id": id, "loops": loops.len() }))
+        }
+
         AgentCommand::ExportScene => {
             let json = harness.export_scene_json();
             CommandResponse::ok_with_data(serde_json::json!({ "scene_json": json }))
         }
+
+        AgentCommand::ImportMesh { path, name } => {
+            let path = std::path::Path::new(&path);
+            match cad_core::import::import_mesh_file(path) {
+                Ok(mesh) => {
+                    let triangles = mesh.triangle_count();
+                    let name = name.unwrap_or_else(|| {
+                        path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+                    });
+                    let id = harness.scene.create_body_with_mesh(name, mesh);
+                    CommandResponse::ok_with_data(serde_json::json!({ "id": id, "triangles": triangles }))
+                }
+                Err(e) => CommandResponse::err(e),
+            }
+        }
+
+        AgentCommand::ImportDxf { path, name, plane, offset, construction_layers, scale, height } => {
+            let path = std::path::Path::new(&path);
+            let options = cad_core::import::DxfOptions {
+                plane: plane.unwrap_or(SketchPlane::Xy),
+                offset,
+                construction_layers,
+                scale,
+            };
+            match cad_core::import::import_dxf_file(path, &options) {
+                Ok(import) => {
+                    let elements = import.sketch.elements.len();
+                    let id = create_sketch_body(harness, path, name, import.sketch, height);
+                    CommandResponse::ok_with_data(serde_json::json!({
+                        "id": id,
+                        "elements": elements,
+                        "layers": import.layers,
+                        "skipped": import.skipped,
+                        "scale": import.scale,
+                        "warnings": import.warnings,
+                    }))
+                }
+                Err(e) => CommandResponse::err(e),
+            }
+        }
+
+        AgentCommand::ImportSvg { path, name, plane, offset, tolerance, height } => {
+            let path = std::path::Path::new(&path);
+            let mut options = cad_core::import::SvgOptions {
+                plane: plane.unwrap_or(SketchPlane::Xy),
+                offset,
+                ..Default::default()
+            };
+            if let Some(tolerance) = tolerance {
+                options.tolerance = tolerance;
+            }
+            match cad_core::import::import_svg_file(path, &options) {
+                Ok(import) => {
+                    let elements = import.sketch.elements.len();
+                    let id = create_sketch_body(harness, path, name, import.sketch, height);
+                    CommandResponse::ok_with_data(serde_json::json!({
+                        "id": id,
+                        "elements": elements,
+                        "skipped": import.skipped,
+                        "size": import.size,
+                    }))
+                }
+                Err(e) => CommandResponse::err(e),
+            }
+        }
+
+        AgentCommand::ExportSketch { path, body_id, feature_id, dimensions } => {
+            let Some(body) = harness.scene.get_body(&body_id) else {
+                return CommandResponse::err(format!("Body {} not found", body_id));
+            };
+            let Some((_, sketch)) = find_feature_sketch(body, feature_id.as_deref()) else {
+                return CommandResponse::err(format!("Body {} has no such sketch", body_id));
+            };
+            let options = export::DrawingOptions { dimensions, ..Default::default() };
+            let drawing = export::sketch_drawing(sketch, &options);
+            let bytes = match std::path::Path::new(&path).extension().and_then(|e| e.to_str()) {
+                Some(e) if e.eq_ignore_ascii_case("dxf") => export::build_dxf(&drawing),
+                Some(e) if e.eq_ignore_ascii_case("svg") => export::build_svg(&drawing),
+                _ => return CommandResponse::err("Drawing path must end in .dxf or .svg".to_string()),
+            };
+            match std::fs::write(&path, bytes) {
+                Ok(()) => CommandResponse::ok_with_data(serde_json::json!({
+                    "path": path,
+                    "shapes": drawing.shapes.len(),
+                    "annotations": drawing.annotations.len(),
+                })),
+                Err(e) => CommandResponse::err(format!("Failed to write {}: {}", path, e)),
+            }
+        }
+
+        AgentCommand::ExportStl { path, format, per_body } => export_mesh_files(harness, &path, "stl", |meshes| {
+            if per_body {
+                MeshFiles::PerBody(export::build_stl_per_body(meshes, format))
+            } else {
+                MeshFiles::Single(export::build_stl(meshes, format))
+            }
+        }),
+
+        AgentCommand::ExportObj { path } => {
+            export_mesh_files(harness, &path, "obj", |meshes| MeshFiles::Single(export::build_obj(meshes)))
+        }
+
+        AgentCommand::ExportPly { path, format, per_body } => export_mesh_files(harness, &path, "ply", |meshes| {
+            if per_body {
+                MeshFiles::PerBody(export::build_ply_per_body(meshes, format))
+            } else {
+                MeshFiles::Single(export::build_ply(meshes, format))
+            }
+        }),
+
+        AgentCommand::Export3mf { path, unit } => {
+            let meshes = export::meshes_by_body_name(&harness.scene.scene, &export_meshes(harness));
+            if meshes.is_empty() {
+                return CommandResponse::err("Nothing to export: no visible geometry");
+            }
+            if let Err(e) = std::fs::write(&path, export::build_3mf(&meshes, unit)) {
+                return CommandResponse::err(format!("Failed to write {path}: {e}"));
+            }
+            let mut objects: Vec<&String> = meshes.keys().collect();
+            objects.sort();
+            CommandResponse::ok_with_data(serde_json::json!({ "path": path, "objects": objects }))
+        }
+
+        AgentCommand::ExportGlb { path, unlit } => {
+            let meshes = export_meshes(harness);
+            if meshes.is_empty() {
+                return CommandResponse::err("Nothing to export: no visible geometry");
+            }
+            let glb = export::build_scene_glb(&harness.scene.scene, &meshes, &export::GltfOptions { unlit });
+            if let Err(e) = std::fs::write(&path, glb) {
+                return CommandResponse::err(format!("Failed to write {path}: {e}"));
+            }
+            let nodes: Vec<&str> = harness
+                .scene
+                .scene
+                .bodies
+                .iter()
+                .filter(|b| meshes.contains_key(&b.id))
+                .map(|b| b.name.as_str())
+                .collect();
+            CommandResponse::ok_with_data(serde_json::json!({ "path": path, "nodes": nodes }))
+        }
+    }
+}
+
+/// Build visible bodies for export: keyed by body id, in body colours
+/// without the selection highlight
+fn export_meshes(harness: &mut TestHarness) -> HashMap<String, MeshData> {
+    harness.build();
+    export::meshes_with_body_colors(&harness.scene.scene, harness.meshes())
+}
+
+/// Output of a mesh exporter: one file, or named files for a directory
+enum MeshFiles {
+    Single(Vec<u8>),
+    PerBody(Vec<(String, Vec<u8>)>),
+}
+
+/// Build visible bodies (keyed by body name), export them with `build` and
+/// write the result to `path`. Responds with the written files and the
+/// triangle count.
+fn export_mesh_files(
+    harness: &mut TestHarness,
+    path: &str,
+    extension: &str,
+    build: impl FnOnce(&HashMap<String, MeshData>) -> MeshFiles,
+) -> CommandResponse {
+    let meshes = export::meshes_by_body_name(&harness.scene.scene, &export_meshes(harness));
+    if meshes.is_empty() {
+        return CommandResponse::err("Nothing to export: no visible geometry");
+    }
+
+    let files = match build(&meshes) {
+        MeshFiles::Single(bytes) => vec![(std::path::PathBuf::from(path), bytes)],
+        MeshFiles::PerBody(files) => {
+            let dir = std::path::Path::new(path);
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                return CommandResponse::err(format!("Failed to create {path}: {e}"));
+            }
+            let names = export::unique_file_names(files.iter().map(|(name, _)| name.as_str()), extension);
+            files
+                .into_iter()
+                .zip(names)
+                .map(|((_, bytes), file)| (dir.join(file), bytes))
+                .collect()
+        }
+    };
+
+    let mut written = Vec::new();
+    for (file, bytes) in files {
+        if let Err(e) = std::fs::write(&file, bytes) {
+            return CommandResponse::err(format!("Failed to write {}: {e}", file.display()));
+        }
+        written.push(file.display().to_string());
+    }
+    let triangles: usize = meshes.values().map(|m| m.indices.len() / 3).sum();
+    CommandResponse::ok_with_data(serde_json::json!({ "files": written, "triangles": triangles }))
+}
+
+/// Create a body with an imported sketch, named after the file unless
+/// `name` is given. With `height` the sketch is extruded right away.
+fn create_sketch_body(
+    harness: &mut TestHarness,
+    path: &std::path::Path,
+    name: Option<String>,
+    sketch: shared::Sketch,
+    height: Option<f64>,
+) -> String {
+    let name = name.unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default());
+    let id = harness.scene.create_body_with_sketch(name, sketch, Transform::new());
+    if let Some(height) = height {
+        let sketch_id = harness
+            .scene
+            .get_body(&id)
+            .and_then(|body| body.features.first())
+            .map(|feature| feature.id().to_string())
+            .unwrap_or_default();
+        harness.scene.convert_sketch_to_base_extrude(&id, &sketch_id, height, 0.0, 0.0);
     }
+    id
 }
 
 /// Parse and execute a single JSON command string.
@@ -236,6 +699,119 @@ mod tests {
         assert_eq!(data["body_count"], 2);
     }
 
+    #[test]
+    fn test_execute_mass_properties() {
+        let mut h = TestHarness::new();
+        let cube = h.create_cube("Block", 10.0, 10.0, 10.0);
+        let sphere = h.create_sphere("Ball", 5.0);
+        let cmd = serde_json::json!({ "command": "set_appearance", "body_id": cube, "material": "Aluminium" });
+        assert!(execute_json(&mut h, &cmd.to_string()).unwrap().success);
+
+        let resp = execute_json(&mut h, r#"{"command": "mass_properties"}"#).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let report: shared::MassReport = serde_json::from_value(resp.data.unwrap()).unwrap();
+        assert_eq!(report.bodies.len(), 2);
+        let block = &report.bodies[0].properties;
+        assert!((block.mass - 2.7).abs() < 1e-3, "mass = {}", block.mass);
+        assert!((block.principal_moments[0] - 2.7 * 200.0 / 12.0).abs() < 1e-2);
+        assert!(report.total.mass > block.mass);
+
+        let cmd = serde_json::json!({ "command": "mass_properties", "body_ids": [sphere] });
+        let data = execute_json(&mut h, &cmd.to_string()).unwrap().data.unwrap();
+        assert_eq!(data["bodies"].as_array().unwrap().len(), 1);
+        assert_eq!(data["total"]["mass"], data["bodies"][0]["mass"]);
+
+        let resp = execute_json(&mut h, r#"{"command": "mass_properties", "body_ids": ["nope"]}"#).unwrap();
+        assert!(!resp.success);
+    }
+
+    #[test]
+    fn test_execute_interference() {
+        let mut h = TestHarness::new();
+        let cube = Primitive::Cube { width: 10.0, height: 10.0, depth: 10.0 };
+        let at = |x: f64| Transform { position: [x, 0.0, 0.0], ..Transform::new() };
+        let a = h.scene.create_body_with_primitive("A".to_string(), cube.clone(), at(0.0));
+        let b = h.scene.create_body_with_primitive("B".to_string(), cube.clone(), at(5.0));
+        h.scene.create_body_with_primitive("Far".to_string(), cube, at(30.0));
+
+        let resp = execute_json(&mut h, r#"{"command": "interference"}"#).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let report: shared::InterferenceReport = serde_json::from_value(resp.data.unwrap()).unwrap();
+        assert_eq!(report.body_count, 3);
+        assert_eq!(report.checked_pairs, 1);
+        assert_eq!(report.clashes.len(), 1);
+        let clash = &report.clashes[0];
+        let mut pair = [clash.body_a.clone(), clash.body_b.clone()];
+        pair.sort();
+        let mut expected = [a, b];
+        expected.sort();
+        assert_eq!(pair, expected);
+        assert!((clash.volume - 500.0).abs() < 1e-2, "volume = {}", clash.volume);
+        assert!((clash.bbox_max[0] - clash.bbox_min[0] - 5.0).abs() < 1e-4);
+
+        // Hidden bodies are not checked
+        h.hide(&clash.body_b);
+        let data = execute_json(&mut h, r#"{"command": "interference"}"#).unwrap().data.unwrap();
+        assert!(data["clashes"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_execute_printability() {
+        let mut h = TestHarness::new();
+        let block = h.create_cube("Block", 10.0, 10.0, 10.0);
+        let ball = h.create_sphere("Ball", 5.0);
+        let plate = h.create_cube("Plate", 20.0, 20.0, 0.5);
+
+        let cmd = serde_json::json!({ "command": "printability", "settings": { "min_wall": 1.0 } });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let report: shared::PrintabilityReport = serde_json::from_value(resp.data.unwrap()).unwrap();
+        assert_eq!(report.settings.overhang_angle, 45.0);
+        assert_eq!(report.bodies.len(), 3);
+        let body = |id: &str| report.bodies.iter().find(|b| b.id == id).unwrap();
+
+        let block = body(&block);
+        assert!(block.is_clean());
+        assert!((block.min_thickness.unwrap() - 10.0).abs() < 1e-3);
+
+        // The lower cap of the ball within 45° of straight down: 2πr²(1 − cos 45°) ≈ 46 mm²
+        let ball = body(&ball);
+        assert!(ball.overhang_area > 30.0 && ball.overhang_area < 60.0, "{}", ball.overhang_area);
+        assert!(ball.max_overhang_angle > 80.0);
+
+        let plate_report = body(&plate);
+        assert!((plate_report.thin_wall_area - 800.0).abs() < 1e-2, "{}", plate_report.thin_wall_area);
+        assert!((plate_report.min_thickness.unwrap() - 0.5).abs() < 1e-3);
+
+        let cmd = serde_json::json!({ "command": "printability", "body_ids": [plate], "settings": { "min_feature": 0.6 } });
+        let data = execute_json(&mut h, &cmd.to_string()).unwrap().data.unwrap();
+        assert_eq!(data["bodies"].as_array().unwrap().len(), 1);
+        assert_eq!(data["bodies"][0]["thin_wall_faces"], 0);
+        assert!((data["bodies"][0]["small_feature_area"].as_f64().unwrap() - 800.0).abs() < 1e-2);
+
+        let resp = execute_json(&mut h, r#"{"command": "printability", "body_ids": ["nope"]}"#).unwrap();
+        assert!(!resp.success);
+    }
+
+    #[test]
+    fn test_execute_section_sketch() {
+        let mut h = TestHarness::new();
+        h.create_cube("Block", 10.0, 10.0, 10.0);
+
+        let cmd = serde_json::json!({ "command": "section_sketch", "plane": "XY", "offset": 2.5 });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let data = resp.data.unwrap();
+        assert_eq!(data["loops"], 1);
+        let id = data["id"].as_str().unwrap().to_string();
+        let body = h.scene.get_body(&id).unwrap();
+        assert_eq!(body.name, "Section");
+        assert_eq!(body.features.len(), 1);
+
+        let cmd = serde_json::json!({ "command": "section_sketch", "plane": "XY", "offset": 50.0 });
+        assert!(!execute_json(&mut h, &cmd.to_string()).unwrap().success);
+    }
+
     #[test]
     fn test_execute_undo_redo() {
         let mut h = TestHarness::new();
@@ -264,6 +840,232 @@ mod tests {
         assert!(scene_json.contains("bodies"));
     }
 
+    #[test]
+    fn test_command_serde_export_stl_defaults() {
+        let json = r#"{"command": "export_stl", "path": "out.stl"}"#;
+        let cmd: AgentCommand = serde_json::from_str(json).unwrap();
+        match cmd {
+            AgentCommand::ExportStl { path, format, per_body } => {
+                assert_eq!(path, "out.stl");
+                assert_eq!(format, StlFormat::Binary);
+                assert!(!per_body);
+            }
+            _ => panic!("Expected ExportStl"),
+        }
+    }
+
+    #[test]
+    fn test_execute_export_stl_per_body() {
+        let mut h = TestHarness::new();
+        h.create_cube("Left", 1.0, 1.0, 1.0);
+        h.create_cube("Right", 2.0, 2.0, 2.0);
+
+        let dir = std::env::temp_dir().join(format!("vcad_stl_{}", std::process::id()));
+        let cmd = serde_json::json!({
+            "command": "export_stl",
+            "path": dir.display().to_string(),
+            "format": "ascii",
+            "per_body": true,
+        });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        assert_eq!(resp.data.unwrap()["files"].as_array().unwrap().len(), 2);
+        let left = std::fs::read_to_string(dir.join("Left.stl")).unwrap();
+        assert!(left.starts_with("solid Left"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_execute_import_mesh_roundtrip() {
+        let mut h = TestHarness::new();
+        h.create_cube("Block", 2.0, 2.0, 2.0);
+        let path = std::env::temp_dir().join(format!("vcad_import_{}.stl", std::process::id()));
+        let export = serde_json::json!({ "command": "export_stl", "path": path.display().to_string() });
+        assert!(execute_json(&mut h, &export.to_string()).unwrap().success);
+
+        let import = serde_json::json!({ "command": "import_mesh", "path": path.display().to_string(), "name": "Copy" });
+        let resp = execute_json(&mut h, &import.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        assert_eq!(resp.data.unwrap()["triangles"], 12);
+        h.build();
+        assert!(h.errors().is_empty(), "{:?}", h.errors());
+        assert_eq!(h.meshes().len(), 2);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_execute_import_dxf_and_extrude() {
+        let mut h = TestHarness::new();
+        let path = std::env::temp_dir().join(format!("vcad_import_{}.dxf", std::process::id()));
+        let dxf = "0\nSECTION\n2\nENTITIES\n\
+                   0\nCIRCLE\n8\nCUT\n10\n0\n20\n0\n40\n0.5\n\
+                   0\nLINE\n8\nAXIS\n10\n-1\n20\n0\n11\n1\n21\n0\n\
+                   0\nENDSEC\n0\nEOF\n";
+        std::fs::write(&path, dxf).unwrap();
+
+        let cmd = serde_json::json!({
+            "command": "import_dxf",
+            "path": path.display().to_string(),
+            "plane": "XZ",
+            "construction_layers": ["axis"],
+            "scale": 25.4,
+            "height": 3.0,
+        });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let data = resp.data.unwrap();
+        assert_eq!(data["elements"], 2);
+        assert_eq!(data["layers"], serde_json::json!(["AXIS", "CUT"]));
+        h.build();
+        assert!(h.errors().is_empty(), "{:?}", h.errors());
+        assert_eq!(h.meshes().len(), 1);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_execute_import_svg_with_hole() {
+        let mut h = TestHarness::new();
+        let path = std::env::temp_dir().join(format!("vcad_import_{}.svg", std::process::id()));
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="30mm" height="20mm" viewBox="0 0 30 20">
+            <path d="M0 0h30v20h-30z"/><circle cx="15" cy="10" r="4"/><text>Logo</text></svg>"#;
+        std::fs::write(&path, svg).unwrap();
+
+        let cmd = serde_json::json!({ "command": "import_svg", "path": path.display().to_string(), "height": 2.0 });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let data = resp.data.unwrap();
+        assert_eq!(data["elements"], 2);
+        assert_eq!(data["skipped"], serde_json::json!(["text"]));
+        assert_eq!(data["size"], serde_json::json!([30.0, 20.0]));
+        h.build();
+        assert!(h.errors().is_empty(), "{:?}", h.errors());
+        assert_eq!(h.meshes().len(), 1);
+        // The circle is cut out of the plate, not extruded as a second solid
+        let mesh = h.meshes().values().next().unwrap();
+        let volume = crate::analysis::MassMoments::from_mesh(mesh, 1.0).volume;
+        let expected = 30.0 * 20.0 * 2.0 - std::f64::consts::PI * 4.0 * 4.0 * 2.0;
+        assert!((volume - expected).abs() < 1.5, "volume = {volume}, expected {expected}");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_execute_export_sketch() {
+        let mut h = TestHarness::new();
+        let path = std::env::temp_dir().join(format!("vcad_sketch_{}.dxf", std::process::id()));
+        let dxf = "0\nSECTION\n2\nENTITIES\n\
+                   0\nCIRCLE\n8\nCUT\n10\n0\n20\n0\n40\n2\n\
+                   0\nLINE\n8\nAXIS\n10\n-3\n20\n0\n11\n3\n21\n0\n\
+                   0\nENDSEC\n0\nEOF\n";
+        std::fs::write(&path, dxf).unwrap();
+        let cmd = serde_json::json!({
+            "command": "import_dxf",
+            "path": path.display().to_string(),
+            "construction_layers": ["AXIS"],
+        });
+        let id = execute_json(&mut h, &cmd.to_string()).unwrap().data.unwrap()["id"].clone();
+
+        let svg = path.with_extension("svg");
+        let cmd = serde_json::json!({ "command": "export_sketch", "path": svg.display().to_string(), "body_id": id });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        // The construction line is left out
+        assert_eq!(resp.data.unwrap()["shapes"], 1);
+        assert!(std::fs::read_to_string(&svg).unwrap().contains(r#"<circle cx="0" cy="0" r="2"/>"#));
+
+        let cmd = serde_json::json!({ "command": "export_sketch", "path": "out.step", "body_id": id });
+        assert!(!execute_json(&mut h, &cmd.to_string()).unwrap().success);
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&svg).ok();
+    }
+
+    #[test]
+    fn test_execute_export_obj_and_ply() {
+        let mut h = TestHarness::new();
+        let id = h.create_cube("Block", 1.0, 1.0, 1.0);
+        // The selection highlight stays out of exported colours
+        h.select_body(&id);
+
+        let dir = std::env::temp_dir().join(format!("vcad_mesh_{}", std::process::id()));
+        let obj = dir.join("scene.obj");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cmd = serde_json::json!({ "command": "export_obj", "path": obj.display().to_string() });
+        assert!(execute_json(&mut h, &cmd.to_string()).unwrap().success);
+        assert!(std::fs::read_to_string(&obj).unwrap().contains("o Block\n"));
+
+        let cmd = serde_json::json!({
+            "command": "export_ply",
+            "path": dir.display().to_string(),
+            "format": "ascii",
+            "per_body": true,
+        });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        assert_eq!(resp.data.unwrap()["triangles"], 12);
+        let ply = std::fs::read_to_string(dir.join("Block.ply")).unwrap();
+        assert!(ply.starts_with("ply\nformat ascii 1.0\n"));
+        assert!(ply.lines().filter(|l| l.split(' ').count() == 9).all(|l| l.ends_with(" 153 153 166")), "{ply}");
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_execute_export_3mf() {
+        let mut h = TestHarness::new();
+        h.create_cube("Base", 10.0, 2.0, 10.0);
+        h.create_sphere("Knob", 1.0);
+
+        let path = std::env::temp_dir().join(format!("vcad_{}.3mf", std::process::id()));
+        let cmd = serde_json::json!({ "command": "export_3mf", "path": path.display().to_string(), "unit": "inch" });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        assert_eq!(resp.data.unwrap()["objects"], serde_json::json!(["Base", "Knob"]));
+        let package = std::fs::read(&path).unwrap();
+        assert!(package.starts_with(b"PK\x03\x04"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_execute_export_glb() {
+        let mut h = TestHarness::new();
+        h.create_cube("Base", 10.0, 2.0, 10.0);
+
+        let path = std::env::temp_dir().join(format!("vcad_{}.glb", std::process::id()));
+        let cmd = serde_json::json!({ "command": "export_glb", "path": path.display().to_string(), "unlit": true });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        assert_eq!(resp.data.unwrap()["nodes"], serde_json::json!(["Base"]));
+        let glb = std::fs::read(&path).unwrap();
+        assert!(glb.starts_with(b"glTF"));
+        assert!(glb.windows(19).any(|w| w == b"KHR_materials_unlit"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_execute_set_appearance() {
+        let mut h = TestHarness::new();
+        let id = h.create_cube("Cover", 1.0, 1.0, 1.0);
+
+        let cmd = serde_json::json!({
+            "command": "set_appearance", "body_id": id, "color": [1.0, 0.5, 0.0], "opacity": 0.5, "material": "pla"
+        });
+        let resp = execute_json(&mut h, &cmd.to_string()).unwrap();
+        assert!(resp.success, "{:?}", resp.error);
+        let appearance = h.scene.get_body(&id).unwrap().appearance.clone().unwrap();
+        assert_eq!(appearance.color, [1.0, 0.5, 0.0]);
+        assert_eq!(appearance.material.as_ref().map(|m| m.name.as_str()), Some("PLA"));
+
+        // Only the given fields change
+        let cmd = serde_json::json!({ "command": "set_appearance", "body_id": id, "density": 1.1 });
+        assert!(execute_json(&mut h, &cmd.to_string()).unwrap().success);
+        let appearance = h.scene.get_body(&id).unwrap().appearance.clone().unwrap();
+        assert_eq!((appearance.opacity, appearance.material.unwrap().density), (0.5, 1.1));
+
+        let cmd = serde_json::json!({ "command": "set_appearance", "body_id": id, "material": "cheese" });
+        assert!(!execute_json(&mut h, &cmd.to_string()).unwrap().success);
+        let cmd = serde_json::json!({ "command": "set_appearance", "body_id": id, "reset": true });
+        assert!(execute_json(&mut h, &cmd.to_string()).unwrap().success);
+        assert!(h.scene.get_body(&id).unwrap().appearance.is_none());
+    }
+
     #[test]
     fn test_execute_invalid_json() {
         let mut h = TestHarness::new();
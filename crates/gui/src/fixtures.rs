@@ -24,6 +24,8 @@ pub fn cube_body(id: &str, name: &str, w: f64, h: f64, d: f64) -> Body {
         }],
         visible: true,
         parameters: HashMap::new(),
+        bindings: HashMap::new(),
+        appearance: None,
     }
 }
 
@@ -52,6 +54,8 @@ pub fn cube_body_at(id: &str, name: &str, w: f64, h: f64, d: f64, pos: [f64; 3])
         }],
         visible: true,
         parameters: HashMap::new(),
+        bindings: HashMap::new(),
+        appearance: None,
     }
 }
 
@@ -67,6 +71,8 @@ pub fn cylinder_body(id: &str, name: &str, r: f64, h: f64) -> Body {
         }],
         visible: true,
         parameters: HashMap::new(),
+        bindings: HashMap::new(),
+        appearance: None,
     }
 }
 
@@ -82,6 +88,8 @@ pub fn sphere_body(id: &str, name: &str, r: f64) -> Body {
         }],
         visible: true,
         parameters: HashMap::new(),
+        bindings: HashMap::new(),
+        appearance: None,
     }
 }
 
@@ -97,6 +105,8 @@ pub fn cone_body(id: &str, name: &str, r: f64, h: f64) -> Body {
         }],
         visible: true,
         parameters: HashMap::new(),
+        bindings: HashMap::new(),
+        appearance: None,
     }
 }
 
@@ -108,6 +118,8 @@ pub fn scene_v2(bodies: Vec<Body>) -> SceneDescriptionV2 {
         version: 2,
         bodies,
         body_operations: vec![],
+        parameters: HashMap::new(),
+        design_table: None,
     }
 }
 
@@ -117,6 +129,8 @@ pub fn empty_scene_v2() -> SceneDescriptionV2 {
         version: 2,
         bodies: vec![],
         body_operations: vec![],
+        parameters: HashMap::new(),
+        design_table: None,
     }
 }
 
@@ -205,6 +205,11 @@ impl TestHarness {
         self.last_meshes.len()
     }
 
+    /// All meshes of the last build, keyed by body ID
+    pub fn meshes(&self) -> &HashMap<String, MeshData> {
+        &self.last_meshes
+    }
+
     /// Get mesh data for a body ID
     pub fn mesh_of(&self, body_id: &str) -> Option<&MeshData> {
         self.last_meshes.get(body_id)
@@ -7,53 +7,7 @@ use shared::{Body, Feature, Sketch, SketchElement, Transform};
 use crate::state::AppState;
 use crate::state::operation_dialog::RevolveAxis;
 
-/// Check if body has base geometry (primitive, extrude, or revolve)
-pub fn has_base_geometry(body: &Body) -> bool {
-    body.features.iter().any(|f| {
-        matches!(
-            f,
-            Feature::BasePrimitive { .. }
-                | Feature::BaseExtrude { .. }
-                | Feature::BaseRevolve { .. }
-        )
-    })
-}
-
-/// Get the base transform of a body (from BasePrimitive, BaseExtrude, or BaseRevolve)
-/// Returns Transform::new() if no base feature found
-pub fn get_body_base_transform(body: &Body) -> Transform {
-    for feature in &body.features {
-        match feature {
-            Feature::BasePrimitive { transform, .. } => return transform.clone(),
-            Feature::BaseExtrude { sketch_transform, .. } => return sketch_transform.clone(),
-            Feature::BaseRevolve { sketch_transform, .. } => return sketch_transform.clone(),
-            _ => continue,
-        }
-    }
-    Transform::new()
-}
-
-/// Combine two transforms (parent + child)
-/// Result position = parent.position + child.position * parent.scale
-pub fn combine_transforms(parent: &Transform, child: &Transform) -> Transform {
-    Transform {
-        position: [
-            parent.position[0] + child.position[0] * parent.scale[0],
-            parent.position[1] + child.position[1] * parent.scale[1],
-            parent.position[2] + child.position[2] * parent.scale[2],
-        ],
-        rotation: [
-            parent.rotation[0] + child.rotation[0],
-            parent.rotation[1] + child.rotation[1],
-            parent.rotation[2] + child.rotation[2],
-        ],
-        scale: [
-            parent.scale[0] * child.scale[0],
-            parent.scale[1] * child.scale[1],
-            parent.scale[2] * child.scale[2],
-        ],
-    }
-}
+pub use cad_core::helpers::{combine_transforms, get_body_base_transform, has_base_geometry};
 
 /// Find a visible body with base geometry, excluding the given ID
 pub fn find_body_with_base<'a>(
@@ -88,6 +42,24 @@ pub fn find_any_sketch(body: &Body) -> Option<(String, Sketch, Transform)> {
     })
 }
 
+/// Find the sketch of a feature (Sketch, BaseExtrude or BaseRevolve) by ID,
+/// or the last such sketch with elements when no ID is given
+pub fn find_feature_sketch<'a>(body: &'a Body, feature_id: Option<&str>) -> Option<(&'a str, &'a Sketch)> {
+    body.features.iter().rev().find_map(|f| {
+        let (id, sketch) = match f {
+            Feature::Sketch { id, sketch, .. }
+            | Feature::BaseExtrude { id, sketch, .. }
+            | Feature::BaseRevolve { id, sketch, .. } => (id.as_str(), sketch),
+            _ => return None,
+        };
+        let matches = match feature_id {
+            Some(feature_id) => id == feature_id,
+            None => !sketch.elements.is_empty(),
+        };
+        matches.then_some((id, sketch))
+    })
+}
+
 /// Check if body has a sketch with elements
 pub fn has_sketch_with_elements(body: &Body) -> bool {
     body.features.iter().any(|f| {
@@ -198,14 +170,14 @@ pub fn find_construction_axes(sketch: &Sketch) -> Vec<RevolveAxis> {
     for (index, element) in sketch.elements.iter().enumerate() {
         // Include if: construction geometry OR designated as revolve axis
         let is_construction = sketch.is_construction(index);
-        let is_designated_axis = sketch.revolve_axis == Some(index);
+        let is_designated_axis = sketch.is_revolve_axis(index);
 
         if !is_construction && !is_designated_axis {
             continue;
         }
 
         // Only lines can be used as axes
-        if let SketchElement::Line { start, end } = element {
+        if let SketchElement::Line { start, end, .. } = element {
             line_count += 1;
             let suffix = if is_designated_axis { " *" } else { "" };
             axes.push(RevolveAxis {
@@ -240,6 +212,8 @@ mod tests {
                 transform: Transform::new(),
             }],
             parameters: std::collections::HashMap::new(),
+            bindings: std::collections::HashMap::new(),
+            appearance: None,
         }
     }
 
@@ -252,6 +226,7 @@ mod tests {
                 id: "sketch-1".to_string(),
                 sketch: Sketch {
                     elements: vec![shared::SketchElement::Circle {
+                        id: None,
                         center: shared::Point2D { x: 0.0, y: 0.0 },
                         radius: 0.5,
                     }],
@@ -260,6 +235,8 @@ mod tests {
                 transform: Transform::new(),
             }],
             parameters: std::collections::HashMap::new(),
+            bindings: std::collections::HashMap::new(),
+            appearance: None,
         }
     }
 
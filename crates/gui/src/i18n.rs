@@ -36,8 +36,31 @@ pub fn t(key: &str) -> &'static str {
         "menu.open_title" => if ru { "Открыть JSON сцены" } else { "Open Scene JSON" },
         "menu.save" => if ru { "Сохранить сцену..." } else { "Save Scene..." },
         "menu.save_title" => if ru { "Сохранить JSON сцены" } else { "Save Scene JSON" },
-        "menu.export_glb" => if ru { "Экспорт GLB..." } else { "Export GLB..." },
+        "menu.export_glb" => if ru { "Экспорт GLB" } else { "Export GLB" },
+        "menu.glb_pbr" => if ru { "PBR-материалы..." } else { "PBR materials..." },
+        "menu.glb_unlit" => if ru { "Без освещения (unlit)..." } else { "Unlit materials..." },
         "menu.export_glb_title" => if ru { "Экспорт GLB" } else { "Export GLB" },
+        "menu.export_stl" => if ru { "Экспорт STL" } else { "Export STL" },
+        "menu.export_stl_title" => if ru { "Экспорт STL" } else { "Export STL" },
+        "menu.mesh_binary" => if ru { "Двоичный, одним файлом..." } else { "Binary, single file..." },
+        "menu.mesh_ascii" => if ru { "Текстовый (ASCII), одним файлом..." } else { "ASCII, single file..." },
+        "menu.mesh_binary_per_body" => if ru { "Двоичный, файл на тело..." } else { "Binary, file per body..." },
+        "menu.mesh_ascii_per_body" => if ru { "Текстовый (ASCII), файл на тело..." } else { "ASCII, file per body..." },
+        "menu.import_mesh" => if ru { "Импорт сетки (STL/OBJ)..." } else { "Import Mesh (STL/OBJ)..." },
+        "menu.import_mesh_title" => if ru { "Импорт сетки" } else { "Import Mesh" },
+        "menu.import_dxf" => if ru { "Импорт DXF в эскиз..." } else { "Import DXF as Sketch..." },
+        "menu.import_dxf_title" => if ru { "Импорт DXF" } else { "Import DXF" },
+        "menu.import_svg" => if ru { "Импорт SVG в эскиз..." } else { "Import SVG as Sketch..." },
+        "menu.import_svg_title" => if ru { "Импорт SVG" } else { "Import SVG" },
+        "menu.export_drawing" => if ru { "Экспорт эскиза/грани (DXF/SVG)" } else { "Export Sketch/Face (DXF/SVG)" },
+        "menu.export_drawing_title" => if ru { "Экспорт чертежа" } else { "Export Drawing" },
+        "menu.drawing_dimensions" => if ru { "С размерами" } else { "Include dimensions" },
+        "menu.export_obj" => if ru { "Экспорт OBJ..." } else { "Export OBJ..." },
+        "menu.export_obj_title" => if ru { "Экспорт OBJ" } else { "Export OBJ" },
+        "menu.export_ply" => if ru { "Экспорт PLY" } else { "Export PLY" },
+        "menu.export_ply_title" => if ru { "Экспорт PLY" } else { "Export PLY" },
+        "menu.export_3mf" => if ru { "Экспорт 3MF..." } else { "Export 3MF..." },
+        "menu.export_3mf_title" => if ru { "Экспорт 3MF" } else { "Export 3MF" },
         "menu.quit" => if ru { "Выход" } else { "Quit" },
 
         "menu.edit" => if ru { "Правка" } else { "Edit" },
@@ -53,6 +76,9 @@ pub fn t(key: &str) -> &'static str {
         "menu.properties" => if ru { "Свойства" } else { "Properties" },
         "menu.parameters" => if ru { "Параметры" } else { "Parameters" },
         "menu.ai_chat" => if ru { "AI Чат" } else { "AI Chat" },
+        "menu.mass_properties" => if ru { "Массовые характеристики" } else { "Mass Properties" },
+        "menu.interference" => if ru { "Пересечения тел" } else { "Interference" },
+        "menu.printability" => if ru { "Пригодность к печати" } else { "Printability" },
         "menu.reset_camera" => if ru { "Сбросить камеру" } else { "Reset camera" },
         "menu.language" => if ru { "Язык" } else { "Language" },
 
@@ -145,6 +171,11 @@ pub fn t(key: &str) -> &'static str {
         "dialog.revolve_axis_hint" => if ru { "Ось вращения: X=0 (вертикальная)" } else { "Axis: X=0 (vertical)" },
         "dialog.revolve_axis_hint_select" => if ru { "Выберите ось вращения" } else { "Select rotation axis" },
         "dialog.revolve_axis_hint_construction" => if ru { "Добавьте construction line для выбора оси" } else { "Add construction line for axis selection" },
+        "dialog.dxf_title" => if ru { "Импорт DXF" } else { "Import DXF" },
+        "dialog.dxf_units" => if ru { "Единицы:" } else { "Units:" },
+        "dialog.dxf_units_auto" => if ru { "Из файла" } else { "From file" },
+        "dialog.dxf_construction_layers" => if ru { "Вспомогательные слои (construction):" } else { "Construction layers:" },
+        "dialog.dxf_skipped" => if ru { "Пропущены:" } else { "Skipped:" },
         "dialog.ok" => if ru { "ОК" } else { "OK" },
         "dialog.cancel" => if ru { "Отмена" } else { "Cancel" },
         "ctx.edit_operation" => if ru { "Редактировать операцию" } else { "Edit Operation" },
@@ -179,6 +210,14 @@ pub fn t(key: &str) -> &'static str {
         "prop.body_info" => if ru { "Информация о теле" } else { "Body Info" },
         "prop.visible" => if ru { "Видимость" } else { "Visible" },
         "prop.features" => if ru { "Операции" } else { "Features" },
+        "prop.appearance" => if ru { "Внешний вид" } else { "Appearance" },
+        "prop.color" => if ru { "Цвет" } else { "Color" },
+        "prop.opacity" => if ru { "Непрозрачность" } else { "Opacity" },
+        "prop.material" => if ru { "Материал" } else { "Material" },
+        "prop.material_none" => if ru { "Не задан" } else { "None" },
+        "prop.density" => if ru { "Плотность" } else { "Density" },
+        "prop.density_unit" => if ru { "г/см³" } else { "g/cm³" },
+        "prop.appearance_reset" => if ru { "Сбросить вид" } else { "Reset appearance" },
         "prop.elem_props" => if ru { "Свойства элемента" } else { "Element Properties" },
         "prop.elem_selection" => if ru { "Выбранные элементы" } else { "Selected Elements" },
         "prop.selected_count" => if ru { "Выбрано" } else { "Selected" },
@@ -289,6 +328,37 @@ pub fn t(key: &str) -> &'static str {
         "chamfer3d.cancel" => if ru { "Отмена" } else { "Cancel" },
         "chamfer3d.hint" => if ru { "ЛКМ - выбрать ребро, Ctrl+ЛКМ - добавить, Shift+ЛКМ - цепь" } else { "LMB - select edge, Ctrl+LMB - add, Shift+LMB - chain" },
 
+        // ── Measure tool (Измерение) ──────────────────────────────────
+        "tb.measure" => if ru { "Измерить" } else { "Measure" },
+        "tip.measure" => if ru { "Расстояния, углы и радиусы между элементами (M)" } else { "Distances, angles and radii between entities (M)" },
+        "tb.section" => if ru { "Сечение" } else { "Section" },
+        "tip.section" => if ru { "Разрезать все тела секущей плоскостью" } else { "Cut all bodies with a section plane" },
+        "measure.title" => if ru { "Измерение" } else { "Measure" },
+        "measure.pick_first" => if ru { "Выберите вершину, ребро или грань" } else { "Pick a vertex, edge or face" },
+        "measure.pick_second" => if ru { "Выберите второй элемент" } else { "Pick a second entity" },
+        "measure.distance" => if ru { "Расстояние:" } else { "Distance:" },
+        "measure.angle" => if ru { "Угол:" } else { "Angle:" },
+        "measure.radius" => if ru { "Радиус:" } else { "Radius:" },
+        "measure.copy" => if ru { "Копировать" } else { "Copy" },
+        "measure.copy_hint" => if ru { "Копировать результаты как текст" } else { "Copy results as text" },
+        "measure.clear" => if ru { "Сбросить" } else { "Clear" },
+        "measure.close" => if ru { "Закрыть" } else { "Close" },
+        "measure.hint" => if ru { "ЛКМ - вершина/ребро/грань, Shift+ЛКМ - точка на поверхности, Esc - выход" } else { "LMB - vertex/edge/face, Shift+LMB - surface point, Esc - exit" },
+
+        // ── Section view (Сечение) ──────────────────────────────
+        "section.title" => if ru { "Сечение" } else { "Section" },
+        "section.custom" => if ru { "Произвольная" } else { "Custom" },
+        "section.azimuth" => if ru { "Азимут:" } else { "Azimuth:" },
+        "section.elevation" => if ru { "Наклон:" } else { "Elevation:" },
+        "section.position" => if ru { "Положение:" } else { "Position:" },
+        "section.flip" => if ru { "Обратная сторона" } else { "Flip side" },
+        "section.export" => if ru { "В эскиз" } else { "To sketch" },
+        "section.export_hint" => if ru { "Создать тело с эскизом контура сечения" } else { "Create a body with a sketch of the section outline" },
+        "section.export_custom" => if ru { "Эскиз можно создать только на плоскостях XY, XZ, YZ" } else { "Only XY, XZ and YZ sections can become a sketch" },
+        "section.close" => if ru { "Закрыть" } else { "Close" },
+        "section.hint" => if ru { "Гизмо перемещает плоскость, Esc - выход" } else { "Drag the gizmo to move the plane, Esc - exit" },
+        "section.sketch_name" => if ru { "Сечение" } else { "Section" },
+
         // ── Sketch toolbar ──────────────────────────────────
         "stb.sketch" => if ru { "Эскиз:" } else { "Sketch:" },
         "stb.done" => if ru { "Готово" } else { "Done" },
@@ -419,6 +489,7 @@ pub fn t(key: &str) -> &'static str {
         "settings.dim_font_size" => if ru { "Размер шрифта" } else { "Font size" },
         "settings.dim_precision" => if ru { "Точность (знаков)" } else { "Precision (decimals)" },
         "settings.dim_show_units" => if ru { "Показывать единицы" } else { "Show units" },
+        "settings.dim_export" => if ru { "Размеры в экспорте DXF/SVG" } else { "Dimensions in DXF/SVG export" },
 
         "settings.apply" => if ru { "Применить" } else { "Apply" },
         "settings.reset" => if ru { "Сбросить" } else { "Reset" },
@@ -443,6 +514,22 @@ pub fn t(key: &str) -> &'static str {
         "constraint.tangent" => if ru { "Касательная" } else { "Tangent" },
         "constraint.concentric" => if ru { "Концентрические" } else { "Concentric" },
         "constraint.symmetric" => if ru { "Симметрия" } else { "Symmetric" },
+        "constraint.distance" => if ru { "Расстояние" } else { "Distance" },
+        "constraint.distance_point_line" => if ru { "Расстояние до линии" } else { "Distance to line" },
+        "constraint.horizontal_distance" => if ru { "Расстояние по X" } else { "Horizontal distance" },
+        "constraint.vertical_distance" => if ru { "Расстояние по Y" } else { "Vertical distance" },
+        "constraint.angle" => if ru { "Угол" } else { "Angle" },
+        "constraint.radius" => if ru { "Радиус" } else { "Radius" },
+        "constraint.diameter" => if ru { "Диаметр" } else { "Diameter" },
+        "constraint.point_on_line" => if ru { "Точка на линии" } else { "Point on line" },
+        "constraint.point_on_circle" => if ru { "Точка на окружности" } else { "Point on circle" },
+        "constraint.midpoint" => if ru { "Середина" } else { "Midpoint" },
+        "constraints.sketch_status" => if ru { "Эскиз" } else { "Sketch" },
+        "constraints.element_status" => if ru { "Элемент" } else { "Element" },
+        "constraints.fully_defined" => if ru { "Полностью определён" } else { "Fully defined" },
+        "constraints.under_defined" => if ru { "Недоопределён" } else { "Under-defined" },
+        "constraints.over_constrained" => if ru { "Переопределён" } else { "Over-constrained" },
+        "constraints.dof" => if ru { "степ. свободы" } else { "DOF" },
         "symmetry.set_axis" => if ru { "Отметить как ось симметрии" } else { "Mark as Symmetry Axis" },
         "symmetry.unset_axis" => if ru { "Снять отметку оси симметрии" } else { "Unmark Symmetry Axis" },
         "symmetry.mirror_copy" => if ru { "Отразить (копия)" } else { "Mirror (Copy)" },
@@ -460,7 +547,70 @@ pub fn t(key: &str) -> &'static str {
         "params.error" => if ru { "Ошибка:" } else { "Error:" },
         "params.unit" => if ru { "Единица:" } else { "Unit:" },
         "params.description" => if ru { "Описание:" } else { "Description:" },
-        "params.reference_not_supported" => if ru { "Ссылки пока не поддерживаются" } else { "References not supported yet" },
+        "params.reference" => if ru { "Ссылка:" } else { "Reference:" },
+        "params.global" => if ru { "Глобальные параметры" } else { "Global parameters" },
+        "params.formula_hint" => if ru { "Имена: param, тело.param, фича.свойство (extrude_1.height)" } else { "Names: param, body.param, feature.property (extrude_1.height)" },
+        "params.design_table" => if ru { "Таблица исполнений" } else { "Design table" },
+        "params.no_design_table" => if ru { "Таблица не загружена (CSV: name, параметр, ...)" } else { "No table loaded (CSV: name, parameter, ...)" },
+        "params.configuration" => if ru { "Исполнение:" } else { "Configuration:" },
+        "params.configurations" => if ru { "Исполнений" } else { "Configurations" },
+        "params.import_table" => if ru { "Загрузить таблицу..." } else { "Load table..." },
+        "params.export_configurations" => if ru { "Экспорт всех в GLB..." } else { "Export all to GLB..." },
+        "params.remove_table" => if ru { "Удалить таблицу" } else { "Remove table" },
+        "params.functions" => if ru { "Функции (углы в градусах):" } else { "Functions (angles in degrees):" },
+        "params.units" => if ru { "Единицы:" } else { "Units:" },
+        "params.unknown_unit" => if ru { "Неизвестная единица: значение используется как есть (мм, градусы)" } else { "Unknown unit: the value is used as is (mm, degrees)" },
+        "params.bindings" => if ru { "Привязки свойств фич" } else { "Feature bindings" },
+        "params.no_bindings" => if ru { "Нет привязок" } else { "No bindings" },
+        "params.add_binding" => if ru { "Привязать свойство к формуле" } else { "Bind property to a formula" },
+
+        // ── Mass properties (Массовые характеристики) ────────────
+        "mass.title" => if ru { "Массовые характеристики" } else { "Mass Properties" },
+        "mass.total" => if ru { "Все тела" } else { "All bodies" },
+        "mass.empty" => if ru { "Нет построенных тел" } else { "No built bodies" },
+        "mass.copy" => if ru { "Копировать" } else { "Copy" },
+        "mass.copy_hint" => if ru { "Копировать как JSON" } else { "Copy as JSON" },
+        "mass.default_material" => if ru { "не задан (1 г/см³)" } else { "not set (1 g/cm³)" },
+        "mass.incomplete" => if ru { "Часть тел не построена" } else { "Some bodies failed to build" },
+        "mass.mass" => if ru { "Масса" } else { "Mass" },
+        "mass.volume" => if ru { "Объём" } else { "Volume" },
+        "mass.area" => if ru { "Площадь" } else { "Surface area" },
+        "mass.center" => if ru { "Центр масс" } else { "Center of mass" },
+        "mass.inertia_centroid" => if ru { "Инерция отн. центра масс" } else { "Inertia about centroid" },
+        "mass.inertia_origin" => if ru { "Инерция отн. начала координат" } else { "Inertia about origin" },
+        "mass.principal" => if ru { "Главные моменты и оси" } else { "Principal moments and axes" },
+
+        // ── Interference (Пересечения тел) ───────────────────────
+        "interference.title" => if ru { "Пересечения тел" } else { "Interference" },
+        "interference.none" => if ru { "Пересечений нет" } else { "No clashes" },
+        "interference.bodies" => if ru { "Тел" } else { "Bodies" },
+        "interference.pairs" => if ru { "проверено пар" } else { "pairs checked" },
+        "interference.total" => if ru { "Суммарный объём" } else { "Total volume" },
+        "interference.select_hint" => if ru { "Выделить оба тела" } else { "Select both bodies" },
+        "interference.copy" => if ru { "Копировать" } else { "Copy" },
+        "interference.copy_hint" => if ru { "Копировать как JSON" } else { "Copy as JSON" },
+        "interference.highlight_hint" => if ru { "Общие объёмы подсвечены красным, тела полупрозрачны, пока окно открыто" } else { "Common volumes are shown in red over translucent bodies while this window is open" },
+
+        // ── Printability (Пригодность к печати) ─────────────────
+        "print.title" => if ru { "Пригодность к печати" } else { "Printability" },
+        "print.direction" => if ru { "Направление печати:" } else { "Build direction:" },
+        "print.overhang_angle" => if ru { "Допустимый навес:" } else { "Overhang limit:" },
+        "print.min_wall" => if ru { "Мин. стенка:" } else { "Min wall:" },
+        "print.min_feature" => if ru { "Мин. элемент:" } else { "Min feature:" },
+        "print.heatmap" => if ru { "Тепловая карта" } else { "Heatmap" },
+        "print.overhang" => if ru { "Навесы" } else { "Overhangs" },
+        "print.thickness" => if ru { "Толщина" } else { "Thickness" },
+        "print.copy" => if ru { "Копировать" } else { "Copy" },
+        "print.copy_hint" => if ru { "Копировать отчёт как JSON" } else { "Copy the report as JSON" },
+        "print.empty" => if ru { "Нет построенных тел" } else { "No built bodies" },
+        "print.body" => if ru { "Тело" } else { "Body" },
+        "print.thin" => if ru { "Тонкие стенки" } else { "Thin walls" },
+        "print.small" => if ru { "Мелкие элементы" } else { "Small features" },
+        "print.min_thickness" => if ru { "Мин. толщина" } else { "Min thickness" },
+        "print.max_overhang" => if ru { "Наибольший навес" } else { "Steepest overhang" },
+        "print.select_hint" => if ru { "Выделить тело" } else { "Select the body" },
+        "print.invalid" => if ru { "Сетки с ошибками не проверялись:" } else { "Meshes with errors were not checked:" },
+        "print.heatmap_hint" => if ru { "Пока окно открыто, тела окрашены тепловой картой: серые грани в норме, от жёлтого к красному - выход за предел" } else { "While this window is open the bodies show the heatmap: grey faces are fine, yellow to red exceed the limit" },
 
         // ── ViewCube (навигационный куб) ──────────────────
         "view.front" => if ru { "Перед" } else { "Front" },
@@ -3,15 +3,15 @@
 
 pub mod build;
 pub mod command;
-pub mod export;
-pub mod extrude;
 pub mod fixtures;
 pub mod harness;
 pub mod helpers;
-pub mod sketch;
 pub mod state;
 pub mod validation;
 
+// Headless feature evaluation shared with the server and CLI.
+pub use cad_core::{analysis, export, extrude, sketch};
+
 /// Subset of viewport types needed by build/extrude (MeshData, Aabb, Ray, picking).
 /// The full viewport (camera, renderer, GL) stays in the binary crate.
 pub mod viewport {
@@ -5,12 +5,14 @@ mod viewport;
 
 // Re-export library modules so that `crate::build`, `crate::extrude`, etc.
 // resolve to the lib crate types everywhere in the binary.
+pub use vcad_gui_lib::analysis;
 pub use vcad_gui_lib::build;
 pub use vcad_gui_lib::export;
 pub use vcad_gui_lib::extrude;
 pub use vcad_gui_lib::helpers;
 pub use vcad_gui_lib::sketch;
 pub use vcad_gui_lib::state;
+pub use vcad_gui_lib::validation;
 
 use app::CadApp;
 
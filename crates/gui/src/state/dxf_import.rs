@@ -0,0 +1,104 @@
+//! DXF import dialog state (data only)
+//!
+//! The UI rendering is in ui/dxf_import.rs.
+
+use std::path::PathBuf;
+
+use cad_core::import::{parse_dxf, DxfImport, DxfOptions};
+use shared::SketchPlane;
+
+/// Dialog state for importing a DXF drawing as a sketch
+pub struct DxfImportDialog {
+    /// Is the dialog open?
+    pub open: bool,
+    pub path: PathBuf,
+    /// Contents of the file (parsed again with the chosen options on OK)
+    pub text: String,
+    /// Layers found in the drawing with the "construction" flag
+    pub layers: Vec<(String, bool)>,
+    /// Skipped entity types
+    pub skipped: Vec<String>,
+    /// Import warnings of the preview (e.g. unknown units)
+    pub warnings: Vec<String>,
+    /// Scale from `$INSUNITS` (mm per drawing unit)
+    pub detected_scale: f64,
+    /// Scale chosen by the user; `None` uses the detected one
+    pub scale: Option<f64>,
+    pub plane: SketchPlane,
+    pub offset: f64,
+}
+
+impl Default for DxfImportDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: PathBuf::new(),
+            text: String::new(),
+            layers: Vec::new(),
+            skipped: Vec::new(),
+            warnings: Vec::new(),
+            detected_scale: 1.0,
+            scale: None,
+            plane: SketchPlane::Xy,
+            offset: 0.0,
+        }
+    }
+}
+
+impl DxfImportDialog {
+    /// Read the file and open the dialog. Layers named like construction
+    /// layers ("construction", "center...", "axis...") are pre-checked.
+    pub fn open_file(&mut self, path: PathBuf) -> Result<(), String> {
+        let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        let preview = parse_dxf(&text, &DxfOptions::default())?;
+
+        self.layers = preview
+            .layers
+            .into_iter()
+            .map(|layer| {
+                let lower = layer.to_lowercase();
+                let construction = ["constr", "center", "centre", "axis"].iter().any(|p| lower.starts_with(p));
+                (layer, construction)
+            })
+            .collect();
+        self.skipped = preview.skipped;
+        self.warnings = preview.warnings;
+        self.detected_scale = preview.scale;
+        self.scale = None;
+        self.path = path;
+        self.text = text;
+        self.open = true;
+        Ok(())
+    }
+
+    /// Options for the current dialog settings
+    pub fn options(&self) -> DxfOptions {
+        DxfOptions {
+            plane: self.plane.clone(),
+            offset: self.offset,
+            construction_layers: self
+                .layers
+                .iter()
+                .filter(|(_, construction)| *construction)
+                .map(|(layer, _)| layer.clone())
+                .collect(),
+            scale: self.scale,
+        }
+    }
+
+    /// Parse the drawing with the chosen options
+    pub fn import(&self) -> Result<DxfImport, String> {
+        parse_dxf(&self.text, &self.options())
+    }
+
+    /// Body name: the file name without extension
+    pub fn body_name(&self) -> String {
+        self.path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.text.clear();
+    }
+}
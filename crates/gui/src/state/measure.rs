@@ -0,0 +1,83 @@
+//! Measure tool state
+
+use shared::ObjectId;
+
+use crate::analysis::{measure, MeasureEntity, Measurement};
+
+/// Entity picked on a body for measuring
+#[derive(Clone, Debug)]
+pub struct MeasurePick {
+    pub object_id: ObjectId,
+    pub entity: MeasureEntity,
+}
+
+/// State for the measure tool: up to two picks and what they measure
+#[derive(Clone, Default)]
+pub struct MeasureState {
+    /// Whether measure mode is active
+    pub active: bool,
+    /// Picked entities, oldest first
+    pub picks: Vec<MeasurePick>,
+}
+
+impl MeasureState {
+    /// Enter measure mode with no picks
+    pub fn activate(&mut self) {
+        self.active = true;
+        self.picks.clear();
+    }
+
+    /// Leave measure mode
+    pub fn deactivate(&mut self) {
+        self.active = false;
+        self.picks.clear();
+    }
+
+    /// Check if measure mode is active
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Add a pick; a third pick starts a new measurement
+    pub fn pick(&mut self, pick: MeasurePick) {
+        if self.picks.len() >= 2 {
+            self.picks.clear();
+        }
+        self.picks.push(pick);
+    }
+
+    /// Values measured between the current picks
+    pub fn measurements(&self) -> Vec<Measurement> {
+        match self.picks.as_slice() {
+            [first] => measure(&first.entity, None),
+            [first, second] => measure(&first.entity, Some(&second.entity)),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::Vec3;
+
+    use super::*;
+
+    fn point(x: f32) -> MeasurePick {
+        MeasurePick { object_id: "a".into(), entity: MeasureEntity::Point(Vec3::new(x, 0.0, 0.0)) }
+    }
+
+    #[test]
+    fn test_third_pick_restarts() {
+        let mut state = MeasureState::default();
+        state.activate();
+        state.pick(point(0.0));
+        assert!(state.measurements().is_empty());
+        state.pick(point(3.0));
+        assert!(matches!(state.measurements()[..], [Measurement::Distance { value, .. }] if value == 3.0));
+
+        state.pick(point(5.0));
+        assert_eq!(state.picks.len(), 1);
+        state.deactivate();
+        assert!(!state.is_active() && state.picks.is_empty());
+    }
+}
@@ -1,8 +1,12 @@
 pub mod chamfer3d;
 pub mod chat;
+pub mod dxf_import;
 pub mod fillet3d;
+pub mod measure;
 pub mod operation_dialog;
+pub mod printability;
 pub mod scene;
+pub mod section;
 pub mod selection;
 pub mod settings;
 pub mod sketch;
@@ -11,9 +15,13 @@ use std::collections::{HashMap, HashSet};
 
 pub use chamfer3d::Chamfer3DState;
 use chat::ChatState;
+pub use dxf_import::DxfImportDialog;
 pub use fillet3d::Fillet3DState;
+pub use measure::MeasureState;
 pub use operation_dialog::{ExtrudeParams, OperationDialog, OperationType};
+pub use printability::PrintabilityState;
 pub use scene::{body_display_name, feature_display_name, feature_icon, short_id, SceneState};
+pub use section::SectionState;
 use selection::SelectionState;
 pub use settings::{AppSettings, DimensionSettings, Units};
 use sketch::SketchState;
@@ -24,6 +32,12 @@ pub struct PanelVisibility {
     pub properties: bool,
     pub parameters: bool,
     pub chat: bool,
+    /// Mass properties window
+    pub mass_properties: bool,
+    /// Interference (clash) window
+    pub interference: bool,
+    /// Printability window
+    pub printability: bool,
 }
 
 impl Default for PanelVisibility {
@@ -33,6 +47,9 @@ impl Default for PanelVisibility {
             properties: true,
             parameters: true,  // Включена по умолчанию
             chat: true,
+            mass_properties: false,
+            interference: false,
+            printability: false,
         }
     }
 }
@@ -53,10 +70,18 @@ pub struct AppState {
     pub show_settings_window: bool,
     /// Operation dialog state
     pub operation_dialog: OperationDialog,
+    /// DXF import dialog state
+    pub dxf_import: DxfImportDialog,
     /// 3D Fillet tool state
     pub fillet3d: Fillet3DState,
     /// 3D Chamfer tool state
     pub chamfer3d: Chamfer3DState,
+    /// Measure tool state
+    pub measure: MeasureState,
+    /// Section view state
+    pub section: SectionState,
+    /// Printability check settings
+    pub printability: PrintabilityState,
 }
 
 impl Default for AppState {
@@ -72,8 +97,12 @@ impl Default for AppState {
             csg_errors: HashMap::new(),
             show_settings_window: false,
             operation_dialog: OperationDialog::default(),
+            dxf_import: DxfImportDialog::default(),
             fillet3d: Fillet3DState::default(),
             chamfer3d: Chamfer3DState::default(),
+            measure: MeasureState::default(),
+            section: SectionState::default(),
+            printability: PrintabilityState::default(),
         }
     }
 }
@@ -0,0 +1,21 @@
+//! Printability check state
+
+use shared::PrintSettings;
+
+use crate::analysis::Heatmap;
+
+/// Settings of the printability window and its viewport overlay
+#[derive(Clone, Debug)]
+pub struct PrintabilityState {
+    /// Build direction and limits the bodies are checked against
+    pub settings: PrintSettings,
+    /// Colour the bodies by `heatmap` while the window is open
+    pub show_heatmap: bool,
+    pub heatmap: Heatmap,
+}
+
+impl Default for PrintabilityState {
+    fn default() -> Self {
+        Self { settings: PrintSettings::default(), show_heatmap: true, heatmap: Heatmap::default() }
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 use shared::{
-    Body, BodyId, BodyOperation, BooleanOp, BooleanResult, Feature, Primitive,
+    Appearance, Body, BodyId, BodyOperation, BooleanOp, BooleanResult, Feature, ImportedMesh, Primitive,
     SceneDescriptionV2, Sketch, Transform,
 };
 
@@ -22,6 +22,8 @@ impl SceneState {
             features: vec![],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         });
 
         self.version += 1;
@@ -51,6 +53,34 @@ impl SceneState {
             }],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        });
+
+        self.version += 1;
+        body_id
+    }
+
+    /// Create a new body from an imported mesh
+    pub fn create_body_with_mesh(&mut self, name: String, mesh: ImportedMesh) -> BodyId {
+        self.save_undo();
+        self.redo_stack.clear();
+
+        let body_id = uuid::Uuid::new_v4().to_string();
+        let feature_id = uuid::Uuid::new_v4().to_string();
+
+        self.scene.bodies.push(Body {
+            id: body_id.clone(),
+            name,
+            features: vec![Feature::BaseMesh {
+                id: feature_id,
+                mesh,
+                transform: Transform::new(),
+            }],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         });
 
         self.version += 1;
@@ -84,6 +114,8 @@ impl SceneState {
             }],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         });
 
         self.version += 1;
@@ -117,6 +149,8 @@ impl SceneState {
             }],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         });
 
         self.version += 1;
@@ -146,6 +180,8 @@ impl SceneState {
             }],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         });
 
         self.version += 1;
@@ -203,6 +239,28 @@ impl SceneState {
         }
     }
 
+    /// Set body appearance (`None` restores the default look).
+    ///
+    /// `record_undo` is false for the following frames of a continuous edit
+    /// (dragging a slider or colour picker), so the edit is one undo step.
+    pub fn set_body_appearance(&mut self, body_id: &BodyId, appearance: Option<Appearance>, record_undo: bool) -> bool {
+        match self.get_body(body_id) {
+            None => return false,
+            Some(body) if body.appearance == appearance => return true,
+            Some(_) => {}
+        }
+
+        if record_undo {
+            self.save_undo();
+            self.redo_stack.clear();
+        }
+        if let Some(body) = self.get_body_mut(body_id) {
+            body.appearance = appearance;
+            self.version += 1;
+        }
+        true
+    }
+
     /// Toggle body visibility
     pub fn toggle_body_visibility(&mut self, body_id: &BodyId) {
         if let Some(body) = self.get_body_mut(body_id) {
@@ -0,0 +1,20 @@
+//! Design table configurations
+
+use shared::DesignTableError;
+
+use super::SceneState;
+
+impl SceneState {
+    /// Apply configuration `name` of the design table as one undo step;
+    /// on error the scene is left unchanged
+    pub fn apply_configuration(&mut self, name: &str) -> Result<(), DesignTableError> {
+        let mut configured = self.scene.clone();
+        cad_core::configurations::apply_configuration(&mut configured, name)?;
+
+        self.save_undo();
+        self.redo_stack.clear();
+        self.scene = configured;
+        self.version += 1;
+        Ok(())
+    }
+}
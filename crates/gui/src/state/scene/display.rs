@@ -21,6 +21,10 @@ pub fn feature_display_name(feature: &Feature) -> String {
         }
         Feature::BaseExtrude { .. } => "Base Extrude".to_string(),
         Feature::BaseRevolve { .. } => "Base Revolve".to_string(),
+        Feature::BaseMesh { mesh, .. } => match &mesh.source {
+            Some(source) => format!("Mesh {}", source),
+            None => "Base Mesh".to_string(),
+        },
         Feature::Sketch { .. } => "Sketch".to_string(),
         Feature::Extrude { cut, .. } => {
             if *cut {
@@ -57,6 +61,7 @@ pub fn feature_icon(feature: &Feature) -> &'static str {
         },
         Feature::BaseExtrude { .. } => "[E]",
         Feature::BaseRevolve { .. } => "[R]",
+        Feature::BaseMesh { .. } => "[M]",
         Feature::Sketch { .. } => "[~]",
         Feature::Extrude { cut, .. } => {
             if *cut {
@@ -1,8 +1,37 @@
 //! Feature add/remove/update operations
 
-use shared::{BodyId, Feature, ObjectId, Primitive, Sketch, Transform};
+use shared::{Body, BodyId, EdgeRef, Feature, ObjectId, Primitive, Sketch, Transform};
 
 use super::SceneState;
+use crate::state::selection::EdgeSelection;
+
+/// Edges stored by coordinates (start, end, normal1, normal2)
+type EdgeCoords = Vec<([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)>;
+
+/// Split selected edges into named references (by the faces of the features
+/// that generated them) and plain coordinates for edges that can't be named
+fn split_edge_selection(body: &Body, edges: &[EdgeSelection]) -> (EdgeCoords, Vec<EdgeRef>) {
+    let mut coords = Vec::new();
+    let mut refs = Vec::new();
+    for e in edges {
+        let start = [e.start.x as f64, e.start.y as f64, e.start.z as f64];
+        let end = [e.end.x as f64, e.end.y as f64, e.end.z as f64];
+        match cad_core::build::topology::name_edge(body, start, end) {
+            // Several mesh edges may belong to the same model edge
+            Some(edge_ref) => {
+                if !refs.contains(&edge_ref) {
+                    refs.push(edge_ref);
+                }
+            }
+            None => {
+                let n1 = [e.normal1.x as f64, e.normal1.y as f64, e.normal1.z as f64];
+                let n2 = e.normal2.map(|n| [n.x as f64, n.y as f64, n.z as f64]);
+                coords.push((start, end, n1, n2));
+            }
+        }
+    }
+    (coords, refs)
+}
 
 impl SceneState {
     /// Add a primitive feature to an existing body
@@ -163,7 +192,7 @@ impl SceneState {
     pub fn add_fillet_to_body(
         &mut self,
         body_id: &BodyId,
-        edges: Vec<crate::state::selection::EdgeSelection>,
+        edges: Vec<EdgeSelection>,
         radius: f64,
         segments: u32,
     ) -> bool {
@@ -181,23 +210,14 @@ impl SceneState {
         if let Some(body) = self.scene.bodies.iter_mut().find(|b| &b.id == body_id) {
             let feature_id = uuid::Uuid::new_v4().to_string();
 
-            // Convert EdgeSelection to the serializable format
-            let edge_data: Vec<([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)> = edges
-                .iter()
-                .map(|e| {
-                    let start = [e.start.x as f64, e.start.y as f64, e.start.z as f64];
-                    let end = [e.end.x as f64, e.end.y as f64, e.end.z as f64];
-                    let n1 = [e.normal1.x as f64, e.normal1.y as f64, e.normal1.z as f64];
-                    let n2 = e.normal2.map(|n| [n.x as f64, n.y as f64, n.z as f64]);
-                    (start, end, n1, n2)
-                })
-                .collect();
+            let (edge_data, edge_refs) = split_edge_selection(body, &edges);
 
             body.features.push(Feature::Fillet3D {
                 id: feature_id,
                 radius,
                 segments,
                 edges: edge_data,
+                edge_refs,
             });
             self.version += 1;
             true
@@ -210,7 +230,7 @@ impl SceneState {
     pub fn add_chamfer_to_body(
         &mut self,
         body_id: &BodyId,
-        edges: Vec<crate::state::selection::EdgeSelection>,
+        edges: Vec<EdgeSelection>,
         distance: f64,
     ) -> bool {
         if !self.scene.bodies.iter().any(|b| &b.id == body_id) {
@@ -227,22 +247,13 @@ impl SceneState {
         if let Some(body) = self.scene.bodies.iter_mut().find(|b| &b.id == body_id) {
             let feature_id = uuid::Uuid::new_v4().to_string();
 
-            // Convert EdgeSelection to the serializable format
-            let edge_data: Vec<([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)> = edges
-                .iter()
-                .map(|e| {
-                    let start = [e.start.x as f64, e.start.y as f64, e.start.z as f64];
-                    let end = [e.end.x as f64, e.end.y as f64, e.end.z as f64];
-                    let n1 = [e.normal1.x as f64, e.normal1.y as f64, e.normal1.z as f64];
-                    let n2 = e.normal2.map(|n| [n.x as f64, n.y as f64, n.z as f64]);
-                    (start, end, n1, n2)
-                })
-                .collect();
+            let (edge_data, edge_refs) = split_edge_selection(body, &edges);
 
             body.features.push(Feature::Chamfer3D {
                 id: feature_id,
                 distance,
                 edges: edge_data,
+                edge_refs,
             });
             self.version += 1;
             true
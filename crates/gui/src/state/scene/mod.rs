@@ -3,6 +3,7 @@
 //! This module provides scene state with bodies, features, and undo/redo history.
 
 mod body_ops;
+mod config_ops;
 mod display;
 mod feature_ops;
 mod history;
@@ -60,7 +60,7 @@ impl SceneState {
                 Feature::Sketch { sketch, .. }
                 | Feature::BaseExtrude { sketch, .. }
                 | Feature::BaseRevolve { sketch, .. } => {
-                    sketch.elements.push(element);
+                    sketch.push_element(element);
                     self.version += 1;
                     return true;
                 }
@@ -106,17 +106,17 @@ impl SceneState {
                         // Search for a line or rectangle edge that matches dimension from/to
                         for (elem_idx, other) in sketch.elements.iter().enumerate() {
                             match other {
-                                SketchElement::Line { start, end } => {
+                                SketchElement::Line { start, end, .. } => {
                                     let line_start = (start.x, start.y);
                                     let line_end = (end.x, end.y);
 
                                     if (dist(dim_from, line_start) < threshold && dist(dim_to, line_end) < threshold) ||
                                        (dist(dim_from, line_end) < threshold && dist(dim_to, line_start) < threshold) {
-                                        *target_element = Some(elem_idx);
+                                        *target_element = sketch.element_id(elem_idx).map(|id| id.to_string());
                                         break;
                                     }
                                 }
-                                SketchElement::Rectangle { corner, width, height } => {
+                                SketchElement::Rectangle { corner, width, height, .. } => {
                                     // Rectangle corners
                                     let c0 = (corner.x, corner.y);
                                     let c1 = (corner.x + *width, corner.y);
@@ -128,7 +128,7 @@ impl SceneState {
                                     for (e_start, e_end) in edges {
                                         if (dist(dim_from, e_start) < threshold && dist(dim_to, e_end) < threshold) ||
                                            (dist(dim_from, e_end) < threshold && dist(dim_to, e_start) < threshold) {
-                                            *target_element = Some(elem_idx);
+                                            *target_element = sketch.element_id(elem_idx).map(|id| id.to_string());
                                             break;
                                         }
                                     }
@@ -142,7 +142,7 @@ impl SceneState {
                     }
                 }
 
-                sketch.elements.push(element);
+                sketch.push_element(element);
                 self.version += 1;
             }
         }
@@ -154,7 +154,7 @@ impl SceneState {
     }
 
     /// Remove sketch elements by indices
-    /// Also removes dimensions that reference the deleted elements and updates remaining dimension indices
+    /// Also removes dimensions and constraints that reference the deleted elements
     pub fn remove_sketch_elements(
         &mut self,
         body_id: &BodyId,
@@ -190,38 +190,7 @@ impl SceneState {
                 _ => return false,
             };
 
-            // Find dimensions that reference any of the elements being deleted
-            let indices_set: std::collections::HashSet<usize> = indices.iter().cloned().collect();
-            let mut all_indices: Vec<usize> = indices.to_vec();
-
-            for (dim_idx, elem) in sketch.elements.iter().enumerate() {
-                if let SketchElement::Dimension { target_element: Some(target), .. } = elem {
-                    if indices_set.contains(target) && !indices_set.contains(&dim_idx) {
-                        all_indices.push(dim_idx);
-                    }
-                }
-            }
-
-            // Sort in descending order to remove from the end first
-            all_indices.sort_by(|a, b| b.cmp(a));
-            all_indices.dedup();
-
-            // Remove elements
-            for idx in &all_indices {
-                if *idx < sketch.elements.len() {
-                    sketch.elements.remove(*idx);
-                }
-            }
-
-            // Update target_element indices for remaining dimensions
-            for elem in sketch.elements.iter_mut() {
-                if let SketchElement::Dimension { target_element: Some(ref mut target), .. } = elem {
-                    let shift = all_indices.iter().filter(|&&idx| idx < *target).count();
-                    if shift > 0 {
-                        *target -= shift;
-                    }
-                }
-            }
+            sketch.remove_elements(indices);
 
             self.version += 1;
             true
@@ -231,7 +200,7 @@ impl SceneState {
     }
 
     /// Remove a single sketch element by index
-    /// Also removes dimensions that reference this element and updates remaining dimension indices
+    /// Also removes dimensions and constraints that reference this element
     /// If feature_id is Some, removes from that specific feature
     /// If feature_id is None, removes from the LAST Sketch feature, or falls back to BaseExtrude/BaseRevolve
     pub fn remove_sketch_element(
@@ -252,37 +221,7 @@ impl SceneState {
                     _ => return,
                 };
                 if element_index < sketch.elements.len() {
-                    // Find dimensions that reference this element
-                    let mut dims_to_remove: Vec<usize> = sketch.elements.iter().enumerate()
-                        .filter_map(|(i, elem)| {
-                            if let SketchElement::Dimension { target_element: Some(target), .. } = elem {
-                                if *target == element_index { Some(i) } else { None }
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-
-                    // Add the element itself
-                    dims_to_remove.push(element_index);
-                    dims_to_remove.sort_by(|a, b| b.cmp(a));
-                    dims_to_remove.dedup();
-
-                    // Remove all (element + its dimensions)
-                    for idx in &dims_to_remove {
-                        sketch.elements.remove(*idx);
-                    }
-
-                    // Update target_element indices for remaining dimensions
-                    for elem in sketch.elements.iter_mut() {
-                        if let SketchElement::Dimension { target_element: Some(ref mut target), .. } = elem {
-                            let shift = dims_to_remove.iter().filter(|&&idx| idx < *target).count();
-                            if shift > 0 {
-                                *target -= shift;
-                            }
-                        }
-                    }
-
+                    sketch.remove_elements(&[element_index]);
                     self.version += 1;
                 }
             }
@@ -311,12 +250,8 @@ impl SceneState {
                     _ => return,
                 };
                 if element_index < sketch.elements.len() {
-                    // Remove the original element
-                    sketch.elements.remove(element_index);
-                    // Insert new elements at the same position
-                    for (i, elem) in new_elements.into_iter().enumerate() {
-                        sketch.elements.insert(element_index + i, elem);
-                    }
+                    // The first piece keeps the element id, point references follow the pieces
+                    sketch.replace_element(element_index, new_elements);
                     self.version += 1;
                 }
             }
@@ -417,13 +352,13 @@ impl SceneState {
         }
     }
 
-    /// Remove selected sketch elements by indices (handles multiple at once, sorted descending)
-    /// Also removes dimensions that reference the deleted elements and updates remaining dimension indices
+    /// Remove selected sketch elements by indices (handles multiple at once)
+    /// Also removes dimensions and constraints that reference the deleted elements
     pub fn remove_sketch_elements_by_indices(
         &mut self,
         body_id: &str,
         feature_id: Option<&str>,
-        mut indices: Vec<usize>,
+        indices: Vec<usize>,
     ) {
         if indices.is_empty() {
             return;
@@ -441,43 +376,7 @@ impl SceneState {
                     _ => return,
                 };
 
-                // Find dimensions that reference any of the elements being deleted
-                let indices_set: std::collections::HashSet<usize> = indices.iter().cloned().collect();
-                for (dim_idx, elem) in sketch.elements.iter().enumerate() {
-                    if let SketchElement::Dimension { target_element: Some(target), .. } = elem {
-                        if indices_set.contains(target) && !indices_set.contains(&dim_idx) {
-                            indices.push(dim_idx);
-                        }
-                    }
-                }
-
-                // Sort in descending order to remove from the end first
-                indices.sort_by(|a, b| b.cmp(a));
-                indices.dedup();
-
-                // Remove elements
-                for elem_idx in &indices {
-                    if *elem_idx < sketch.elements.len() {
-                        sketch.elements.remove(*elem_idx);
-                        // Also remove construction flag if exists
-                        if *elem_idx < sketch.construction.len() {
-                            sketch.construction.remove(*elem_idx);
-                        }
-                    }
-                }
-
-                // Update target_element indices for remaining dimensions
-                // (indices shift down after removal)
-                for elem in sketch.elements.iter_mut() {
-                    if let SketchElement::Dimension { target_element: Some(ref mut target), .. } = elem {
-                        // Count how many removed elements were before this target
-                        let shift = indices.iter().filter(|&&idx| idx < *target).count();
-                        if shift > 0 {
-                            *target -= shift;
-                        }
-                    }
-                }
-
+                sketch.remove_elements(&indices);
                 self.version += 1;
             }
         }
@@ -623,12 +522,45 @@ impl SceneState {
             }
         }
     }
+
+    /// Replace a constraint (e.g. new dimension value or parameter binding) and re-solve
+    pub fn update_sketch_constraint(
+        &mut self,
+        body_id: &str,
+        feature_id: Option<&str>,
+        constraint_index: usize,
+        constraint: shared::SketchConstraint,
+    ) {
+        self.save_undo();
+        self.redo_stack.clear();
+
+        if let Some(body) = self.scene.bodies.iter_mut().find(|b| b.id == body_id) {
+            if let Some(idx) = find_sketch_feature_index(body, feature_id) {
+                match &mut body.features[idx] {
+                    Feature::Sketch { sketch, .. }
+                    | Feature::BaseExtrude { sketch, .. }
+                    | Feature::BaseRevolve { sketch, .. } => {
+                        match sketch.constraints.get_mut(constraint_index) {
+                            Some(c) => *c = constraint,
+                            None => return,
+                        }
+                    }
+                    _ => return,
+                }
+
+                self.version += 1;
+            }
+        }
+
+        // Привязанное значение берётся из параметра (в т.ч. глобального), затем эскизы перерешиваются
+        crate::sketch::constraints::solve_scene_sketches(&mut self.scene);
+    }
 }
 
 /// Update a control point of a sketch element
 fn update_element_point(elem: &mut SketchElement, point_index: usize, new_pos: [f64; 2]) {
     match elem {
-        SketchElement::Line { start, end } => match point_index {
+        SketchElement::Line { start, end, .. } => match point_index {
             0 => {
                 start.x = new_pos[0];
                 start.y = new_pos[1];
@@ -639,7 +571,7 @@ fn update_element_point(elem: &mut SketchElement, point_index: usize, new_pos: [
             }
             _ => {}
         },
-        SketchElement::Circle { center, radius } => match point_index {
+        SketchElement::Circle { center, radius, .. } => match point_index {
             0 => {
                 center.x = new_pos[0];
                 center.y = new_pos[1];
@@ -656,6 +588,7 @@ fn update_element_point(elem: &mut SketchElement, point_index: usize, new_pos: [
             radius,
             start_angle,
             end_angle,
+            ..
         } => match point_index {
             0 => {
                 center.x = new_pos[0];
@@ -678,6 +611,7 @@ fn update_element_point(elem: &mut SketchElement, point_index: usize, new_pos: [
             corner,
             width,
             height,
+            ..
         } => match point_index {
             0 => {
                 let old_x1 = corner.x + *width;
@@ -699,7 +633,7 @@ fn update_element_point(elem: &mut SketchElement, point_index: usize, new_pos: [
             }
             _ => {}
         },
-        SketchElement::Polyline { points } | SketchElement::Spline { points } => {
+        SketchElement::Polyline { points, .. } | SketchElement::Spline { points, .. } => {
             if let Some(pt) = points.get_mut(point_index) {
                 pt.x = new_pos[0];
                 pt.y = new_pos[1];
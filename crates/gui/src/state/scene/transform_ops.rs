@@ -17,6 +17,7 @@ impl SceneState {
             for feature in &mut body.features {
                 let transform = match feature {
                     Feature::BasePrimitive { transform, .. } => transform,
+                    Feature::BaseMesh { transform, .. } => transform,
                     Feature::BaseExtrude {
                         sketch_transform, ..
                     } => sketch_transform,
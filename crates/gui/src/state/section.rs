@@ -0,0 +1,131 @@
+//! Section view state
+
+use glam::Vec3;
+use shared::SketchPlane;
+
+use crate::analysis::SectionPlane;
+
+/// Orientation of the cutting plane
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SectionOrientation {
+    /// Normal along +Z
+    #[default]
+    Xy,
+    /// Normal along +Y
+    Xz,
+    /// Normal along +X
+    Yz,
+    /// Normal given by azimuth and elevation
+    Custom,
+}
+
+/// State for the section view: a cutting plane clipping every body
+#[derive(Clone, Debug)]
+pub struct SectionState {
+    /// Whether section view is active
+    pub active: bool,
+    pub orientation: SectionOrientation,
+    /// Point on the plane (moved by the gizmo)
+    pub origin: Vec3,
+    /// Custom normal: angle from +X towards +Y, degrees
+    pub azimuth: f32,
+    /// Custom normal: angle above the XY plane, degrees
+    pub elevation: f32,
+    /// Cut away the other side of the plane
+    pub flipped: bool,
+}
+
+impl Default for SectionState {
+    fn default() -> Self {
+        Self {
+            active: false,
+            orientation: SectionOrientation::default(),
+            origin: Vec3::ZERO,
+            azimuth: 30.0,
+            elevation: 30.0,
+            flipped: false,
+        }
+    }
+}
+
+impl SectionState {
+    /// Enter section view, keeping the last plane
+    pub fn activate(&mut self) {
+        self.active = true;
+    }
+
+    /// Leave section view
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+
+    /// Check if section view is active
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Plane normal before flipping
+    pub fn axis(&self) -> Vec3 {
+        match self.orientation {
+            SectionOrientation::Xy => Vec3::Z,
+            SectionOrientation::Xz => Vec3::Y,
+            SectionOrientation::Yz => Vec3::X,
+            SectionOrientation::Custom => {
+                let (azimuth, elevation) = (self.azimuth.to_radians(), self.elevation.to_radians());
+                Vec3::new(elevation.cos() * azimuth.cos(), elevation.cos() * azimuth.sin(), elevation.sin())
+            }
+        }
+    }
+
+    /// Position of the plane along its axis
+    pub fn position(&self) -> f32 {
+        self.axis().dot(self.origin)
+    }
+
+    /// Move the plane along its axis to `position`
+    pub fn set_position(&mut self, position: f32) {
+        self.origin += self.axis() * (position - self.position());
+    }
+
+    /// Cutting plane; the side the normal points to is cut away
+    pub fn plane(&self) -> SectionPlane {
+        let axis = self.axis();
+        SectionPlane::new(self.origin, if self.flipped { -axis } else { axis })
+    }
+
+    /// Sketch plane and offset of the section, for principal orientations
+    pub fn sketch_plane(&self) -> Option<(SketchPlane, f64)> {
+        let (plane, offset) = match self.orientation {
+            SectionOrientation::Xy => (SketchPlane::Xy, self.origin.z),
+            SectionOrientation::Xz => (SketchPlane::Xz, self.origin.y),
+            SectionOrientation::Yz => (SketchPlane::Yz, self.origin.x),
+            SectionOrientation::Custom => return None,
+        };
+        Some((plane, offset as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_plane_follows_orientation() {
+        let mut section = SectionState { origin: Vec3::new(1.0, 2.0, 3.0), ..Default::default() };
+        assert_eq!(section.plane().normal, Vec3::Z);
+        assert_eq!(section.sketch_plane(), Some((SketchPlane::Xy, 3.0)));
+
+        section.orientation = SectionOrientation::Xz;
+        section.flipped = true;
+        assert_eq!(section.plane().normal, Vec3::NEG_Y);
+        section.set_position(-4.0);
+        assert_eq!(section.origin, Vec3::new(1.0, -4.0, 3.0));
+        assert_eq!(section.sketch_plane(), Some((SketchPlane::Xz, -4.0)));
+
+        section.orientation = SectionOrientation::Custom;
+        section.azimuth = 90.0;
+        section.elevation = 0.0;
+        assert!((section.axis() - Vec3::Y).length() < 1e-6);
+        assert_eq!(section.sketch_plane(), None);
+    }
+}
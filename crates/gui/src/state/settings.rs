@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::export::ModelUnit;
+
 /// Unit system for display
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Units {
@@ -47,6 +49,16 @@ impl Units {
     pub fn all() -> &'static [Units] {
         &[Units::Millimeters, Units::Centimeters, Units::Meters, Units::Inches]
     }
+
+    /// Matching 3MF model unit
+    pub fn model_unit(&self) -> ModelUnit {
+        match self {
+            Units::Millimeters => ModelUnit::Millimeter,
+            Units::Centimeters => ModelUnit::Centimeter,
+            Units::Meters => ModelUnit::Meter,
+            Units::Inches => ModelUnit::Inch,
+        }
+    }
 }
 
 /// Grid display settings
@@ -172,6 +184,9 @@ pub struct DimensionSettings {
     pub precision: usize,
     /// Show dimension units suffix
     pub show_units: bool,
+    /// Include sketch dimensions in DXF/SVG drawing export
+    #[serde(default)]
+    pub export_dimensions: bool,
 }
 
 impl Default for DimensionSettings {
@@ -180,6 +195,7 @@ impl Default for DimensionSettings {
             font_size: 14.0,
             precision: 2,
             show_units: false,
+            export_dimensions: false,
         }
     }
 }
@@ -279,8 +279,8 @@ impl SketchTool {
 /// Информация о dimension для окружности
 #[derive(Debug, Clone)]
 pub struct DimensionCircleInfo {
-    /// Индекс элемента окружности
-    pub circle_index: usize,
+    /// Id элемента окружности
+    pub circle_id: Option<shared::ElementId>,
     /// Тип dimension (Radius или Diameter)
     pub dimension_type: shared::DimensionType,
     /// Центр окружности
@@ -430,6 +430,7 @@ impl SketchState {
         let pts = &self.drawing_points;
         match self.tool {
             SketchTool::Line if pts.len() >= 2 => Some(SketchElement::Line {
+                id: None,
                 start: Point2D { x: pts[0][0], y: pts[0][1] },
                 end: Point2D { x: pts[1][0], y: pts[1][1] },
             }),
@@ -438,6 +439,7 @@ impl SketchState {
                 let dy = pts[1][1] - pts[0][1];
                 let radius = (dx * dx + dy * dy).sqrt();
                 Some(SketchElement::Circle {
+                    id: None,
                     center: Point2D { x: pts[0][0], y: pts[0][1] },
                     radius,
                 })
@@ -448,6 +450,7 @@ impl SketchState {
                 let width = (pts[1][0] - pts[0][0]).abs();
                 let height = (pts[1][1] - pts[0][1]).abs();
                 Some(SketchElement::Rectangle {
+                    id: None,
                     corner: Point2D { x: x0, y: y0 },
                     width,
                     height,
@@ -464,6 +467,7 @@ impl SketchState {
                 let dy2 = pts[2][1] - cy;
                 let end_angle = dy2.atan2(dx2);
                 Some(SketchElement::Arc {
+                    id: None,
                     center: Point2D { x: cx, y: cy },
                     radius,
                     start_angle,
@@ -516,7 +520,7 @@ impl SketchState {
                         value,
                         parameter_name: None,
                         dimension_line_pos: dim_line_pos,
-                        target_element: Some(circle_info.circle_index),
+                        target_element: circle_info.circle_id.clone(),
                         dimension_type: circle_info.dimension_type,
                     })
                 } else if pts.len() >= 3 {
@@ -552,8 +556,8 @@ impl SketchState {
             .map(|p| Point2D { x: p[0], y: p[1] })
             .collect();
         match self.tool {
-            SketchTool::Polyline => Some(SketchElement::Polyline { points }),
-            SketchTool::Spline => Some(SketchElement::Spline { points }),
+            SketchTool::Polyline => Some(SketchElement::Polyline { id: None, points }),
+            SketchTool::Spline => Some(SketchElement::Spline { id: None, points }),
             _ => None,
         }
     }
@@ -659,7 +663,7 @@ mod tests {
         s.add_point([3.0, 4.0]);
         let elem = s.try_finalize().unwrap();
         match elem {
-            SketchElement::Line { start, end } => {
+            SketchElement::Line { start, end, .. } => {
                 assert_eq!(start, Point2D { x: 0.0, y: 0.0 });
                 assert_eq!(end, Point2D { x: 3.0, y: 4.0 });
             }
@@ -675,7 +679,7 @@ mod tests {
         s.add_point([3.0, 4.0]);
         let elem = s.try_finalize().unwrap();
         match elem {
-            SketchElement::Circle { center, radius } => {
+            SketchElement::Circle { center, radius, .. } => {
                 assert_eq!(center, Point2D { x: 0.0, y: 0.0 });
                 assert!((radius - 5.0).abs() < 1e-10);
             }
@@ -692,7 +696,7 @@ mod tests {
         s.add_point([1.0, 2.0]);
         let elem = s.try_finalize().unwrap();
         match elem {
-            SketchElement::Rectangle { corner, width, height } => {
+            SketchElement::Rectangle { corner, width, height, .. } => {
                 assert_eq!(corner, Point2D { x: 1.0, y: 2.0 });
                 assert!((width - 2.0).abs() < 1e-10);
                 assert!((height - 2.0).abs() < 1e-10);
@@ -710,7 +714,7 @@ mod tests {
         s.add_point([0.0, 1.0]);  // end on arc
         let elem = s.try_finalize().unwrap();
         match elem {
-            SketchElement::Arc { center, radius, start_angle, end_angle } => {
+            SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
                 assert_eq!(center, Point2D { x: 0.0, y: 0.0 });
                 assert!((radius - 1.0).abs() < 1e-10);
                 assert!(start_angle.abs() < 1e-10); // atan2(0, 1) = 0
@@ -771,7 +775,7 @@ mod tests {
         s.add_point([2.0, 0.0]);
         let elem = s.try_finalize_multi().unwrap();
         match elem {
-            SketchElement::Polyline { points } => {
+            SketchElement::Polyline { points, .. } => {
                 assert_eq!(points.len(), 3);
             }
             _ => panic!("Expected Polyline"),
@@ -786,7 +790,7 @@ mod tests {
         s.add_point([1.0, 2.0]);
         let elem = s.try_finalize_multi().unwrap();
         match elem {
-            SketchElement::Spline { points } => {
+            SketchElement::Spline { points, .. } => {
                 assert_eq!(points.len(), 2);
             }
             _ => panic!("Expected Spline"),
@@ -0,0 +1,116 @@
+//! DXF import dialog UI rendering
+//!
+//! Shows the plane, units and construction layers for an imported drawing.
+//! Data structures are in state/dxf_import.rs.
+
+use shared::SketchPlane;
+
+use crate::i18n::t;
+use crate::state::DxfImportDialog;
+
+/// Drawing units: label and millimetres per unit
+const UNITS: [(&str, f64); 4] = [("mm", 1.0), ("cm", 10.0), ("m", 1000.0), ("inch", 25.4)];
+
+/// Extension trait for DxfImportDialog to add UI rendering
+pub trait DxfImportDialogUi {
+    /// Show the dialog UI, returns Some(true) if OK clicked, Some(false) if cancelled
+    fn show(&mut self, ctx: &egui::Context) -> Option<bool>;
+}
+
+impl DxfImportDialogUi for DxfImportDialog {
+    fn show(&mut self, ctx: &egui::Context) -> Option<bool> {
+        if !self.open {
+            return None;
+        }
+
+        let mut result = None;
+        egui::Window::new(t("dialog.dxf_title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.set_min_width(280.0);
+                ui.label(self.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default());
+                ui.add_space(4.0);
+
+                egui::Grid::new("dxf_import_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(t("prop.plane"));
+                        ui.horizontal(|ui| {
+                            let planes = [(SketchPlane::Xy, "XY"), (SketchPlane::Xz, "XZ"), (SketchPlane::Yz, "YZ")];
+                            for (plane, name) in planes {
+                                if ui.selectable_label(self.plane == plane, name).clicked() {
+                                    self.plane = plane;
+                                }
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(t("prop.offset"));
+                        ui.add(egui::DragValue::new(&mut self.offset).speed(0.1).suffix(" mm"));
+                        ui.end_row();
+
+                        ui.label(t("dialog.dxf_units"));
+                        let auto = format!("{} ({} mm)", t("dialog.dxf_units_auto"), self.detected_scale);
+                        let selected = match self.scale {
+                            None => auto.clone(),
+                            Some(scale) => UNITS
+                                .iter()
+                                .find(|(_, s)| *s == scale)
+                                .map(|(name, _)| name.to_string())
+                                .unwrap_or_default(),
+                        };
+                        egui::ComboBox::from_id_salt("dxf_units_combo")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut self.scale, None, auto);
+                                for (name, scale) in UNITS {
+                                    ui.selectable_value(&mut self.scale, Some(scale), name);
+                                }
+                            });
+                        ui.end_row();
+                    });
+
+                ui.add_space(8.0);
+                ui.label(t("dialog.dxf_construction_layers"));
+                egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    for (layer, construction) in &mut self.layers {
+                        ui.checkbox(construction, layer.as_str());
+                    }
+                });
+
+                if !self.skipped.is_empty() {
+                    ui.add_space(4.0);
+                    ui.label(
+                        egui::RichText::new(format!("{} {}", t("dialog.dxf_skipped"), self.skipped.join(", ")))
+                            .small()
+                            .color(egui::Color32::from_rgb(140, 140, 150))
+                    );
+                }
+                for warning in &self.warnings {
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new(warning).small().color(egui::Color32::from_rgb(220, 160, 60)));
+                }
+
+                ui.add_space(12.0);
+                ui.separator();
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button(t("dialog.cancel")).clicked() {
+                            result = Some(false);
+                            self.close();
+                        }
+                        if ui.button(t("dialog.ok")).clicked() {
+                            result = Some(true);
+                        }
+                    });
+                });
+            });
+
+        result
+    }
+}
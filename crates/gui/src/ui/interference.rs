@@ -0,0 +1,65 @@
+//! Interference window
+//!
+//! Lists clashing body pairs with the volume of their common solid. While the
+//! window is open the viewport draws those solids over translucent bodies;
+//! clicking a row selects both bodies of the pair.
+
+use shared::InterferenceReport;
+
+use crate::i18n::t;
+use crate::state::AppState;
+
+pub fn show(ctx: &egui::Context, state: &mut AppState, report: &InterferenceReport) {
+    let mut open = state.panels.interference;
+    egui::Window::new(t("interference.title"))
+        .open(&mut open)
+        .resizable(false)
+        .default_width(340.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.weak(format!(
+                    "{}: {}, {}: {}",
+                    t("interference.bodies"),
+                    report.body_count,
+                    t("interference.pairs"),
+                    report.checked_pairs
+                ));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button(t("interference.copy")).on_hover_text(t("interference.copy_hint")).clicked() {
+                        ui.ctx().copy_text(serde_json::to_string_pretty(report).unwrap_or_default());
+                    }
+                });
+            });
+            ui.separator();
+
+            if report.clashes.is_empty() {
+                ui.label(t("interference.none"));
+                return;
+            }
+
+            egui::Grid::new("interference_clashes")
+                .num_columns(2)
+                .spacing([12.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for clash in &report.clashes {
+                        let selected =
+                            state.selection.is_selected(&clash.body_a) && state.selection.is_selected(&clash.body_b);
+                        let pair = format!("{} ↔ {}", clash.name_a, clash.name_b);
+                        let response = ui.selectable_label(selected, pair).on_hover_text(t("interference.select_hint"));
+                        if response.clicked() {
+                            state.selection.select(clash.body_a.clone());
+                            state.selection.toggle(clash.body_b.clone());
+                        }
+                        ui.monospace(format!("{:.3} mm³", clash.volume));
+                        ui.end_row();
+                    }
+                });
+
+            let total: f64 = report.clashes.iter().map(|c| c.volume).sum();
+            ui.separator();
+            ui.label(format!("{}: {:.3} mm³", t("interference.total"), total));
+            ui.weak(t("interference.highlight_hint"));
+        });
+    state.panels.interference = open;
+}
@@ -0,0 +1,119 @@
+//! Mass properties window
+//!
+//! Shows mass, centre of mass, inertia tensors and principal axes of the
+//! selected body, or of all built bodies when nothing is selected. Values are
+//! computed from the cached meshes with the density of each body's material.
+
+use egui::Ui;
+use shared::{MassProperties, MassReport};
+
+use crate::i18n::t;
+use crate::state::AppState;
+
+pub fn show(ctx: &egui::Context, state: &mut AppState, report: &MassReport) {
+    let mut open = state.panels.mass_properties;
+    egui::Window::new(t("mass.title"))
+        .open(&mut open)
+        .resizable(false)
+        .default_width(340.0)
+        .show(ctx, |ui| {
+            if report.bodies.is_empty() {
+                ui.weak(t("mass.empty"));
+                return;
+            }
+
+            let selected = state.selection.primary().and_then(|id| report.bodies.iter().find(|b| &b.id == id));
+            let (title, material, props) = match selected {
+                Some(body) => (body.name.clone(), body.material.clone(), &body.properties),
+                None => (format!("{} ({})", t("mass.total"), report.bodies.len()), None, &report.total),
+            };
+
+            ui.horizontal(|ui| {
+                ui.strong(title);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button(t("mass.copy")).on_hover_text(t("mass.copy_hint")).clicked() {
+                        ui.ctx().copy_text(serde_json::to_string_pretty(props).unwrap_or_default());
+                    }
+                });
+            });
+            if selected.is_some() {
+                let material = material.unwrap_or_else(|| t("mass.default_material").to_string());
+                ui.weak(format!("{}: {}", t("prop.material"), material));
+            }
+            ui.separator();
+            show_properties(ui, props);
+
+            if !report.errors.is_empty() {
+                ui.separator();
+                ui.colored_label(egui::Color32::from_rgb(220, 160, 60), t("mass.incomplete"));
+            }
+        });
+    state.panels.mass_properties = open;
+}
+
+fn show_properties(ui: &mut Ui, props: &MassProperties) {
+    egui::Grid::new("mass_summary")
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            let rows = [
+                (t("mass.mass"), format!("{} g", num(props.mass))),
+                (t("mass.volume"), format!("{} mm³", num(props.volume))),
+                (t("mass.area"), format!("{} mm²", num(props.surface_area))),
+                (t("prop.density"), format!("{} {}", num(props.density), t("prop.density_unit"))),
+                (t("mass.center"), vector(props.center_of_mass)),
+            ];
+            for (label, value) in rows {
+                ui.label(label);
+                ui.monospace(value);
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(6.0);
+    ui.label(format!("{}, g·mm²", t("mass.inertia_centroid")));
+    show_matrix(ui, "mass_inertia_centroid", &props.inertia_centroid);
+    ui.label(format!("{}, g·mm²", t("mass.inertia_origin")));
+    show_matrix(ui, "mass_inertia_origin", &props.inertia_origin);
+
+    ui.add_space(6.0);
+    ui.label(t("mass.principal"));
+    egui::Grid::new("mass_principal")
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            for (moment, axis) in props.principal_moments.iter().zip(&props.principal_axes) {
+                ui.monospace(num(*moment));
+                ui.monospace(vector(*axis));
+                ui.end_row();
+            }
+        });
+}
+
+fn show_matrix(ui: &mut Ui, id: &str, matrix: &[[f64; 3]; 3]) {
+    egui::Grid::new(id)
+        .num_columns(3)
+        .spacing([12.0, 2.0])
+        .show(ui, |ui| {
+            for row in matrix {
+                for value in row {
+                    ui.monospace(num(*value));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+/// Fixed notation for ordinary magnitudes, scientific for very large or small
+fn num(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude != 0.0 && !(1e-3..1e7).contains(&magnitude) {
+        format!("{:.4e}", value)
+    } else {
+        format!("{:.4}", value)
+    }
+}
+
+fn vector(v: [f64; 3]) -> String {
+    format!("({}, {}, {})", num(v[0]), num(v[1]), num(v[2]))
+}
@@ -0,0 +1,84 @@
+//! Measure tool panel UI
+
+use egui::Ui;
+
+use crate::analysis::Measurement;
+use crate::i18n::t;
+use crate::state::{AppSettings, AppState};
+
+/// Show measure panel when the measure tool is active
+pub fn show(ui: &mut Ui, state: &mut AppState) {
+    if !state.measure.is_active() {
+        return;
+    }
+
+    let measurements = state.measure.measurements();
+    ui.horizontal(|ui| {
+        ui.label(t("measure.title"));
+        ui.separator();
+
+        if measurements.is_empty() {
+            ui.weak(match state.measure.picks.len() {
+                0 => t("measure.pick_first"),
+                _ => t("measure.pick_second"),
+            });
+        }
+        for measurement in &measurements {
+            ui.monospace(measurement_line(measurement, &state.settings));
+            ui.separator();
+        }
+
+        if ui
+            .add_enabled(!measurements.is_empty(), egui::Button::new(t("measure.copy")))
+            .on_hover_text(t("measure.copy_hint"))
+            .clicked()
+        {
+            let text: Vec<String> = measurements.iter().map(|m| measurement_line(m, &state.settings)).collect();
+            ui.ctx().copy_text(text.join("\n"));
+        }
+        if ui.add_enabled(!state.measure.picks.is_empty(), egui::Button::new(t("measure.clear"))).clicked() {
+            state.measure.picks.clear();
+        }
+        if ui.button(t("measure.close")).clicked() {
+            state.measure.deactivate();
+        }
+    });
+
+    // Hint
+    ui.weak(t("measure.hint"));
+}
+
+/// Short value shown next to the measurement in the viewport
+pub fn measurement_label(measurement: &Measurement, settings: &AppSettings) -> String {
+    let prec = settings.dimensions.precision;
+    let unit = settings.units.abbrev();
+    match *measurement {
+        Measurement::Distance { value, .. } => format!("{:.prec$} {}", value, unit),
+        Measurement::Angle { degrees, .. } => format!("{:.prec$}°", degrees),
+        Measurement::Radius { value, .. } => format!("R {:.prec$} {}", value, unit),
+    }
+}
+
+/// Full description used in the panel and copied to the clipboard
+pub fn measurement_line(measurement: &Measurement, settings: &AppSettings) -> String {
+    let prec = settings.dimensions.precision;
+    let unit = settings.units.abbrev();
+    let label = measurement_label(measurement, settings);
+    match *measurement {
+        Measurement::Distance { from, to, .. } => {
+            let d = to - from;
+            format!(
+                "{} {} (ΔX {:.prec$}, ΔY {:.prec$}, ΔZ {:.prec$})",
+                t("measure.distance"),
+                label,
+                d.x.abs(),
+                d.y.abs(),
+                d.z.abs()
+            )
+        }
+        Measurement::Angle { .. } => format!("{} {}", t("measure.angle"), label),
+        Measurement::Radius { value, .. } => {
+            format!("{} {} (Ø {:.prec$} {})", t("measure.radius"), label, value * 2.0, unit)
+        }
+    }
+}
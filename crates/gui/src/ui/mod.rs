@@ -1,10 +1,16 @@
 pub mod chamfer3d_panel;
 pub mod chat_panel;
+pub mod dxf_import;
 pub mod fillet3d_panel;
+pub mod interference;
+pub mod mass_properties;
+pub mod measure_panel;
 pub mod operation_dialog;
 pub mod parameters;
+pub mod printability;
 pub mod properties;
 pub mod scene_tree;
+pub mod section_panel;
 pub mod sketch_toolbar;
 pub mod status_bar;
 pub mod toolbar;
@@ -1,17 +1,69 @@
 //! Parameters panel for parametric modeling
 //!
-//! Allows users to create, edit, and manage parameters with formulas
+//! Allows users to create, edit, and manage parameters with formulas.
+//! A design table (CSV/JSON) switches all parameters between named configurations.
+//! Global (scene-level) parameters are shown above the selected body's ones,
+//! followed by the body's feature bindings (`extrude_1.height = thickness*2`).
+
+use std::collections::HashMap;
 
 use egui::{TextEdit, Ui};
-use shared::{Parameter, ParameterValue};
+use shared::{parameters, DesignTable, Parameter, ParameterValue, SceneDescriptionV2};
 
 use crate::i18n::t;
+use crate::state::scene::feature_display_name;
 use crate::state::AppState;
 
+/// Which parameter set is being edited
+#[derive(Clone, Copy)]
+enum ParamTarget {
+    Global,
+    Body(usize),
+}
+
+impl ParamTarget {
+    fn parameters<'a>(&self, scene: &'a SceneDescriptionV2) -> &'a HashMap<String, Parameter> {
+        match self {
+            ParamTarget::Global => &scene.parameters,
+            ParamTarget::Body(idx) => &scene.bodies[*idx].parameters,
+        }
+    }
+
+    fn parameters_mut<'a>(&self, scene: &'a mut SceneDescriptionV2) -> &'a mut HashMap<String, Parameter> {
+        match self {
+            ParamTarget::Global => &mut scene.parameters,
+            ParamTarget::Body(idx) => &mut scene.bodies[*idx].parameters,
+        }
+    }
+
+    fn body_id<'a>(&self, scene: &'a SceneDescriptionV2) -> Option<&'a str> {
+        match self {
+            ParamTarget::Global => None,
+            ParamTarget::Body(idx) => Some(scene.bodies[*idx].id.as_str()),
+        }
+    }
+}
+
 pub fn show(ui: &mut Ui, state: &mut AppState) {
     ui.heading(t("params.title"));
     ui.separator();
 
+    egui::CollapsingHeader::new(t("params.design_table"))
+        .id_salt("params_design_table")
+        .default_open(state.scene.scene.design_table.is_some())
+        .show(ui, |ui| {
+            show_design_table(ui, state);
+        });
+
+    egui::CollapsingHeader::new(t("params.global"))
+        .id_salt("params_global")
+        .default_open(true)
+        .show(ui, |ui| {
+            show_parameters_panel(ui, state, ParamTarget::Global);
+        });
+
+    ui.separator();
+
     let selected_id = match state.selection.primary() {
         Some(id) => id.clone(),
         None => {
@@ -34,22 +86,33 @@ pub fn show(ui: &mut Ui, state: &mut AppState) {
     };
 
     // Display parameters panel
-    show_parameters_panel(ui, state, body_idx);
+    ui.strong(&state.scene.scene.bodies[body_idx].name);
+    show_parameters_panel(ui, state, ParamTarget::Body(body_idx));
+
+    ui.separator();
+    egui::CollapsingHeader::new(t("params.bindings"))
+        .id_salt("params_bindings")
+        .default_open(true)
+        .show(ui, |ui| {
+            show_bindings_panel(ui, state, body_idx);
+        });
 }
 
-fn show_parameters_panel(ui: &mut Ui, state: &mut AppState, body_idx: usize) {
+fn show_parameters_panel(ui: &mut Ui, state: &mut AppState, target: ParamTarget) {
     // Button to add new parameter
     if ui.button(format!("➕ {}", t("params.add_parameter"))).clicked() {
-        add_new_parameter(state, body_idx);
+        add_new_parameter(state, target);
     }
 
     ui.separator();
 
-    // Get body (need mutable access)
-    let body = &mut state.scene.scene.bodies[body_idx];
+    let scene = &state.scene.scene;
+    let parameters = target.parameters(scene);
+    let body_id = target.body_id(scene);
 
     // Collect parameter names to avoid borrow checker issues
-    let param_names: Vec<String> = body.parameters.keys().cloned().collect();
+    let mut param_names: Vec<String> = parameters.keys().cloned().collect();
+    param_names.sort();
 
     if param_names.is_empty() {
         ui.add_space(10.0);
@@ -65,129 +128,144 @@ fn show_parameters_panel(ui: &mut Ui, state: &mut AppState, body_idx: usize) {
     let mut params_to_rename: Vec<(String, String)> = Vec::new(); // (old_name, new_name)
 
     for param_name in &param_names {
-        if let Some(param) = body.parameters.get(param_name) {
-            ui.group(|ui| {
-                ui.horizontal(|ui| {
-                    ui.label("📊");
-
-                    // Editable parameter name
-                    let mut new_name = param_name.clone();
-                    let name_response = ui.add(
-                        TextEdit::singleline(&mut new_name)
-                            .desired_width(120.0)
-                            .font(egui::TextStyle::Body)
-                    );
-
-                    // Check if name changed and is valid
-                    if name_response.lost_focus() && new_name != *param_name {
-                        // Validate: not empty, no duplicates
-                        let is_valid = !new_name.is_empty()
-                            && !new_name.contains(' ')
-                            && !body.parameters.contains_key(&new_name);
-                        if is_valid {
-                            params_to_rename.push((param_name.clone(), new_name));
+        if let Some(param) = parameters.get(param_name) {
+            ui.push_id((body_id, param_name), |ui| {
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("📊");
+
+                        // Editable parameter name
+                        let mut new_name = param_name.clone();
+                        let name_response = ui.add(
+                            TextEdit::singleline(&mut new_name)
+                                .desired_width(120.0)
+                                .font(egui::TextStyle::Body)
+                        );
+
+                        // Check if name changed and is valid
+                        if name_response.lost_focus() && new_name != *param_name {
+                            // Validate: not empty, no duplicates, no dots (reserved for references)
+                            let is_valid = !new_name.is_empty()
+                                && !new_name.contains(' ')
+                                && !new_name.contains('.')
+                                && !parameters.contains_key(&new_name);
+                            if is_valid {
+                                params_to_rename.push((param_name.clone(), new_name));
+                            }
                         }
-                    }
 
-                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        // Delete button
-                        if ui.small_button("🗑").clicked() {
-                            params_to_remove.push(param_name.clone());
-                        }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            // Delete button
+                            if ui.small_button("🗑").clicked() {
+                                params_to_remove.push(param_name.clone());
+                            }
+                        });
                     });
-                });
 
-                ui.separator();
+                    ui.separator();
 
-                // Show parameter details
-                let mut updated_param = param.clone();
-                let mut changed = false;
+                    // Show parameter details
+                    let mut updated_param = param.clone();
+                    let mut changed = false;
 
-                let is_formula = matches!(&param.value, ParameterValue::Formula { .. });
+                    let is_number = matches!(&param.value, ParameterValue::Number { .. });
 
-                // Always show the computed value first
-                ui.horizontal(|ui| {
-                    ui.label(t("params.value"));
-                    match body.evaluate_parameter(param_name) {
-                        Ok(val) => {
-                            if is_formula {
-                                // Read-only for formula
-                                ui.strong(format!("{:.6}", val));
-                            } else {
-                                // Editable for number
-                                let mut new_value = val;
-                                let response = ui.add(
-                                    egui::DragValue::new(&mut new_value)
-                                        .speed(0.1)
-                                        .range(f64::NEG_INFINITY..=f64::INFINITY)
-                                );
-                                if response.changed() {
-                                    updated_param.value = ParameterValue::Number { value: new_value };
-                                    changed = true;
+                    // Always show the computed value first
+                    ui.horizontal(|ui| {
+                        ui.label(t("params.value"));
+                        match scene.evaluate_parameter(body_id, param_name) {
+                            Ok(val) => {
+                                if is_number {
+                                    // Editable for number
+                                    let mut new_value = val;
+                                    let response = ui.add(
+                                        egui::DragValue::new(&mut new_value)
+                                            .speed(0.1)
+                                            .range(f64::NEG_INFINITY..=f64::INFINITY)
+                                    );
+                                    if response.changed() {
+                                        updated_param.value = ParameterValue::Number { value: new_value };
+                                        changed = true;
+                                    }
+                                } else {
+                                    // Read-only for formula and reference
+                                    ui.strong(format!("{:.6}", val));
                                 }
                             }
+                            Err(e) => {
+                                ui.colored_label(egui::Color32::RED, format!("Err: {}", e));
+                            }
                         }
-                        Err(e) => {
-                            ui.colored_label(egui::Color32::RED, format!("Err: {}", e));
-                        }
-                    }
-                });
+                    });
 
-                // Formula toggle and input
-                ui.horizontal(|ui| {
-                    let mut use_formula = is_formula;
-                    if ui.checkbox(&mut use_formula, t("params.formula")).changed() {
-                        if use_formula {
-                            // Switch to Formula mode
-                            if let ParameterValue::Number { value } = &param.value {
-                                updated_param.value = ParameterValue::Formula {
-                                    expression: format!("{}", value)
-                                };
+                    // Formula toggle and input
+                    ui.horizontal(|ui| {
+                        let mut use_formula = !is_number;
+                        if ui.checkbox(&mut use_formula, t("params.formula")).changed() {
+                            if use_formula {
+                                // Switch to Formula mode
+                                if let ParameterValue::Number { value } = &param.value {
+                                    updated_param.value = ParameterValue::Formula {
+                                        expression: format!("{}", value)
+                                    };
+                                    changed = true;
+                                }
+                            } else {
+                                // Switch to Number mode
+                                let evaluated = scene.evaluate_parameter(body_id, param_name).unwrap_or(0.0);
+                                updated_param.value = ParameterValue::Number { value: evaluated };
                                 changed = true;
                             }
-                        } else {
-                            // Switch to Number mode
-                            let evaluated = body.evaluate_parameter(param_name).unwrap_or(0.0);
-                            updated_param.value = ParameterValue::Number { value: evaluated };
-                            changed = true;
                         }
-                    }
 
-                    // Show formula input if in formula mode
-                    if let ParameterValue::Formula { expression } = &param.value {
-                        let mut expr_str = expression.clone();
-                        if ui.add(TextEdit::singleline(&mut expr_str).desired_width(150.0)).changed() {
-                            updated_param.value = ParameterValue::Formula { expression: expr_str };
-                            changed = true;
+                        // Show formula input if in formula mode
+                        if let ParameterValue::Formula { expression } = &param.value {
+                            let mut expr_str = expression.clone();
+                            if ui.add(TextEdit::singleline(&mut expr_str).desired_width(150.0)).changed() {
+                                updated_param.value = ParameterValue::Formula { expression: expr_str };
+                                changed = true;
+                            }
                         }
-                    }
-                });
+                    });
 
-                if let ParameterValue::Reference { .. } = &param.value {
-                    ui.label(t("params.reference_not_supported"));
-                }
+                    if let ParameterValue::Formula { .. } = &param.value {
+                        ui.weak(t("params.formula_hint"));
+                        show_formula_reference(ui);
+                    }
 
-                // Unit
-                ui.horizontal(|ui| {
-                    ui.label(t("params.unit"));
-                    let mut unit_str = param.unit.clone().unwrap_or_default();
-                    if ui.add(TextEdit::singleline(&mut unit_str).desired_width(60.0)).changed() {
-                        updated_param.unit = if unit_str.is_empty() { None } else { Some(unit_str) };
-                        changed = true;
+                    if let ParameterValue::Reference { reference } = &param.value {
+                        ui.horizontal(|ui| {
+                            ui.label(t("params.reference"));
+                            ui.monospace(reference.to_string());
+                        });
                     }
-                });
 
-                // Description
-                if let Some(desc) = &param.description {
+                    // Unit
                     ui.horizontal(|ui| {
-                        ui.label(t("params.description"));
-                        ui.weak(desc);
+                        ui.label(t("params.unit"));
+                        let mut unit_str = param.unit.clone().unwrap_or_default();
+                        if ui.add(TextEdit::singleline(&mut unit_str).desired_width(60.0)).changed() {
+                            updated_param.unit = if unit_str.is_empty() { None } else { Some(unit_str.clone()) };
+                            changed = true;
+                        }
+                        // Значения хранятся в единицах модели (мм, градусы)
+                        if !unit_str.is_empty() && parameters::unit_factor(&unit_str).is_none() {
+                            ui.label("⚠").on_hover_text(t("params.unknown_unit"));
+                        }
                     });
-                }
 
-                if changed {
-                    params_to_update.push((param_name.clone(), updated_param));
-                }
+                    // Description
+                    if let Some(desc) = &param.description {
+                        ui.horizontal(|ui| {
+                            ui.label(t("params.description"));
+                            ui.weak(desc);
+                        });
+                    }
+
+                    if changed {
+                        params_to_update.push((param_name.clone(), updated_param));
+                    }
+                });
             });
 
             ui.add_space(5.0);
@@ -195,50 +273,267 @@ fn show_parameters_panel(ui: &mut Ui, state: &mut AppState, body_idx: usize) {
     }
 
     // Apply removals, renames, and updates
-    let needs_undo = !params_to_remove.is_empty() || !params_to_update.is_empty() || !params_to_rename.is_empty();
+    let needs_update = !params_to_remove.is_empty() || !params_to_update.is_empty() || !params_to_rename.is_empty();
+    if !needs_update {
+        return;
+    }
 
-    if !params_to_remove.is_empty() {
-        let body = &mut state.scene.scene.bodies[body_idx];
-        for param_name in params_to_remove {
-            body.parameters.remove(&param_name);
-        }
+    let parameters = target.parameters_mut(&mut state.scene.scene);
+    for param_name in params_to_remove {
+        parameters.remove(&param_name);
     }
 
     // Apply renames
-    if !params_to_rename.is_empty() {
-        let body = &mut state.scene.scene.bodies[body_idx];
-        for (old_name, new_name) in params_to_rename {
-            if let Some(mut param) = body.parameters.remove(&old_name) {
-                param.name = new_name.clone();
-                body.parameters.insert(new_name, param);
-            }
+    for (old_name, new_name) in params_to_rename {
+        if let Some(mut param) = parameters.remove(&old_name) {
+            param.name = new_name.clone();
+            parameters.insert(new_name, param);
         }
-        // Обновить размеры, привязанные к параметрам
-        body.update_dimensions_from_parameters();
     }
 
-    if !params_to_update.is_empty() {
-        let body = &mut state.scene.scene.bodies[body_idx];
-        for (param_name, updated_param) in params_to_update {
-            body.parameters.insert(param_name, updated_param);
+    for (param_name, updated_param) in params_to_update {
+        parameters.insert(param_name, updated_param);
+    }
+
+    // Параметр может использоваться и в других телах
+    reevaluate_scene(state);
+}
+
+/// Active configuration, table import and batch export
+fn show_design_table(ui: &mut Ui, state: &mut AppState) {
+    let mut selected = None;
+    let mut remove = false;
+
+    match &state.scene.scene.design_table {
+        Some(table) => {
+            ui.horizontal(|ui| {
+                ui.label(t("params.configuration"));
+                let active = table.active.clone().unwrap_or_default();
+                egui::ComboBox::from_id_salt("design_table_active")
+                    .selected_text(if active.is_empty() { "—" } else { active.as_str() })
+                    .show_ui(ui, |ui| {
+                        for config in &table.configurations {
+                            if ui.selectable_label(config.name == active, &config.name).clicked() {
+                                selected = Some(config.name.clone());
+                            }
+                        }
+                    });
+                if ui.small_button("🗑").on_hover_text(t("params.remove_table")).clicked() {
+                    remove = true;
+                }
+            });
+            ui.weak(format!("{}: {}", t("params.configurations"), table.configurations.len()));
+        }
+        None => {
+            ui.weak(t("params.no_design_table"));
         }
-        // Обновить размеры, привязанные к параметрам
-        body.update_dimensions_from_parameters();
     }
 
-    if needs_undo {
+    ui.horizontal(|ui| {
+        if ui.button(t("params.import_table")).clicked() {
+            import_design_table(state);
+        }
+        let has_table = state.scene.scene.design_table.is_some();
+        if ui.add_enabled(has_table, egui::Button::new(t("params.export_configurations"))).clicked() {
+            export_configurations(state);
+        }
+    });
+
+    if let Some(name) = selected {
+        if let Err(e) = state.scene.apply_configuration(&name) {
+            tracing::error!("{e}");
+        }
+    }
+    if remove {
+        state.scene.scene.design_table = None;
         state.scene.notify_mutated();
     }
 }
 
-fn add_new_parameter(state: &mut AppState, body_idx: usize) {
-    let body = &mut state.scene.scene.bodies[body_idx];
+fn import_design_table(state: &mut AppState) {
+    let Some(path) = rfd::FileDialog::new()
+        .set_title(t("params.import_table"))
+        .add_filter("CSV / JSON", &["csv", "json"])
+        .pick_file()
+    else {
+        return;
+    };
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) => {
+            tracing::error!("Failed to read design table: {e}");
+            return;
+        }
+    };
+    let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let table = if is_json { DesignTable::from_json(&text) } else { DesignTable::from_csv(&text) };
+    match table {
+        Ok(table) => {
+            tracing::info!("Loaded {} configurations from {}", table.configurations.len(), path.display());
+            state.scene.scene.design_table = Some(table);
+            state.scene.notify_mutated();
+        }
+        Err(e) => tracing::error!("{e}"),
+    }
+}
+
+/// Build every configuration into `<folder>/<name>.glb`
+fn export_configurations(state: &AppState) {
+    let Some(dir) = rfd::FileDialog::new()
+        .set_title(t("params.export_configurations"))
+        .pick_folder()
+    else {
+        return;
+    };
+
+    let built = match cad_core::configurations::build_configurations_glb(&state.scene.scene) {
+        Ok(built) => built,
+        Err(e) => {
+            tracing::error!("{e}");
+            return;
+        }
+    };
+    let names = crate::export::unique_file_names(built.iter().map(|config| config.name.as_str()), "glb");
+    for (config, file) in built.into_iter().zip(names) {
+        for (id, error) in &config.errors {
+            tracing::warn!("Configuration '{}', body '{}': {}", config.name, id, error);
+        }
+        if config.glb.is_empty() {
+            tracing::error!("Configuration '{}' produced no geometry", config.name);
+            continue;
+        }
+        let path = dir.join(file);
+        if let Err(e) = std::fs::write(&path, &config.glb) {
+            tracing::error!("Failed to write {}: {e}", path.display());
+        }
+    }
+}
+
+/// Feature properties bound to formulas in the selected body
+fn show_bindings_panel(ui: &mut Ui, state: &mut AppState, body_idx: usize) {
+    let scene = &state.scene.scene;
+    let body = &scene.bodies[body_idx];
+
+    let mut bindings: Vec<(&String, &String)> = body.bindings.iter().collect();
+    bindings.sort();
+
+    let mut to_remove = Vec::new();
+    let mut to_update: Vec<(String, String)> = Vec::new();
+
+    if bindings.is_empty() {
+        ui.weak(t("params.no_bindings"));
+    }
+
+    for (binding, expression) in bindings {
+        ui.push_id(binding, |ui| {
+            ui.horizontal(|ui| {
+                ui.monospace(binding);
+                ui.label("=");
+                let mut expr_str = expression.clone();
+                if ui.add(TextEdit::singleline(&mut expr_str).desired_width(120.0)).changed() {
+                    to_update.push((binding.clone(), expr_str));
+                }
+                match scene.evaluate_binding(&body.id, binding) {
+                    Ok(val) => {
+                        ui.strong(format!("{:.4}", val));
+                    }
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("Err: {}", e));
+                    }
+                }
+                if ui.small_button("🗑").clicked() {
+                    to_remove.push(binding.clone());
+                }
+            });
+        });
+    }
+
+    // Новая привязка: фича + свойство; формула стартует с текущего значения
+    let draft_id = ui.id().with(("binding_draft", &body.id));
+    let (mut feature_id, mut property) = ui
+        .data(|d| d.get_temp::<(String, String)>(draft_id))
+        .unwrap_or_default();
+
+    let bindable: Vec<_> = body
+        .features
+        .iter()
+        .filter(|f| !f.numeric_property_names().is_empty())
+        .collect();
+    let feature = bindable.iter().find(|f| *f.id() == feature_id).or(bindable.first());
+    let mut new_binding = None;
+
+    if let Some(feature) = feature {
+        feature_id = feature.id().clone();
+        let properties = feature.numeric_property_names();
+        if !properties.contains(&property.as_str()) {
+            property = properties[0].to_string();
+        }
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("binding_feature")
+                .selected_text(feature_display_name(feature))
+                .show_ui(ui, |ui| {
+                    for f in &bindable {
+                        ui.selectable_value(&mut feature_id, f.id().clone(), feature_display_name(f));
+                    }
+                });
+            egui::ComboBox::from_id_salt("binding_property")
+                .selected_text(&property)
+                .show_ui(ui, |ui| {
+                    for name in &properties {
+                        ui.selectable_value(&mut property, name.to_string(), *name);
+                    }
+                });
+            let key = format!("{}.{}", feature_id, property);
+            let enabled = !body.bindings.contains_key(&key);
+            if ui.add_enabled(enabled, egui::Button::new("➕")).on_hover_text(t("params.add_binding")).clicked() {
+                let value = feature.numeric_property(&property).unwrap_or(0.0);
+                new_binding = Some((key, format!("{}", value)));
+            }
+        });
+    }
+
+    ui.data_mut(|d| d.insert_temp(draft_id, (feature_id, property)));
+
+    if to_remove.is_empty() && to_update.is_empty() && new_binding.is_none() {
+        return;
+    }
+
+    let bindings = &mut state.scene.scene.bodies[body_idx].bindings;
+    for binding in to_remove {
+        bindings.remove(&binding);
+    }
+    for (binding, expression) in to_update.into_iter().chain(new_binding) {
+        bindings.insert(binding, expression);
+    }
+    reevaluate_scene(state);
+}
+
+/// Пересчитать всё, что зависит от параметров: размеры эскизов
+/// (с перерешением) и привязанные свойства фич
+fn reevaluate_scene(state: &mut AppState) {
+    crate::sketch::constraints::solve_scene_sketches(&mut state.scene.scene);
+    // Ошибки привязок показываются в панели и в ошибках построения
+    state.scene.scene.apply_feature_bindings();
+    state.scene.notify_mutated();
+}
+
+/// Built-in functions and unit suffixes accepted in formulas
+fn show_formula_reference(ui: &mut Ui) {
+    let units: Vec<&str> = parameters::UNITS.iter().map(|(name, _)| *name).collect();
+    ui.weak(format!("{} min, max, floor, round, ceil, {}", t("params.functions"), parameters::FUNCTIONS.join(", ")));
+    ui.weak(format!("{} 10mm, 1in, 30deg ({})", t("params.units"), units.join(", ")));
+}
+
+fn add_new_parameter(state: &mut AppState, target: ParamTarget) {
+    let parameters = target.parameters_mut(&mut state.scene.scene);
 
     // Generate unique name
     let mut counter = 1;
     let name = loop {
         let candidate = format!("param{}", counter);
-        if !body.parameters.contains_key(&candidate) {
+        if !parameters.contains_key(&candidate) {
             break candidate;
         }
         counter += 1;
@@ -252,8 +547,6 @@ fn add_new_parameter(state: &mut AppState, body_idx: usize) {
         description: None,
     };
 
-    body.parameters.insert(name, new_param);
-    // Обновить размеры, привязанные к параметрам
-    body.update_dimensions_from_parameters();
-    state.scene.notify_mutated();
+    parameters.insert(name, new_param);
+    reevaluate_scene(state);
 }
@@ -0,0 +1,136 @@
+//! Printability window
+//!
+//! Edits the build direction and limits, lists overhang, thin wall and small
+//! feature areas per body and toggles the viewport heatmap. Clicking a row
+//! selects the body.
+
+use egui::Ui;
+use shared::{PrintSettings, PrintabilityReport};
+
+use crate::analysis::Heatmap;
+use crate::i18n::t;
+use crate::state::AppState;
+
+/// Build directions offered in the window
+const DIRECTIONS: [(&str, [f64; 3]); 6] = [
+    ("+Z", [0.0, 0.0, 1.0]),
+    ("-Z", [0.0, 0.0, -1.0]),
+    ("+Y", [0.0, 1.0, 0.0]),
+    ("-Y", [0.0, -1.0, 0.0]),
+    ("+X", [1.0, 0.0, 0.0]),
+    ("-X", [-1.0, 0.0, 0.0]),
+];
+
+const WARN_COLOR: egui::Color32 = egui::Color32::from_rgb(220, 160, 60);
+
+pub fn show(ctx: &egui::Context, state: &mut AppState, report: &PrintabilityReport) {
+    let mut open = state.panels.printability;
+    egui::Window::new(t("print.title"))
+        .open(&mut open)
+        .resizable(false)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            show_settings(ui, &mut state.printability.settings);
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.printability.show_heatmap, t("print.heatmap"));
+                ui.add_enabled_ui(state.printability.show_heatmap, |ui| {
+                    let heatmap = &mut state.printability.heatmap;
+                    ui.selectable_value(heatmap, Heatmap::Overhang, t("print.overhang"));
+                    ui.selectable_value(heatmap, Heatmap::Thickness, t("print.thickness"));
+                });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button(t("print.copy")).on_hover_text(t("print.copy_hint")).clicked() {
+                        ui.ctx().copy_text(serde_json::to_string_pretty(report).unwrap_or_default());
+                    }
+                });
+            });
+            ui.separator();
+
+            if report.bodies.is_empty() {
+                ui.weak(t("print.empty"));
+            } else {
+                show_bodies(ui, state, report);
+            }
+
+            if !report.errors.is_empty() {
+                ui.separator();
+                ui.colored_label(WARN_COLOR, t("print.invalid"));
+                for (id, error) in &report.errors {
+                    ui.weak(format!("{id}: {error}"));
+                }
+            }
+        });
+    state.panels.printability = open;
+}
+
+fn show_settings(ui: &mut Ui, settings: &mut PrintSettings) {
+    egui::Grid::new("print_settings")
+        .num_columns(2)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.label(t("print.direction"));
+            let current = DIRECTIONS
+                .iter()
+                .find(|(_, dir)| *dir == settings.build_direction)
+                .map_or_else(|| format!("{:?}", settings.build_direction), |(name, _)| name.to_string());
+            egui::ComboBox::from_id_salt("print_direction")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    for (name, dir) in DIRECTIONS {
+                        ui.selectable_value(&mut settings.build_direction, dir, name);
+                    }
+                });
+            ui.end_row();
+
+            ui.label(t("print.overhang_angle"));
+            ui.add(egui::DragValue::new(&mut settings.overhang_angle).range(0.0..=89.0).speed(1.0).suffix("°"));
+            ui.end_row();
+
+            ui.label(t("print.min_wall"));
+            ui.add(egui::DragValue::new(&mut settings.min_wall).range(0.0..=100.0).speed(0.05).suffix(" mm"));
+            ui.end_row();
+
+            ui.label(t("print.min_feature"));
+            ui.add(egui::DragValue::new(&mut settings.min_feature).range(0.0..=100.0).speed(0.05).suffix(" mm"));
+            ui.end_row();
+        });
+}
+
+fn show_bodies(ui: &mut Ui, state: &mut AppState, report: &PrintabilityReport) {
+    egui::Grid::new("print_bodies")
+        .num_columns(5)
+        .spacing([12.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["print.body", "print.overhang", "print.thin", "print.small", "print.min_thickness"] {
+                ui.strong(t(header));
+            }
+            ui.end_row();
+
+            for body in &report.bodies {
+                let mark = if body.is_clean() { "✔" } else { "⚠" };
+                let selected = state.selection.is_selected(&body.id);
+                let response = ui
+                    .selectable_label(selected, format!("{mark} {}", body.name))
+                    .on_hover_text(t("print.select_hint"));
+                if response.clicked() {
+                    state.selection.select(body.id.clone());
+                }
+                ui.monospace(area(body.overhang_area))
+                    .on_hover_text(format!("{}: {:.1}°", t("print.max_overhang"), body.max_overhang_angle));
+                ui.monospace(area(body.thin_wall_area));
+                ui.monospace(area(body.small_feature_area));
+                ui.monospace(body.min_thickness.map_or_else(|| "—".to_string(), |min| format!("{min:.3} mm")));
+                ui.end_row();
+            }
+        });
+    ui.weak(t("print.heatmap_hint"));
+}
+
+fn area(value: f64) -> String {
+    if value > 0.0 {
+        format!("{value:.2} mm²")
+    } else {
+        "—".to_string()
+    }
+}
@@ -3,9 +3,10 @@
 //! Simplified for V2 Body-based architecture.
 
 use egui::Ui;
-use shared::{Body, SketchElement};
+use shared::{Appearance, Body, Material, Sketch, SketchElement};
 
 use crate::i18n::t;
+use crate::sketch::constraints::{analyze_constraints, ConstraintStatus, SolveResult};
 use crate::state::scene::{body_display_name, feature_display_name};
 use crate::state::AppState;
 use crate::viewport::sketch_utils;
@@ -43,6 +44,16 @@ pub fn show(ui: &mut Ui, state: &mut AppState) {
 
     // Display body properties
     show_body_properties(ui, body);
+
+    // Consecutive frames of one edit (slider or colour drag) are one undo step
+    ui.add_space(8.0);
+    let edit_id = ui.id().with("appearance_edit");
+    let was_editing = ui.ctx().data(|d| d.get_temp::<bool>(edit_id)).unwrap_or(false);
+    let edit = show_appearance(ui, body);
+    ui.ctx().data_mut(|d| d.insert_temp(edit_id, edit.is_some()));
+    if let Some(appearance) = edit {
+        state.scene.set_body_appearance(&selected_id, appearance, !was_editing);
+    }
 }
 
 fn show_sketch_element_properties(ui: &mut Ui, state: &mut AppState) {
@@ -68,9 +79,20 @@ fn show_sketch_element_properties(ui: &mut Ui, state: &mut AppState) {
         None => return,
     };
 
+    // Состояние определённости эскиза (степени свободы, конфликты)
+    let sketch_key = (body_id.clone(), feature_id.clone());
+    let analysis = cached_analysis(ui, state.scene.version(), sketch_key, &sketch);
+    show_definition_status(ui, t("constraints.sketch_status"), analysis.status());
+
     if selected_count == 1 {
         let elem_idx = selected[0];
         if let Some(element) = sketch.elements.get(elem_idx) {
+            show_definition_status(
+                ui,
+                t("constraints.element_status"),
+                analysis.element_status(&sketch, elem_idx),
+            );
+            ui.add_space(4.0);
             show_single_element_properties(ui, state, element, elem_idx, &body_id, &feature_id);
         }
     } else {
@@ -90,6 +112,27 @@ fn show_sketch_element_properties(ui: &mut Ui, state: &mut AppState) {
     }
 }
 
+/// Constraint analysis of the edited sketch, solved again only when the
+/// scene version or the edited sketch changes
+fn cached_analysis(
+    ui: &Ui,
+    version: u64,
+    sketch_key: (Option<String>, Option<String>),
+    sketch: &Sketch,
+) -> SolveResult {
+    type Cached = (u64, (Option<String>, Option<String>), SolveResult);
+    let cache_id = ui.id().with("constraint_analysis");
+    let cached = ui.ctx().data(|d| d.get_temp::<Cached>(cache_id));
+    if let Some((v, key, analysis)) = cached {
+        if v == version && key == sketch_key {
+            return analysis;
+        }
+    }
+    let analysis = analyze_constraints(sketch);
+    ui.ctx().data_mut(|d| d.insert_temp(cache_id, (version, sketch_key, analysis.clone())));
+    analysis
+}
+
 fn show_single_element_properties(
     ui: &mut Ui,
     state: &mut AppState,
@@ -105,7 +148,7 @@ fn show_single_element_properties(
     ui.separator();
 
     match element {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             let dx = end.x - start.x;
             let dy = end.y - start.y;
             let length = (dx * dx + dy * dy).sqrt();
@@ -137,7 +180,7 @@ fn show_single_element_properties(
                         });
                 });
         }
-        SketchElement::Circle { center, radius } => {
+        SketchElement::Circle { center, radius, .. } => {
             let circumference = 2.0 * std::f64::consts::PI * radius;
             let area = std::f64::consts::PI * radius * radius;
 
@@ -171,7 +214,7 @@ fn show_single_element_properties(
                         });
                 });
         }
-        SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             let mut angle_span = end_angle - start_angle;
             if angle_span < 0.0 {
                 angle_span += std::f64::consts::TAU;
@@ -208,7 +251,7 @@ fn show_single_element_properties(
                         });
                 });
         }
-        SketchElement::Rectangle { corner, width, height } => {
+        SketchElement::Rectangle { corner, width, height, .. } => {
             let area = width * height;
             let perimeter = 2.0 * (width + height);
 
@@ -242,7 +285,7 @@ fn show_single_element_properties(
                         });
                 });
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             let mut total_length = 0.0;
             for i in 0..points.len().saturating_sub(1) {
                 let dx = points[i + 1].x - points[i].x;
@@ -268,7 +311,7 @@ fn show_single_element_properties(
                         });
                 });
         }
-        SketchElement::Spline { points } => {
+        SketchElement::Spline { points, .. } => {
             egui::CollapsingHeader::new(t("prop.geometry"))
                 .id_salt("spline_geometry")
                 .default_open(true)
@@ -317,7 +360,7 @@ fn show_single_element_properties(
                             // Make value editable only if not linked to parameter
                             let mut new_value = *value;
                             let is_linked = parameter_name.is_some();
-                            let saved_target = *target_element;
+                            let saved_target = target_element.clone();
 
                             let value_response = ui.add_enabled(
                                 !is_linked,
@@ -380,11 +423,11 @@ fn show_single_element_properties(
                                                 let mut found_target_idx: Option<usize> = None;
 
                                                 // If target_element is set, use it directly
-                                                if let Some(target_idx) = saved_target {
+                                                if let Some(target_idx) = saved_target.as_deref().and_then(|id| sketch.element_index(id)) {
                                                     if target_idx != elem_idx {
                                                         if let Some(target_elem) = sketch.elements.get_mut(target_idx) {
                                                             match target_elem {
-                                                                SketchElement::Line { start, end } => {
+                                                                SketchElement::Line { start, end, .. } => {
                                                                     // Check which end to update
                                                                     let dist_start_from = ((start.x - dim_from.x).powi(2) + (start.y - dim_from.y).powi(2)).sqrt();
                                                                     let dist_end_to = ((end.x - original_to.x).powi(2) + (end.y - original_to.y).powi(2)).sqrt();
@@ -400,7 +443,7 @@ fn show_single_element_properties(
                                                                     }
                                                                     target_updated = true;
                                                                 }
-                                                                SketchElement::Rectangle { corner, width, height } => {
+                                                                SketchElement::Rectangle { corner, width, height, .. } => {
                                                                     // Determine which edge matches and update width or height
                                                                     let c0 = (corner.x, corner.y);
                                                                     let c1 = (corner.x + *width, corner.y);
@@ -455,7 +498,7 @@ fn show_single_element_properties(
                                                         }
 
                                                         match other_elem {
-                                                            SketchElement::Line { start, end } => {
+                                                            SketchElement::Line { start, end, .. } => {
                                                                 let dist_start_from = ((start.x - dim_from.x).powi(2) + (start.y - dim_from.y).powi(2)).sqrt();
                                                                 let dist_end_to = ((end.x - original_to.x).powi(2) + (end.y - original_to.y).powi(2)).sqrt();
                                                                 let dist_start_to = ((start.x - original_to.x).powi(2) + (start.y - original_to.y).powi(2)).sqrt();
@@ -474,7 +517,7 @@ fn show_single_element_properties(
                                                                     break;
                                                                 }
                                                             }
-                                                            SketchElement::Rectangle { corner, width, height } => {
+                                                            SketchElement::Rectangle { corner, width, height, .. } => {
                                                                 // Rectangle corners:
                                                                 // c0 = corner, c1 = corner + (width, 0)
                                                                 // c2 = corner + (width, height), c3 = corner + (0, height)
@@ -522,6 +565,10 @@ fn show_single_element_properties(
                                                     }
                                                 }
 
+                                                let found_target_id = found_target_idx
+                                                    .and_then(|idx| sketch.element_id(idx))
+                                                    .map(str::to_string);
+
                                                 // Update dimension
                                                 if let Some(SketchElement::Dimension {
                                                     from: dim_from_mut,
@@ -558,8 +605,8 @@ fn show_single_element_properties(
                                                     }
 
                                                     // Save found target for future updates
-                                                    if dim_target.is_none() && found_target_idx.is_some() {
-                                                        *dim_target = found_target_idx;
+                                                    if dim_target.is_none() && found_target_id.is_some() {
+                                                        *dim_target = found_target_id;
                                                     }
                                                 }
 
@@ -578,8 +625,8 @@ fn show_single_element_properties(
                 .id_salt("dimension_parameter")
                 .default_open(true)
                 .show(ui, |ui| {
-                    // Get body parameters
-                    let body_params: Vec<String> = if let Some(ref bid) = body_id {
+                    // Get body parameters, then global ones not shadowed by the body
+                    let mut body_params: Vec<String> = if let Some(ref bid) = body_id {
                         state.scene.scene.bodies.iter()
                             .find(|b| &b.id == bid)
                             .map(|b| b.parameters.keys().cloned().collect())
@@ -587,6 +634,13 @@ fn show_single_element_properties(
                     } else {
                         vec![]
                     };
+                    body_params.sort();
+                    let mut global_params: Vec<String> = state.scene.scene.parameters.keys()
+                        .filter(|name| !body_params.contains(name))
+                        .cloned()
+                        .collect();
+                    global_params.sort();
+                    body_params.extend(global_params);
 
                     let mut new_param_name = parameter_name.clone();
                     let mut changed = false;
@@ -624,9 +678,7 @@ fn show_single_element_properties(
                         if let (Some(ref bid), Some(ref fid)) = (&body_id, &feature_id) {
                             // First, evaluate the parameter value (if linking to a parameter)
                             let new_value: Option<f64> = if let Some(ref param_name) = new_param_name {
-                                state.scene.scene.bodies.iter()
-                                    .find(|b| &b.id == bid)
-                                    .and_then(|b| b.evaluate_parameter(param_name).ok())
+                                state.scene.scene.evaluate_parameter(Some(bid), param_name).ok()
                             } else {
                                 None
                             };
@@ -667,7 +719,7 @@ fn show_single_element_properties(
 
     // Show constraints for this element
     // Collect constraint info (index, label) to avoid borrow issues
-    let constraints_info: Vec<(usize, String)> = if let Some(ref bid) = body_id {
+    let constraints_info: Vec<(usize, String, shared::SketchConstraint)> = if let Some(ref bid) = body_id {
         sketch_utils::find_sketch_data_ex(
             &state.scene.scene,
             bid,
@@ -675,8 +727,8 @@ fn show_single_element_properties(
         )
         .map(|(sketch, _)| {
             sketch.constraints.iter().enumerate()
-                .filter(|(_, c)| constraint_involves_element(c, elem_idx))
-                .map(|(idx, c)| (idx, constraint_display_name(c)))
+                .filter(|(_, c)| constraint_involves_element(sketch, c, elem_idx))
+                .map(|(idx, c)| (idx, constraint_display_name(c), c.clone()))
                 .collect()
         })
         .unwrap_or_default()
@@ -690,8 +742,20 @@ fn show_single_element_properties(
             .id_salt("element_constraints")
             .default_open(true)
             .show(ui, |ui| {
+                // Параметры тела для привязки размерных ограничений
+                let body_params: Vec<String> = body_id
+                    .as_ref()
+                    .and_then(|bid| state.scene.scene.bodies.iter().find(|b| &b.id == bid))
+                    .map(|b| {
+                        let mut names: Vec<String> = b.parameters.keys().cloned().collect();
+                        names.sort();
+                        names
+                    })
+                    .unwrap_or_default();
+
                 let mut to_remove: Option<usize> = None;
-                for (idx, label) in &constraints_info {
+                let mut to_update: Option<(usize, shared::SketchConstraint)> = None;
+                for (idx, label, constraint) in &constraints_info {
                     let response = ui.add(egui::Label::new(format!("  {}", label)).sense(egui::Sense::click()));
                     response.context_menu(|ui| {
                         if ui.button(t("tree.delete")).clicked() {
@@ -699,6 +763,39 @@ fn show_single_element_properties(
                             ui.close_menu();
                         }
                     });
+
+                    // Размерные ограничения: значение и привязка к параметру
+                    if let Some(mut value) = constraint.dimension_value() {
+                        let bound = constraint.parameter().map(|p| p.to_string());
+                        ui.horizontal(|ui| {
+                            ui.add_space(12.0);
+                            let suffix = if matches!(constraint, shared::SketchConstraint::Angle { .. }) { "°" } else { "" };
+                            let drag = egui::DragValue::new(&mut value).speed(0.1).suffix(suffix);
+                            if ui.add_enabled(bound.is_none(), drag).changed() {
+                                let mut updated = constraint.clone();
+                                updated.set_dimension_value(value);
+                                to_update = Some((*idx, updated));
+                            }
+
+                            let mut selected = bound.clone();
+                            egui::ComboBox::from_id_salt(("constraint_param", *idx))
+                                .selected_text(bound.as_deref().unwrap_or(t("prop.none")))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut selected, None, t("prop.none"));
+                                    for name in &body_params {
+                                        ui.selectable_value(&mut selected, Some(name.clone()), name);
+                                    }
+                                });
+                            if selected != bound {
+                                let mut updated = constraint.clone();
+                                updated.set_parameter(selected);
+                                to_update = Some((*idx, updated));
+                            }
+                        });
+                    }
+                }
+                if let (Some((idx, updated)), Some(bid)) = (to_update, &body_id) {
+                    state.scene.update_sketch_constraint(bid, feature_id.as_deref(), idx, updated);
                 }
                 if let Some(idx) = to_remove {
                     if let Some(ref bid) = body_id {
@@ -713,6 +810,27 @@ fn show_single_element_properties(
     }
 }
 
+fn show_definition_status(ui: &mut Ui, label: &str, status: ConstraintStatus) {
+    let (color, text) = match status {
+        ConstraintStatus::FullyDefined => (
+            egui::Color32::from_rgb(130, 255, 170),
+            t("constraints.fully_defined").to_string(),
+        ),
+        ConstraintStatus::UnderDefined { dof } => (
+            egui::Color32::from_rgb(130, 190, 255),
+            format!("{} ({} {})", t("constraints.under_defined"), dof, t("constraints.dof")),
+        ),
+        ConstraintStatus::OverConstrained => (
+            egui::Color32::from_rgb(255, 130, 130),
+            t("constraints.over_constrained").to_string(),
+        ),
+    };
+    ui.horizontal(|ui| {
+        ui.label(format!("{}:", label));
+        ui.colored_label(color, text);
+    });
+}
+
 fn element_type_name(element: &SketchElement) -> &'static str {
     match element {
         SketchElement::Line { .. } => "Line",
@@ -725,33 +843,8 @@ fn element_type_name(element: &SketchElement) -> &'static str {
     }
 }
 
-fn constraint_involves_element(constraint: &shared::SketchConstraint, elem_idx: usize) -> bool {
-    match constraint {
-        shared::SketchConstraint::Horizontal { element } => *element == elem_idx,
-        shared::SketchConstraint::Vertical { element } => *element == elem_idx,
-        shared::SketchConstraint::Parallel { element1, element2 } => {
-            *element1 == elem_idx || *element2 == elem_idx
-        }
-        shared::SketchConstraint::Perpendicular { element1, element2 } => {
-            *element1 == elem_idx || *element2 == elem_idx
-        }
-        shared::SketchConstraint::Coincident { point1, point2 } => {
-            point1.element_index == elem_idx || point2.element_index == elem_idx
-        }
-        shared::SketchConstraint::Fixed { element } => *element == elem_idx,
-        shared::SketchConstraint::Equal { element1, element2 } => {
-            *element1 == elem_idx || *element2 == elem_idx
-        }
-        shared::SketchConstraint::Tangent { element1, element2 } => {
-            *element1 == elem_idx || *element2 == elem_idx
-        }
-        shared::SketchConstraint::Concentric { element1, element2 } => {
-            *element1 == elem_idx || *element2 == elem_idx
-        }
-        shared::SketchConstraint::Symmetric { element1, element2, axis } => {
-            *element1 == elem_idx || *element2 == elem_idx || *axis == elem_idx
-        }
-    }
+fn constraint_involves_element(sketch: &shared::Sketch, constraint: &shared::SketchConstraint, elem_idx: usize) -> bool {
+    crate::sketch::constraints::constraint_elements(sketch, constraint).contains(&elem_idx)
 }
 
 fn constraint_display_name(constraint: &shared::SketchConstraint) -> String {
@@ -767,6 +860,16 @@ fn constraint_display_name(constraint: &shared::SketchConstraint) -> String {
         shared::SketchConstraint::Tangent { .. } => t("constraint.tangent"),
         shared::SketchConstraint::Concentric { .. } => t("constraint.concentric"),
         shared::SketchConstraint::Symmetric { .. } => t("constraint.symmetric"),
+        shared::SketchConstraint::Distance { .. } => t("constraint.distance"),
+        shared::SketchConstraint::DistancePointLine { .. } => t("constraint.distance_point_line"),
+        shared::SketchConstraint::HorizontalDistance { .. } => t("constraint.horizontal_distance"),
+        shared::SketchConstraint::VerticalDistance { .. } => t("constraint.vertical_distance"),
+        shared::SketchConstraint::Angle { .. } => t("constraint.angle"),
+        shared::SketchConstraint::Radius { .. } => t("constraint.radius"),
+        shared::SketchConstraint::Diameter { .. } => t("constraint.diameter"),
+        shared::SketchConstraint::PointOnLine { .. } => t("constraint.point_on_line"),
+        shared::SketchConstraint::PointOnCircle { .. } => t("constraint.point_on_circle"),
+        shared::SketchConstraint::Midpoint { .. } => t("constraint.midpoint"),
     };
     format!("{} {}", icon, name)
 }
@@ -783,6 +886,16 @@ fn constraint_icon(constraint: &shared::SketchConstraint) -> &'static str {
         shared::SketchConstraint::Tangent { .. } => "TG",
         shared::SketchConstraint::Concentric { .. } => "O",
         shared::SketchConstraint::Symmetric { .. } => "S",
+        shared::SketchConstraint::Distance { .. }
+        | shared::SketchConstraint::DistancePointLine { .. } => "D",
+        shared::SketchConstraint::HorizontalDistance { .. } => "DX",
+        shared::SketchConstraint::VerticalDistance { .. } => "DY",
+        shared::SketchConstraint::Angle { .. } => "A",
+        shared::SketchConstraint::Radius { .. } => "R",
+        shared::SketchConstraint::Diameter { .. } => "Ø",
+        shared::SketchConstraint::PointOnLine { .. }
+        | shared::SketchConstraint::PointOnCircle { .. } => "On",
+        shared::SketchConstraint::Midpoint { .. } => "M",
     }
 }
 
@@ -841,6 +954,77 @@ fn show_body_properties(ui: &mut Ui, body: &Body) {
     }
 }
 
+/// Appearance editor: colour, opacity and material. Returns the edited
+/// appearance when something changed (`Some(None)` resets to the default)
+fn show_appearance(ui: &mut Ui, body: &Body) -> Option<Option<Appearance>> {
+    let mut appearance = body.appearance.clone().unwrap_or_default();
+    let mut changed = false;
+    let mut reset = false;
+
+    egui::CollapsingHeader::new(t("prop.appearance"))
+        .id_salt("body_appearance")
+        .default_open(true)
+        .show(ui, |ui| {
+            egui::Grid::new("appearance_props")
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(format!("{}:", t("prop.color")));
+                    changed |= ui.color_edit_button_rgb(&mut appearance.color).changed();
+                    ui.end_row();
+
+                    ui.label(format!("{}:", t("prop.opacity")));
+                    changed |= ui.add(egui::Slider::new(&mut appearance.opacity, 0.05..=1.0)).changed();
+                    ui.end_row();
+
+                    ui.label(format!("{}:", t("prop.material")));
+                    let current = appearance.material.as_ref().map_or(t("prop.material_none"), |m| m.name.as_str());
+                    let mut selected = None;
+                    egui::ComboBox::from_id_salt("body_material")
+                        .selected_text(current)
+                        .show_ui(ui, |ui| {
+                            if ui.selectable_label(appearance.material.is_none(), t("prop.material_none")).clicked() {
+                                selected = Some(None);
+                            }
+                            for preset in Material::presets() {
+                                let active = appearance.material.as_ref().is_some_and(|m| m.name == preset.name);
+                                let label = format!("{} ({} {})", preset.name, preset.density, t("prop.density_unit"));
+                                if ui.selectable_label(active, label).clicked() {
+                                    selected = Some(Some(preset));
+                                }
+                            }
+                        });
+                    if let Some(material) = selected {
+                        appearance.material = material;
+                        changed = true;
+                    }
+                    ui.end_row();
+
+                    if let Some(material) = &mut appearance.material {
+                        ui.label(format!("{}:", t("prop.density")));
+                        let density = egui::DragValue::new(&mut material.density)
+                            .speed(0.01)
+                            .range(0.001..=30.0)
+                            .suffix(format!(" {}", t("prop.density_unit")));
+                        changed |= ui.add(density).changed();
+                        ui.end_row();
+                    }
+                });
+
+            if body.appearance.is_some() && ui.button(t("prop.appearance_reset")).clicked() {
+                reset = true;
+            }
+        });
+
+    if reset {
+        Some(None)
+    } else if changed {
+        Some(Some(appearance))
+    } else {
+        None
+    }
+}
+
 fn short_id(id: &str) -> &str {
     if id.len() > 8 {
         &id[..8]
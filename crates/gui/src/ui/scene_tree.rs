@@ -278,6 +278,7 @@ pub fn show(ui: &mut Ui, state: &mut AppState) {
                                     offset: 0.0,
                                     elements: vec![],
                                     face_normal: None,
+                                    face: None,
                                     construction: vec![],
                                     revolve_axis: None,
                                     symmetry_axis: None,
@@ -295,6 +296,7 @@ pub fn show(ui: &mut Ui, state: &mut AppState) {
                                     offset: 0.0,
                                     elements: vec![],
                                     face_normal: None,
+                                    face: None,
                                     construction: vec![],
                                     revolve_axis: None,
                                     symmetry_axis: None,
@@ -312,6 +314,7 @@ pub fn show(ui: &mut Ui, state: &mut AppState) {
                                     offset: 0.0,
                                     elements: vec![],
                                     face_normal: None,
+                                    face: None,
                                     construction: vec![],
                                     revolve_axis: None,
                                     symmetry_axis: None,
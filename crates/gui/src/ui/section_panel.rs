@@ -0,0 +1,64 @@
+//! Section view panel UI
+
+use egui::Ui;
+
+use crate::i18n::t;
+use crate::state::section::SectionOrientation;
+use crate::state::AppState;
+
+/// Show section panel when section view is active. Returns true when the
+/// outline should be exported as a sketch (the viewport holds the section).
+pub fn show(ui: &mut Ui, state: &mut AppState, has_section: bool) -> bool {
+    if !state.section.is_active() {
+        return false;
+    }
+
+    let mut export = false;
+    ui.horizontal(|ui| {
+        ui.label(t("section.title"));
+        ui.separator();
+
+        let section = &mut state.section;
+        for (orientation, label) in [
+            (SectionOrientation::Xy, "XY"),
+            (SectionOrientation::Xz, "XZ"),
+            (SectionOrientation::Yz, "YZ"),
+            (SectionOrientation::Custom, t("section.custom")),
+        ] {
+            // The plane keeps passing through the same origin
+            if ui.selectable_label(section.orientation == orientation, label).clicked() {
+                section.orientation = orientation;
+            }
+        }
+        if section.orientation == SectionOrientation::Custom {
+            ui.label(t("section.azimuth"));
+            ui.add(egui::DragValue::new(&mut section.azimuth).range(-180.0..=180.0).speed(1.0).suffix("°"));
+            ui.label(t("section.elevation"));
+            ui.add(egui::DragValue::new(&mut section.elevation).range(-90.0..=90.0).speed(1.0).suffix("°"));
+        }
+        ui.separator();
+
+        ui.label(t("section.position"));
+        let mut position = section.position();
+        let unit = format!(" {}", state.settings.units.abbrev());
+        if ui.add(egui::DragValue::new(&mut position).speed(0.1).suffix(unit)).changed() {
+            section.set_position(position);
+        }
+        ui.checkbox(&mut section.flipped, t("section.flip"));
+        ui.separator();
+
+        let can_export = has_section && section.sketch_plane().is_some();
+        let hint = if section.sketch_plane().is_some() { t("section.export_hint") } else { t("section.export_custom") };
+        let button = ui.add_enabled(can_export, egui::Button::new(t("section.export")));
+        if button.on_hover_text(hint).on_disabled_hover_text(hint).clicked() {
+            export = true;
+        }
+        if ui.button(t("section.close")).clicked() {
+            section.deactivate();
+        }
+    });
+
+    // Hint
+    ui.weak(t("section.hint"));
+    export
+}
@@ -62,6 +62,27 @@ pub fn action_chamfer3d(state: &mut AppState) {
     tracing::info!("Chamfer3D: activated, select edges (body: {:?})", state.chamfer3d.body_id);
 }
 
+pub fn action_measure(state: &mut AppState) {
+    if state.measure.is_active() {
+        state.measure.deactivate();
+        return;
+    }
+
+    // Edge picking of fillet/chamfer would take the clicks
+    state.fillet3d.deactivate();
+    state.chamfer3d.deactivate();
+    state.selection.clear_edges();
+    state.measure.activate();
+}
+
+pub fn action_section(state: &mut AppState) {
+    if state.section.is_active() {
+        state.section.deactivate();
+    } else {
+        state.section.activate();
+    }
+}
+
 pub fn action_extrude(state: &mut AppState) {
     let ctx = match get_selected_body_context(state) {
         Ok(ctx) => ctx,
@@ -131,7 +152,7 @@ pub fn action_revolve(state: &mut AppState) {
 
     // Open dialog for revolve parameters with available axes
     // Pass designated revolve_axis from sketch (if set)
-    state.operation_dialog.open_revolve_with_axes(ctx.body_id.clone(), sketch_id, axes, sketch.revolve_axis);
+    state.operation_dialog.open_revolve_with_axes(ctx.body_id.clone(), sketch_id, axes, sketch.revolve_axis_index());
 }
 
 pub fn action_cut_revolve(state: &mut AppState) {
@@ -158,7 +179,7 @@ pub fn action_cut_revolve(state: &mut AppState) {
 
     // Open dialog for cut revolve parameters with available axes
     // Pass designated revolve_axis from sketch (if set)
-    state.operation_dialog.open_cut_revolve_with_axes(ctx.body_id.clone(), sketch_id, axes, sketch.revolve_axis);
+    state.operation_dialog.open_cut_revolve_with_axes(ctx.body_id.clone(), sketch_id, axes, sketch.revolve_axis_index());
 }
 
 /// Apply revolve operation with parameters from dialog
@@ -411,6 +432,24 @@ pub fn show(ui: &mut Ui, state: &mut AppState) {
             }
         });
 
+        // ── Measure tool ──
+        if ui
+            .selectable_label(state.measure.is_active(), t("tb.measure"))
+            .on_hover_text(t("tip.measure"))
+            .clicked()
+        {
+            action_measure(state);
+        }
+
+        // ── Section view ──
+        if ui
+            .selectable_label(state.section.is_active(), t("tb.section"))
+            .on_hover_text(t("tip.section"))
+            .clicked()
+        {
+            action_section(state);
+        }
+
         ui.separator();
 
         // ── History buttons ──
@@ -460,6 +499,7 @@ fn create_sketch_body(state: &mut AppState, name: &str, plane: SketchPlane) {
                 offset: 0.0,
                 elements: vec![],
                 face_normal: None,
+                face: None,
                 construction: vec![],
                 revolve_axis: None,
                 symmetry_axis: None,
@@ -483,6 +523,7 @@ fn create_sketch_body(state: &mut AppState, name: &str, plane: SketchPlane) {
         offset: 0.0,
         elements: vec![],
         face_normal: None,
+        face: None,
         construction: vec![],
         revolve_axis: None,
         symmetry_axis: None,
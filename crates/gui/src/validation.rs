@@ -2,7 +2,14 @@
 //!
 //! `MeshValidator` provides methods to check mesh data integrity:
 //! correct stride, in-range indices, normalized normals, AABB dimensions, etc.
+//! `validated_printability` runs the printability checks on meshes with a
+//! sound buffer layout.
 
+use std::collections::HashMap;
+
+use shared::{PrintSettings, SceneDescriptionV2};
+
+use crate::analysis::{scene_printability, Printability};
 use crate::viewport::mesh::MeshData;
 use crate::viewport::picking::Aabb;
 
@@ -96,6 +103,16 @@ impl<'a> MeshValidator<'a> {
     /// Run all validation checks and return a list of error messages.
     /// An empty list means the mesh is valid.
     pub fn validate_all(&self) -> Vec<String> {
+        let mut errors = self.structural_errors();
+        if self.vertex_count() > 0 && !self.are_normals_normalized(0.1) {
+            errors.push("Some normals are not unit-length (epsilon=0.1)".to_string());
+        }
+        errors
+    }
+
+    /// Buffer layout checks only (stride, index range): meshes that pass can
+    /// be indexed safely, even with degenerate triangles.
+    pub fn structural_errors(&self) -> Vec<String> {
         let mut errors = Vec::new();
 
         if !self.is_stride_valid() {
@@ -127,14 +144,34 @@ impl<'a> MeshValidator<'a> {
             ));
         }
 
-        if self.vertex_count() > 0 && !self.are_normals_normalized(0.1) {
-            errors.push("Some normals are not unit-length (epsilon=0.1)".to_string());
-        }
-
         errors
     }
 }
 
+/// Printability of the meshes that pass `MeshValidator::structural_errors`.
+/// Meshes that fail are not ray-cast; their errors go to `report.errors`.
+/// Degenerate triangles (zero normals) are checked like the rest.
+pub fn validated_printability(
+    scene: &SceneDescriptionV2,
+    meshes: &HashMap<String, MeshData>,
+    settings: &PrintSettings,
+) -> Printability {
+    let mut valid = HashMap::new();
+    let mut errors = HashMap::new();
+    for (id, mesh) in meshes {
+        let problems = MeshValidator::new(mesh).structural_errors();
+        if problems.is_empty() {
+            valid.insert(id.clone(), mesh.clone());
+        } else {
+            errors.insert(id.clone(), problems.join("; "));
+        }
+    }
+
+    let mut printability = scene_printability(scene, &valid, settings);
+    printability.report.errors = errors;
+    printability
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,4 +329,54 @@ mod tests {
         let errors = v.validate_all();
         assert!(errors.iter().any(|e| e.contains("out of range")));
     }
+
+    #[test]
+    fn test_validated_printability_skips_invalid_meshes() {
+        let meshes = HashMap::from([
+            ("good".to_string(), simple_triangle()),
+            (
+                "bad".to_string(),
+                MeshData {
+                    vertices: vec![0.0; 9],
+                    indices: vec![0, 5, 2],
+                },
+            ),
+        ]);
+        let printability = validated_printability(
+            &SceneDescriptionV2::default(),
+            &meshes,
+            &PrintSettings::default(),
+        );
+        let report = &printability.report;
+        assert_eq!(report.bodies.len(), 1);
+        assert_eq!(report.bodies[0].id, "good");
+        assert!(report.errors["bad"].contains("out of range"));
+        assert!(!printability.faces.contains_key("bad"));
+    }
+
+    #[test]
+    fn test_validated_printability_keeps_mesh_with_degenerate_triangle() {
+        let mut h = crate::harness::TestHarness::new();
+        let id = h.create_cube("Box", 10.0, 10.0, 10.0);
+        h.build();
+        let mut mesh = h.mesh_of(&id).unwrap().clone();
+        let base = mesh.vertices.len() as u32 / 9;
+        for _ in 0..3 {
+            mesh.vertices.extend_from_slice(&[1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.5, 0.5, 0.5]);
+        }
+        mesh.indices.extend_from_slice(&[base, base + 1, base + 2]);
+        assert!(!MeshValidator::new(&mesh).validate_all().is_empty());
+
+        let meshes = HashMap::from([(id.clone(), mesh)]);
+        let printability = validated_printability(
+            &SceneDescriptionV2::default(),
+            &meshes,
+            &PrintSettings::default(),
+        );
+        let report = &printability.report;
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.bodies.len(), 1);
+        assert_eq!(report.bodies[0].triangles, 13);
+        assert!(report.bodies[0].is_clean(), "{:?}", report.bodies[0]);
+    }
 }
@@ -21,6 +21,8 @@ pub struct GizmoState {
     pub dragging: bool,
     /// ID of the object being moved
     pub drag_object_id: Option<String>,
+    /// Whether the section plane (not an object) is being moved
+    pub drag_section: bool,
 }
 
 
@@ -29,6 +31,7 @@ impl GizmoState {
         self.active_axis = None;
         self.dragging = false;
         self.drag_object_id = None;
+        self.drag_section = false;
     }
 }
 
@@ -145,6 +148,28 @@ pub fn build_gizmo_lines(center: Vec3, length: f32) -> LineMeshData {
     LineMeshData { vertices }
 }
 
+/// Build the outline of a section plane: a square of half-size `half`
+/// around `origin`, plus a tick along the normal (towards the cut-away side).
+pub fn build_section_plane_lines(origin: Vec3, normal: Vec3, half: f32) -> LineMeshData {
+    let mut vertices = Vec::new();
+    let color = [1.0_f32, 0.75, 0.2, 0.9];
+
+    let u = normal.any_orthonormal_vector() * half;
+    let v = normal.cross(u);
+    let corners = [origin - u - v, origin + u - v, origin + u + v, origin - u + v];
+    for i in 0..4 {
+        let (a, b) = (corners[i], corners[(i + 1) % 4]);
+        push_line_vert(&mut vertices, a.x, a.y, a.z, color);
+        push_line_vert(&mut vertices, b.x, b.y, b.z, color);
+    }
+
+    let tip = origin + normal * (half * 0.25);
+    push_line_vert(&mut vertices, origin.x, origin.y, origin.z, color);
+    push_line_vert(&mut vertices, tip.x, tip.y, tip.z, color);
+
+    LineMeshData { vertices }
+}
+
 // ── Helpers ──────────────────────────────────────────────────
 
 fn push_line_vert(v: &mut Vec<f32>, px: f32, py: f32, pz: f32, c: [f32; 4]) {
@@ -20,8 +20,15 @@ pub struct RenderParams {
     pub axes_thickness: f32,
     /// Background color RGB
     pub bg_color: [u8; 3],
+    /// Opacity of translucent bodies (body id → opacity below 1)
+    pub opacity: HashMap<String, f32>,
+    /// Section plane `[a, b, c, d]`: geometry with `a·x + b·y + c·z + d > 0` is clipped
+    pub clip_plane: Option<[f32; 4]>,
 }
 
+/// Spacing of the section cap hatching, pixels
+const HATCH_SPACING: f32 = 8.0;
+
 // ── GPU mesh handles ─────────────────────────────────────────
 
 struct GpuMesh {
@@ -52,8 +59,16 @@ pub struct GlRenderer {
     scene_meshes: HashMap<String, GpuMesh>,
     /// Translation gizmo lines (shown when an object is selected)
     gizmo: Option<GpuLines>,
-    /// Version counter to detect scene changes
-    last_scene_version: u64,
+    /// Rebuild count and heatmap upload key of the uploaded scene meshes
+    last_scene_version: (u64, Option<u64>),
+    /// Highlighted solids drawn with the opaque bodies (interference results)
+    highlight_meshes: Vec<GpuMesh>,
+    /// Version of the uploaded highlights (None when nothing is highlighted)
+    highlight_version: Option<u64>,
+    /// Cap filling the cut of the section plane
+    section_cap: Option<GpuMesh>,
+    /// Version of the uploaded section cap
+    section_cap_version: Option<u64>,
 }
 
 impl GlRenderer {
@@ -76,7 +91,11 @@ impl GlRenderer {
             cached_axes_length: Some(1.5),
             scene_meshes: HashMap::new(),
             gizmo: None,
-            last_scene_version: 0,
+            last_scene_version: (0, None),
+            highlight_meshes: Vec::new(),
+            highlight_version: None,
+            section_cap: None,
+            section_cap_version: None,
         }
     }
 
@@ -127,7 +146,7 @@ impl GlRenderer {
         &mut self,
         gl: &glow::Context,
         meshes: &HashMap<String, MeshData>,
-        version: u64,
+        version: (u64, Option<u64>),
     ) {
         if version == self.last_scene_version && !self.scene_meshes.is_empty() {
             return;
@@ -165,6 +184,44 @@ impl GlRenderer {
         }
     }
 
+    /// Upload highlighted solids, replacing previous ones when the version changes
+    pub fn sync_highlights(&mut self, gl: &glow::Context, highlights: Option<(u64, &[MeshData])>) {
+        let version = highlights.map(|(version, _)| version);
+        if version == self.highlight_version {
+            return;
+        }
+        self.highlight_version = version;
+
+        for mesh in self.highlight_meshes.drain(..) {
+            unsafe {
+                gl.delete_vertex_array(mesh.vao);
+                gl.delete_buffer(mesh._vbo);
+                gl.delete_buffer(mesh.ibo);
+            }
+        }
+        if let Some((_, meshes)) = highlights {
+            self.highlight_meshes = meshes.iter().map(|mesh_data| upload_mesh(gl, mesh_data)).collect();
+        }
+    }
+
+    /// Upload the section cap, replacing the previous one when the version changes
+    pub fn sync_section_cap(&mut self, gl: &glow::Context, cap: Option<(u64, &MeshData)>) {
+        let version = cap.map(|(version, _)| version);
+        if version == self.section_cap_version {
+            return;
+        }
+        self.section_cap_version = version;
+
+        if let Some(old) = self.section_cap.take() {
+            unsafe {
+                gl.delete_vertex_array(old.vao);
+                gl.delete_buffer(old._vbo);
+                gl.delete_buffer(old.ibo);
+            }
+        }
+        self.section_cap = cap.filter(|(_, mesh)| !mesh.indices.is_empty()).map(|(_, mesh)| upload_mesh(gl, mesh));
+    }
+
     /// Render the scene
     pub fn paint(
         &self,
@@ -228,10 +285,49 @@ impl GlRenderer {
             let light_dir = glam::Vec3::new(0.3, 0.8, 0.5).normalize();
             set_uniform_vec3(gl, self.mesh_program, "u_light_dir", &light_dir);
 
-            for mesh in self.scene_meshes.values() {
+            // Section view clips every body against the plane
+            let clip_plane = params.clip_plane.unwrap_or([0.0, 0.0, 0.0, -1.0]);
+            set_uniform_vec4(gl, self.mesh_program, "u_clip_plane", &clip_plane);
+            set_uniform_f32(gl, self.mesh_program, "u_hatch", 0.0);
+            if params.clip_plane.is_some() {
+                gl.enable(glow::CLIP_DISTANCE0);
+            }
+
+            // Opaque bodies first, then translucent ones blended over them
+            // without writing depth, so they don't hide each other
+            set_uniform_f32(gl, self.mesh_program, "u_alpha", 1.0);
+            for (id, mesh) in &self.scene_meshes {
+                if !params.opacity.contains_key(id) {
+                    draw_mesh(gl, mesh);
+                }
+            }
+            for mesh in &self.highlight_meshes {
                 draw_mesh(gl, mesh);
             }
 
+            // The cap lies on the plane itself, so it is drawn unclipped
+            if let (Some(cap), Some(_)) = (&self.section_cap, params.clip_plane) {
+                gl.disable(glow::CLIP_DISTANCE0);
+                set_uniform_f32(gl, self.mesh_program, "u_hatch", HATCH_SPACING);
+                draw_mesh(gl, cap);
+                set_uniform_f32(gl, self.mesh_program, "u_hatch", 0.0);
+                gl.enable(glow::CLIP_DISTANCE0);
+            }
+            if !params.opacity.is_empty() {
+                gl.enable(glow::BLEND);
+                gl.blend_func(glow::SRC_ALPHA, glow::ONE_MINUS_SRC_ALPHA);
+                gl.depth_mask(false);
+                for (id, alpha) in &params.opacity {
+                    if let Some(mesh) = self.scene_meshes.get(id) {
+                        set_uniform_f32(gl, self.mesh_program, "u_alpha", *alpha);
+                        draw_mesh(gl, mesh);
+                    }
+                }
+                gl.depth_mask(true);
+                gl.disable(glow::BLEND);
+            }
+            gl.disable(glow::CLIP_DISTANCE0);
+
             // Draw gizmo on top (no depth test so it's always visible)
             if let Some(ref gizmo) = self.gizmo {
                 gl.disable(glow::DEPTH_TEST);
@@ -266,7 +362,7 @@ impl GlRenderer {
                 gl.delete_vertex_array(gizmo.vao);
                 gl.delete_buffer(gizmo._vbo);
             }
-            for mesh in self.scene_meshes.values() {
+            for mesh in self.scene_meshes.values().chain(&self.highlight_meshes).chain(&self.section_cap) {
                 gl.delete_vertex_array(mesh.vao);
                 gl.delete_buffer(mesh._vbo);
                 gl.delete_buffer(mesh.ibo);
@@ -412,6 +508,13 @@ fn set_uniform_mat4(gl: &glow::Context, program: glow::Program, name: &str, mat:
     }
 }
 
+fn set_uniform_f32(gl: &glow::Context, program: glow::Program, name: &str, v: f32) {
+    unsafe {
+        let loc = gl.get_uniform_location(program, name);
+        gl.uniform_1_f32(loc.as_ref(), v);
+    }
+}
+
 fn set_uniform_vec3(gl: &glow::Context, program: glow::Program, name: &str, v: &glam::Vec3) {
     unsafe {
         let loc = gl.get_uniform_location(program, name);
@@ -419,6 +522,13 @@ fn set_uniform_vec3(gl: &glow::Context, program: glow::Program, name: &str, v: &
     }
 }
 
+fn set_uniform_vec4(gl: &glow::Context, program: glow::Program, name: &str, v: &[f32; 4]) {
+    unsafe {
+        let loc = gl.get_uniform_location(program, name);
+        gl.uniform_4_f32(loc.as_ref(), v[0], v[1], v[2], v[3]);
+    }
+}
+
 // ── Byte cast helper ─────────────────────────────────────────
 
 fn bytemuck_cast_slice<T: Copy>(slice: &[T]) -> &[u8] {
@@ -434,6 +544,7 @@ fn bytemuck_cast_slice<T: Copy>(slice: &[T]) -> &[u8] {
 
 const MESH_VERT: &str = r#"#version 330 core
 uniform mat4 u_mvp;
+uniform vec4 u_clip_plane;
 
 layout(location = 0) in vec3 a_position;
 layout(location = 1) in vec3 a_normal;
@@ -444,6 +555,7 @@ out vec3 v_color;
 
 void main() {
     gl_Position = u_mvp * vec4(a_position, 1.0);
+    gl_ClipDistance[0] = -dot(u_clip_plane, vec4(a_position, 1.0));
     v_normal = a_normal;
     v_color = a_color;
 }
@@ -451,6 +563,8 @@ void main() {
 
 const MESH_FRAG: &str = r#"#version 330 core
 uniform vec3 u_light_dir;
+uniform float u_alpha;
+uniform float u_hatch;
 
 in vec3 v_normal;
 in vec3 v_color;
@@ -462,7 +576,12 @@ void main() {
     float diffuse = max(dot(n, u_light_dir), 0.0);
     float ambient = 0.25;
     float light = ambient + diffuse * 0.75;
-    frag_color = vec4(v_color * light, 1.0);
+    vec3 color = v_color * light;
+    // Diagonal screen-space hatching (section caps)
+    if (u_hatch > 0.0 && mod(gl_FragCoord.x + gl_FragCoord.y, u_hatch) < 1.5) {
+        color *= 0.4;
+    }
+    frag_color = vec4(color, u_alpha);
 }
 "#;
 
@@ -2,19 +2,7 @@
 
 use glam::Vec3;
 
-/// CPU-side mesh data: interleaved [pos.x, pos.y, pos.z, norm.x, norm.y, norm.z, r, g, b]
-#[derive(Clone)]
-pub struct MeshData {
-    /// 9 floats per vertex: position(3) + normal(3) + color(3)
-    pub vertices: Vec<f32>,
-    pub indices: Vec<u32>,
-}
-
-impl MeshData {
-    pub fn vertex_count(&self) -> usize {
-        self.vertices.len() / 9
-    }
-}
+pub use cad_core::mesh::MeshData;
 
 /// Lines mesh: interleaved [pos.x, pos.y, pos.z, r, g, b, a]
 pub struct LineMeshData {
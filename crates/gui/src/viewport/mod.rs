@@ -15,18 +15,59 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use egui::Ui;
+use shared::{MassReport, PrintSettings, SceneDescriptionV2};
 
-use crate::build::CsgCache;
+use crate::analysis::{
+    cap_mesh, face_entity, heatmap_mesh, scene_interference, scene_mass_properties, section_loops, section_sketch,
+    Heatmap, Interference, MeasureEntity, Printability, SectionPlane,
+};
+use crate::build::{apply_body_color, CsgCache};
 use crate::i18n::t;
+use crate::state::measure::MeasurePick;
 use crate::state::selection::FaceSelection;
 use crate::state::AppState;
+use crate::validation::validated_printability;
 use camera::ArcBallCamera;
-use gizmo::{build_gizmo_lines, compute_drag_delta, gizmo_hit_test, GizmoState};
+use gizmo::{build_gizmo_lines, build_section_plane_lines, compute_drag_delta, gizmo_hit_test, GizmoState};
 use gl_renderer::GlRenderer;
 use mesh::{LineMeshData, MeshData};
 use picking::{group_coplanar_triangles, calculate_face_area, pick_nearest, pick_triangle};
 
 const GIZMO_LENGTH: f32 = 2.0;
+/// Screen distance (pixels) within which the measure tool snaps to edges and vertices
+const MEASURE_SNAP_PX: f32 = 8.0;
+/// Dihedral angle (degrees) above which a mesh edge is measurable; smaller
+/// angles are tessellation seams of curved faces
+const MEASURE_EDGE_ANGLE: f32 = 30.0;
+/// Colour of interference solids
+const CLASH_COLOR: [f32; 3] = [0.9, 0.15, 0.1];
+/// Opacity of clashing bodies while their interference solids are shown
+const CLASH_BODY_OPACITY: f32 = 0.3;
+
+/// Section of the cached meshes by the cutting plane
+struct SectionCut {
+    /// Rebuild and plane the section belongs to
+    rebuild: u64,
+    plane: SectionPlane,
+    /// Closed outlines of every body on the plane
+    loops: Vec<Vec<glam::Vec3>>,
+    /// Cap filling the outlines, drawn hatched
+    cap: MeshData,
+    /// Upload key for the renderer, bumped on every recomputation
+    version: u64,
+}
+
+/// Printability heatmap of the cached meshes, drawn instead of the body colours
+struct HeatmapMeshes {
+    /// Scene version, settings and quantity the colours belong to
+    scene_version: u64,
+    settings: PrintSettings,
+    heatmap: Heatmap,
+    /// Recoloured meshes by body id
+    meshes: HashMap<String, MeshData>,
+    /// Upload key for the renderer, bumped on every recomputation
+    version: u64,
+}
 
 /// 3D viewport panel with OpenGL rendering
 pub struct ViewportPanel {
@@ -42,6 +83,20 @@ pub struct ViewportPanel {
     sketch_tools_context_menu: bool,
     /// ViewCube state for click detection
     view_cube_state: Option<overlays::ViewCubeState>,
+    /// Mass properties of the cached meshes, with the rebuild they belong to
+    mass_report: Option<(u64, MassReport)>,
+    /// Clashes between the cached meshes, with the scene version they belong to
+    interference: Option<(u64, Interference)>,
+    /// Section by the cutting plane while section view is active
+    section_cut: Option<SectionCut>,
+    /// Last `SectionCut::version` handed out
+    section_version: u64,
+    /// Printability of the cached meshes, with the scene version and settings it belongs to
+    printability: Option<(u64, PrintSettings, Printability)>,
+    /// Heatmap shown while the printability window is open
+    heatmap: Option<HeatmapMeshes>,
+    /// Last `HeatmapMeshes::version` handed out
+    heatmap_version: u64,
 }
 
 impl ViewportPanel {
@@ -55,6 +110,13 @@ impl ViewportPanel {
             sketch_element_context_menu: false,
             sketch_tools_context_menu: false,
             view_cube_state: None,
+            mass_report: None,
+            interference: None,
+            section_cut: None,
+            section_version: 0,
+            printability: None,
+            heatmap: None,
+            heatmap_version: 0,
         }
     }
 
@@ -78,9 +140,149 @@ impl ViewportPanel {
         self.csg_cache.aabbs().get(id).map(|aabb| aabb.center())
     }
 
-    /// Get a clone of all cached meshes for export
-    pub fn export_meshes(&self) -> HashMap<String, MeshData> {
-        self.csg_cache.meshes_clone()
+    /// Cached meshes for export: in body colours, without selection and
+    /// face highlights
+    pub fn export_meshes(&self, scene: &SceneDescriptionV2) -> HashMap<String, MeshData> {
+        crate::export::meshes_with_body_colors(scene, self.csg_cache.meshes())
+    }
+
+    /// Mass properties of the built bodies, recomputed after each rebuild
+    pub fn mass_properties(&mut self, scene: &SceneDescriptionV2) -> &MassReport {
+        let rebuild = self.csg_cache.rebuild_count();
+        if self.mass_report.as_ref().is_some_and(|(count, _)| *count != rebuild) {
+            self.mass_report = None;
+        }
+        let cache = &self.csg_cache;
+        let (_, report) = self.mass_report.get_or_insert_with(|| {
+            let mut report = scene_mass_properties(scene, cache.meshes());
+            report.errors = cache.errors().clone();
+            (rebuild, report)
+        });
+        report
+    }
+
+    /// Clashes between the built bodies, recomputed when the scene changes
+    /// (selection-only rebuilds keep the result)
+    pub fn interference(&mut self, scene: &SceneDescriptionV2) -> &Interference {
+        let version = self.csg_cache.scene_version();
+        if self.interference.as_ref().is_some_and(|(v, _)| *v != version) {
+            self.interference = None;
+        }
+        let cache = &self.csg_cache;
+        let (_, interference) = self.interference.get_or_insert_with(|| {
+            let bounds = cache.aabbs().iter().map(|(id, aabb)| (id.clone(), (aabb.min, aabb.max))).collect();
+            let mut interference = scene_interference(scene, &bounds);
+            for solid in &mut interference.solids {
+                apply_body_color(solid, CLASH_COLOR);
+            }
+            (version, interference)
+        });
+        interference
+    }
+
+    /// Printability of the built bodies, recomputed when the scene or the
+    /// settings change (selection-only rebuilds keep the result)
+    pub fn printability(&mut self, scene: &SceneDescriptionV2, settings: &PrintSettings) -> &Printability {
+        let version = self.csg_cache.scene_version();
+        if self.printability.as_ref().is_some_and(|(v, s, _)| *v != version || s != settings) {
+            self.printability = None;
+        }
+        let cache = &self.csg_cache;
+        let (_, _, printability) = self
+            .printability
+            .get_or_insert_with(|| (version, settings.clone(), validated_printability(scene, cache.meshes(), settings)));
+        printability
+    }
+
+    /// Recolour the cached meshes by the printability heatmap while the
+    /// window is open and its result matches the current meshes
+    fn update_heatmap(&mut self, state: &AppState) {
+        let version = self.csg_cache.scene_version();
+        let current = self.printability.as_ref().filter(|(v, settings, _)| {
+            state.panels.printability
+                && state.printability.show_heatmap
+                && *v == version
+                && *settings == state.printability.settings
+        });
+        let Some((_, settings, printability)) = current else {
+            self.heatmap = None;
+            return;
+        };
+        let heatmap = state.printability.heatmap;
+        if self.heatmap.as_ref().is_some_and(|h| {
+            h.scene_version == version && h.settings == *settings && h.heatmap == heatmap
+        }) {
+            return;
+        }
+
+        let meshes = self
+            .csg_cache
+            .meshes()
+            .iter()
+            .filter_map(|(id, mesh)| {
+                let checks = printability.faces.get(id).filter(|c| c.overhang.len() == mesh.indices.len() / 3)?;
+                Some((id.clone(), heatmap_mesh(mesh, checks, heatmap, settings)))
+            })
+            .collect();
+        self.heatmap_version += 1;
+        self.heatmap = Some(HeatmapMeshes {
+            scene_version: version,
+            settings: settings.clone(),
+            heatmap,
+            meshes,
+            version: self.heatmap_version,
+        });
+    }
+
+    /// Add the current section outline as a sketch body on the section
+    /// plane. Only planes parallel to XY/XZ/YZ can hold a sketch.
+    pub fn export_section_sketch(&mut self, state: &mut AppState) {
+        let (Some(cut), Some((plane, offset))) = (&self.section_cut, state.section.sketch_plane()) else {
+            return;
+        };
+        if cut.loops.is_empty() {
+            return;
+        }
+        let sketch = section_sketch(&cut.loops, plane, offset);
+        let id = state.scene.create_body_with_sketch(t("section.sketch_name").to_string(), sketch, shared::Transform::new());
+        state.selection.select(id);
+    }
+
+    /// Whether the cutting plane currently crosses any body
+    pub fn has_section(&self) -> bool {
+        self.section_cut.as_ref().is_some_and(|cut| !cut.loops.is_empty())
+    }
+
+    /// Recompute the section outlines and cap when the plane or the meshes change
+    fn update_section(&mut self, state: &AppState) {
+        if !state.section.is_active() {
+            self.section_cut = None;
+            return;
+        }
+        let plane = state.section.plane();
+        let rebuild = self.csg_cache.rebuild_count();
+        if self.section_cut.as_ref().is_some_and(|cut| cut.rebuild == rebuild && cut.plane == plane) {
+            return;
+        }
+
+        let mut loops = Vec::new();
+        let mut cap = MeshData { vertices: Vec::new(), indices: Vec::new() };
+        for mesh in self.csg_cache.meshes().values() {
+            let body_loops = section_loops(mesh, &plane);
+            if body_loops.is_empty() {
+                continue;
+            }
+            // Cap in a darker shade of the body colour
+            let color = mesh.vertices.get(6..9).map_or([0.5; 3], |c| [c[0] * 0.8, c[1] * 0.8, c[2] * 0.8]);
+            let body_cap = cap_mesh(&body_loops, &plane, color);
+            let offset = cap.vertex_count() as u32;
+            cap.vertices.extend(body_cap.vertices);
+            cap.indices.extend(body_cap.indices.iter().map(|i| i + offset));
+            loops.extend(body_loops);
+        }
+
+        self.section_version += 1;
+        self.section_cut = Some(SectionCut { rebuild, plane, loops, cap, version: self.section_version });
     }
 
     pub fn show(&mut self, ui: &mut Ui, state: &mut AppState) {
@@ -112,6 +314,10 @@ impl ViewportPanel {
         // ── Build CSG meshes BEFORE selection (so picking uses fresh normals) ────
         self.rebuild_csg_if_needed(state);
 
+        // ── Section by the cutting plane (after gizmo drags and rebuilds) ──
+        self.update_section(state);
+        self.update_heatmap(state);
+
         // ── Object/Face selection via click ──────────────────────────
         self.handle_selection(&response, ui, rect, state, sketch_consumed, mod_tool_consumed);
 
@@ -400,13 +606,13 @@ impl ViewportPanel {
                                 if let Some(ref snap) = state.sketch.active_snap {
                                     if let Some(source_idx) = snap.source_element {
                                         if let Some(elem) = sketch.elements.get(source_idx) {
-                                            if let shared::SketchElement::Circle { center, radius } = elem {
+                                            if let shared::SketchElement::Circle { center, radius, .. } = elem {
                                                 match snap.snap_type {
                                                     crate::state::sketch::SnapType::Center => {
                                                         // Clicked on center - create radius dimension
                                                         state.sketch.dimension_circle_info = Some(
                                                             crate::state::sketch::DimensionCircleInfo {
-                                                                circle_index: source_idx,
+                                                                circle_id: sketch.element_id(source_idx).map(str::to_string),
                                                                 dimension_type: shared::DimensionType::Radius,
                                                                 center: [center.x, center.y],
                                                                 radius: *radius,
@@ -421,7 +627,7 @@ impl ViewportPanel {
                                                         // Clicked on circle quadrant - create diameter dimension
                                                         state.sketch.dimension_circle_info = Some(
                                                             crate::state::sketch::DimensionCircleInfo {
-                                                                circle_index: source_idx,
+                                                                circle_id: sketch.element_id(source_idx).map(str::to_string),
                                                                 dimension_type: shared::DimensionType::Diameter,
                                                                 center: [center.x, center.y],
                                                                 radius: *radius,
@@ -617,7 +823,7 @@ impl ViewportPanel {
                 if let Some(element) = sketch.elements.get(hit.element_index) {
                     tracing::info!("Trim tool: element type = {:?}", std::mem::discriminant(element));
                     let trim_result = match element {
-                        shared::SketchElement::Line { start, end } => {
+                        shared::SketchElement::Line { start, end, .. } => {
                             tracing::info!("Trim tool: trimming LINE from {:?} to {:?}", start, end);
                             trim_line(
                                 hit.element_index,
@@ -632,6 +838,7 @@ impl ViewportPanel {
                             radius,
                             start_angle,
                             end_angle,
+                            ..
                         } => {
                             tracing::info!("Trim tool: trimming ARC center={:?}, r={}, angles={}->{}", center, radius, start_angle, end_angle);
                             trim_arc(
@@ -644,7 +851,7 @@ impl ViewportPanel {
                                 &sketch,
                             )
                         }
-                        shared::SketchElement::Circle { center, radius } => {
+                        shared::SketchElement::Circle { center, radius, .. } => {
                             tracing::info!("Trim tool: trimming CIRCLE center={:?}, r={}", center, radius);
                             trim_circle(
                                 hit.element_index,
@@ -654,7 +861,7 @@ impl ViewportPanel {
                                 &sketch,
                             )
                         }
-                        shared::SketchElement::Polyline { points } => {
+                        shared::SketchElement::Polyline { points, .. } => {
                             tracing::info!("Trim tool: trimming POLYLINE with {} points", points.len());
                             trim_polyline(
                                 hit.element_index,
@@ -663,7 +870,7 @@ impl ViewportPanel {
                                 &sketch,
                             )
                         }
-                        shared::SketchElement::Rectangle { corner, width, height } => {
+                        shared::SketchElement::Rectangle { corner, width, height, .. } => {
                             tracing::info!("Trim tool: trimming RECTANGLE at {:?} {}x{}", corner, width, height);
                             trim_rectangle(
                                 hit.element_index,
@@ -741,7 +948,7 @@ impl ViewportPanel {
                 // Mirror tool: click on a line to use as axis, mirror selected elements
                 if let Some(element) = sketch.elements.get(hit.element_index) {
                     // Only lines can be used as mirror axis
-                    if let shared::SketchElement::Line { start, end } = element {
+                    if let shared::SketchElement::Line { start, end, .. } = element {
                         let selected = state.sketch.element_selection.selected.clone();
                         if selected.is_empty() {
                             tracing::info!("Mirror tool: no elements selected");
@@ -824,7 +1031,12 @@ impl ViewportPanel {
         if self.gizmo_state.dragging {
             if response.dragged_by(egui::PointerButton::Primary) {
                 let delta = response.drag_delta();
-                if let (Some(axis), Some(ref obj_id)) =
+                if self.gizmo_state.drag_section {
+                    if let Some(axis) = self.gizmo_state.active_axis {
+                        let origin = state.section.origin;
+                        state.section.origin += compute_drag_delta(&self.camera, origin, axis, delta, rect);
+                    }
+                } else if let (Some(axis), Some(ref obj_id)) =
                     (self.gizmo_state.active_axis, &self.gizmo_state.drag_object_id)
                 {
                     // Compute gizmo center from AABB
@@ -868,7 +1080,15 @@ impl ViewportPanel {
             {
                 let pointer_pos = response.interact_pointer_pos().or_else(|| response.hover_pos());
                 if let Some(pos) = pointer_pos {
-                    if let Some(primary_id) = state.selection.primary().cloned() {
+                    // The section plane gizmo replaces the object gizmo
+                    if state.section.is_active() {
+                        let ray = self.camera.screen_ray(pos, rect);
+                        if let Some(axis) = gizmo_hit_test(&ray, state.section.origin, GIZMO_LENGTH) {
+                            self.gizmo_state.active_axis = Some(axis);
+                            self.gizmo_state.dragging = true;
+                            self.gizmo_state.drag_section = true;
+                        }
+                    } else if let Some(primary_id) = state.selection.primary().cloned() {
                         if let Some(aabb) = self.csg_cache.aabbs().get(&primary_id) {
                             let center = aabb.center();
                             let ray = self.camera.screen_ray(pos, rect);
@@ -910,7 +1130,9 @@ impl ViewportPanel {
         let ray = self.camera.screen_ray(pos, rect);
 
         // First check gizmo hit — if gizmo visible, don't pick through it
-        let gizmo_hit = if let Some(primary_id) = state.selection.primary() {
+        let gizmo_hit = if state.section.is_active() {
+            gizmo_hit_test(&ray, state.section.origin, GIZMO_LENGTH).is_some()
+        } else if let Some(primary_id) = state.selection.primary() {
             self.csg_cache
                 .aabbs()
                 .get(primary_id)
@@ -934,6 +1156,11 @@ impl ViewportPanel {
             return;
         }
 
+        if state.measure.is_active() {
+            self.handle_measure_pick(pos, &ray, rect, state, shift_pressed);
+            return;
+        }
+
         if shift_pressed {
             // Shift+Click = face selection
             self.handle_face_selection(&ray, state);
@@ -1077,6 +1304,65 @@ impl ViewportPanel {
         }
     }
 
+    /// Pick a vertex, edge or face under the cursor for the measure tool.
+    /// Shift+click picks the surface point instead.
+    fn handle_measure_pick(
+        &self,
+        cursor_pos: egui::Pos2,
+        ray: &picking::Ray,
+        rect: egui::Rect,
+        state: &mut AppState,
+        point_only: bool,
+    ) {
+        // Nearest surface hit over all bodies
+        let hit = self
+            .csg_cache
+            .meshes()
+            .iter()
+            .filter_map(|(id, mesh)| pick_triangle(ray, mesh).map(|hit| (id, mesh, hit)))
+            .min_by(|a, b| a.2.distance.total_cmp(&b.2.distance));
+        let Some((object_id, mesh, hit)) = hit else {
+            return;
+        };
+        let hit_point = ray.origin + ray.direction * hit.distance;
+
+        let entity = if point_only {
+            MeasureEntity::Point(hit_point)
+        } else {
+            self.pick_measure_edge(cursor_pos, rect, mesh).unwrap_or_else(|| {
+                face_entity(mesh, hit.triangle_index).unwrap_or(MeasureEntity::Point(hit_point))
+            })
+        };
+        tracing::debug!("Measure: picked {:?} on {}", entity, object_id);
+        state.measure.pick(MeasurePick { object_id: object_id.clone(), entity });
+    }
+
+    /// Edge of `mesh` near the cursor, or its endpoint when the cursor is on it
+    fn pick_measure_edge(&self, cursor_pos: egui::Pos2, rect: egui::Rect, mesh: &MeshData) -> Option<MeasureEntity> {
+        let edges = edge::extract_sharp_edges(mesh, MEASURE_EDGE_ANGLE);
+        let view_proj = self.camera.view_projection(rect.width() / rect.height());
+        let hit = edge::pick_edge_2d(
+            [cursor_pos.x - rect.min.x, cursor_pos.y - rect.min.y],
+            &edges,
+            self.camera.eye_position(),
+            &view_proj,
+            [rect.width(), rect.height()],
+            MEASURE_SNAP_PX,
+        )?;
+
+        let e = &edges[hit.edge_index];
+        for vertex in [e.start, e.end] {
+            let on_vertex = self
+                .camera
+                .project(vertex.to_array(), rect)
+                .is_some_and(|screen| screen.distance(cursor_pos) <= MEASURE_SNAP_PX);
+            if on_vertex {
+                return Some(MeasureEntity::Point(vertex));
+            }
+        }
+        Some(MeasureEntity::Edge { start: e.start, end: e.end })
+    }
+
     /// Handle edge hover for visual feedback in fillet/chamfer mode
     fn handle_fillet_hover(
         &self,
@@ -1261,7 +1547,16 @@ impl ViewportPanel {
                 face_normal[2] as f64,
             ]);
 
-            sketch_utils::add_sketch_to_existing_body(state, &body_id, plane, local_offset, face_normal_f64);
+            // Name the face so the sketch follows it when earlier features change
+            let face = state.scene.get_body(&body_id).and_then(|body| {
+                cad_core::build::topology::name_face(
+                    body,
+                    [centroid.x as f64, centroid.y as f64, centroid.z as f64],
+                    face_normal_f64?,
+                )
+            });
+
+            sketch_utils::add_sketch_to_existing_body(state, &body_id, plane, local_offset, face_normal_f64, face);
         }
     }
 
@@ -1285,6 +1580,21 @@ impl ViewportPanel {
             return;
         }
 
+        // Constraints reference elements by id, the selection works with indices
+        let element_ids: Vec<String> = if let (Some(body_id), feature_id) = (
+            state.sketch.editing_body_id(),
+            state.sketch.active_feature_id(),
+        ) {
+            sketch_utils::find_sketch_data_ex(&state.scene.scene, body_id, feature_id.map(|s| s.as_str()))
+                .map(|(sketch, _)| {
+                    sketch.elements.iter().map(|e| e.id().unwrap_or_default().to_string()).collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let element_id = |idx: usize| element_ids.get(idx).cloned().unwrap_or_default();
+
         response.context_menu(|ui| {
             // Connect option - only when exactly 2 points selected
             if selected_points_count == 2 {
@@ -1295,11 +1605,11 @@ impl ViewportPanel {
                     ) {
                         let points = state.sketch.element_selection.selected_points.clone();
                         let point1 = shared::PointRef {
-                            element_index: points[0].0,
+                            element_id: element_id(points[0].0),
                             point_index: points[0].1,
                         };
                         let point2 = shared::PointRef {
-                            element_index: points[1].0,
+                            element_id: element_id(points[1].0),
                             point_index: points[1].1,
                         };
                         state.scene.add_sketch_constraint(
@@ -1312,6 +1622,71 @@ impl ViewportPanel {
                     ui.close_menu();
                     self.sketch_element_context_menu = false;
                 }
+
+                // Размеры между двумя точками
+                let points = state.sketch.element_selection.selected_points.clone();
+                let point1 = shared::PointRef { element_id: element_id(points[0].0), point_index: points[0].1 };
+                let point2 = shared::PointRef { element_id: element_id(points[1].0), point_index: points[1].1 };
+                let dimensions = [
+                    (
+                        t("constraint.distance"),
+                        shared::SketchConstraint::Distance { point1: point1.clone(), point2: point2.clone(), value: 0.0, parameter: None },
+                    ),
+                    (
+                        t("constraint.horizontal_distance"),
+                        shared::SketchConstraint::HorizontalDistance { point1: point1.clone(), point2: point2.clone(), value: 0.0, parameter: None },
+                    ),
+                    (
+                        t("constraint.vertical_distance"),
+                        shared::SketchConstraint::VerticalDistance { point1, point2, value: 0.0, parameter: None },
+                    ),
+                ];
+                for (label, constraint) in dimensions {
+                    if ui.button(label).clicked() {
+                        add_measured_constraint(state, constraint);
+                        ui.close_menu();
+                        self.sketch_element_context_menu = false;
+                    }
+                }
+                ui.separator();
+            }
+
+            // Точка + элемент: точка на кривой, середина, расстояние до линии
+            if selected_points_count == 1 && selected_count == 1 {
+                let (elem_idx, point_idx) = state.sketch.element_selection.selected_points[0];
+                let point = shared::PointRef { element_id: element_id(elem_idx), point_index: point_idx };
+                let target = state.sketch.element_selection.selected[0];
+                let target_kind = if let (Some(body_id), feature_id) = (
+                    state.sketch.editing_body_id(),
+                    state.sketch.active_feature_id(),
+                ) {
+                    sketch_utils::find_sketch_data_ex(&state.scene.scene, body_id, feature_id.map(|s| s.as_str()))
+                        .and_then(|(sketch, _)| sketch.elements.get(target).cloned())
+                } else {
+                    None
+                };
+
+                let options: Vec<(&str, shared::SketchConstraint)> = match target_kind {
+                    Some(shared::SketchElement::Line { .. }) => vec![
+                        (t("constraint.point_on_line"), shared::SketchConstraint::PointOnLine { point: point.clone(), line: element_id(target) }),
+                        (t("constraint.midpoint"), shared::SketchConstraint::Midpoint { point: point.clone(), line: element_id(target) }),
+                        (
+                            t("constraint.distance_point_line"),
+                            shared::SketchConstraint::DistancePointLine { point, line: element_id(target), value: 0.0, parameter: None },
+                        ),
+                    ],
+                    Some(shared::SketchElement::Circle { .. }) | Some(shared::SketchElement::Arc { .. }) => vec![
+                        (t("constraint.point_on_circle"), shared::SketchConstraint::PointOnCircle { point, circle: element_id(target) }),
+                    ],
+                    _ => vec![],
+                };
+                for (label, constraint) in options {
+                    if ui.button(label).clicked() {
+                        add_measured_constraint(state, constraint);
+                        ui.close_menu();
+                        self.sketch_element_context_menu = false;
+                    }
+                }
                 ui.separator();
             }
 
@@ -1483,7 +1858,7 @@ impl ViewportPanel {
             ui.label(t("constraints.title"));
 
             // Single line selected - Horizontal/Vertical constraints
-            let selected = &state.sketch.element_selection.selected;
+            let selected = state.sketch.element_selection.selected.clone();
             if selected.len() == 1 {
                 let elem_idx = selected[0];
                 // Check if it's a line
@@ -1511,7 +1886,7 @@ impl ViewportPanel {
                             state.scene.add_sketch_constraint(
                                 &body_id,
                                 feature_id.as_deref(),
-                                shared::SketchConstraint::Horizontal { element: elem_idx },
+                                shared::SketchConstraint::Horizontal { element: element_id(elem_idx) },
                             );
                         }
                         ui.close_menu();
@@ -1525,7 +1900,7 @@ impl ViewportPanel {
                             state.scene.add_sketch_constraint(
                                 &body_id,
                                 feature_id.as_deref(),
-                                shared::SketchConstraint::Vertical { element: elem_idx },
+                                shared::SketchConstraint::Vertical { element: element_id(elem_idx) },
                             );
                         }
                         ui.close_menu();
@@ -1533,6 +1908,38 @@ impl ViewportPanel {
                     }
                 }
 
+                // Circle/arc - Radius/Diameter dimensions
+                let is_circle = if let (Some(body_id), feature_id) = (
+                    state.sketch.editing_body_id(),
+                    state.sketch.active_feature_id(),
+                ) {
+                    sketch_utils::find_sketch_data_ex(&state.scene.scene, body_id, feature_id.map(|s| s.as_str()))
+                        .and_then(|(sketch, _)| sketch.elements.get(elem_idx))
+                        .map(|el| matches!(el, shared::SketchElement::Circle { .. } | shared::SketchElement::Arc { .. }))
+                        .unwrap_or(false)
+                } else {
+                    false
+                };
+
+                if is_circle {
+                    if ui.button(t("constraint.radius")).clicked() {
+                        add_measured_constraint(
+                            state,
+                            shared::SketchConstraint::Radius { element: element_id(elem_idx), value: 0.0, parameter: None },
+                        );
+                        ui.close_menu();
+                        self.sketch_element_context_menu = false;
+                    }
+                    if ui.button(t("constraint.diameter")).clicked() {
+                        add_measured_constraint(
+                            state,
+                            shared::SketchConstraint::Diameter { element: element_id(elem_idx), value: 0.0, parameter: None },
+                        );
+                        ui.close_menu();
+                        self.sketch_element_context_menu = false;
+                    }
+                }
+
                 // Fixed constraint - available for any element type
                 if ui.button(t("constraint.fixed")).clicked() {
                     if let (Some(body_id), feature_id) = (
@@ -1542,7 +1949,7 @@ impl ViewportPanel {
                         state.scene.add_sketch_constraint(
                             &body_id,
                             feature_id.as_deref(),
-                            shared::SketchConstraint::Fixed { element: elem_idx },
+                            shared::SketchConstraint::Fixed { element: element_id(elem_idx) },
                         );
                     }
                     ui.close_menu();
@@ -1591,8 +1998,8 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Parallel {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
                                 },
                             );
                         }
@@ -1608,8 +2015,8 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Perpendicular {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
                                 },
                             );
                         }
@@ -1625,14 +2032,27 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Equal {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
                                 },
                             );
                         }
                         ui.close_menu();
                         self.sketch_element_context_menu = false;
                     }
+                    if ui.button(t("constraint.angle")).clicked() {
+                        add_measured_constraint(
+                            state,
+                            shared::SketchConstraint::Angle {
+                                element1: element_id(elem1_idx),
+                                element2: element_id(elem2_idx),
+                                value: 0.0,
+                                parameter: None,
+                            },
+                        );
+                        ui.close_menu();
+                        self.sketch_element_context_menu = false;
+                    }
                 }
 
                 // Two circles/arcs: Concentric, Equal radius
@@ -1646,8 +2066,8 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Concentric {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
                                 },
                             );
                         }
@@ -1663,8 +2083,8 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Equal {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
                                 },
                             );
                         }
@@ -1684,8 +2104,8 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Tangent {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
                                 },
                             );
                         }
@@ -1735,9 +2155,9 @@ impl ViewportPanel {
                                 &body_id,
                                 feature_id.as_deref(),
                                 shared::SketchConstraint::Symmetric {
-                                    element1: elem1_idx,
-                                    element2: elem2_idx,
-                                    axis: axis_idx,
+                                    element1: element_id(elem1_idx),
+                                    element2: element_id(elem2_idx),
+                                    axis: element_id(axis_idx),
                                 },
                             );
                         }
@@ -1890,7 +2310,20 @@ impl ViewportPanel {
     }
 
     fn build_gizmo_lines(&self, state: &AppState) -> Option<LineMeshData> {
-        if state.selection.count() == 1 {
+        if state.section.is_active() {
+            let plane = state.section.plane();
+            // Outline sized to the scene so the plane reads as cutting all of it
+            let half = self
+                .csg_cache
+                .aabbs()
+                .values()
+                .map(|aabb| (aabb.max - aabb.min).max_element())
+                .fold(GIZMO_LENGTH, f32::max)
+                * 0.75;
+            let mut lines = build_gizmo_lines(plane.origin, GIZMO_LENGTH);
+            lines.vertices.extend(build_section_plane_lines(plane.origin, plane.normal, half).vertices);
+            Some(lines)
+        } else if state.selection.count() == 1 {
             let primary_id = state.selection.primary().unwrap();
             self.csg_cache
                 .aabbs()
@@ -1919,12 +2352,40 @@ impl ViewportPanel {
                 let camera_target = self.camera.target;
                 let camera_fov = self.camera.fov;
 
-                let meshes: HashMap<String, MeshData> = self.csg_cache.meshes_clone();
-                let version = self.csg_cache.rebuild_count();
+                let mut meshes: HashMap<String, MeshData> = self.csg_cache.meshes_clone();
+                let mut version = (self.csg_cache.rebuild_count(), None);
+                if let Some(heatmap) = &self.heatmap {
+                    meshes.extend(heatmap.meshes.iter().map(|(id, mesh)| (id.clone(), mesh.clone())));
+                    version.1 = Some(heatmap.version);
+                }
 
                 let grid_settings = state.settings.grid.clone();
                 let axes_settings = state.settings.axes.clone();
                 let bg_color = state.settings.viewport.background_color;
+                let mut opacity: HashMap<String, f32> = state
+                    .scene
+                    .scene
+                    .bodies
+                    .iter()
+                    .filter_map(|b| {
+                        let appearance = b.appearance.as_ref().filter(|a| a.is_transparent())?;
+                        Some((b.id.clone(), appearance.opacity))
+                    })
+                    .collect();
+
+                // Interference solids show through their (now translucent) bodies
+                let clashes = self.interference.as_ref().filter(|_| state.panels.interference);
+                if let Some((_, interference)) = clashes {
+                    for clash in &interference.report.clashes {
+                        for id in [&clash.body_a, &clash.body_b] {
+                            let alpha = opacity.entry(id.clone()).or_insert(CLASH_BODY_OPACITY);
+                            *alpha = alpha.min(CLASH_BODY_OPACITY);
+                        }
+                    }
+                }
+                let highlights = clashes.map(|(version, interference)| (*version, interference.solids.clone()));
+                let clip_plane = self.section_cut.as_ref().map(|cut| cut.plane.equation());
+                let section_cap = self.section_cut.as_ref().map(|cut| (cut.version, cut.cap.clone()));
 
                 let callback = egui::PaintCallback {
                     rect,
@@ -1953,6 +2414,8 @@ impl ViewportPanel {
                                 r.update_axes(gl, &axes_settings);
                                 r.sync_from_meshes(gl, &meshes, version);
                                 r.sync_gizmo(gl, gizmo_lines.as_ref());
+                                r.sync_highlights(gl, highlights.as_ref().map(|(v, solids)| (*v, solids.as_slice())));
+                                r.sync_section_cap(gl, section_cap.as_ref().map(|(v, cap)| (*v, cap)));
 
                                 let render_params = gl_renderer::RenderParams {
                                     viewport,
@@ -1960,6 +2423,8 @@ impl ViewportPanel {
                                     axes_visible: axes_settings.visible,
                                     axes_thickness: axes_settings.thickness,
                                     bg_color,
+                                    opacity: opacity.clone(),
+                                    clip_plane,
                                 };
                                 r.paint(gl, &camera, &render_params);
                             }
@@ -2013,6 +2478,11 @@ impl ViewportPanel {
         if edge_mode_active {
             self.draw_selected_edges(&painter, rect, state);
         }
+
+        // Measure tool picks and results
+        if state.measure.is_active() {
+            overlays::draw_measurements(&painter, rect, &self.camera, state);
+        }
     }
 
     fn draw_selected_edges(&self, painter: &egui::Painter, rect: egui::Rect, state: &AppState) {
@@ -2214,8 +2684,8 @@ impl ViewportPanel {
                 };
                 let sketch_stroke = egui::Stroke::new(1.5, stroke_color);
                 let display_info = renderer::SketchElementDisplayInfo {
-                    construction: sketch.construction.clone(),
-                    revolve_axis: sketch.revolve_axis,
+                    construction: (0..sketch.elements.len()).map(|i| sketch.is_construction(i)).collect(),
+                    revolve_axis: sketch.revolve_axis_index(),
                     dimension_settings: Some(state.settings.dimensions.clone()),
                     unit_abbrev: Some(state.settings.units.abbrev()),
                     ..Default::default()
@@ -2264,8 +2734,8 @@ impl ViewportPanel {
                     selected_points: state.sketch.element_selection.selected_points.clone(),
                     hover_element: state.sketch.element_selection.hover_element,
                     hover_point: state.sketch.element_selection.hover_point,
-                    construction: sketch.construction.clone(),
-                    revolve_axis: sketch.revolve_axis,
+                    construction: (0..sketch.elements.len()).map(|i| sketch.is_construction(i)).collect(),
+                    revolve_axis: sketch.revolve_axis_index(),
                     dimension_settings: Some(state.settings.dimensions.clone()),
                     unit_abbrev: Some(state.settings.units.abbrev()),
                 };
@@ -2364,13 +2834,13 @@ impl ViewportPanel {
             };
 
             // Get symmetry axis
-            let axis_idx = match sketch.symmetry_axis {
+            let axis_idx = match sketch.symmetry_axis_index() {
                 Some(idx) => idx,
                 None => return, // No symmetry axis set
             };
 
             let axis_line = match sketch.elements.get(axis_idx) {
-                Some(shared::SketchElement::Line { start, end }) => {
+                Some(shared::SketchElement::Line { start, end, .. }) => {
                     ((start.x, start.y), (end.x, end.y))
                 }
                 _ => return, // Axis must be a line
@@ -2421,3 +2891,24 @@ impl ViewportPanel {
         }
     }
 }
+
+/// Добавить размерное ограничение с текущим измеренным значением,
+/// чтобы геометрия не сдвигалась в момент создания
+fn add_measured_constraint(state: &mut AppState, mut constraint: shared::SketchConstraint) {
+    let (Some(body_id), feature_id) = (
+        state.sketch.editing_body_id().cloned(),
+        state.sketch.active_feature_id().cloned(),
+    ) else {
+        return;
+    };
+
+    if let Some((sketch, _)) =
+        sketch_utils::find_sketch_data_ex(&state.scene.scene, &body_id, feature_id.as_deref())
+    {
+        if let Some(value) = crate::sketch::constraints::measure_constraint_value(sketch, &constraint) {
+            constraint.set_dimension_value(value);
+        }
+    }
+
+    state.scene.add_sketch_constraint(&body_id, feature_id.as_deref(), constraint);
+}
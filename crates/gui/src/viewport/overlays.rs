@@ -456,12 +456,12 @@ fn draw_element_ghost<F>(
     F: Fn([f64; 2]) -> Option<egui::Pos2>,
 {
     match element {
-        shared::SketchElement::Line { start, end } => {
+        shared::SketchElement::Line { start, end, .. } => {
             if let (Some(a), Some(b)) = (to_screen([start.x, start.y]), to_screen([end.x, end.y])) {
                 painter.line_segment([a, b], stroke);
             }
         }
-        shared::SketchElement::Circle { center, radius } => {
+        shared::SketchElement::Circle { center, radius, .. } => {
             let segments = 32;
             let screen_pts: Vec<_> = (0..=segments)
                 .filter_map(|i| {
@@ -475,7 +475,7 @@ fn draw_element_ghost<F>(
                 painter.line_segment([w[0], w[1]], stroke);
             }
         }
-        shared::SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        shared::SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             let segments = 24;
             let angle_span = end_angle - start_angle;
             let screen_pts: Vec<_> = (0..=segments)
@@ -491,7 +491,7 @@ fn draw_element_ghost<F>(
                 painter.line_segment([w[0], w[1]], stroke);
             }
         }
-        shared::SketchElement::Rectangle { corner, width, height } => {
+        shared::SketchElement::Rectangle { corner, width, height, .. } => {
             let corners = [
                 [corner.x, corner.y],
                 [corner.x + width, corner.y],
@@ -506,7 +506,7 @@ fn draw_element_ghost<F>(
                 }
             }
         }
-        shared::SketchElement::Polyline { points } | shared::SketchElement::Spline { points } => {
+        shared::SketchElement::Polyline { points, .. } | shared::SketchElement::Spline { points, .. } => {
             let screen_pts: Vec<_> = points.iter().filter_map(|p| to_screen([p.x, p.y])).collect();
             for w in screen_pts.windows(2) {
                 painter.line_segment([w[0], w[1]], stroke);
@@ -1031,14 +1031,14 @@ pub fn draw_sketch_revolve_axis_preview(
     };
 
     // Check if sketch has a designated revolve axis
-    let axis_index = match sketch.revolve_axis {
+    let axis_index = match sketch.revolve_axis_index() {
         Some(idx) => idx,
         None => return,
     };
 
     // Get the axis element
     let axis_element = match sketch.elements.get(axis_index) {
-        Some(shared::SketchElement::Line { start, end }) => (start, end),
+        Some(shared::SketchElement::Line { start, end, .. }) => (start, end),
         _ => return,
     };
 
@@ -1255,13 +1255,97 @@ pub fn draw_sketch_revolve_axis_preview(
     }
 }
 
+// ============================================================================
+// Measure tool
+// ============================================================================
+
+/// Draw measure picks and the measured values with their labels
+pub fn draw_measurements(painter: &Painter, rect: egui::Rect, camera: &ArcBallCamera, state: &AppState) {
+    use crate::analysis::{MeasureEntity, Measurement};
+
+    let pick_color = egui::Color32::from_rgb(255, 210, 60);
+    let value_color = egui::Color32::from_rgb(110, 220, 255);
+    let pick_stroke = egui::Stroke::new(3.0, pick_color);
+    let value_stroke = egui::Stroke::new(1.5, value_color);
+    let leader_stroke = egui::Stroke::new(1.0, value_color.gamma_multiply(0.6));
+    let project = |p: Vec3| camera.project(p.to_array(), rect);
+    let segment = |a: Vec3, b: Vec3, stroke: egui::Stroke| {
+        if let (Some(a), Some(b)) = (project(a), project(b)) {
+            painter.line_segment([a, b], stroke);
+        }
+    };
+
+    for pick in &state.measure.picks {
+        match pick.entity {
+            MeasureEntity::Point(p) => {
+                if let Some(s) = project(p) {
+                    painter.circle_filled(s, 4.0, pick_color);
+                }
+            }
+            MeasureEntity::Edge { start, end } => segment(start, end, pick_stroke),
+            MeasureEntity::Plane { point, normal } => {
+                // Normal tick of constant on-screen size
+                segment(point, point + normal * camera.distance * 0.08, pick_stroke);
+                if let Some(s) = project(point) {
+                    painter.circle_stroke(s, 5.0, pick_stroke);
+                }
+            }
+            MeasureEntity::Cylinder { center, axis, radius } => {
+                let u = axis.any_orthonormal_vector() * radius;
+                let v = axis.cross(u);
+                let ring: Vec<egui::Pos2> = (0..=48)
+                    .filter_map(|i| {
+                        let a = i as f32 / 48.0 * std::f32::consts::TAU;
+                        project(center + u * a.cos() + v * a.sin())
+                    })
+                    .collect();
+                painter.add(egui::Shape::line(ring, egui::Stroke::new(2.0, pick_color)));
+                segment(center - axis * radius, center + axis * radius, leader_stroke);
+            }
+        }
+    }
+
+    let anchors: Vec<Vec3> = state.measure.picks.iter().map(|p| p.entity.anchor()).collect();
+    for measurement in state.measure.measurements() {
+        let label_at = match measurement {
+            Measurement::Distance { from, to, .. } => {
+                segment(from, to, value_stroke);
+                for end in [from, to] {
+                    if let Some(s) = project(end) {
+                        painter.circle_filled(s, 3.0, value_color);
+                    }
+                }
+                (from + to) * 0.5
+            }
+            Measurement::Angle { at, .. } => {
+                for anchor in &anchors {
+                    segment(at, *anchor, leader_stroke);
+                }
+                at
+            }
+            Measurement::Radius { value, center, axis } => {
+                let rim = center + axis.any_orthonormal_vector() * value;
+                segment(center, rim, value_stroke);
+                center + (rim - center) * 0.5
+            }
+        };
+
+        let Some(pos) = project(label_at) else { continue };
+        let text = crate::ui::measure_panel::measurement_label(&measurement, &state.settings);
+        let galley = painter.layout_no_wrap(text, egui::FontId::proportional(13.0), value_color);
+        let label_rect = egui::Rect::from_center_size(pos, galley.size()).expand(3.0);
+        painter.rect_filled(label_rect, 3.0, egui::Color32::from_rgba_unmultiplied(20, 20, 28, 210));
+        painter.galley(label_rect.min + egui::vec2(3.0, 3.0), galley, value_color);
+    }
+}
+
 /// Get sample points from a sketch element for preview
 fn get_element_points(element: &shared::SketchElement) -> Vec<[f64; 2]> {
     match element {
-        shared::SketchElement::Line { start, end } => {
+        shared::SketchElement::Line { start, end, .. } => {
             vec![[start.x, start.y], [end.x, end.y]]
         }
-        shared::SketchElement::Circle { center, radius } => {
+        shared::SketchElement::Circle { center, radius, .. } => {
             let segments = 24;
             (0..=segments)
                 .map(|i| {
@@ -1270,7 +1354,7 @@ fn get_element_points(element: &shared::SketchElement) -> Vec<[f64; 2]> {
                 })
                 .collect()
         }
-        shared::SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        shared::SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             let segments = 16;
             let angle_span = end_angle - start_angle;
             (0..=segments)
@@ -1281,7 +1365,7 @@ fn get_element_points(element: &shared::SketchElement) -> Vec<[f64; 2]> {
                 })
                 .collect()
         }
-        shared::SketchElement::Rectangle { corner, width, height } => {
+        shared::SketchElement::Rectangle { corner, width, height, .. } => {
             vec![
                 [corner.x, corner.y],
                 [corner.x + width, corner.y],
@@ -1290,7 +1374,7 @@ fn get_element_points(element: &shared::SketchElement) -> Vec<[f64; 2]> {
                 [corner.x, corner.y], // Close the rectangle
             ]
         }
-        shared::SketchElement::Polyline { points } | shared::SketchElement::Spline { points } => {
+        shared::SketchElement::Polyline { points, .. } | shared::SketchElement::Spline { points, .. } => {
             points.iter().map(|p| [p.x, p.y]).collect()
         }
         shared::SketchElement::Dimension { .. } => vec![],
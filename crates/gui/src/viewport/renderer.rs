@@ -328,7 +328,7 @@ pub(crate) fn draw_sketch_elements(
         let draw_dashed = is_construction;
 
         match elem {
-            shared::SketchElement::Line { start, end } => {
+            shared::SketchElement::Line { start, end, .. } => {
                 let p1 = sketch_point_to_3d(start.x, start.y, sketch, transform);
                 let p2 = sketch_point_to_3d(end.x, end.y, sketch, transform);
                 if draw_dashed {
@@ -337,7 +337,7 @@ pub(crate) fn draw_sketch_elements(
                     draw_line_3d(painter, rect, camera, p1, p2, elem_stroke);
                 }
             }
-            shared::SketchElement::Circle { center, radius } => {
+            shared::SketchElement::Circle { center, radius, .. } => {
                 let segments = 24;
                 let mut ring = Vec::with_capacity(segments);
                 for i in 0..segments {
@@ -352,7 +352,7 @@ pub(crate) fn draw_sketch_elements(
                     draw_ring(painter, rect, camera, &ring, elem_stroke);
                 }
             }
-            shared::SketchElement::Rectangle { corner, width, height } => {
+            shared::SketchElement::Rectangle { corner, width, height, .. } => {
                 let x0 = corner.x;
                 let y0 = corner.y;
                 let x1 = corner.x + width;
@@ -369,7 +369,7 @@ pub(crate) fn draw_sketch_elements(
                     draw_ring(painter, rect, camera, &corners, elem_stroke);
                 }
             }
-            shared::SketchElement::Polyline { points } | shared::SketchElement::Spline { points } => {
+            shared::SketchElement::Polyline { points, .. } | shared::SketchElement::Spline { points, .. } => {
                 let pts: Vec<_> = points.iter()
                     .map(|p| sketch_point_to_3d(p.x, p.y, sketch, transform))
                     .collect();
@@ -381,7 +381,7 @@ pub(crate) fn draw_sketch_elements(
                     }
                 }
             }
-            shared::SketchElement::Arc { center, radius, start_angle, end_angle } => {
+            shared::SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
                 let segments = 24;
                 let mut angle_span = end_angle - start_angle;
                 if angle_span < 0.0 {
@@ -964,15 +964,15 @@ fn get_element_center(sketch: &shared::Sketch, element_idx: usize) -> Option<[f6
     let elem = sketch.elements.get(element_idx)?;
 
     match elem {
-        shared::SketchElement::Line { start, end } => {
+        shared::SketchElement::Line { start, end, .. } => {
             Some([(start.x + end.x) / 2.0, (start.y + end.y) / 2.0])
         }
         shared::SketchElement::Circle { center, .. } => Some([center.x, center.y]),
         shared::SketchElement::Arc { center, .. } => Some([center.x, center.y]),
-        shared::SketchElement::Rectangle { corner, width, height } => {
+        shared::SketchElement::Rectangle { corner, width, height, .. } => {
             Some([corner.x + width / 2.0, corner.y + height / 2.0])
         }
-        shared::SketchElement::Polyline { points } => {
+        shared::SketchElement::Polyline { points, .. } => {
             if points.is_empty() {
                 return None;
             }
@@ -981,7 +981,7 @@ fn get_element_center(sketch: &shared::Sketch, element_idx: usize) -> Option<[f6
             let n = points.len() as f64;
             Some([sum_x / n, sum_y / n])
         }
-        shared::SketchElement::Spline { points } => {
+        shared::SketchElement::Spline { points, .. } => {
             if points.is_empty() {
                 return None;
             }
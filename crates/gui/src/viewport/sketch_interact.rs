@@ -126,7 +126,7 @@ fn get_element_snap_points(
     let mut points = Vec::new();
 
     match elem {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             if settings.endpoint {
                 points.push(SnapPoint {
                     point: [start.x, start.y],
@@ -149,7 +149,7 @@ fn get_element_snap_points(
             }
         }
 
-        SketchElement::Circle { center, radius } => {
+        SketchElement::Circle { center, radius, .. } => {
             if settings.center {
                 points.push(SnapPoint {
                     point: [center.x, center.y],
@@ -175,7 +175,7 @@ fn get_element_snap_points(
             }
         }
 
-        SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             if settings.center {
                 points.push(SnapPoint {
                     point: [center.x, center.y],
@@ -206,7 +206,7 @@ fn get_element_snap_points(
             }
         }
 
-        SketchElement::Rectangle { corner, width, height } => {
+        SketchElement::Rectangle { corner, width, height, .. } => {
             if settings.endpoint {
                 // 4 угла прямоугольника
                 let corners = [
@@ -249,7 +249,7 @@ fn get_element_snap_points(
             }
         }
 
-        SketchElement::Polyline { points: poly_pts } => {
+        SketchElement::Polyline { points: poly_pts, .. } => {
             if settings.endpoint {
                 for pt in poly_pts {
                     points.push(SnapPoint {
@@ -274,7 +274,7 @@ fn get_element_snap_points(
             }
         }
 
-        SketchElement::Spline { points: spline_pts } => {
+        SketchElement::Spline { points: spline_pts, .. } => {
             // Для сплайна только контрольные точки
             if settings.endpoint {
                 for pt in spline_pts {
@@ -359,10 +359,10 @@ pub fn hit_test_elements(
 /// Calculate distance from a point to a sketch element
 fn distance_to_element(point: [f64; 2], element: &SketchElement) -> f64 {
     match element {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             distance_to_line_segment(point, [start.x, start.y], [end.x, end.y])
         }
-        SketchElement::Circle { center, radius } => {
+        SketchElement::Circle { center, radius, .. } => {
             let dist_to_center = distance_2d(point, [center.x, center.y]);
             (dist_to_center - radius).abs()
         }
@@ -371,6 +371,7 @@ fn distance_to_element(point: [f64; 2], element: &SketchElement) -> f64 {
             radius,
             start_angle,
             end_angle,
+            ..
         } => distance_to_arc(
             point,
             [center.x, center.y],
@@ -382,6 +383,7 @@ fn distance_to_element(point: [f64; 2], element: &SketchElement) -> f64 {
             corner,
             width,
             height,
+            ..
         } => {
             // Check distance to all 4 sides
             let corners = [
@@ -399,7 +401,7 @@ fn distance_to_element(point: [f64; 2], element: &SketchElement) -> f64 {
             }
             min_dist
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             if points.len() < 2 {
                 return f64::MAX;
             }
@@ -553,7 +555,7 @@ pub struct PointHit {
 /// Returns Vec of (point_index, position)
 pub fn get_element_control_points(elem: &SketchElement) -> Vec<(usize, [f64; 2])> {
     match elem {
-        SketchElement::Line { start, end } => {
+        SketchElement::Line { start, end, .. } => {
             vec![
                 (0, [start.x, start.y]), // start point
                 (1, [end.x, end.y]),     // end point
@@ -564,7 +566,7 @@ pub fn get_element_control_points(elem: &SketchElement) -> Vec<(usize, [f64; 2])
                 (0, [center.x, center.y]), // center only
             ]
         }
-        SketchElement::Arc { center, radius, start_angle, end_angle } => {
+        SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
             let start_pt = [
                 center.x + radius * start_angle.cos(),
                 center.y + radius * start_angle.sin(),
@@ -579,7 +581,7 @@ pub fn get_element_control_points(elem: &SketchElement) -> Vec<(usize, [f64; 2])
                 (2, end_pt),                // end point on arc
             ]
         }
-        SketchElement::Rectangle { corner, width, height } => {
+        SketchElement::Rectangle { corner, width, height, .. } => {
             vec![
                 (0, [corner.x, corner.y]),                      // bottom-left
                 (1, [corner.x + width, corner.y]),              // bottom-right
@@ -587,12 +589,12 @@ pub fn get_element_control_points(elem: &SketchElement) -> Vec<(usize, [f64; 2])
                 (3, [corner.x, corner.y + height]),             // top-left
             ]
         }
-        SketchElement::Polyline { points } => {
+        SketchElement::Polyline { points, .. } => {
             points.iter().enumerate()
                 .map(|(i, pt)| (i, [pt.x, pt.y]))
                 .collect()
         }
-        SketchElement::Spline { points } => {
+        SketchElement::Spline { points, .. } => {
             points.iter().enumerate()
                 .map(|(i, pt)| (i, [pt.x, pt.y]))
                 .collect()
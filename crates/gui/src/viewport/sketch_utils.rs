@@ -1,6 +1,6 @@
 //! Sketch utility functions for viewport
 
-use shared::{Feature, SceneDescriptionV2, Sketch, SketchPlane, Transform};
+use shared::{FaceRef, Feature, SceneDescriptionV2, Sketch, SketchPlane, Transform};
 
 use crate::state::AppState;
 
@@ -90,10 +90,11 @@ pub fn add_sketch_to_existing_body(
     plane: SketchPlane,
     offset: f64,
     face_normal: Option<[f64; 3]>,
+    face: Option<FaceRef>,
 ) {
     tracing::info!(
-        "Creating sketch on face: body={}, plane={:?}, offset={}, face_normal={:?}",
-        body_id, plane, offset, face_normal
+        "Creating sketch on face: body={}, plane={:?}, offset={}, face_normal={:?}, face={:?}",
+        body_id, plane, offset, face_normal, face
     );
 
     // Create a sketch
@@ -102,6 +103,7 @@ pub fn add_sketch_to_existing_body(
         offset,
         elements: vec![],
         face_normal,
+        face,
         construction: vec![],
         revolve_axis: None,
         symmetry_axis: None,
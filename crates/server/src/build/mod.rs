@@ -1,138 +1,408 @@
 use std::collections::HashMap;
 
-use shared::{BooleanOp, Primitive, SceneDescription, SceneOperation, Transform};
-use vcad::{centered_cube, Part};
-use vcad::export::Material;
-use vcad::export::gltf_export::to_glb_bytes;
+use cad_core::analysis::scene_mass_properties;
+use cad_core::build::{build_body_part, evaluate_feature_bindings, extract_mesh_data};
+use cad_core::export::{build_3mf, build_stl, meshes_by_name, meshes_with_body_colors, GltfOptions, ModelUnit, StlFormat};
+use cad_core::helpers::has_base_geometry;
+use cad_core::mesh::MeshData;
+use shared::{BodyOperation, BooleanOp, BooleanResult, MassReport, SceneDescriptionV2};
+use vcad::Part;
 
-const DEFAULT_SEGMENTS: u32 = 32;
+/// Built solid of a single body (or of a body operation result)
+pub struct BuiltBody {
+    pub id: String,
+    pub name: String,
+    pub part: Part,
+}
+
+/// Result of building a scene: visible solids + per-body errors
+pub struct BuiltScene {
+    pub bodies: Vec<BuiltBody>,
+    pub errors: HashMap<String, String>,
+}
 
-fn create_primitive(id: &str, primitive: &Primitive) -> Part {
-    match primitive {
-        Primitive::Cube { width, height, depth } => centered_cube(id, *width, *height, *depth),
-        Primitive::Cylinder { radius, height } => Part::cylinder(id, *radius, *height, DEFAULT_SEGMENTS),
-        Primitive::Sphere { radius } => Part::sphere(id, *radius, DEFAULT_SEGMENTS),
-        Primitive::Cone { radius, height } => Part::cone(id, *radius, 0.0, *height, DEFAULT_SEGMENTS),
+fn apply_boolean(left: &Part, right: &Part, op: &BooleanOp) -> Part {
+    match op {
+        BooleanOp::Union => left.union(right),
+        BooleanOp::Difference => left.difference(right),
+        BooleanOp::Intersection => left.intersection(right),
     }
 }
 
-fn apply_transform(part: Part, transform: &Transform) -> Part {
-    let [tx, ty, tz] = transform.position;
-    let [rx, ry, rz] = transform.rotation;
-    let [sx, sy, sz] = transform.scale;
+/// Build every body of the scene into a vcad Part.
+///
+/// Hidden bodies are still built because body operations may consume them,
+/// but only visible bodies and operation results end up in `BuiltScene::bodies`.
+pub fn build_scene(scene: &SceneDescriptionV2) -> BuiltScene {
+    // id → (name, part, visible)
+    let mut parts: Vec<(String, String, Part, bool)> = Vec::new();
+    let mut errors: HashMap<String, String> = HashMap::new();
 
-    let mut p = part;
+    let (scene, mut binding_errors) = evaluate_feature_bindings(scene);
 
-    if sx != 1.0 || sy != 1.0 || sz != 1.0 {
-        p = p.scale(sx, sy, sz);
+    for body in &scene.bodies {
+        let mut body_errors = binding_errors.remove(&body.id).unwrap_or_default();
+        match build_body_part(body, &scene.bodies, &mut body_errors) {
+            Some(part) if !part.is_empty() => {
+                parts.push((body.id.clone(), body.name.clone(), part, body.visible));
+            }
+            _ => {
+                // Sketch-only bodies legitimately produce no solid
+                if body.visible && has_base_geometry(body) {
+                    body_errors.insert(0, "Body produced no geometry".to_string());
+                }
+            }
+        }
+        if !body_errors.is_empty() {
+            errors.insert(body.id.clone(), body_errors.join("; "));
+        }
     }
-    if rx != 0.0 || ry != 0.0 || rz != 0.0 {
-        p = p.rotate(rx, ry, rz);
+
+    for body_op in &scene.body_operations {
+        match body_op {
+            BodyOperation::Boolean {
+                id,
+                op,
+                left_body_id,
+                right_body_id,
+                result,
+            } => {
+                let left = parts.iter().position(|(pid, ..)| pid == left_body_id);
+                let right = parts.iter().position(|(pid, ..)| pid == right_body_id);
+                let (Some(left), Some(right)) = (left, right) else {
+                    errors.insert(
+                        id.clone(),
+                        format!("Bodies '{}' / '{}' not found", left_body_id, right_body_id),
+                    );
+                    continue;
+                };
+
+                let combined = apply_boolean(&parts[left].2, &parts[right].2, op);
+                match result {
+                    BooleanResult::MergeIntoLeft => {
+                        parts[left].2 = combined;
+                        parts[right].3 = false;
+                    }
+                    BooleanResult::MergeIntoRight => {
+                        parts[right].2 = combined;
+                        parts[left].3 = false;
+                    }
+                    BooleanResult::CreateNewBody {
+                        new_body_id,
+                        new_body_name,
+                    } => {
+                        // Operands are consumed by the result (also covers chained results)
+                        parts[left].3 = false;
+                        parts[right].3 = false;
+                        parts.push((new_body_id.clone(), new_body_name.clone(), combined, true));
+                    }
+                }
+            }
+        }
     }
-    if tx != 0.0 || ty != 0.0 || tz != 0.0 {
-        p = p.translate(tx, ty, tz);
+
+    let bodies = parts
+        .into_iter()
+        .filter(|(_, _, part, visible)| *visible && !part.is_empty())
+        .map(|(id, name, mut part, _)| {
+            part.name = name.clone();
+            BuiltBody { id, name, part }
+        })
+        .collect();
+
+    BuiltScene { bodies, errors }
+}
+
+/// Meshes of built bodies keyed by body id, in their body colours
+fn built_meshes<'a>(scene: &SceneDescriptionV2, bodies: impl Iterator<Item = &'a BuiltBody>) -> HashMap<String, MeshData> {
+    let meshes: HashMap<_, _> =
+        bodies.filter_map(|body| Some((body.id.clone(), extract_mesh_data(&body.part, false)?))).collect();
+    meshes_with_body_colors(scene, &meshes)
+}
+
+/// Meshes keyed by the names of the built bodies (result bodies of
+/// operations included); empty or repeated names keep the id
+fn meshes_by_built_name(built: &BuiltScene, meshes: &HashMap<String, MeshData>) -> HashMap<String, MeshData> {
+    meshes_by_name(meshes, |id| built.bodies.iter().find(|b| b.id == id).map(|b| b.name.as_str()))
+}
+
+/// Build scene → GLB with one node and material per visible body
+pub fn build_scene_glb(scene: &SceneDescriptionV2) -> Result<Vec<u8>, String> {
+    let built = build_scene(scene);
+
+    for (id, error) in &built.errors {
+        tracing::warn!("Body '{}' skipped: {}", id, error);
     }
 
-    p
+    let meshes = built_meshes(scene, built.bodies.iter());
+    if meshes.is_empty() {
+        return Err("Empty scene".into());
+    }
+    Ok(cad_core::export::build_scene_glb(scene, &meshes, &GltfOptions::default()))
 }
 
-pub fn build_scene_glb(scene: &SceneDescription) -> Result<Vec<u8>, String> {
-    let mut parts: HashMap<String, Part> = HashMap::new();
-    let mut last_id: Option<String> = None;
-
-    for op in &scene.operations {
-        match op {
-            SceneOperation::CreatePrimitive { id, primitive, transform } => {
-                let part = create_primitive(id, primitive);
-                let part = apply_transform(part, transform);
-                parts.insert(id.clone(), part);
-                last_id = Some(id.clone());
-            }
-            SceneOperation::Boolean { id, op: bool_op, left, right } => {
-                let left_part = parts
-                    .get(left)
-                    .ok_or_else(|| format!("Object '{}' not found", left))?;
-                let right_part = parts
-                    .get(right)
-                    .ok_or_else(|| format!("Object '{}' not found", right))?;
-
-                let result = match bool_op {
-                    BooleanOp::Union => left_part.union(right_part),
-                    BooleanOp::Difference => left_part.difference(right_part),
-                    BooleanOp::Intersection => left_part.intersection(right_part),
-                };
+/// Build scene → STL of all visible bodies merged, or of a single body
+pub fn build_scene_stl(scene: &SceneDescriptionV2, format: StlFormat, body_id: Option<&str>) -> Result<Vec<u8>, String> {
+    let built = build_scene(scene);
 
-                parts.insert(id.clone(), result);
-                last_id = Some(id.clone());
-            }
-            SceneOperation::CreateSketch { .. }
-            | SceneOperation::Extrude { .. }
-            | SceneOperation::Revolve { .. }
-            | SceneOperation::Cut { .. } => {
-                // Sketches/Extrude/Revolve/Cut are handled by GUI — skip during server GLB build
-            }
-        }
+    for (id, error) in &built.errors {
+        tracing::warn!("Body '{}' skipped: {}", id, error);
     }
 
-    let final_part = match &last_id {
-        Some(id) => parts.remove(id).ok_or("No parts in scene")?,
-        None => return Err("Empty scene".into()),
-    };
+    let meshes = built_meshes(scene, built.bodies.iter().filter(|body| body_id.is_none_or(|id| body.id == id)));
+    if meshes.is_empty() {
+        return Err(match body_id {
+            Some(id) => format!("Body '{}' not found or empty", id),
+            None => "Empty scene".into(),
+        });
+    }
+    Ok(build_stl(&meshes_by_built_name(&built, &meshes), format))
+}
 
-    let material = Material::default();
-    to_glb_bytes(&final_part, &material).map_err(|e| format!("GLB export error: {}", e))
+/// Build scene → 3MF package with one named object per visible body, in
+/// its body colour
+pub fn build_scene_3mf(scene: &SceneDescriptionV2, unit: ModelUnit) -> Result<Vec<u8>, String> {
+    let built = build_scene(scene);
+
+    for (id, error) in &built.errors {
+        tracing::warn!("Body '{}' skipped: {}", id, error);
+    }
+
+    let meshes = built_meshes(scene, built.bodies.iter());
+    if meshes.is_empty() {
+        return Err("Empty scene".into());
+    }
+    Ok(build_3mf(&meshes_by_built_name(&built, &meshes), unit))
 }
 
-pub fn inspect_scene(scene: &SceneDescription) -> Result<serde_json::Value, String> {
-    let mut parts: HashMap<String, Part> = HashMap::new();
-    let mut last_id: Option<String> = None;
-
-    for op in &scene.operations {
-        match op {
-            SceneOperation::CreatePrimitive { id, primitive, transform } => {
-                let part = create_primitive(id, primitive);
-                let part = apply_transform(part, transform);
-                parts.insert(id.clone(), part);
-                last_id = Some(id.clone());
-            }
-            SceneOperation::Boolean { id, op: bool_op, left, right } => {
-                let left_part = parts
-                    .get(left)
-                    .ok_or_else(|| format!("Object '{}' not found", left))?;
-                let right_part = parts
-                    .get(right)
-                    .ok_or_else(|| format!("Object '{}' not found", right))?;
-
-                let result = match bool_op {
-                    BooleanOp::Union => left_part.union(right_part),
-                    BooleanOp::Difference => left_part.difference(right_part),
-                    BooleanOp::Intersection => left_part.intersection(right_part),
-                };
+/// Inspect scene → per-body metrics plus scene totals
+pub fn inspect_scene(scene: &SceneDescriptionV2) -> Result<serde_json::Value, String> {
+    let built = build_scene(scene);
 
-                parts.insert(id.clone(), result);
-                last_id = Some(id.clone());
-            }
-            SceneOperation::CreateSketch { .. }
-            | SceneOperation::Extrude { .. }
-            | SceneOperation::Revolve { .. }
-            | SceneOperation::Cut { .. } => {
-                // Sketches/Extrude/Revolve/Cut — skip during inspect
-            }
+    if built.bodies.is_empty() {
+        return Err("Empty scene".into());
+    }
+
+    let mut total_volume = 0.0;
+    let mut total_area = 0.0;
+    let mut weighted_com = [0.0; 3];
+    let mut min = [f64::MAX; 3];
+    let mut max = [f64::MIN; 3];
+    let mut bodies = Vec::new();
+
+    for body in &built.bodies {
+        let volume = body.part.volume();
+        let area = body.part.surface_area();
+        let (bmin, bmax) = body.part.bounding_box();
+        let com = body.part.center_of_mass();
+
+        total_volume += volume;
+        total_area += area;
+        for i in 0..3 {
+            weighted_com[i] += com[i] * volume;
+            min[i] = min[i].min(bmin[i]);
+            max[i] = max[i].max(bmax[i]);
         }
+
+        bodies.push(serde_json::json!({
+            "id": body.id,
+            "name": body.name,
+            "volume": volume,
+            "surface_area": area,
+            "bounding_box": { "min": bmin, "max": bmax },
+            "center_of_mass": com,
+            "triangles": body.part.num_triangles(),
+        }));
     }
 
-    let final_part = match &last_id {
-        Some(id) => parts.get(id).ok_or("No parts in scene")?,
-        None => return Err("Empty scene".into()),
+    let center_of_mass = if total_volume.abs() > 1e-12 {
+        weighted_com.map(|c| c / total_volume)
+    } else {
+        [0.0; 3]
     };
 
-    let bbox = final_part.bounding_box();
-    let com = final_part.center_of_mass();
-
     Ok(serde_json::json!({
-        "volume": final_part.volume(),
-        "surface_area": final_part.surface_area(),
-        "bounding_box": { "min": bbox.0, "max": bbox.1 },
-        "center_of_mass": com,
+        "volume": total_volume,
+        "surface_area": total_area,
+        "bounding_box": { "min": min, "max": max },
+        "center_of_mass": center_of_mass,
+        "bodies": bodies,
+        "errors": built.errors,
     }))
 }
+
+/// Mass properties of every visible body (density from the body material)
+/// plus their total
+pub fn mass_properties_scene(scene: &SceneDescriptionV2) -> Result<MassReport, String> {
+    let built = build_scene(scene);
+
+    let meshes: HashMap<_, _> = built
+        .bodies
+        .iter()
+        .filter_map(|body| Some((body.id.clone(), extract_mesh_data(&body.part, false)?)))
+        .collect();
+    if meshes.is_empty() {
+        return Err("Empty scene".into());
+    }
+
+    let mut report = scene_mass_properties(scene, &meshes);
+    // Result bodies of operations have no scene entry: name them as built
+    for entry in &mut report.bodies {
+        if let Some(body) = built.bodies.iter().find(|b| b.id == entry.id) {
+            entry.name = body.name.clone();
+        }
+    }
+    report.errors = built.errors;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{Body, Feature, Point2D, SceneDescription, Primitive, Sketch, SketchElement, SketchPlane, Transform};
+
+    fn cube_body(id: &str, size: f64) -> Body {
+        Body {
+            id: id.to_string(),
+            name: format!("Body {}", id),
+            features: vec![Feature::BasePrimitive {
+                id: format!("{}_prim", id),
+                primitive: Primitive::Cube {
+                    width: size,
+                    height: size,
+                    depth: size,
+                },
+                transform: Transform::new(),
+            }],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        }
+    }
+
+    fn extrude_body(id: &str) -> Body {
+        Body {
+            id: id.to_string(),
+            name: "Extrude".to_string(),
+            features: vec![Feature::BaseExtrude {
+                id: format!("{}_ext", id),
+                sketch: Sketch {
+                    plane: SketchPlane::Xy,
+                    elements: vec![SketchElement::Rectangle {
+                        id: None,
+                        corner: Point2D { x: 0.0, y: 0.0 },
+                        width: 2.0,
+                        height: 1.0,
+                    }],
+                    ..Default::default()
+                },
+                sketch_transform: Transform::new(),
+                height: 3.0,
+                height_backward: 0.0,
+                draft_angle: 0.0,
+            }],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        }
+    }
+
+    fn scene(bodies: Vec<Body>) -> SceneDescriptionV2 {
+        SceneDescriptionV2 {
+            bodies,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_every_visible_body() {
+        let mut s = scene(vec![cube_body("a", 1.0), extrude_body("b"), cube_body("c", 1.0)]);
+        s.bodies[2].visible = false;
+        let built = build_scene(&s);
+        assert!(built.errors.is_empty(), "{:?}", built.errors);
+        let ids: Vec<&str> = built.bodies.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_inspect_extrude_volume() {
+        let info = inspect_scene(&scene(vec![extrude_body("b")])).unwrap();
+        let volume = info["volume"].as_f64().unwrap();
+        assert!((volume - 6.0).abs() < 1e-3, "volume = {}", volume);
+        assert_eq!(info["bodies"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_mass_properties_use_material_density() {
+        let mut steel = cube_body("a", 2.0);
+        let material = shared::Material::preset("Steel");
+        steel.appearance = Some(shared::Appearance { material, ..Default::default() });
+        let report = mass_properties_scene(&scene(vec![steel, cube_body("b", 1.0)])).unwrap();
+
+        assert_eq!(report.bodies.len(), 2);
+        assert_eq!(report.bodies[0].material.as_deref(), Some("Steel"));
+        assert!((report.bodies[0].properties.mass - 8.0 * 7.85e-3).abs() < 1e-6);
+        assert!((report.bodies[1].properties.mass - 1e-3).abs() < 1e-6);
+        assert!((report.total.mass - 0.0638).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_v1_boolean_creates_result_body() {
+        let v1: SceneDescription = serde_json::from_str(
+            r#"{"operations":[
+            {"type":"create_primitive","id":"a","primitive":{"type":"cube","width":2,"height":2,"depth":2},
+             "transform":{"position":[0,0,0],"rotation":[0,0,0],"scale":[1,1,1]}},
+            {"type":"create_primitive","id":"b","primitive":{"type":"cube","width":1,"height":1,"depth":1},
+             "transform":{"position":[0,0,0],"rotation":[0,0,0],"scale":[1,1,1]}},
+            {"type":"boolean","id":"c","op":"difference","left":"a","right":"b"}]}"#,
+        )
+        .unwrap();
+        let built = build_scene(&SceneDescriptionV2::from_v1(&v1));
+        assert_eq!(built.bodies.len(), 1);
+        assert_eq!(built.bodies[0].id, "body_c");
+        assert!((built.bodies[0].part.volume() - 7.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_glb_has_node_per_body() {
+        let mut red = cube_body("a", 1.0);
+        red.appearance = Some(shared::Appearance { color: [1.0, 0.0, 0.0], ..Default::default() });
+        let glb = build_scene_glb(&scene(vec![red, extrude_body("b")])).unwrap();
+        let json_len = u32::from_le_bytes([glb[12], glb[13], glb[14], glb[15]]) as usize;
+        let json: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+        assert_eq!(json["nodes"][0]["children"].as_array().unwrap().len(), 2);
+        assert_eq!(json["nodes"][1]["name"], "Body a");
+        assert_eq!(json["materials"][0]["pbrMetallicRoughness"]["baseColorFactor"], serde_json::json!([1.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_stl_merged_and_single_body() {
+        let s = scene(vec![cube_body("a", 1.0), cube_body("b", 2.0)]);
+        let merged = build_scene_stl(&s, StlFormat::Binary, None).unwrap();
+        let single = build_scene_stl(&s, StlFormat::Binary, Some("b")).unwrap();
+        let count = |stl: &[u8]| u32::from_le_bytes([stl[80], stl[81], stl[82], stl[83]]);
+        assert_eq!(count(&merged), 24);
+        assert_eq!(count(&single), 12);
+
+        let ascii = build_scene_stl(&s, StlFormat::Ascii, Some("a")).unwrap();
+        assert!(ascii.starts_with(b"solid vcad"));
+        assert!(build_scene_stl(&s, StlFormat::Binary, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn test_stl_keeps_bodies_with_same_name() {
+        let mut s = scene(vec![cube_body("a", 1.0), cube_body("b", 2.0)]);
+        s.bodies[1].name = s.bodies[0].name.clone();
+        let merged = build_scene_stl(&s, StlFormat::Binary, None).unwrap();
+        assert_eq!(u32::from_le_bytes([merged[80], merged[81], merged[82], merged[83]]), 24);
+    }
+
+    #[test]
+    fn test_3mf_package() {
+        let package = build_scene_3mf(&scene(vec![cube_body("a", 1.0), cube_body("b", 2.0)]), ModelUnit::Inch).unwrap();
+        assert!(package.starts_with(b"PK\x03\x04"));
+        assert!(build_scene_3mf(&scene(vec![]), ModelUnit::Millimeter).is_err());
+    }
+}
@@ -27,6 +27,9 @@ async fn main() {
         .route("/api/chat", post(routes::chat))
         .route("/api/build", post(routes::build_glb))
         .route("/api/inspect", post(routes::inspect))
+        .route("/api/mass-properties", post(routes::mass_properties))
+        .route("/api/export/stl", post(routes::export_stl))
+        .route("/api/export/3mf", post(routes::export_3mf))
         .route("/api/projects", get(routes::list_projects))
         .route("/api/projects", post(routes::create_project))
         .layer(CorsLayer::permissive())
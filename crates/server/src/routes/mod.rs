@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    extract::State,
+    extract::{Query, State},
     http::{header, StatusCode},
     response::{Json, Response},
 };
@@ -9,7 +9,9 @@ use serde_json::{json, Value};
 use crate::AppState;
 use crate::ai;
 use crate::build;
-use shared::{AiChatRequest, AiChatResponse, SceneDescription};
+use cad_core::export::{ModelUnit, StlFormat};
+use serde::Deserialize;
+use shared::{AiChatRequest, AiChatResponse, MassReport, SceneInput};
 
 /// Health check
 pub async fn health() -> Json<Value> {
@@ -32,10 +34,11 @@ pub async fn list_projects() -> Json<Value> {
     Json(json!({ "projects": [] }))
 }
 
-/// Build scene → GLB binary
+/// Build scene (V2, or V1 migrated to V2) → GLB binary
 pub async fn build_glb(
-    Json(scene): Json<SceneDescription>,
+    Json(scene): Json<SceneInput>,
 ) -> Result<Response, StatusCode> {
+    let scene = scene.into_v2();
     let glb_bytes = tokio::task::spawn_blocking(move || build::build_scene_glb(&scene))
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
@@ -52,10 +55,72 @@ pub async fn build_glb(
         .unwrap())
 }
 
-/// Inspect scene → JSON metrics
+/// Query of `/api/export/stl`: `?format=ascii&body=<id>`
+#[derive(Deserialize)]
+pub struct StlQuery {
+    #[serde(default)]
+    format: StlFormat,
+    /// Export only this body (default: all visible bodies merged)
+    body: Option<String>,
+}
+
+/// Build scene (V2, or V1 migrated to V2) → STL
+pub async fn export_stl(
+    Query(query): Query<StlQuery>,
+    Json(scene): Json<SceneInput>,
+) -> Result<Response, StatusCode> {
+    let scene = scene.into_v2();
+    let StlQuery { format, body } = query;
+    let stl_bytes = tokio::task::spawn_blocking(move || build::build_scene_stl(&scene, format, body.as_deref()))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            tracing::error!("STL export error: {}", e);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "model/stl")
+        .header(header::CONTENT_DISPOSITION, "attachment; filename=\"scene.stl\"")
+        .body(Body::from(stl_bytes))
+        .unwrap())
+}
+
+/// Query of `/api/export/3mf`: `?unit=inch`
+#[derive(Deserialize)]
+pub struct ThreeMfQuery {
+    #[serde(default)]
+    unit: ModelUnit,
+}
+
+/// Build scene (V2, or V1 migrated to V2) → 3MF
+pub async fn export_3mf(
+    Query(query): Query<ThreeMfQuery>,
+    Json(scene): Json<SceneInput>,
+) -> Result<Response, StatusCode> {
+    let scene = scene.into_v2();
+    let package = tokio::task::spawn_blocking(move || build::build_scene_3mf(&scene, query.unit))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            tracing::error!("3MF export error: {}", e);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "model/3mf")
+        .header(header::CONTENT_DISPOSITION, "attachment; filename=\"scene.3mf\"")
+        .body(Body::from(package))
+        .unwrap())
+}
+
+/// Inspect scene (V2, or V1 migrated to V2) → JSON metrics
 pub async fn inspect(
-    Json(scene): Json<SceneDescription>,
+    Json(scene): Json<SceneInput>,
 ) -> Result<Json<Value>, StatusCode> {
+    let scene = scene.into_v2();
     let info = tokio::task::spawn_blocking(move || build::inspect_scene(&scene))
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
@@ -67,6 +132,22 @@ pub async fn inspect(
     Ok(Json(info))
 }
 
+/// Mass properties of every visible body (V2, or V1 migrated to V2)
+pub async fn mass_properties(
+    Json(scene): Json<SceneInput>,
+) -> Result<Json<MassReport>, StatusCode> {
+    let scene = scene.into_v2();
+    let report = tokio::task::spawn_blocking(move || build::mass_properties_scene(&scene))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            tracing::error!("Mass properties error: {}", e);
+            StatusCode::BAD_REQUEST
+        })?;
+
+    Ok(Json(report))
+}
+
 /// Create project (stub)
 pub async fn create_project(
     Json(body): Json<Value>,
@@ -0,0 +1,313 @@
+//! Таблицы исполнений (design tables)
+//!
+//! Строка таблицы — именованное исполнение детали: набор значений
+//! параметров сцены. Столбец `thickness` задаёт глобальный параметр,
+//! `plate.wall` — параметр `wall` тела `plate`. Таблица загружается из
+//! CSV или JSON и хранится в сцене (`SceneDescriptionV2::design_table`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::parameters::{unit_literal, ParameterError};
+use crate::{Parameter, ParameterValue, SceneDescriptionV2};
+
+/// Исполнение: имя и значения параметров (в единицах модели)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Configuration {
+    pub name: String,
+    pub values: HashMap<String, f64>,
+}
+
+/// Таблица исполнений
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DesignTable {
+    pub configurations: Vec<Configuration>,
+    /// Активное исполнение (None — параметры не менялись таблицей)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+}
+
+/// Ошибки таблицы исполнений
+#[derive(Debug, Clone, PartialEq)]
+pub enum DesignTableError {
+    /// Ошибка разбора CSV/JSON (номер строки с 1; 0 — для JSON)
+    Parse { line: usize, message: String },
+    /// Исполнение с таким именем не найдено
+    UnknownConfiguration(String),
+    /// Столбец ссылается на несуществующий параметр
+    Parameter(ParameterError),
+    /// Столбец задаёт параметр-формулу или ссылку: таблица не заменяет их числом
+    NotANumber(String),
+}
+
+impl std::fmt::Display for DesignTableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DesignTableError::Parse { line: 0, message } => write!(f, "Design table: {}", message),
+            DesignTableError::Parse { line, message } => write!(f, "Design table, line {}: {}", line, message),
+            DesignTableError::UnknownConfiguration(name) => write!(f, "Configuration '{}' not found", name),
+            DesignTableError::Parameter(e) => write!(f, "Design table: {}", e),
+            DesignTableError::NotANumber(column) => {
+                write!(f, "Design table: parameter '{}' is a formula or reference, not a number", column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DesignTableError {}
+
+/// Разбить строку CSV на поля (кавычки, `""` внутри кавычек)
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+impl DesignTable {
+    /// Разобрать CSV: первая строка — заголовок, первый столбец — имя
+    /// исполнения, остальные — параметры. Разделитель `,` или `;`
+    /// (с `;` допускается десятичная запятая). Значение — число или число
+    /// с единицей (`10mm`, `1in`); пустая ячейка оставляет параметр как есть.
+    pub fn from_csv(text: &str) -> Result<Self, DesignTableError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let (_, header) = lines.next().ok_or_else(|| DesignTableError::Parse {
+            line: 1,
+            message: "empty table".to_string(),
+        })?;
+        let delimiter = if header.contains(';') && !header.contains(',') { ';' } else { ',' };
+        let columns = split_csv_line(header, delimiter);
+        if columns.len() < 2 || columns[1..].iter().any(String::is_empty) {
+            return Err(DesignTableError::Parse {
+                line: 1,
+                message: "expected header: name, parameter, ...".to_string(),
+            });
+        }
+
+        let mut configurations: Vec<Configuration> = Vec::new();
+        for (line, row) in lines {
+            let cells = split_csv_line(row, delimiter);
+            if cells.len() > columns.len() {
+                return Err(DesignTableError::Parse {
+                    line,
+                    message: format!("{} cells, header has {}", cells.len(), columns.len()),
+                });
+            }
+            let name = cells[0].clone();
+            if name.is_empty() || configurations.iter().any(|c| c.name == name) {
+                return Err(DesignTableError::Parse {
+                    line,
+                    message: format!("configuration name '{}' is empty or duplicated", name),
+                });
+            }
+
+            let mut values = HashMap::new();
+            for (column, cell) in columns.iter().zip(&cells).skip(1) {
+                if cell.is_empty() {
+                    continue;
+                }
+                let number = if delimiter == ';' { cell.replace(',', ".") } else { cell.clone() };
+                let value = number
+                    .parse::<f64>()
+                    .ok()
+                    .or_else(|| unit_literal(&number))
+                    .ok_or_else(|| DesignTableError::Parse {
+                        line,
+                        message: format!("'{}' in column '{}' is not a number", cell, column),
+                    })?;
+                values.insert(column.clone(), value);
+            }
+            configurations.push(Configuration { name, values });
+        }
+
+        Ok(Self { configurations, active: None })
+    }
+
+    /// Разобрать JSON в том же виде, в каком таблица хранится в сцене
+    pub fn from_json(text: &str) -> Result<Self, DesignTableError> {
+        serde_json::from_str(text).map_err(|e| DesignTableError::Parse { line: 0, message: e.to_string() })
+    }
+
+    /// Исполнение по имени
+    pub fn configuration(&self, name: &str) -> Option<&Configuration> {
+        self.configurations.iter().find(|c| c.name == name)
+    }
+}
+
+impl SceneDescriptionV2 {
+    /// Записать значения исполнения из таблицы сцены в параметры и сделать
+    /// его активным. Столбцы могут задавать только числовые параметры:
+    /// формулы и ссылки остаются нетронутыми, исполнение с ними не применяется.
+    ///
+    /// Размеры эскизов и привязки свойств после этого нужно пересчитать
+    /// (`cad_core::configurations::configure_scene` делает всё сразу).
+    pub fn apply_configuration(&mut self, name: &str) -> Result<(), DesignTableError> {
+        let configuration = self
+            .design_table
+            .as_ref()
+            .and_then(|table| table.configuration(name))
+            .cloned()
+            .ok_or_else(|| DesignTableError::UnknownConfiguration(name.to_string()))?;
+
+        // Сначала проверить все столбцы, чтобы не применить исполнение наполовину
+        for column in configuration.values.keys() {
+            if !matches!(self.table_parameter(column)?.value, ParameterValue::Number { .. }) {
+                return Err(DesignTableError::NotANumber(column.clone()));
+            }
+        }
+        for (column, value) in configuration.values {
+            let param = self.table_parameter(&column)?;
+            param.value = ParameterValue::Number { value };
+        }
+
+        if let Some(table) = &mut self.design_table {
+            table.active = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Параметр, заданный столбцом таблицы: `name` или `body.name`
+    fn table_parameter(&mut self, column: &str) -> Result<&mut Parameter, DesignTableError> {
+        let not_found = || DesignTableError::Parameter(ParameterError::NotFound(column.to_string()));
+        match column.split_once('.') {
+            None => self.parameters.get_mut(column).ok_or_else(not_found),
+            Some((body_id, name)) => self
+                .bodies
+                .iter_mut()
+                .find(|b| b.id == body_id)
+                .and_then(|b| b.parameters.get_mut(name))
+                .ok_or_else(not_found),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Body;
+
+    fn number(name: &str, value: f64) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            value: ParameterValue::Number { value },
+            unit: Some("mm".to_string()),
+            description: None,
+        }
+    }
+
+    fn bracket_scene() -> SceneDescriptionV2 {
+        let mut plate = Body {
+            id: "plate".to_string(),
+            name: "Plate".to_string(),
+            features: vec![],
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        };
+        plate.parameters.insert("wall".to_string(), number("wall", 2.0));
+        let mut scene = SceneDescriptionV2 {
+            bodies: vec![plate],
+            ..Default::default()
+        };
+        scene.parameters.insert("thickness".to_string(), number("thickness", 3.0));
+        scene
+    }
+
+    #[test]
+    fn test_csv_parsing() {
+        let csv = "config,thickness,plate.wall\n\
+                   M6,3,2.5\n\
+                   \"M8, long\",4mm,\n\
+                   \n\
+                   M10,0.25in,4\n";
+        let table = DesignTable::from_csv(csv).unwrap();
+
+        assert_eq!(table.configurations.len(), 3);
+        assert_eq!(table.configurations[0].values["plate.wall"], 2.5);
+        let long = table.configuration("M8, long").unwrap();
+        assert_eq!(long.values["thickness"], 4.0);
+        // Пустая ячейка не задаёт значение
+        assert!(!long.values.contains_key("plate.wall"));
+        assert!((table.configurations[2].values["thickness"] - 6.35).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_csv_semicolon_and_errors() {
+        let table = DesignTable::from_csv("name;thickness\nA;2,5\n").unwrap();
+        assert_eq!(table.configurations[0].values["thickness"], 2.5);
+
+        let err = DesignTable::from_csv("name,thickness\nA,1\nA,2\n").unwrap_err();
+        assert!(matches!(err, DesignTableError::Parse { line: 3, .. }));
+        let err = DesignTable::from_csv("name,thickness\nA,thick\n").unwrap_err();
+        assert_eq!(err.to_string(), "Design table, line 2: 'thick' in column 'thickness' is not a number");
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let mut table = DesignTable::from_csv("name,thickness\nA,1\nB,2\n").unwrap();
+        table.active = Some("B".to_string());
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(DesignTable::from_json(&json).unwrap(), table);
+    }
+
+    #[test]
+    fn test_apply_configuration() {
+        let mut scene = bracket_scene();
+        scene.design_table = Some(DesignTable::from_csv("name,thickness,plate.wall\nbig,10,5\n").unwrap());
+
+        scene.apply_configuration("big").unwrap();
+        assert_eq!(scene.evaluate_parameter(None, "thickness"), Ok(10.0));
+        assert_eq!(scene.evaluate_parameter(Some("plate"), "wall"), Ok(5.0));
+        assert_eq!(scene.design_table.as_ref().unwrap().active.as_deref(), Some("big"));
+
+        assert_eq!(
+            scene.apply_configuration("small"),
+            Err(DesignTableError::UnknownConfiguration("small".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_apply_configuration_unknown_parameter_changes_nothing() {
+        let mut scene = bracket_scene();
+        scene.design_table = Some(DesignTable::from_csv("name,thickness,plate.height\nbig,10,5\n").unwrap());
+
+        let err = scene.apply_configuration("big").unwrap_err();
+        assert_eq!(err, DesignTableError::Parameter(ParameterError::NotFound("plate.height".to_string())));
+        assert_eq!(scene.evaluate_parameter(None, "thickness"), Ok(3.0));
+        assert!(scene.design_table.as_ref().unwrap().active.is_none());
+    }
+
+    #[test]
+    fn test_apply_configuration_keeps_formula_parameters() {
+        let mut scene = bracket_scene();
+        let formula = ParameterValue::Formula { expression: "thickness*2".to_string() };
+        scene.bodies[0].parameters.get_mut("wall").unwrap().value = formula.clone();
+        scene.design_table = Some(DesignTable::from_csv("name,thickness,plate.wall
+big,10,5
+").unwrap());
+
+        let err = scene.apply_configuration("big").unwrap_err();
+        assert_eq!(err, DesignTableError::NotANumber("plate.wall".to_string()));
+        assert_eq!(scene.bodies[0].parameters["wall"].value, formula);
+        assert_eq!(scene.evaluate_parameter(None, "thickness"), Ok(3.0));
+    }
+}
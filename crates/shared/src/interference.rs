@@ -0,0 +1,57 @@
+//! Пересечения тел: формат ответа, общий для GUI и команд агента
+//!
+//! Единицы: длина — мм, объём — мм³.
+
+use serde::{Deserialize, Serialize};
+
+/// Пересечение двух тел
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Clash {
+    pub body_a: String,
+    pub name_a: String,
+    pub body_b: String,
+    pub name_b: String,
+    /// Объём общей части, мм³
+    pub volume: f64,
+    /// Габарит общей части, мм
+    pub bbox_min: [f64; 3],
+    pub bbox_max: [f64; 3],
+}
+
+/// Результат проверки пересечений видимых тел
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InterferenceReport {
+    /// Пересечения по убыванию объёма
+    pub clashes: Vec<Clash>,
+    /// Число построенных тел
+    pub body_count: usize,
+    /// Число пар с пересекающимися габаритами (проверенных булевой операцией)
+    pub checked_pairs: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_roundtrip() {
+        let report = InterferenceReport {
+            clashes: vec![Clash {
+                body_a: "body_1".to_string(),
+                name_a: "Shaft".to_string(),
+                body_b: "body_2".to_string(),
+                name_b: "Bushing".to_string(),
+                volume: 12.5,
+                bbox_min: [0.0, 0.0, 0.0],
+                bbox_max: [1.0, 2.0, 3.0],
+            }],
+            body_count: 2,
+            checked_pairs: 1,
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["clashes"][0]["volume"], 12.5);
+        assert_eq!(json["checked_pairs"], 1);
+        let back: InterferenceReport = serde_json::from_value(json).unwrap();
+        assert_eq!(back, report);
+    }
+}
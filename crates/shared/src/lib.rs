@@ -1,11 +1,26 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub mod design_table;
+pub mod interference;
+pub mod mass;
 pub mod parameters;
+pub mod printability;
+mod sketch_migration;
+pub mod topology;
+
+pub use design_table::{Configuration, DesignTable, DesignTableError};
+pub use interference::{Clash, InterferenceReport};
+pub use mass::{BodyMassProperties, MassProperties, MassReport};
+pub use printability::{BodyPrintability, PrintSettings, PrintabilityReport};
+pub use topology::{CapSide, EdgeRef, FaceRef, PrimitiveFace};
 
 /// Уникальный идентификатор объекта в сцене
 pub type ObjectId = String;
 
+/// Идентификатор элемента эскиза (уникален в пределах эскиза)
+pub type ElementId = String;
+
 /// Тип примитива
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -120,58 +135,312 @@ pub enum SketchElement {
         /// Если None, размерная линия автоматически смещается
         #[serde(skip_serializing_if = "Option::is_none", default)]
         dimension_line_pos: Option<Point2D>,
-        /// Индекс целевого элемента (линии, окружности), к которому привязан размер
+        /// Id целевого элемента (линии, окружности), к которому привязан размер
         /// Если установлен, изменение размера будет влиять на этот элемент
         #[serde(skip_serializing_if = "Option::is_none", default)]
-        target_element: Option<usize>,
+        target_element: Option<ElementId>,
         /// Тип размера (линейный, радиус, диаметр)
         #[serde(default)]
         dimension_type: DimensionType,
     },
 }
 
+impl SketchElement {
+    /// Id элемента (у размеров id нет)
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            SketchElement::Line { id, .. }
+            | SketchElement::Circle { id, .. }
+            | SketchElement::Arc { id, .. }
+            | SketchElement::Rectangle { id, .. }
+            | SketchElement::Polyline { id, .. }
+            | SketchElement::Spline { id, .. } => id.as_deref(),
+            SketchElement::Dimension { .. } => None,
+        }
+    }
+
+    /// Установить id элемента (для размеров игнорируется)
+    pub fn set_id(&mut self, new_id: Option<ElementId>) {
+        match self {
+            SketchElement::Line { id, .. }
+            | SketchElement::Circle { id, .. }
+            | SketchElement::Arc { id, .. }
+            | SketchElement::Rectangle { id, .. }
+            | SketchElement::Polyline { id, .. }
+            | SketchElement::Spline { id, .. } => *id = new_id,
+            SketchElement::Dimension { .. } => {}
+        }
+    }
+
+    /// Координаты характерной точки элемента (нумерация как в `PointRef::point_index`)
+    pub fn point(&self, point_index: usize) -> Option<(f64, f64)> {
+        match self {
+            SketchElement::Line { start, end, .. } => match point_index {
+                0 => Some((start.x, start.y)),
+                1 => Some((end.x, end.y)),
+                _ => None,
+            },
+            SketchElement::Circle { center, .. } => match point_index {
+                0 => Some((center.x, center.y)),
+                _ => None,
+            },
+            SketchElement::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                ..
+            } => match point_index {
+                0 => Some((center.x, center.y)),
+                1 => Some((
+                    center.x + radius * start_angle.cos(),
+                    center.y + radius * start_angle.sin(),
+                )),
+                2 => Some((
+                    center.x + radius * end_angle.cos(),
+                    center.y + radius * end_angle.sin(),
+                )),
+                _ => None,
+            },
+            SketchElement::Rectangle {
+                corner,
+                width,
+                height,
+                ..
+            } => match point_index {
+                0 => Some((corner.x, corner.y)),
+                1 => Some((corner.x + width, corner.y)),
+                2 => Some((corner.x + width, corner.y + height)),
+                3 => Some((corner.x, corner.y + height)),
+                _ => None,
+            },
+            SketchElement::Polyline { points, .. } | SketchElement::Spline { points, .. } => {
+                points.get(point_index).map(|p| (p.x, p.y))
+            }
+            SketchElement::Dimension { .. } => None,
+        }
+    }
+
+    /// Все характерные точки элемента по порядку `point_index`
+    pub fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        (0..).map_while(|pi| self.point(pi))
+    }
+}
+
 /// Ссылка на точку элемента эскиза
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PointRef {
-    /// Индекс элемента в массиве elements
-    pub element_index: usize,
+    /// Id элемента
+    pub element_id: ElementId,
     /// Индекс точки внутри элемента (0=start, 1=end для линии, и т.д.)
     pub point_index: usize,
 }
 
 /// Геометрическое ограничение (constraint) эскиза
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "type", rename_all = "lowercase")]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum SketchConstraint {
     /// Линия горизонтальна (параллельна оси X)
-    Horizontal { element: usize },
+    Horizontal { element: ElementId },
     /// Линия вертикальна (параллельна оси Y)
-    Vertical { element: usize },
+    Vertical { element: ElementId },
     /// Две линии параллельны
-    Parallel { element1: usize, element2: usize },
+    Parallel { element1: ElementId, element2: ElementId },
     /// Две линии перпендикулярны
-    Perpendicular { element1: usize, element2: usize },
+    Perpendicular { element1: ElementId, element2: ElementId },
     /// Две точки совпадают
     Coincident { point1: PointRef, point2: PointRef },
     /// Элемент зафиксирован (не может быть перемещён)
-    Fixed { element: usize },
+    Fixed { element: ElementId },
     /// Равные длины линий или радиусы окружностей
-    Equal { element1: usize, element2: usize },
+    Equal { element1: ElementId, element2: ElementId },
     /// Касательная (линия касается окружности/дуги)
-    Tangent { element1: usize, element2: usize },
+    Tangent { element1: ElementId, element2: ElementId },
     /// Концентрические окружности (совпадающие центры)
-    Concentric { element1: usize, element2: usize },
+    Concentric { element1: ElementId, element2: ElementId },
     /// Симметрия относительно линии
     Symmetric {
-        element1: usize,
-        element2: usize,
+        element1: ElementId,
+        element2: ElementId,
         /// Линия симметрии (ось)
-        axis: usize,
+        axis: ElementId,
+    },
+    /// Расстояние между двумя точками
+    Distance {
+        point1: PointRef,
+        point2: PointRef,
+        value: f64,
+        /// Имя параметра тела, задающего значение (если есть)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
+    },
+    /// Расстояние от точки до линии (бесконечной прямой)
+    DistancePointLine {
+        point: PointRef,
+        line: ElementId,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
+    },
+    /// Расстояние между точками по оси X
+    HorizontalDistance {
+        point1: PointRef,
+        point2: PointRef,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
+    },
+    /// Расстояние между точками по оси Y
+    VerticalDistance {
+        point1: PointRef,
+        point2: PointRef,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
+    },
+    /// Угол между двумя линиями (в градусах)
+    Angle {
+        element1: ElementId,
+        element2: ElementId,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
+    },
+    /// Радиус окружности или дуги
+    Radius {
+        element: ElementId,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
+    },
+    /// Диаметр окружности или дуги
+    Diameter {
+        element: ElementId,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameter: Option<String>,
     },
+    /// Точка лежит на линии (бесконечной прямой)
+    PointOnLine { point: PointRef, line: ElementId },
+    /// Точка лежит на окружности или дуге
+    PointOnCircle { point: PointRef, circle: ElementId },
+    /// Точка — середина линии
+    Midpoint { point: PointRef, line: ElementId },
+}
+
+impl SketchConstraint {
+    /// Id всех элементов, на которые ссылается ограничение
+    pub fn element_refs(&self) -> Vec<&ElementId> {
+        match self {
+            SketchConstraint::Horizontal { element }
+            | SketchConstraint::Vertical { element }
+            | SketchConstraint::Fixed { element }
+            | SketchConstraint::Radius { element, .. }
+            | SketchConstraint::Diameter { element, .. } => vec![element],
+            SketchConstraint::Parallel { element1, element2 }
+            | SketchConstraint::Perpendicular { element1, element2 }
+            | SketchConstraint::Equal { element1, element2 }
+            | SketchConstraint::Tangent { element1, element2 }
+            | SketchConstraint::Concentric { element1, element2 }
+            | SketchConstraint::Angle { element1, element2, .. } => vec![element1, element2],
+            SketchConstraint::Symmetric { element1, element2, axis } => vec![element1, element2, axis],
+            SketchConstraint::Coincident { point1, point2 }
+            | SketchConstraint::Distance { point1, point2, .. }
+            | SketchConstraint::HorizontalDistance { point1, point2, .. }
+            | SketchConstraint::VerticalDistance { point1, point2, .. } => {
+                vec![&point1.element_id, &point2.element_id]
+            }
+            SketchConstraint::DistancePointLine { point, line, .. }
+            | SketchConstraint::PointOnLine { point, line }
+            | SketchConstraint::Midpoint { point, line } => vec![&point.element_id, line],
+            SketchConstraint::PointOnCircle { point, circle } => vec![&point.element_id, circle],
+        }
+    }
+
+    /// Ссылки на точки элементов (для переназначения при обрезке)
+    pub fn point_refs_mut(&mut self) -> Vec<&mut PointRef> {
+        match self {
+            SketchConstraint::Coincident { point1, point2 }
+            | SketchConstraint::Distance { point1, point2, .. }
+            | SketchConstraint::HorizontalDistance { point1, point2, .. }
+            | SketchConstraint::VerticalDistance { point1, point2, .. } => vec![point1, point2],
+            SketchConstraint::DistancePointLine { point, .. }
+            | SketchConstraint::PointOnLine { point, .. }
+            | SketchConstraint::Midpoint { point, .. }
+            | SketchConstraint::PointOnCircle { point, .. } => vec![point],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Ссылается ли ограничение на элемент
+    pub fn references(&self, id: &str) -> bool {
+        self.element_refs().iter().any(|r| r.as_str() == id)
+    }
+
+    /// Значение размерного ограничения (None для геометрических)
+    pub fn dimension_value(&self) -> Option<f64> {
+        match self {
+            SketchConstraint::Distance { value, .. }
+            | SketchConstraint::DistancePointLine { value, .. }
+            | SketchConstraint::HorizontalDistance { value, .. }
+            | SketchConstraint::VerticalDistance { value, .. }
+            | SketchConstraint::Angle { value, .. }
+            | SketchConstraint::Radius { value, .. }
+            | SketchConstraint::Diameter { value, .. } => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Установить значение размерного ограничения
+    pub fn set_dimension_value(&mut self, new_value: f64) {
+        match self {
+            SketchConstraint::Distance { value, .. }
+            | SketchConstraint::DistancePointLine { value, .. }
+            | SketchConstraint::HorizontalDistance { value, .. }
+            | SketchConstraint::VerticalDistance { value, .. }
+            | SketchConstraint::Angle { value, .. }
+            | SketchConstraint::Radius { value, .. }
+            | SketchConstraint::Diameter { value, .. } => *value = new_value,
+            _ => {}
+        }
+    }
+
+    /// Параметр тела, к которому привязано значение
+    pub fn parameter(&self) -> Option<&str> {
+        match self {
+            SketchConstraint::Distance { parameter, .. }
+            | SketchConstraint::DistancePointLine { parameter, .. }
+            | SketchConstraint::HorizontalDistance { parameter, .. }
+            | SketchConstraint::VerticalDistance { parameter, .. }
+            | SketchConstraint::Angle { parameter, .. }
+            | SketchConstraint::Radius { parameter, .. }
+            | SketchConstraint::Diameter { parameter, .. } => parameter.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Привязать значение к параметру тела (None — отвязать)
+    pub fn set_parameter(&mut self, name: Option<String>) {
+        match self {
+            SketchConstraint::Distance { parameter, .. }
+            | SketchConstraint::DistancePointLine { parameter, .. }
+            | SketchConstraint::HorizontalDistance { parameter, .. }
+            | SketchConstraint::VerticalDistance { parameter, .. }
+            | SketchConstraint::Angle { parameter, .. }
+            | SketchConstraint::Radius { parameter, .. }
+            | SketchConstraint::Diameter { parameter, .. } => *parameter = name,
+            _ => {}
+        }
+    }
 }
 
 /// Эскиз — набор 2D-элементов на плоскости
+///
+/// Все ссылки на элементы (ограничения, оси, вспомогательная геометрия, размеры)
+/// хранятся по id элемента, поэтому удаление и обрезка не сдвигают их.
+/// Старые файлы с индексами мигрируются при десериализации.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "sketch_migration::SketchRaw")]
 pub struct Sketch {
     pub plane: SketchPlane,
     pub offset: f64,
@@ -180,16 +449,19 @@ pub struct Sketch {
     /// Если None — используется направление по умолчанию (в сторону отрицательной оси)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub face_normal: Option<[f64; 3]>,
-    /// Флаги вспомогательной геометрии (параллельный вектор к elements)
-    /// true = вспомогательная геометрия (не используется для 3D)
+    /// Грань, на которой создан эскиз; смещение плоскости пересчитывается
+    /// по ней при каждой перестройке
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub face: Option<FaceRef>,
+    /// Id элементов вспомогательной геометрии (не используется для 3D)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub construction: Vec<bool>,
-    /// Индекс элемента, помеченного как ось вращения (только один на эскиз)
+    pub construction: Vec<ElementId>,
+    /// Id элемента, помеченного как ось вращения (только один на эскиз)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub revolve_axis: Option<usize>,
-    /// Индекс элемента, помеченного как ось симметрии (только один на эскиз)
+    pub revolve_axis: Option<ElementId>,
+    /// Id элемента, помеченного как ось симметрии (только один на эскиз)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub symmetry_axis: Option<usize>,
+    pub symmetry_axis: Option<ElementId>,
     /// Геометрические ограничения (constraints) эскиза
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub constraints: Vec<SketchConstraint>,
@@ -202,6 +474,7 @@ impl Default for Sketch {
             offset: 0.0,
             elements: Vec::new(),
             face_normal: None,
+            face: None,
             construction: Vec::new(),
             revolve_axis: None,
             symmetry_axis: None,
@@ -211,45 +484,212 @@ impl Default for Sketch {
 }
 
 impl Sketch {
+    /// Индекс элемента по id
+    pub fn element_index(&self, id: &str) -> Option<usize> {
+        self.elements.iter().position(|e| e.id() == Some(id))
+    }
+
+    /// Id элемента по индексу
+    pub fn element_id(&self, index: usize) -> Option<&str> {
+        self.elements.get(index).and_then(|e| e.id())
+    }
+
+    /// Элемент по id
+    pub fn element_by_id(&self, id: &str) -> Option<&SketchElement> {
+        self.elements.iter().find(|e| e.id() == Some(id))
+    }
+
+    /// Следующий свободный id вида `e<N>`
+    pub fn next_element_id(&self) -> ElementId {
+        let max = self
+            .elements
+            .iter()
+            .filter_map(|e| e.id())
+            .filter_map(|id| id.strip_prefix('e')?.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("e{}", max + 1)
+    }
+
+    /// Назначить id всем элементам, у которых его нет
+    pub fn ensure_element_ids(&mut self) {
+        for i in 0..self.elements.len() {
+            if self.elements[i].id().is_none() && !matches!(self.elements[i], SketchElement::Dimension { .. }) {
+                let id = self.next_element_id();
+                self.elements[i].set_id(Some(id));
+            }
+        }
+    }
+
+    /// Добавить элемент, назначив ему id (если его нет); возвращает индекс
+    pub fn push_element(&mut self, element: SketchElement) -> usize {
+        self.insert_element(self.elements.len(), element)
+    }
+
+    /// Вставить элемент в позицию, назначив ему id (если его нет или он занят)
+    pub fn insert_element(&mut self, index: usize, mut element: SketchElement) -> usize {
+        let taken = element.id().is_some_and(|id| self.element_index(id).is_some());
+        if !matches!(element, SketchElement::Dimension { .. }) && (element.id().is_none() || taken) {
+            element.set_id(Some(self.next_element_id()));
+        }
+        let index = index.min(self.elements.len());
+        self.elements.insert(index, element);
+        index
+    }
+
+    /// Удалить элементы по индексам вместе со всем, что на них ссылается:
+    /// размерами, ограничениями, флагами вспомогательной геометрии и осями
+    pub fn remove_elements(&mut self, indices: &[usize]) {
+        let mut removed: Vec<usize> = indices.iter().copied().filter(|&i| i < self.elements.len()).collect();
+        let removed_ids: Vec<ElementId> = removed
+            .iter()
+            .filter_map(|&i| self.element_id(i).map(|s| s.to_string()))
+            .collect();
+
+        // Размеры, привязанные к удаляемым элементам, удаляются вместе с ними
+        for (i, elem) in self.elements.iter().enumerate() {
+            if let SketchElement::Dimension { target_element: Some(target), .. } = elem {
+                if removed_ids.contains(target) {
+                    removed.push(i);
+                }
+            }
+        }
+        removed.sort_unstable_by(|a, b| b.cmp(a));
+        removed.dedup();
+        for i in removed {
+            self.elements.remove(i);
+        }
+
+        self.constraints
+            .retain(|c| !removed_ids.iter().any(|id| c.references(id)));
+        self.construction.retain(|id| !removed_ids.contains(id));
+        if self.revolve_axis.as_ref().is_some_and(|id| removed_ids.contains(id)) {
+            self.revolve_axis = None;
+        }
+        if self.symmetry_axis.as_ref().is_some_and(|id| removed_ids.contains(id)) {
+            self.symmetry_axis = None;
+        }
+    }
+
+    /// Заменить элемент одним или несколькими новыми (обрезка, разбиение).
+    ///
+    /// Первый новый элемент наследует id исходного, поэтому ограничения на весь
+    /// элемент остаются на нём. Ссылки на точки переносятся на тот новый элемент,
+    /// у которого есть точка в том же месте; ограничения на исчезнувшие точки удаляются.
+    pub fn replace_element(&mut self, index: usize, new_elements: Vec<SketchElement>) {
+        let Some(old) = self.elements.get(index).cloned() else { return };
+        let Some(old_id) = old.id().map(|s| s.to_string()) else {
+            // Без id ссылок на элемент быть не может
+            self.elements.remove(index);
+            for (i, elem) in new_elements.into_iter().enumerate() {
+                self.insert_element(index + i, elem);
+            }
+            return;
+        };
+
+        self.elements.remove(index);
+        let mut new_ids = Vec::new();
+        for (i, mut elem) in new_elements.into_iter().enumerate() {
+            elem.set_id(if i == 0 { Some(old_id.clone()) } else { None });
+            let at = self.insert_element(index + i, elem);
+            new_ids.push((at, self.element_id(at).map(|s| s.to_string())));
+        }
+
+        if new_ids.is_empty() {
+            self.remove_references(&old_id);
+            return;
+        }
+
+        const EPS: f64 = 1e-6;
+        let mut dropped = Vec::new();
+        for (ci, constraint) in self.constraints.iter_mut().enumerate() {
+            for point in constraint.point_refs_mut() {
+                if point.element_id != old_id {
+                    continue;
+                }
+                let Some(pos) = old.point(point.point_index) else { continue };
+                // Найти новый элемент с точкой в той же позиции
+                let target = new_ids.iter().find_map(|(at, id)| {
+                    let elem = &self.elements[*at];
+                    elem.points()
+                        .position(|p| (p.0 - pos.0).abs() < EPS && (p.1 - pos.1).abs() < EPS)
+                        .map(|pi| (id.clone(), pi))
+                });
+                match target {
+                    Some((Some(id), pi)) => {
+                        point.element_id = id;
+                        point.point_index = pi;
+                    }
+                    _ => dropped.push(ci),
+                }
+            }
+        }
+        dropped.dedup();
+        for ci in dropped.into_iter().rev() {
+            self.constraints.remove(ci);
+        }
+    }
+
+    /// Удалить все ссылки на элемент (ограничения, флаги, оси, размеры)
+    fn remove_references(&mut self, id: &str) {
+        self.constraints.retain(|c| !c.references(id));
+        self.construction.retain(|c| c != id);
+        if self.revolve_axis.as_deref() == Some(id) {
+            self.revolve_axis = None;
+        }
+        if self.symmetry_axis.as_deref() == Some(id) {
+            self.symmetry_axis = None;
+        }
+        self.elements.retain(|e| {
+            !matches!(e, SketchElement::Dimension { target_element: Some(t), .. } if t == id)
+        });
+    }
+
     /// Проверить, является ли элемент вспомогательной геометрией
     pub fn is_construction(&self, index: usize) -> bool {
-        self.construction.get(index).copied().unwrap_or(false)
+        self.element_id(index)
+            .is_some_and(|id| self.construction.iter().any(|c| c == id))
     }
 
     /// Установить флаг вспомогательной геометрии
     pub fn set_construction(&mut self, index: usize, value: bool) {
-        // Расширить вектор если нужно
-        while self.construction.len() <= index {
-            self.construction.push(false);
+        let Some(id) = self.element_id(index).map(|s| s.to_string()) else { return };
+        self.construction.retain(|c| *c != id);
+        if value {
+            self.construction.push(id);
         }
-        self.construction[index] = value;
     }
 
     /// Получить только не-вспомогательные элементы (для формирования 3D)
     pub fn geometry_elements(&self) -> impl Iterator<Item = (usize, &SketchElement)> {
-        self.elements.iter().enumerate().filter(|(i, _)| !self.is_construction(*i) && self.revolve_axis != Some(*i) && self.symmetry_axis != Some(*i))
+        self.elements.iter().enumerate().filter(|(i, _)| !self.is_construction(*i) && !self.is_revolve_axis(*i) && !self.is_symmetry_axis(*i))
+    }
+
+    /// Индекс элемента — оси вращения
+    pub fn revolve_axis_index(&self) -> Option<usize> {
+        self.revolve_axis.as_deref().and_then(|id| self.element_index(id))
     }
 
     /// Проверить, является ли элемент осью вращения
     pub fn is_revolve_axis(&self, index: usize) -> bool {
-        self.revolve_axis == Some(index)
+        self.revolve_axis.is_some() && self.element_id(index) == self.revolve_axis.as_deref()
     }
 
     /// Установить/сбросить элемент как ось вращения (toggle)
     /// Возвращает true если ось установлена, false если сброшена
     pub fn toggle_revolve_axis(&mut self, index: usize) -> bool {
-        if self.revolve_axis == Some(index) {
+        if self.is_revolve_axis(index) {
             self.revolve_axis = None;
             false
         } else {
-            self.revolve_axis = Some(index);
-            true
+            self.revolve_axis = self.element_id(index).map(|s| s.to_string());
+            self.revolve_axis.is_some()
         }
     }
 
     /// Получить ось вращения как элемент (если установлена и это линия)
     pub fn get_revolve_axis_line(&self) -> Option<(usize, &SketchElement)> {
-        self.revolve_axis.and_then(|idx| {
+        self.revolve_axis_index().and_then(|idx| {
             self.elements.get(idx).and_then(|el| {
                 if matches!(el, SketchElement::Line { .. }) {
                     Some((idx, el))
@@ -260,26 +700,31 @@ impl Sketch {
         })
     }
 
+    /// Индекс элемента — оси симметрии
+    pub fn symmetry_axis_index(&self) -> Option<usize> {
+        self.symmetry_axis.as_deref().and_then(|id| self.element_index(id))
+    }
+
     /// Проверить, является ли элемент осью симметрии
     pub fn is_symmetry_axis(&self, index: usize) -> bool {
-        self.symmetry_axis == Some(index)
+        self.symmetry_axis.is_some() && self.element_id(index) == self.symmetry_axis.as_deref()
     }
 
     /// Установить/сбросить элемент как ось симметрии (toggle)
     /// Возвращает true если ось установлена, false если сброшена
     pub fn toggle_symmetry_axis(&mut self, index: usize) -> bool {
-        if self.symmetry_axis == Some(index) {
+        if self.is_symmetry_axis(index) {
             self.symmetry_axis = None;
             false
         } else {
-            self.symmetry_axis = Some(index);
-            true
+            self.symmetry_axis = self.element_id(index).map(|s| s.to_string());
+            self.symmetry_axis.is_some()
         }
     }
 
     /// Получить ось симметрии как элемент (если установлена и это линия)
     pub fn get_symmetry_axis_line(&self) -> Option<(usize, &SketchElement)> {
-        self.symmetry_axis.and_then(|idx| {
+        self.symmetry_axis_index().and_then(|idx| {
             self.elements.get(idx).and_then(|el| {
                 if matches!(el, SketchElement::Line { .. }) {
                     Some((idx, el))
@@ -396,6 +841,16 @@ pub struct ParameterRef {
     pub property: String,
 }
 
+/// Ссылка в том виде, как она пишется в формулах: `body.feature.property`
+impl std::fmt::Display for ParameterRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in [&self.body_id, &self.feature_id].into_iter().flatten() {
+            write!(f, "{}.", part)?;
+        }
+        write!(f, "{}", self.property)
+    }
+}
+
 /// Значение параметра
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -450,6 +905,131 @@ pub struct Body {
     /// Параметры тела (для параметрического моделирования)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub parameters: HashMap<String, Parameter>,
+    /// Привязки числовых свойств фич к формулам: `"extrude_1.height"` → `"thickness*2"`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub bindings: HashMap<String, String>,
+    /// Внешний вид (цвет, прозрачность, материал); None — вид по умолчанию
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appearance: Option<Appearance>,
+}
+
+/// Внешний вид тела: учитывается рендером и экспортом (GLB, 3MF)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Appearance {
+    /// Цвет RGB, компоненты 0..1
+    #[serde(default = "default_body_color")]
+    pub color: [f32; 3],
+    /// Непрозрачность 0..1 (1 — непрозрачное)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Материал (плотность, металличность/шероховатость для PBR)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<Material>,
+}
+
+/// Цвет тела по умолчанию
+pub const DEFAULT_BODY_COLOR: [f32; 3] = [0.6, 0.6, 0.65];
+
+fn default_body_color() -> [f32; 3] {
+    DEFAULT_BODY_COLOR
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            color: DEFAULT_BODY_COLOR,
+            opacity: 1.0,
+            material: None,
+        }
+    }
+}
+
+impl Appearance {
+    /// Прозрачное ли тело (нужно смешивание при отрисовке)
+    pub fn is_transparent(&self) -> bool {
+        self.opacity < 1.0
+    }
+}
+
+/// Именованный материал
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Material {
+    /// Название ("Steel", "PLA", ...)
+    pub name: String,
+    /// Плотность, г/см³
+    pub density: f64,
+    /// Металличность для PBR, 0..1
+    #[serde(default)]
+    pub metallic: f32,
+    /// Шероховатость для PBR, 0..1
+    #[serde(default = "default_roughness")]
+    pub roughness: f32,
+}
+
+fn default_roughness() -> f32 {
+    0.5
+}
+
+/// Типовые материалы: название, плотность (г/см³), металличность, шероховатость
+const MATERIAL_PRESETS: [(&str, f64, f32, f32); 8] = [
+    ("Steel", 7.85, 0.9, 0.4),
+    ("Stainless Steel", 8.0, 0.9, 0.3),
+    ("Aluminium", 2.7, 0.9, 0.35),
+    ("Brass", 8.5, 0.9, 0.3),
+    ("Titanium", 4.43, 0.9, 0.4),
+    ("PLA", 1.24, 0.0, 0.6),
+    ("ABS", 1.04, 0.0, 0.6),
+    ("Wood (pine)", 0.5, 0.0, 0.8),
+];
+
+impl Material {
+    /// Все типовые материалы
+    pub fn presets() -> Vec<Material> {
+        MATERIAL_PRESETS
+            .iter()
+            .map(|&(name, density, metallic, roughness)| Material {
+                name: name.to_string(),
+                density,
+                metallic,
+                roughness,
+            })
+            .collect()
+    }
+
+    /// Типовой материал по названию (без учёта регистра)
+    pub fn preset(name: &str) -> Option<Material> {
+        Self::presets().into_iter().find(|m| m.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Треугольная сетка, импортированная из файла (STL, OBJ)
+///
+/// Хранится в сцене целиком, чтобы сцена не зависела от внешних файлов.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ImportedMesh {
+    /// Координаты вершин подряд (x, y, z), мм
+    pub positions: Vec<f32>,
+    /// Индексы вершин треугольников (по три на треугольник)
+    pub indices: Vec<u32>,
+    /// Имя исходного файла (для отображения)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl ImportedMesh {
+    /// Количество вершин
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len() / 3
+    }
+
+    /// Количество треугольников
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
 }
 
 /// Фича (Feature) — операция внутри тела
@@ -483,6 +1063,13 @@ pub enum Feature {
         angle: f64,
         segments: u32,
     },
+    /// Базовая сетка — импортированный меш (должен быть замкнутым
+    /// многообразием, чтобы участвовать в булевых операциях)
+    BaseMesh {
+        id: ObjectId,
+        mesh: ImportedMesh,
+        transform: Transform,
+    },
 
     // ── Референсная геометрия ──
 
@@ -542,16 +1129,24 @@ pub enum Feature {
         radius: f64,
         /// Количество сегментов
         segments: u32,
-        /// Выбранные рёбра (start, end, normal1, normal2)
+        /// Рёбра, заданные координатами (start, end, normal1, normal2);
+        /// используются для рёбер без топологического имени
         edges: Vec<([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)>,
+        /// Рёбра по топологическим именам, разрешаются при перестройке
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        edge_refs: Vec<EdgeRef>,
     },
     /// 3D фаска рёбер
     Chamfer3D {
         id: ObjectId,
         /// Расстояние фаски
         distance: f64,
-        /// Выбранные рёбра (start, end, normal1, normal2)
+        /// Рёбра, заданные координатами (start, end, normal1, normal2);
+        /// используются для рёбер без топологического имени
         edges: Vec<([f64; 3], [f64; 3], [f64; 3], Option<[f64; 3]>)>,
+        /// Рёбра по топологическим именам, разрешаются при перестройке
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        edge_refs: Vec<EdgeRef>,
     },
 }
 
@@ -562,6 +1157,7 @@ impl Feature {
             Feature::BasePrimitive { id, .. } => id,
             Feature::BaseExtrude { id, .. } => id,
             Feature::BaseRevolve { id, .. } => id,
+            Feature::BaseMesh { id, .. } => id,
             Feature::Sketch { id, .. } => id,
             Feature::Extrude { id, .. } => id,
             Feature::Revolve { id, .. } => id,
@@ -612,6 +1208,12 @@ pub struct SceneDescriptionV2 {
     /// Операции между телами
     #[serde(default)]
     pub body_operations: Vec<BodyOperation>,
+    /// Глобальные параметры сцены (доступны в формулах всех тел)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, Parameter>,
+    /// Таблица исполнений: именованные наборы значений параметров
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub design_table: Option<DesignTable>,
 }
 
 impl Default for SceneDescriptionV2 {
@@ -620,6 +1222,29 @@ impl Default for SceneDescriptionV2 {
             version: 2,
             bodies: Vec::new(),
             body_operations: Vec::new(),
+            parameters: HashMap::new(),
+            design_table: None,
+        }
+    }
+}
+
+/// Сцена в любом поддерживаемом формате (для загрузки JSON извне)
+///
+/// V2 (на основе тел) используется как есть, V1 (плоский список операций)
+/// мигрируется через `SceneDescriptionV2::from_v1`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum SceneInput {
+    V2(SceneDescriptionV2),
+    V1(SceneDescription),
+}
+
+impl SceneInput {
+    /// Привести к V2
+    pub fn into_v2(self) -> SceneDescriptionV2 {
+        match self {
+            SceneInput::V2(scene) => scene,
+            SceneInput::V1(scene) => SceneDescriptionV2::from_v1(&scene),
         }
     }
 }
@@ -649,6 +1274,8 @@ impl SceneDescriptionV2 {
                         }],
                         visible: true,
                         parameters: HashMap::new(),
+                        bindings: HashMap::new(),
+                        appearance: None,
                     });
                     id_to_body.insert(id.clone(), body_id);
                 }
@@ -675,6 +1302,8 @@ impl SceneDescriptionV2 {
                             }],
                             visible: true,
                             parameters: HashMap::new(),
+                            bindings: HashMap::new(),
+                            appearance: None,
                         });
                         id_to_body.insert(id.clone(), body_id);
                     }
@@ -695,6 +1324,8 @@ impl SceneDescriptionV2 {
                             }],
                             visible: true,
                             parameters: HashMap::new(),
+                            bindings: HashMap::new(),
+                            appearance: None,
                         });
                         id_to_body.insert(id.clone(), body_id);
                     }
@@ -761,6 +1392,8 @@ impl SceneDescriptionV2 {
             version: 2,
             bodies,
             body_operations: body_ops,
+            parameters: HashMap::new(),
+            design_table: None,
         }
     }
 
@@ -912,6 +1545,7 @@ mod tests {
     #[test]
     fn test_sketch_element_line_serde() {
         let e = SketchElement::Line {
+            id: None,
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 1.0, y: 2.0 },
         };
@@ -923,6 +1557,7 @@ mod tests {
     #[test]
     fn test_sketch_element_circle_serde() {
         let e = SketchElement::Circle {
+            id: None,
             center: Point2D { x: 1.0, y: 2.0 },
             radius: 3.0,
         };
@@ -932,6 +1567,7 @@ mod tests {
     #[test]
     fn test_sketch_element_arc_serde() {
         let e = SketchElement::Arc {
+            id: None,
             center: Point2D { x: 0.0, y: 0.0 },
             radius: 1.0,
             start_angle: 0.0,
@@ -943,6 +1579,7 @@ mod tests {
     #[test]
     fn test_sketch_element_rectangle_serde() {
         let e = SketchElement::Rectangle {
+            id: None,
             corner: Point2D { x: -1.0, y: -1.0 },
             width: 2.0,
             height: 3.0,
@@ -953,6 +1590,7 @@ mod tests {
     #[test]
     fn test_sketch_element_polyline_serde() {
         let e = SketchElement::Polyline {
+            id: None,
             points: vec![
                 Point2D { x: 0.0, y: 0.0 },
                 Point2D { x: 1.0, y: 0.0 },
@@ -976,6 +1614,148 @@ mod tests {
         roundtrip(&e);
     }
 
+    #[test]
+    fn test_sketch_constraint_dimensional_serde() {
+        let c = SketchConstraint::HorizontalDistance {
+            point1: PointRef { element_id: "e1".to_string(), point_index: 0 },
+            point2: PointRef { element_id: "e2".to_string(), point_index: 1 },
+            value: 12.5,
+            parameter: Some("width".to_string()),
+        };
+        roundtrip(&c);
+
+        let json = serde_json::to_value(&c).unwrap();
+        assert_eq!(json["type"], "horizontal_distance");
+        assert_eq!(c.dimension_value(), Some(12.5));
+        assert_eq!(c.parameter(), Some("width"));
+
+        let h: SketchConstraint = serde_json::from_str(r#"{"type":"horizontal","element":"e3"}"#).unwrap();
+        assert_eq!(h, SketchConstraint::Horizontal { element: "e3".to_string() });
+        assert_eq!(h.dimension_value(), None);
+    }
+
+    fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> SketchElement {
+        SketchElement::Line {
+            id: None,
+            start: Point2D { x: x1, y: y1 },
+            end: Point2D { x: x2, y: y2 },
+        }
+    }
+
+    #[test]
+    fn test_sketch_migrates_index_references() {
+        // Формат до перехода на id: индексы в ограничениях, осях, флагах и размерах
+        let json = r#"{
+            "plane": "XY", "offset": 0.0,
+            "elements": [
+                {"type":"line","start":{"x":0,"y":0},"end":{"x":1,"y":0}},
+                {"type":"line","id":"axis","start":{"x":0,"y":0},"end":{"x":0,"y":1}},
+                {"type":"circle","center":{"x":2,"y":2},"radius":1},
+                {"type":"dimension","from":{"x":0,"y":0},"to":{"x":1,"y":0},"value":1.0,
+                 "parameter_name":null,"target_element":0}
+            ],
+            "construction": [false, false, true],
+            "revolve_axis": 1,
+            "constraints": [
+                {"type":"horizontal","element":0},
+                {"type":"coincident","point1":{"element_index":0,"point_index":0},
+                 "point2":{"element_index":1,"point_index":0}},
+                {"type":"vertical","element":7}
+            ]
+        }"#;
+        let sketch: Sketch = serde_json::from_str(json).unwrap();
+
+        assert_eq!(sketch.element_id(0), Some("e1"));
+        assert_eq!(sketch.element_id(1), Some("axis"));
+        assert_eq!(sketch.element_id(2), Some("e2"));
+        assert_eq!(sketch.construction, vec!["e2".to_string()]);
+        assert_eq!(sketch.revolve_axis.as_deref(), Some("axis"));
+        assert!(matches!(&sketch.elements[3],
+            SketchElement::Dimension { target_element: Some(t), .. } if t == "e1"));
+        // Ссылка на несуществующий элемент отброшена
+        assert_eq!(sketch.constraints.len(), 2);
+        assert_eq!(sketch.constraints[0], SketchConstraint::Horizontal { element: "e1".to_string() });
+
+        // Новый формат читается без изменений
+        let again: Sketch = serde_json::from_str(&serde_json::to_string(&sketch).unwrap()).unwrap();
+        assert_eq!(again, sketch);
+    }
+
+    #[test]
+    fn test_sketch_references_survive_element_removal() {
+        let mut sketch = Sketch::default();
+        sketch.push_element(line(0.0, 0.0, 1.0, 0.0));
+        sketch.push_element(line(1.0, 0.0, 1.0, 1.0));
+        sketch.push_element(line(1.0, 1.0, 0.0, 1.0));
+        sketch.constraints.push(SketchConstraint::Horizontal { element: "e3".to_string() });
+        sketch.constraints.push(SketchConstraint::Vertical { element: "e2".to_string() });
+        sketch.set_construction(2, true);
+        sketch.toggle_revolve_axis(1);
+
+        sketch.remove_elements(&[0, 1]);
+
+        assert_eq!(sketch.elements.len(), 1);
+        assert_eq!(sketch.element_id(0), Some("e3"));
+        // Ограничение на удалённый элемент и ось удалены, остальное указывает на тот же элемент
+        assert_eq!(sketch.constraints, vec![SketchConstraint::Horizontal { element: "e3".to_string() }]);
+        assert!(sketch.is_construction(0));
+        assert_eq!(sketch.revolve_axis, None);
+    }
+
+    #[test]
+    fn test_replace_element_keeps_point_references() {
+        let mut sketch = Sketch::default();
+        sketch.push_element(line(0.0, 0.0, 10.0, 0.0));
+        sketch.push_element(line(10.0, 0.0, 10.0, 5.0));
+        sketch.constraints.push(SketchConstraint::Coincident {
+            point1: PointRef { element_id: "e1".to_string(), point_index: 1 },
+            point2: PointRef { element_id: "e2".to_string(), point_index: 0 },
+        });
+        sketch.constraints.push(SketchConstraint::Horizontal { element: "e1".to_string() });
+
+        // Обрезка середины первой линии: два куска
+        sketch.replace_element(0, vec![line(0.0, 0.0, 3.0, 0.0), line(7.0, 0.0, 10.0, 0.0)]);
+
+        assert_eq!(sketch.element_id(0), Some("e1"));
+        assert_eq!(sketch.element_id(1), Some("e3"));
+        assert_eq!(sketch.element_id(2), Some("e2"));
+        // Конец (10, 0) теперь принадлежит второму куску
+        assert_eq!(
+            sketch.constraints[0],
+            SketchConstraint::Coincident {
+                point1: PointRef { element_id: "e3".to_string(), point_index: 1 },
+                point2: PointRef { element_id: "e2".to_string(), point_index: 0 },
+            }
+        );
+        assert_eq!(sketch.constraints[1], SketchConstraint::Horizontal { element: "e1".to_string() });
+    }
+
+    #[test]
+    fn test_replace_element_keeps_references_past_eighth_point() {
+        let polyline = |xs: std::ops::Range<usize>| SketchElement::Polyline {
+            id: None,
+            points: xs.map(|x| Point2D { x: x as f64, y: (x % 2) as f64 }).collect(),
+        };
+        let mut sketch = Sketch::default();
+        sketch.push_element(polyline(0..10));
+        sketch.push_element(line(9.0, 1.0, 9.0, 5.0));
+        sketch.constraints.push(SketchConstraint::Coincident {
+            point1: PointRef { element_id: "e1".to_string(), point_index: 9 },
+            point2: PointRef { element_id: "e2".to_string(), point_index: 0 },
+        });
+
+        // Разбиение в точке 1: точка 9 становится точкой 8 второго куска
+        sketch.replace_element(0, vec![polyline(0..2), polyline(1..10)]);
+
+        assert_eq!(
+            sketch.constraints,
+            vec![SketchConstraint::Coincident {
+                point1: PointRef { element_id: "e3".to_string(), point_index: 8 },
+                point2: PointRef { element_id: "e2".to_string(), point_index: 0 },
+            }]
+        );
+    }
+
     // --- Transform ---
 
     #[test]
@@ -1041,13 +1821,16 @@ mod tests {
                 offset: 0.0,
                 elements: vec![
                     SketchElement::Circle {
+                        id: Some("e1".to_string()),
                         center: Point2D { x: 0.0, y: 0.0 },
                         radius: 1.0,
                     },
                 ],
                 face_normal: None,
+                face: None,
                 construction: vec![],
                 revolve_axis: None,
+                symmetry_axis: None,
                 constraints: vec![],
             },
             transform: Transform::new(),
@@ -1122,14 +1905,17 @@ mod tests {
                         offset: 1.0,
                         elements: vec![
                             SketchElement::Rectangle {
+                                id: Some("e1".to_string()),
                                 corner: Point2D { x: -0.5, y: -0.5 },
                                 width: 1.0,
                                 height: 1.0,
                             },
                         ],
                         face_normal: None,
+                        face: None,
                         construction: vec![],
                         revolve_axis: None,
+                        symmetry_axis: None,
                         constraints: vec![],
                     },
                     transform: Transform::new(),
@@ -1214,6 +2000,8 @@ mod tests {
             }],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         };
         roundtrip(&body);
     }
@@ -1238,16 +2026,21 @@ mod tests {
                 plane: SketchPlane::Xy,
                 offset: 0.0,
                 elements: vec![SketchElement::Circle {
+                    id: Some("e1".to_string()),
                     center: Point2D { x: 0.0, y: 0.0 },
                     radius: 1.0,
                 }],
                 face_normal: None,
+                face: None,
                 construction: vec![],
                 revolve_axis: None,
+                symmetry_axis: None,
                 constraints: vec![],
             },
             sketch_transform: Transform::new(),
             height: 5.0,
+            height_backward: 0.0,
+            draft_angle: 0.0,
         };
         roundtrip(&f);
         let json = serde_json::to_string(&f).unwrap();
@@ -1263,8 +2056,10 @@ mod tests {
                 offset: 1.0,
                 elements: vec![],
                 face_normal: None,
+                face: None,
                 construction: vec![],
                 revolve_axis: None,
+                symmetry_axis: None,
                 constraints: vec![],
             },
             transform: Transform::new(),
@@ -1336,9 +2131,13 @@ mod tests {
                     }],
                     visible: true,
                     parameters: HashMap::new(),
+                    bindings: HashMap::new(),
+                    appearance: None,
                 },
             ],
             body_operations: vec![],
+            parameters: HashMap::new(),
+            design_table: None,
         };
         roundtrip(&scene);
     }
@@ -1376,6 +2175,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scene_input_accepts_v1_and_v2() {
+        let v1 = r#"{"operations":[{"type":"create_primitive","id":"a",
+            "primitive":{"type":"cube","width":1,"height":1,"depth":1},
+            "transform":{"position":[0,0,0],"rotation":[0,0,0],"scale":[1,1,1]}}]}"#;
+        let input: SceneInput = serde_json::from_str(v1).unwrap();
+        assert!(matches!(input, SceneInput::V1(_)));
+        assert_eq!(input.into_v2().bodies.len(), 1);
+
+        let v2 = r#"{"version":2,"bodies":[],"body_operations":[]}"#;
+        let input: SceneInput = serde_json::from_str(v2).unwrap();
+        assert!(matches!(input, SceneInput::V2(_)));
+    }
+
+    #[test]
+    fn test_body_appearance_serde() {
+        let json = r#"{"id": "b1", "name": "Body", "features": [],
+            "appearance": {"color": [1.0, 0.0, 0.0], "material": {"name": "PLA", "density": 1.24}}}"#;
+        let body: Body = serde_json::from_str(json).unwrap();
+        let appearance = body.appearance.as_ref().unwrap();
+        assert_eq!(appearance.opacity, 1.0);
+        assert!(!appearance.is_transparent());
+        assert_eq!(appearance.material.as_ref().unwrap().roughness, 0.5);
+
+        // Без appearance поле не пишется
+        let plain = Body { appearance: None, ..body };
+        assert!(!serde_json::to_string(&plain).unwrap().contains("appearance"));
+
+        assert_eq!(Material::preset("steel").map(|m| m.density), Some(7.85));
+        assert!(Material::preset("unobtainium").is_none());
+    }
+
     #[test]
     fn test_feature_id() {
         let f = Feature::BasePrimitive {
@@ -0,0 +1,105 @@
+//! Массовые характеристики тел: формат ответа, общий для GUI, сервера и команд агента
+//!
+//! Единицы: длина — мм, плотность — г/см³, масса — г, момент инерции — г·мм².
+//! Тензоры инерции записаны по строкам; главные оси — строки `principal_axes`
+//! в порядке `principal_moments` (по возрастанию).
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Body;
+
+/// Плотность тела без материала, г/см³ (вода)
+pub const DEFAULT_DENSITY: f64 = 1.0;
+
+/// Плотность тела: из материала внешнего вида или `DEFAULT_DENSITY`
+pub fn body_density(body: &Body) -> f64 {
+    body.appearance
+        .as_ref()
+        .and_then(|a| a.material.as_ref())
+        .map_or(DEFAULT_DENSITY, |m| m.density)
+}
+
+/// Массовые характеристики одного тела или сборки
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MassProperties {
+    /// Объём, мм³
+    pub volume: f64,
+    /// Площадь поверхности, мм²
+    pub surface_area: f64,
+    /// Плотность, г/см³ (для сборки — средняя: масса / объём)
+    pub density: f64,
+    /// Масса, г
+    pub mass: f64,
+    /// Центр масс, мм
+    pub center_of_mass: [f64; 3],
+    /// Тензор инерции относительно центра масс в осях сцены, г·мм²
+    pub inertia_centroid: [[f64; 3]; 3],
+    /// Тензор инерции относительно начала координат, г·мм²
+    pub inertia_origin: [[f64; 3]; 3],
+    /// Главные моменты инерции по возрастанию, г·мм²
+    pub principal_moments: [f64; 3],
+    /// Главные оси (единичные векторы, правая тройка)
+    pub principal_axes: [[f64; 3]; 3],
+}
+
+/// Массовые характеристики тела сцены
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BodyMassProperties {
+    pub id: String,
+    pub name: String,
+    /// Название материала; None — плотность по умолчанию
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    #[serde(flatten)]
+    pub properties: MassProperties,
+}
+
+/// Массовые характеристики всех построенных тел и их сумма
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MassReport {
+    pub bodies: Vec<BodyMassProperties>,
+    pub total: MassProperties,
+    /// Ошибки построения по id тела
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub errors: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Appearance, Material};
+
+    #[test]
+    fn test_body_density_and_report_shape() {
+        let mut body = Body {
+            id: "body_1".to_string(),
+            name: "Plate".to_string(),
+            features: Vec::new(),
+            visible: true,
+            parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
+        };
+        assert_eq!(body_density(&body), DEFAULT_DENSITY);
+        body.appearance = Some(Appearance { material: Material::preset("aluminium"), ..Default::default() });
+        assert_eq!(body_density(&body), 2.7);
+
+        let report = MassReport {
+            bodies: vec![BodyMassProperties {
+                id: body.id.clone(),
+                name: body.name.clone(),
+                material: Some("Aluminium".to_string()),
+                properties: MassProperties { mass: 2.7, ..Default::default() },
+            }],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["bodies"][0]["mass"], 2.7);
+        assert_eq!(json["bodies"][0]["material"], "Aluminium");
+        assert!(json.get("errors").is_none());
+        let back: MassReport = serde_json::from_value(json).unwrap();
+        assert_eq!(back, report);
+    }
+}
@@ -1,7 +1,11 @@
-use crate::{Body, ParameterValue};
-#[cfg(test)]
-use crate::Parameter;
-use evalexpr::{build_operator_tree, ContextWithMutableVariables, HashMapContext, Value};
+use crate::{
+    Body, BodyId, Feature, Parameter, ParameterRef, ParameterValue, Primitive, SceneDescriptionV2, SketchElement,
+    Transform,
+};
+use evalexpr::{
+    build_operator_tree, ContextWithMutableFunctions, ContextWithMutableVariables, Function, HashMapContext, Node,
+    Value,
+};
 use std::collections::{HashMap, HashSet};
 
 /// Результат вычисления параметра
@@ -38,94 +42,552 @@ impl std::fmt::Display for ParameterError {
 
 impl std::error::Error for ParameterError {}
 
-impl Body {
-    /// Вычислить значение параметра
-    pub fn evaluate_parameter(&self, param_name: &str) -> ParameterResult {
-        let mut visited = HashSet::new();
-        let mut path = Vec::new();
-        self.evaluate_parameter_internal(param_name, &mut visited, &mut path)
-    }
-
-    /// Внутренняя рекурсивная функция для вычисления параметра
-    fn evaluate_parameter_internal(
-        &self,
-        param_name: &str,
-        visited: &mut HashSet<String>,
-        path: &mut Vec<String>,
-    ) -> ParameterResult {
-        // Проверка на циклическую зависимость
-        if visited.contains(param_name) {
-            path.push(param_name.to_string());
-            return Err(ParameterError::CircularDependency(path.clone()));
+/// Ошибка вычисления привязки свойства фичи
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingError {
+    pub body_id: BodyId,
+    /// Привязка в виде `feature_id.property`
+    pub binding: String,
+    pub error: ParameterError,
+}
+
+impl std::fmt::Display for BindingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Binding '{}': {}", self.binding, self.error)
+    }
+}
+
+/// Единицы, допустимые в `Parameter::unit` и как суффикс числа в формуле
+/// (`10mm`, `1in`, `30deg`), с множителем перевода в единицы модели:
+/// длины — в миллиметрах, углы — в градусах.
+pub const UNITS: [(&str, f64); 7] = [
+    ("mm", 1.0),
+    ("cm", 10.0),
+    ("m", 1000.0),
+    ("in", 25.4),
+    ("ft", 304.8),
+    ("deg", 1.0),
+    ("rad", 180.0 / std::f64::consts::PI),
+];
+
+/// Встроенные функции формул (помимо `min`/`max`/`floor`/`round`/`ceil`
+/// из evalexpr). Тригонометрия работает в градусах, как и углы модели.
+pub const FUNCTIONS: [&str; 9] = ["sqrt", "abs", "sin", "cos", "tan", "asin", "acos", "atan", "atan2"];
+
+/// Константы, доступные в любой формуле
+const CONSTANTS: [(&str, f64); 2] = [("PI", std::f64::consts::PI), ("E", std::f64::consts::E)];
+
+/// Множитель перевода единицы в единицы модели
+pub fn unit_factor(unit: &str) -> Option<f64> {
+    UNITS.iter().find(|(name, _)| *name == unit).map(|(_, factor)| *factor)
+}
+
+/// Число с суффиксом единицы (`2.5in`) в единицах модели.
+/// evalexpr разбирает такую запись как идентификатор.
+pub(crate) fn unit_literal(token: &str) -> Option<f64> {
+    let split = token.find(|c: char| c.is_ascii_alphabetic())?;
+    let (number, unit) = token.split_at(split);
+    let value: f64 = number.parse().ok()?;
+    Some(value * unit_factor(unit)?)
+}
+
+/// Имена, которые формула берёт из сцены (без констант и чисел с единицами)
+fn referenced_identifiers(tree: &Node) -> HashSet<String> {
+    tree.iter_variable_identifiers()
+        .filter(|ident| !CONSTANTS.iter().any(|(name, _)| name == ident) && unit_literal(ident).is_none())
+        .map(str::to_string)
+        .collect()
+}
+
+type UnaryFn = fn(f64) -> f64;
+
+/// Зарегистрировать встроенные функции (`FUNCTIONS`) в контексте
+fn add_functions(context: &mut HashMapContext) {
+    let unary: [(&str, UnaryFn); 8] = [
+        ("sqrt", f64::sqrt),
+        ("abs", f64::abs),
+        ("sin", |x| x.to_radians().sin()),
+        ("cos", |x| x.to_radians().cos()),
+        ("tan", |x| x.to_radians().tan()),
+        ("asin", |x| x.asin().to_degrees()),
+        ("acos", |x| x.acos().to_degrees()),
+        ("atan", |x| x.atan().to_degrees()),
+    ];
+    for (name, f) in unary {
+        let function = Function::new(move |argument| Ok(Value::Float(f(argument.as_number()?))));
+        context.set_function(name.to_string(), function).ok();
+    }
+    let atan2 = Function::new(|argument| {
+        let args = argument.as_fixed_len_tuple(2)?;
+        let (y, x) = (args[0].as_number()?, args[1].as_number()?);
+        Ok(Value::Float(y.atan2(x).to_degrees()))
+    });
+    context.set_function("atan2".to_string(), atan2).ok();
+}
+
+/// Контекст вычисления: тела сцены и глобальные параметры.
+///
+/// Имена в формулах разрешаются так:
+/// - `name` — параметр текущего тела, иначе глобальный параметр;
+/// - `feature.property` — свойство фичи текущего тела (`extrude_1.height`),
+///   иначе параметр `property` тела с id `feature`;
+/// - `body.feature.property` — свойство фичи другого тела.
+///
+/// Свойство фичи с привязкой (`Body::bindings`) вычисляется по её формуле.
+/// Циклы обнаруживаются по всей сцене: узлом графа считается параметр
+/// тела, глобальный параметр или свойство фичи.
+struct Evaluator<'a> {
+    bodies: &'a [Body],
+    globals: Option<&'a HashMap<String, Parameter>>,
+    visited: HashSet<String>,
+    path: Vec<String>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(bodies: &'a [Body], globals: Option<&'a HashMap<String, Parameter>>) -> Self {
+        Self {
+            bodies,
+            globals,
+            visited: HashSet::new(),
+            path: Vec::new(),
         }
+    }
 
-        // Получить параметр
-        let param = self
-            .parameters
-            .get(param_name)
-            .ok_or_else(|| ParameterError::NotFound(param_name.to_string()))?;
+    fn body(&self, body_id: &str) -> Option<&'a Body> {
+        self.bodies.iter().find(|b| b.id == body_id)
+    }
 
-        visited.insert(param_name.to_string());
-        path.push(param_name.to_string());
+    /// Параметр по имени: сначала в теле, затем среди глобальных
+    fn parameter(&mut self, body: Option<&'a Body>, name: &str) -> ParameterResult {
+        if let Some(body) = body {
+            if let Some(param) = body.parameters.get(name) {
+                return self.evaluate_node(format!("{}.{}", body.id, name), Some(body), param);
+            }
+        }
+        match self.globals.and_then(|g| g.get(name)) {
+            Some(param) => self.evaluate_node(name.to_string(), None, param),
+            None => Err(ParameterError::NotFound(name.to_string())),
+        }
+    }
+
+    /// Числовое свойство фичи тела (по привязке, если она задана)
+    fn feature_property(&mut self, body: &'a Body, feature_id: &str, property: &str) -> ParameterResult {
+        let binding = format!("{}.{}", feature_id, property);
+        let value = body
+            .features
+            .iter()
+            .find(|f| f.id() == feature_id)
+            .and_then(|feature| feature.numeric_property(property))
+            .ok_or_else(|| ParameterError::NotFound(binding.clone()))?;
+        match body.bindings.get(&binding) {
+            Some(expression) => {
+                self.guarded(format!("{}.{}", body.id, binding), |this| this.formula(Some(body), expression))
+            }
+            None => Ok(value),
+        }
+    }
+
+    /// Разрешить идентификатор формулы (возможно, с точками)
+    fn identifier(&mut self, body: Option<&'a Body>, ident: &str) -> ParameterResult {
+        let parts: Vec<&str> = ident.split('.').collect();
+        match parts.as_slice() {
+            [name] => self.parameter(body, name),
+            [owner, property] => {
+                if let Some(body) = body.filter(|b| b.features.iter().any(|f| f.id() == *owner)) {
+                    return self.feature_property(body, owner, property);
+                }
+                if let Some(other) = self.body(owner) {
+                    return self.parameter(Some(other), property);
+                }
+                // Глобальный параметр может ссылаться на фичу любого тела
+                let bodies = self.bodies;
+                match bodies.iter().find(|b| b.features.iter().any(|f| f.id() == *owner)) {
+                    Some(owner_body) => self.feature_property(owner_body, owner, property),
+                    None => Err(ParameterError::NotFound(ident.to_string())),
+                }
+            }
+            [body_id, feature_id, property] => {
+                let other = self
+                    .body(body_id)
+                    .ok_or_else(|| ParameterError::NotFound(ident.to_string()))?;
+                self.feature_property(other, feature_id, property)
+            }
+            _ => Err(ParameterError::NotFound(ident.to_string())),
+        }
+    }
+
+    /// Разрешить явную ссылку `ParameterValue::Reference`
+    fn reference(&mut self, body: Option<&'a Body>, reference: &ParameterRef) -> ParameterResult {
+        let target = match &reference.body_id {
+            Some(id) => Some(self.body(id).ok_or_else(|| ParameterError::NotFound(id.clone()))?),
+            None => body,
+        };
+        match (&reference.feature_id, target) {
+            (Some(feature_id), Some(target)) => self.feature_property(target, feature_id, &reference.property),
+            (Some(feature_id), None) => self.identifier(None, &format!("{}.{}", feature_id, reference.property)),
+            (None, target) => self.parameter(target, &reference.property),
+        }
+    }
 
-        let result = match &param.value {
+    fn evaluate_node(&mut self, key: String, body: Option<&'a Body>, param: &'a Parameter) -> ParameterResult {
+        self.guarded(key, |this| match &param.value {
             ParameterValue::Number { value } => Ok(*value),
+            ParameterValue::Formula { expression } => this.formula(body, expression),
+            ParameterValue::Reference { reference } => this.reference(body, reference),
+        })
+    }
 
-            ParameterValue::Formula { expression } => {
-                // Создать контекст для evalexpr
-                let mut context = HashMapContext::new();
+    /// Вычислить узел графа зависимостей `key` с проверкой на цикл
+    fn guarded(&mut self, key: String, evaluate: impl FnOnce(&mut Self) -> ParameterResult) -> ParameterResult {
+        // Проверка на циклическую зависимость
+        if self.visited.contains(&key) {
+            self.path.push(key);
+            return Err(ParameterError::CircularDependency(self.path.clone()));
+        }
 
-                // Получить зависимости этого параметра
-                let deps = self.get_parameter_dependencies(param_name);
+        self.visited.insert(key.clone());
+        self.path.push(key.clone());
 
-                // Добавить только необходимые параметры в контекст (рекурсивно вычислить зависимости)
-                for dep_name in deps {
-                    let dep_value = self.evaluate_parameter_internal(&dep_name, visited, path)?;
-                    context
-                        .set_value(dep_name.clone(), Value::Float(dep_value))
-                        .map_err(|e| ParameterError::EvaluationError(e.to_string()))?;
-                }
+        let result = evaluate(self);
+
+        // При ошибке путь цикла нужен вызывающему, поэтому он не очищается
+        if result.is_ok() {
+            self.path.pop();
+        }
+        self.visited.remove(&key);
+
+        result
+    }
+
+    fn formula(&mut self, body: Option<&'a Body>, expression: &str) -> ParameterResult {
+        // Парсить выражение
+        let tree = build_operator_tree(expression).map_err(|e| ParameterError::ParseError(e.to_string()))?;
+
+        // Создать контекст для evalexpr: константы, функции, числа с единицами
+        let mut context = HashMapContext::new();
+        for (name, value) in CONSTANTS {
+            context.set_value(name.to_string(), Value::Float(value)).ok();
+        }
+        add_functions(&mut context);
+        for ident in tree.iter_variable_identifiers() {
+            if let Some(value) = unit_literal(ident) {
+                context.set_value(ident.to_string(), Value::Float(value)).ok();
+            }
+        }
+
+        // Добавить только используемые имена (рекурсивно вычислить зависимости)
+        for ident in referenced_identifiers(&tree) {
+            let value = self.identifier(body, &ident)?;
+            context
+                .set_value(ident, Value::Float(value))
+                .map_err(|e| ParameterError::EvaluationError(e.to_string()))?;
+        }
+
+        let value = tree
+            .eval_with_context(&context)
+            .map_err(|e| ParameterError::EvaluationError(e.to_string()))?;
+
+        // Преобразовать в f64
+        match value {
+            Value::Float(f) => Ok(f),
+            Value::Int(i) => Ok(i as f64),
+            _ => Err(ParameterError::InvalidType(format!("Expected number, got {:?}", value))),
+        }
+    }
+}
+
+/// Свойства трансформации, доступные для ссылок и привязок
+const TRANSFORM_PROPERTIES: [&str; 9] = [
+    "position_x",
+    "position_y",
+    "position_z",
+    "rotation_x",
+    "rotation_y",
+    "rotation_z",
+    "scale_x",
+    "scale_y",
+    "scale_z",
+];
+
+/// Разобрать имя компоненты трансформации: `rotation_z` → ("rotation", 2)
+fn transform_component(property: &str) -> Option<(&str, usize)> {
+    let (vector, axis) = property.rsplit_once('_')?;
+    let axis = match axis {
+        "x" => 0,
+        "y" => 1,
+        "z" => 2,
+        _ => return None,
+    };
+    Some((vector, axis))
+}
 
-                // Добавить математические константы
-                context
-                    .set_value("PI".to_string(), Value::Float(std::f64::consts::PI))
-                    .ok();
-                context
-                    .set_value("E".to_string(), Value::Float(std::f64::consts::E))
-                    .ok();
-
-                // Парсить и вычислить выражение
-                let tree = build_operator_tree(expression)
-                    .map_err(|e| ParameterError::ParseError(e.to_string()))?;
-
-                let value = tree
-                    .eval_with_context(&context)
-                    .map_err(|e| ParameterError::EvaluationError(e.to_string()))?;
-
-                // Преобразовать в f64
-                match value {
-                    Value::Float(f) => Ok(f),
-                    Value::Int(i) => Ok(i as f64),
-                    _ => Err(ParameterError::InvalidType(format!(
-                        "Expected number, got {:?}",
-                        value
-                    ))),
+fn transform_field(transform: &Transform, property: &str) -> Option<f64> {
+    match transform_component(property)? {
+        ("position", axis) => Some(transform.position[axis]),
+        ("rotation", axis) => Some(transform.rotation[axis]),
+        ("scale", axis) => Some(transform.scale[axis]),
+        _ => None,
+    }
+}
+
+fn transform_field_mut<'t>(transform: &'t mut Transform, property: &str) -> Option<&'t mut f64> {
+    match transform_component(property)? {
+        ("position", axis) => Some(&mut transform.position[axis]),
+        ("rotation", axis) => Some(&mut transform.rotation[axis]),
+        ("scale", axis) => Some(&mut transform.scale[axis]),
+        _ => None,
+    }
+}
+
+impl Feature {
+    /// Числовое свойство фичи по имени (для ссылок вида `extrude_1.height`)
+    pub fn numeric_property(&self, property: &str) -> Option<f64> {
+        match (self, property) {
+            (Feature::BasePrimitive { primitive, .. }, "width" | "height" | "depth" | "radius") => {
+                match (primitive, property) {
+                    (Primitive::Cube { width, .. }, "width") => Some(*width),
+                    (Primitive::Cube { height, .. }, "height")
+                    | (Primitive::Cylinder { height, .. }, "height")
+                    | (Primitive::Cone { height, .. }, "height") => Some(*height),
+                    (Primitive::Cube { depth, .. }, "depth") => Some(*depth),
+                    (Primitive::Cylinder { radius, .. }, "radius")
+                    | (Primitive::Sphere { radius }, "radius")
+                    | (Primitive::Cone { radius, .. }, "radius") => Some(*radius),
+                    _ => None,
                 }
             }
+            (Feature::BaseExtrude { height, .. }, "height")
+            | (Feature::Extrude { height, .. }, "height") => Some(*height),
+            (Feature::BaseExtrude { height_backward, .. }, "height_backward")
+            | (Feature::Extrude { height_backward, .. }, "height_backward") => Some(*height_backward),
+            (Feature::BaseExtrude { draft_angle, .. }, "draft_angle")
+            | (Feature::Extrude { draft_angle, .. }, "draft_angle") => Some(*draft_angle),
+            (Feature::BaseRevolve { angle, .. }, "angle") | (Feature::Revolve { angle, .. }, "angle") => {
+                Some(*angle)
+            }
+            (Feature::BaseRevolve { segments, .. }, "segments")
+            | (Feature::Revolve { segments, .. }, "segments")
+            | (Feature::Fillet3D { segments, .. }, "segments") => Some(*segments as f64),
+            (Feature::Sketch { sketch, .. }, "offset")
+            | (Feature::BaseExtrude { sketch, .. }, "offset")
+            | (Feature::BaseRevolve { sketch, .. }, "offset") => Some(sketch.offset),
+            (Feature::Fillet3D { radius, .. }, "radius") => Some(*radius),
+            (Feature::Chamfer3D { distance, .. }, "distance") => Some(*distance),
+            _ => transform_field(self.transform()?, property),
+        }
+    }
 
-            ParameterValue::Reference { reference: _ } => {
-                // TODO: реализовать ссылки на свойства других объектов
-                // Пока что возвращаем ошибку
-                Err(ParameterError::EvaluationError(
-                    "Parameter references not yet implemented".to_string(),
-                ))
+    /// Записать числовое свойство фичи; `false`, если такого свойства нет.
+    /// Количество сегментов округляется до целого (не меньше 1).
+    pub fn set_numeric_property(&mut self, property: &str, value: f64) -> bool {
+        if let Some(field) = self.numeric_field_mut(property) {
+            *field = value;
+            return true;
+        }
+        match (self, property) {
+            (Feature::BaseRevolve { segments, .. }, "segments")
+            | (Feature::Revolve { segments, .. }, "segments")
+            | (Feature::Fillet3D { segments, .. }, "segments") => {
+                *segments = value.round().max(1.0) as u32;
+                true
             }
+            _ => false,
+        }
+    }
+
+    /// Имена числовых свойств фичи (для выбора привязки в UI)
+    pub fn numeric_property_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = match self {
+            Feature::BasePrimitive { primitive, .. } => match primitive {
+                Primitive::Cube { .. } => vec!["width", "height", "depth"],
+                Primitive::Cylinder { .. } | Primitive::Cone { .. } => vec!["radius", "height"],
+                Primitive::Sphere { .. } => vec!["radius"],
+            },
+            Feature::BaseExtrude { .. } => vec!["height", "height_backward", "draft_angle", "offset"],
+            Feature::BaseRevolve { .. } => vec!["angle", "segments", "offset"],
+            Feature::BaseMesh { .. } => vec![],
+            Feature::Sketch { .. } => vec!["offset"],
+            Feature::Extrude { .. } => vec!["height", "height_backward", "draft_angle"],
+            Feature::Revolve { .. } => vec!["angle", "segments"],
+            Feature::BooleanModify { .. } => vec![],
+            Feature::Fillet3D { .. } => vec!["radius", "segments"],
+            Feature::Chamfer3D { .. } => vec!["distance"],
         };
+        if self.transform().is_some() {
+            names.extend(TRANSFORM_PROPERTIES);
+        }
+        names
+    }
 
-        visited.remove(param_name);
-        path.pop();
+    /// Трансформация фичи (примитива или эскиза), если она есть
+    fn transform(&self) -> Option<&Transform> {
+        match self {
+            Feature::BasePrimitive { transform, .. }
+            | Feature::BaseMesh { transform, .. }
+            | Feature::Sketch { transform, .. } => Some(transform),
+            Feature::BaseExtrude { sketch_transform, .. } | Feature::BaseRevolve { sketch_transform, .. } => {
+                Some(sketch_transform)
+            }
+            _ => None,
+        }
+    }
 
-        result
+    /// Ссылка на вещественное поле фичи по имени свойства
+    fn numeric_field_mut(&mut self, property: &str) -> Option<&mut f64> {
+        match (self, property) {
+            (Feature::BasePrimitive { primitive, .. }, "width" | "height" | "depth" | "radius") => {
+                match (primitive, property) {
+                    (Primitive::Cube { width, .. }, "width") => Some(width),
+                    (Primitive::Cube { height, .. }, "height")
+                    | (Primitive::Cylinder { height, .. }, "height")
+                    | (Primitive::Cone { height, .. }, "height") => Some(height),
+                    (Primitive::Cube { depth, .. }, "depth") => Some(depth),
+                    (Primitive::Cylinder { radius, .. }, "radius")
+                    | (Primitive::Sphere { radius }, "radius")
+                    | (Primitive::Cone { radius, .. }, "radius") => Some(radius),
+                    _ => None,
+                }
+            }
+            (Feature::BaseExtrude { height, .. }, "height") | (Feature::Extrude { height, .. }, "height") => {
+                Some(height)
+            }
+            (Feature::BaseExtrude { height_backward, .. }, "height_backward")
+            | (Feature::Extrude { height_backward, .. }, "height_backward") => Some(height_backward),
+            (Feature::BaseExtrude { draft_angle, .. }, "draft_angle")
+            | (Feature::Extrude { draft_angle, .. }, "draft_angle") => Some(draft_angle),
+            (Feature::BaseRevolve { angle, .. }, "angle") | (Feature::Revolve { angle, .. }, "angle") => Some(angle),
+            (Feature::Sketch { sketch, .. }, "offset")
+            | (Feature::BaseExtrude { sketch, .. }, "offset")
+            | (Feature::BaseRevolve { sketch, .. }, "offset") => Some(&mut sketch.offset),
+            (Feature::Fillet3D { radius, .. }, "radius") => Some(radius),
+            (Feature::Chamfer3D { distance, .. }, "distance") => Some(distance),
+            (Feature::BasePrimitive { transform, .. }, _)
+            | (Feature::BaseMesh { transform, .. }, _)
+            | (Feature::Sketch { transform, .. }, _) => transform_field_mut(transform, property),
+            (Feature::BaseExtrude { sketch_transform, .. }, _) | (Feature::BaseRevolve { sketch_transform, .. }, _) => {
+                transform_field_mut(sketch_transform, property)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl SceneDescriptionV2 {
+    /// Вычислить параметр в контексте сцены.
+    ///
+    /// `body_id = None` — глобальный параметр; иначе параметр тела
+    /// (с откатом на глобальный, если в теле такого нет).
+    pub fn evaluate_parameter(&self, body_id: Option<&str>, param_name: &str) -> ParameterResult {
+        let mut evaluator = Evaluator::new(&self.bodies, Some(&self.parameters));
+        let body = match body_id {
+            Some(id) => Some(evaluator.body(id).ok_or_else(|| ParameterError::NotFound(id.to_string()))?),
+            None => None,
+        };
+        evaluator.parameter(body, param_name)
+    }
+
+    /// Вычислить выражение в контексте тела (или глобальном, если `body_id = None`)
+    pub fn evaluate_expression(&self, body_id: Option<&str>, expression: &str) -> ParameterResult {
+        let mut evaluator = Evaluator::new(&self.bodies, Some(&self.parameters));
+        let body = body_id.and_then(|id| evaluator.body(id));
+        evaluator.formula(body, expression)
+    }
+
+    /// Все глобальные параметры с вычисленными значениями
+    pub fn evaluate_global_parameters(&self) -> HashMap<String, ParameterResult> {
+        self.parameters
+            .keys()
+            .map(|name| (name.clone(), self.evaluate_parameter(None, name)))
+            .collect()
+    }
+
+    /// Проверить, есть ли циклы или неразрешимые ссылки среди параметров и привязок сцены
+    pub fn has_circular_dependencies(&self) -> bool {
+        self.parameters.keys().any(|name| self.evaluate_parameter(None, name).is_err())
+            || self.bodies.iter().any(|body| {
+                body.parameters
+                    .keys()
+                    .any(|name| self.evaluate_parameter(Some(&body.id), name).is_err())
+                    || body.bindings.keys().any(|binding| self.evaluate_binding(&body.id, binding).is_err())
+            })
+    }
+
+    /// Обновить размеры и размерные ограничения всех тел по параметрам сцены
+    /// (размер может быть привязан и к глобальному параметру)
+    pub fn update_dimensions_from_parameters(&mut self) {
+        let values: Vec<HashMap<String, f64>> = self
+            .bodies
+            .iter()
+            .map(|body| {
+                body.bound_parameter_names()
+                    .into_iter()
+                    .filter_map(|name| {
+                        let value = self.evaluate_parameter(Some(&body.id), &name).ok()?;
+                        Some((name, value))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for (body, values) in self.bodies.iter_mut().zip(values) {
+            body.apply_parameter_values(&values);
+        }
+    }
+
+    /// Вычислить привязку `feature_id.property` тела `body_id`
+    pub fn evaluate_binding(&self, body_id: &str, binding: &str) -> ParameterResult {
+        let mut evaluator = Evaluator::new(&self.bodies, Some(&self.parameters));
+        let body = evaluator
+            .body(body_id)
+            .ok_or_else(|| ParameterError::NotFound(body_id.to_string()))?;
+        let (feature_id, property) = binding
+            .split_once('.')
+            .ok_or_else(|| ParameterError::NotFound(binding.to_string()))?;
+        evaluator.feature_property(body, feature_id, property)
+    }
+
+    /// Записать в фичи значения всех привязок (`Body::bindings`).
+    ///
+    /// Вызывается перед построением, чтобы изменение одного параметра
+    /// перестраивало всю модель. Свойства с ошибочной привязкой сохраняют
+    /// прежнее значение; ошибки возвращаются вызывающему.
+    pub fn apply_feature_bindings(&mut self) -> Vec<BindingError> {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for (body_idx, body) in self.bodies.iter().enumerate() {
+            let mut bindings: Vec<&String> = body.bindings.keys().collect();
+            bindings.sort();
+            for binding in bindings {
+                match self.evaluate_binding(&body.id, binding) {
+                    Ok(value) => values.push((body_idx, binding.clone(), value)),
+                    Err(error) => errors.push(BindingError {
+                        body_id: body.id.clone(),
+                        binding: binding.clone(),
+                        error,
+                    }),
+                }
+            }
+        }
+
+        for (body_idx, binding, value) in values {
+            // evaluate_binding уже проверил, что фича и свойство существуют
+            if let Some((feature_id, property)) = binding.split_once('.') {
+                let feature = self.bodies[body_idx].features.iter_mut().find(|f| f.id() == feature_id);
+                if let Some(feature) = feature {
+                    feature.set_numeric_property(property, value);
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+impl Body {
+    /// Вычислить значение параметра (только в пределах тела, без глобальных)
+    pub fn evaluate_parameter(&self, param_name: &str) -> ParameterResult {
+        let mut evaluator = Evaluator::new(std::slice::from_ref(self), None);
+        evaluator.parameter(Some(self), param_name)
     }
 
     /// Получить все параметры с вычисленными значениями
@@ -136,22 +598,18 @@ impl Body {
             .collect()
     }
 
-    /// Получить список зависимостей параметра (какие параметры он использует)
+    /// Получить список зависимостей параметра: имена, которые использует
+    /// его формула (`width`, `extrude_1.height`), или цель ссылки.
+    /// Имена берутся из дерева разбора, так что `w` не считается
+    /// зависимостью формулы `width*2`; формула с ошибкой разбора зависимостей не имеет.
     pub fn get_parameter_dependencies(&self, param_name: &str) -> HashSet<String> {
-        let mut deps = HashSet::new();
-
-        if let Some(param) = self.parameters.get(param_name) {
-            if let ParameterValue::Formula { expression } = &param.value {
-                // Простой способ: проверить все параметры, входят ли их имена в формулу
-                for (name, _) in &self.parameters {
-                    if name != param_name && expression.contains(name) {
-                        deps.insert(name.clone());
-                    }
-                }
-            }
+        match self.parameters.get(param_name).map(|p| &p.value) {
+            Some(ParameterValue::Formula { expression }) => build_operator_tree(expression)
+                .map(|tree| referenced_identifiers(&tree))
+                .unwrap_or_default(),
+            Some(ParameterValue::Reference { reference }) => HashSet::from([reference.to_string()]),
+            _ => HashSet::new(),
         }
-
-        deps
     }
 
     /// Проверить, есть ли циклические зависимости в параметрах
@@ -164,11 +622,10 @@ impl Body {
         false
     }
 
-    /// Обновить все размеры (Dimension), привязанные к параметрам
-    /// Вызывается после изменения значения параметра
+    /// Обновить все размеры (Dimension) и размерные ограничения, привязанные к параметрам
+    /// Вызывается после изменения значения параметра; геометрию после этого
+    /// нужно перерешить (`cad_core::sketch::constraints::solve_body_sketches`)
     pub fn update_dimensions_from_parameters(&mut self) {
-        use crate::{Feature, SketchElement};
-
         // Сначала вычислить все значения параметров
         let param_values: HashMap<String, f64> = self
             .parameters
@@ -180,7 +637,31 @@ impl Body {
             })
             .collect();
 
-        // Теперь обновить размеры используя вычисленные значения
+        self.apply_parameter_values(&param_values);
+    }
+
+    /// Имена параметров, к которым привязаны размеры и ограничения эскизов тела
+    fn bound_parameter_names(&self) -> HashSet<String> {
+        let mut names = HashSet::new();
+        for feature in &self.features {
+            let sketch = match feature {
+                Feature::Sketch { sketch, .. } => sketch,
+                Feature::BaseExtrude { sketch, .. } => sketch,
+                Feature::BaseRevolve { sketch, .. } => sketch,
+                _ => continue,
+            };
+            for element in &sketch.elements {
+                if let SketchElement::Dimension { parameter_name: Some(name), .. } = element {
+                    names.insert(name.clone());
+                }
+            }
+            names.extend(sketch.constraints.iter().filter_map(|c| c.parameter().map(str::to_string)));
+        }
+        names
+    }
+
+    /// Записать вычисленные значения параметров в привязанные размеры
+    fn apply_parameter_values(&mut self, param_values: &HashMap<String, f64>) {
         for feature in &mut self.features {
             let sketch = match feature {
                 Feature::Sketch { sketch, .. } => sketch,
@@ -201,6 +682,13 @@ impl Body {
                     }
                 }
             }
+
+            for constraint in &mut sketch.constraints {
+                let new_value = constraint.parameter().and_then(|name| param_values.get(name));
+                if let Some(&new_value) = new_value {
+                    constraint.set_dimension_value(new_value);
+                }
+            }
         }
     }
 }
@@ -225,6 +713,8 @@ mod tests {
             }],
             visible: true,
             parameters: HashMap::new(),
+            bindings: HashMap::new(),
+            appearance: None,
         }
     }
 
@@ -362,4 +852,268 @@ mod tests {
         assert!(deps.contains("width"));
         assert_eq!(deps.len(), 1);
     }
+
+    #[test]
+    fn test_dependencies_are_whole_identifiers() {
+        let mut body = create_test_body();
+        body.parameters.insert("w".to_string(), formula("w", "1"));
+        body.parameters.insert("width".to_string(), formula("width", "10mm"));
+        body.parameters.insert("area".to_string(), formula("area", "width*2 + prim1.depth * PI + sqrt(4)"));
+
+        let deps = body.get_parameter_dependencies("area");
+        assert_eq!(deps, HashSet::from(["width".to_string(), "prim1.depth".to_string()]));
+        // Числа с единицами — не зависимости
+        assert!(body.get_parameter_dependencies("width").is_empty());
+        // `w` — префикс `width`, но не зависимость, поэтому и ложного цикла нет
+        body.parameters.insert("w".to_string(), formula("w", "width / 2"));
+        assert!(!body.has_circular_dependencies());
+        assert_eq!(body.evaluate_parameter("w"), Ok(5.0));
+    }
+
+    #[test]
+    fn test_unit_literals() {
+        let scene = SceneDescriptionV2::default();
+        let eval = |expr: &str| scene.evaluate_expression(None, expr).unwrap();
+
+        assert_eq!(eval("10mm"), 10.0);
+        assert_eq!(eval("2.5cm + 1m"), 1025.0);
+        assert!((eval("1in") - 25.4).abs() < 1e-9);
+        assert_eq!(eval("30deg"), 30.0);
+        assert!((eval("PI * 1rad") - 180.0).abs() < 1e-9);
+        assert_eq!(unit_factor("ft"), Some(304.8));
+        assert!(matches!(
+            scene.evaluate_expression(None, "10parsec"),
+            Err(ParameterError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_builtin_functions() {
+        let scene = SceneDescriptionV2::default();
+        let eval = |expr: &str| scene.evaluate_expression(None, expr).unwrap();
+
+        assert_eq!(eval("min(3, 2.5)"), 2.5);
+        assert_eq!(eval("max(1mm, 2)"), 2.0);
+        assert_eq!(eval("sqrt(16)"), 4.0);
+        assert_eq!(eval("abs(-2)"), 2.0);
+        // Тригонометрия в градусах
+        assert!((eval("sin(30deg)") - 0.5).abs() < 1e-12);
+        assert!((eval("cos(60)") - 0.5).abs() < 1e-12);
+        assert!((eval("atan2(1, 1)") - 45.0).abs() < 1e-12);
+        assert!((eval("acos(0)") - 90.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_update_constraint_values_from_parameters() {
+        use crate::{PointRef, Sketch, SketchConstraint};
+
+        let mut body = create_test_body();
+        body.parameters.insert(
+            "r".to_string(),
+            Parameter {
+                name: "r".to_string(),
+                value: ParameterValue::Formula {
+                    expression: "2 * 3".to_string(),
+                },
+                unit: None,
+                description: None,
+            },
+        );
+        body.features.push(Feature::Sketch {
+            id: "sketch1".to_string(),
+            sketch: Sketch {
+                constraints: vec![
+                    SketchConstraint::Radius {
+                        element: "e1".to_string(),
+                        value: 1.0,
+                        parameter: Some("r".to_string()),
+                    },
+                    SketchConstraint::Distance {
+                        point1: PointRef { element_id: "e1".to_string(), point_index: 0 },
+                        point2: PointRef { element_id: "e2".to_string(), point_index: 0 },
+                        value: 4.0,
+                        parameter: None,
+                    },
+                ],
+                ..Default::default()
+            },
+            transform: Transform::new(),
+        });
+
+        body.update_dimensions_from_parameters();
+
+        let Feature::Sketch { sketch, .. } = &body.features[1] else { unreachable!() };
+        assert_eq!(sketch.constraints[0].dimension_value(), Some(6.0));
+        assert_eq!(sketch.constraints[1].dimension_value(), Some(4.0));
+    }
+
+    fn number(name: &str, value: f64) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            value: ParameterValue::Number { value },
+            unit: None,
+            description: None,
+        }
+    }
+
+    fn formula(name: &str, expression: &str) -> Parameter {
+        Parameter {
+            name: name.to_string(),
+            value: ParameterValue::Formula { expression: expression.to_string() },
+            unit: None,
+            description: None,
+        }
+    }
+
+    fn two_body_scene() -> SceneDescriptionV2 {
+        let mut plate = create_test_body();
+        plate.id = "plate".to_string();
+        plate.features.push(Feature::Extrude {
+            id: "extrude_1".to_string(),
+            sketch_id: "s".to_string(),
+            height: 4.0,
+            height_backward: 0.0,
+            cut: false,
+            draft_angle: 0.0,
+        });
+        let mut bolt = create_test_body();
+        bolt.id = "bolt".to_string();
+
+        let mut scene = SceneDescriptionV2 {
+            bodies: vec![plate, bolt],
+            ..Default::default()
+        };
+        scene.parameters.insert("wall".to_string(), number("wall", 2.5));
+        scene
+    }
+
+    #[test]
+    fn test_body_formula_uses_global_parameter() {
+        let mut scene = two_body_scene();
+        scene.bodies[0].parameters.insert("gap".to_string(), formula("gap", "wall * 2"));
+
+        assert_eq!(scene.evaluate_parameter(Some("plate"), "gap"), Ok(5.0));
+        // Глобальный параметр виден и через тело
+        assert_eq!(scene.evaluate_parameter(Some("bolt"), "wall"), Ok(2.5));
+        // Без контекста сцены глобальные параметры недоступны
+        assert!(scene.bodies[0].evaluate_parameter("gap").is_err());
+    }
+
+    #[test]
+    fn test_feature_property_and_other_body_references() {
+        let mut scene = two_body_scene();
+        scene.bodies[0].parameters.insert("depth".to_string(), formula("depth", "extrude_1.height + 1"));
+        scene.bodies[1].parameters.insert("length".to_string(), formula("length", "plate.depth + plate.extrude_1.height"));
+        scene.parameters.insert("total".to_string(), formula("total", "extrude_1.height * 2"));
+
+        assert_eq!(scene.evaluate_parameter(Some("plate"), "depth"), Ok(5.0));
+        assert_eq!(scene.evaluate_parameter(Some("bolt"), "length"), Ok(9.0));
+        assert_eq!(scene.evaluate_parameter(None, "total"), Ok(8.0));
+    }
+
+    #[test]
+    fn test_reference_value() {
+        let mut scene = two_body_scene();
+        scene.bodies[1].parameters.insert(
+            "h".to_string(),
+            Parameter {
+                name: "h".to_string(),
+                value: ParameterValue::Reference {
+                    reference: ParameterRef {
+                        body_id: Some("plate".to_string()),
+                        feature_id: Some("extrude_1".to_string()),
+                        property: "height".to_string(),
+                    },
+                },
+                unit: None,
+                description: None,
+            },
+        );
+        scene.bodies[1].parameters.insert(
+            "w".to_string(),
+            Parameter {
+                name: "w".to_string(),
+                value: ParameterValue::Reference {
+                    reference: ParameterRef { body_id: None, feature_id: None, property: "wall".to_string() },
+                },
+                unit: None,
+                description: None,
+            },
+        );
+
+        assert_eq!(scene.evaluate_parameter(Some("bolt"), "h"), Ok(4.0));
+        assert_eq!(scene.evaluate_parameter(Some("bolt"), "w"), Ok(2.5));
+    }
+
+    #[test]
+    fn test_circular_dependency_across_bodies() {
+        let mut scene = two_body_scene();
+        scene.bodies[0].parameters.insert("a".to_string(), formula("a", "bolt.b + 1"));
+        scene.bodies[1].parameters.insert("b".to_string(), formula("b", "total"));
+        scene.parameters.insert("total".to_string(), formula("total", "plate.a"));
+
+        let result = scene.evaluate_parameter(Some("plate"), "a");
+        assert!(matches!(result, Err(ParameterError::CircularDependency(_))));
+        assert!(scene.has_circular_dependencies());
+    }
+
+    #[test]
+    fn test_dimension_bound_to_global_parameter() {
+        use crate::{Sketch, SketchConstraint};
+
+        let mut scene = two_body_scene();
+        scene.bodies[1].features.push(Feature::Sketch {
+            id: "sketch1".to_string(),
+            sketch: Sketch {
+                constraints: vec![SketchConstraint::Radius {
+                    element: "e1".to_string(),
+                    value: 1.0,
+                    parameter: Some("wall".to_string()),
+                }],
+                ..Default::default()
+            },
+            transform: Transform::new(),
+        });
+
+        scene.update_dimensions_from_parameters();
+
+        let Feature::Sketch { sketch, .. } = &scene.bodies[1].features[1] else { unreachable!() };
+        assert_eq!(sketch.constraints[0].dimension_value(), Some(2.5));
+    }
+
+    #[test]
+    fn test_feature_bindings_reflow_model() {
+        let mut scene = two_body_scene();
+        scene.bodies[0].bindings.insert("extrude_1.height".to_string(), "wall * 2".to_string());
+        scene.bodies[0].bindings.insert("prim1.position_z".to_string(), "extrude_1.height + 1".to_string());
+        // Формула другого тела видит значение по привязке
+        scene.bodies[1].parameters.insert("h".to_string(), formula("h", "plate.extrude_1.height"));
+
+        assert!(scene.apply_feature_bindings().is_empty());
+        assert_eq!(scene.bodies[0].features[1].numeric_property("height"), Some(5.0));
+        assert_eq!(scene.bodies[0].features[0].numeric_property("position_z"), Some(6.0));
+        assert_eq!(scene.evaluate_parameter(Some("bolt"), "h"), Ok(5.0));
+
+        // Одно изменение параметра перестраивает все привязанные свойства
+        scene.parameters.insert("wall".to_string(), number("wall", 1.0));
+        assert!(scene.apply_feature_bindings().is_empty());
+        assert_eq!(scene.bodies[0].features[1].numeric_property("height"), Some(2.0));
+        assert_eq!(scene.bodies[0].features[0].numeric_property("position_z"), Some(3.0));
+    }
+
+    #[test]
+    fn test_feature_binding_errors() {
+        let mut scene = two_body_scene();
+        scene.bodies[0].bindings.insert("extrude_1.height".to_string(), "gap + 1".to_string());
+        scene.bodies[0].parameters.insert("gap".to_string(), formula("gap", "extrude_1.height"));
+        scene.bodies[1].bindings.insert("prim1.volume".to_string(), "1".to_string());
+
+        let errors = scene.apply_feature_bindings();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0].error, ParameterError::CircularDependency(_)));
+        assert_eq!(errors[1].to_string(), "Binding 'prim1.volume': Parameter 'prim1.volume' not found");
+        // Свойство с ошибочной привязкой сохраняет прежнее значение
+        assert_eq!(scene.bodies[0].features[1].numeric_property("height"), Some(4.0));
+        assert!(scene.has_circular_dependencies());
+    }
 }
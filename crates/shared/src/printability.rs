@@ -0,0 +1,90 @@
+//! Пригодность к 3D-печати: параметры проверки и формат отчёта, общий для GUI
+//! и команд агента
+//!
+//! Единицы: длина — мм, площадь — мм², углы — градусы.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Параметры проверки
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrintSettings {
+    /// Направление построения (вверх от стола), не обязательно единичное
+    pub build_direction: [f64; 3],
+    /// Допустимый угол нависания от вертикали; грани круче требуют поддержек
+    pub overhang_angle: f64,
+    /// Минимальная толщина стенки
+    pub min_wall: f64,
+    /// Минимальный размер элемента (разрешение принтера)
+    pub min_feature: f64,
+}
+
+impl Default for PrintSettings {
+    fn default() -> Self {
+        Self { build_direction: [0.0, 0.0, 1.0], overhang_angle: 45.0, min_wall: 0.8, min_feature: 0.4 }
+    }
+}
+
+/// Результат проверки одного тела
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BodyPrintability {
+    pub id: String,
+    pub name: String,
+    pub triangles: usize,
+    pub surface_area: f64,
+    /// Нависающие грани (кроме лежащих на столе)
+    pub overhang_faces: usize,
+    pub overhang_area: f64,
+    /// Наибольший угол нависания от вертикали
+    pub max_overhang_angle: f64,
+    /// Грани со стенкой тоньше `min_wall`, но не тоньше `min_feature`
+    pub thin_wall_faces: usize,
+    pub thin_wall_area: f64,
+    /// Грани со стенкой тоньше `min_feature` — элементы меньше разрешения
+    pub small_feature_faces: usize,
+    pub small_feature_area: f64,
+    /// Наименьшая измеренная толщина; `None`, если лучи не нашли противоположную стенку
+    pub min_thickness: Option<f64>,
+}
+
+impl BodyPrintability {
+    /// Тело можно напечатать без поддержек и без потери деталей
+    pub fn is_clean(&self) -> bool {
+        self.overhang_faces == 0 && self.thin_wall_faces == 0 && self.small_feature_faces == 0
+    }
+}
+
+/// Отчёт о пригодности видимых тел к печати
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrintabilityReport {
+    pub settings: PrintSettings,
+    pub bodies: Vec<BodyPrintability>,
+    /// Тела, сетки которых не прошли проверку целостности, и причины
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub errors: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_defaults_fill_missing_fields() {
+        let settings: PrintSettings = serde_json::from_str(r#"{"overhang_angle": 60}"#).unwrap();
+        assert_eq!(settings.overhang_angle, 60.0);
+        assert_eq!(settings.build_direction, [0.0, 0.0, 1.0]);
+        assert_eq!(settings.min_wall, 0.8);
+
+        let report = PrintabilityReport {
+            bodies: vec![BodyPrintability { id: "body_1".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json.get("errors").is_none());
+        assert_eq!(json["bodies"][0]["min_thickness"], serde_json::Value::Null);
+        let back: PrintabilityReport = serde_json::from_value(json).unwrap();
+        assert_eq!(back, report);
+    }
+}
@@ -0,0 +1,142 @@
+//! Миграция эскизов со ссылками по индексам на ссылки по id
+//!
+//! Старые файлы хранят в ограничениях, осях, флагах вспомогательной геометрии
+//! и размерах индексы элементов (`usize`). При чтении элементам без id
+//! назначаются id, а все числовые ссылки заменяются на id.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{FaceRef, Sketch, SketchConstraint, SketchElement, SketchPlane};
+
+/// Сырое представление эскиза (ссылки ещё могут быть индексами)
+#[derive(Deserialize)]
+pub(crate) struct SketchRaw {
+    plane: SketchPlane,
+    offset: f64,
+    elements: Vec<Value>,
+    #[serde(default)]
+    face_normal: Option<[f64; 3]>,
+    #[serde(default)]
+    face: Option<FaceRef>,
+    #[serde(default)]
+    construction: Vec<Value>,
+    #[serde(default)]
+    revolve_axis: Option<Value>,
+    #[serde(default)]
+    symmetry_axis: Option<Value>,
+    #[serde(default)]
+    constraints: Vec<Value>,
+}
+
+/// Поля ограничений, содержащие ссылку на элемент
+const ELEMENT_FIELDS: [&str; 6] = ["element", "element1", "element2", "axis", "line", "circle"];
+/// Поля ограничений, содержащие `PointRef`
+const POINT_FIELDS: [&str; 3] = ["point", "point1", "point2"];
+
+impl TryFrom<SketchRaw> for Sketch {
+    type Error = String;
+
+    fn try_from(raw: SketchRaw) -> Result<Self, Self::Error> {
+        // Числовой target_element размеров извлекается до десериализации
+        let mut legacy_targets = Vec::new();
+        let mut elements = Vec::with_capacity(raw.elements.len());
+        for mut value in raw.elements {
+            let legacy = value
+                .as_object_mut()
+                .and_then(|obj| match obj.get("target_element") {
+                    Some(Value::Number(n)) => {
+                        let index = n.as_u64();
+                        obj.remove("target_element");
+                        index
+                    }
+                    _ => None,
+                });
+            legacy_targets.push(legacy);
+            elements.push(serde_json::from_value::<SketchElement>(value).map_err(|e| e.to_string())?);
+        }
+
+        let mut sketch = Sketch {
+            plane: raw.plane,
+            offset: raw.offset,
+            elements,
+            face_normal: raw.face_normal,
+            face: raw.face,
+            construction: Vec::new(),
+            revolve_axis: None,
+            symmetry_axis: None,
+            constraints: Vec::new(),
+        };
+        sketch.ensure_element_ids();
+
+        // Индекс → id (после назначения id)
+        let ids: Vec<Option<String>> = sketch
+            .elements
+            .iter()
+            .map(|e| e.id().map(|s| s.to_string()))
+            .collect();
+        let resolve = |value: &Value| -> Option<String> {
+            match value {
+                Value::String(id) => Some(id.clone()),
+                Value::Number(n) => ids.get(n.as_u64()? as usize).cloned().flatten(),
+                _ => None,
+            }
+        };
+
+        for (elem, legacy) in sketch.elements.iter_mut().zip(legacy_targets) {
+            if let (SketchElement::Dimension { target_element, .. }, Some(index)) = (elem, legacy) {
+                *target_element = ids.get(index as usize).cloned().flatten();
+            }
+        }
+
+        // Вспомогательная геометрия: раньше — вектор флагов, параллельный elements
+        for (i, value) in raw.construction.iter().enumerate() {
+            match value {
+                Value::Bool(true) => {
+                    if let Some(Some(id)) = ids.get(i) {
+                        sketch.construction.push(id.clone());
+                    }
+                }
+                Value::String(id) => sketch.construction.push(id.clone()),
+                _ => {}
+            }
+        }
+
+        sketch.revolve_axis = raw.revolve_axis.as_ref().and_then(resolve);
+        sketch.symmetry_axis = raw.symmetry_axis.as_ref().and_then(resolve);
+
+        for mut value in raw.constraints {
+            let Some(obj) = value.as_object_mut() else { continue };
+
+            let mut valid = true;
+            for field in ELEMENT_FIELDS {
+                if let Some(v) = obj.get_mut(field) {
+                    match resolve(v) {
+                        Some(id) => *v = Value::String(id),
+                        None => valid = false,
+                    }
+                }
+            }
+            for field in POINT_FIELDS {
+                let Some(point) = obj.get_mut(field).and_then(|p| p.as_object_mut()) else { continue };
+                if let Some(index) = point.remove("element_index") {
+                    match resolve(&index) {
+                        Some(id) => {
+                            point.insert("element_id".to_string(), Value::String(id));
+                        }
+                        None => valid = false,
+                    }
+                }
+            }
+
+            // Ссылка на несуществующий элемент — ограничение отбрасывается
+            if valid {
+                let constraint: SketchConstraint =
+                    serde_json::from_value(value).map_err(|e| e.to_string())?;
+                sketch.constraints.push(constraint);
+            }
+        }
+
+        Ok(sketch)
+    }
+}
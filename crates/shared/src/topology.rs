@@ -0,0 +1,119 @@
+//! Топологические имена граней и рёбер
+//!
+//! Грань называется по фиче, которая её породила, и по элементу эскиза
+//! (а не по координатам), поэтому ссылка переживает изменение размеров
+//! предыдущих фич. Ребро — общая граница двух именованных граней.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ElementId, ObjectId};
+
+/// Торец выдавливания
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapSide {
+    /// Торец со стороны плоскости эскиза (для выреза не существует)
+    Start,
+    /// Дальний торец (для выреза — дно)
+    End,
+}
+
+/// Грань примитива-куба (в локальных осях до поворота)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrimitiveFace {
+    XMin,
+    XMax,
+    YMin,
+    YMax,
+    ZMin,
+    ZMax,
+}
+
+/// Ссылка на грань тела через породившую её фичу
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FaceRef {
+    /// Торец выдавливания (Extrude или BaseExtrude)
+    ExtrudeCap { feature_id: ObjectId, side: CapSide },
+    /// Боковая грань выдавливания, порождённая элементом эскиза
+    ExtrudeSide {
+        feature_id: ObjectId,
+        element_id: ElementId,
+        /// Номер стороны прямоугольника/полилинии (0 для отрезка, окружности, дуги)
+        #[serde(default)]
+        segment: usize,
+    },
+    /// Грань базового примитива
+    Primitive { feature_id: ObjectId, face: PrimitiveFace },
+}
+
+impl FaceRef {
+    /// Id фичи, породившей грань
+    pub fn feature_id(&self) -> &str {
+        match self {
+            FaceRef::ExtrudeCap { feature_id, .. }
+            | FaceRef::ExtrudeSide { feature_id, .. }
+            | FaceRef::Primitive { feature_id, .. } => feature_id,
+        }
+    }
+}
+
+impl std::fmt::Display for FaceRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FaceRef::ExtrudeCap { feature_id, side } => write!(f, "{}.{:?}", feature_id, side),
+            FaceRef::ExtrudeSide { feature_id, element_id, segment } => {
+                write!(f, "{}.side({}:{})", feature_id, element_id, segment)
+            }
+            FaceRef::Primitive { feature_id, face } => write!(f, "{}.{:?}", feature_id, face),
+        }
+    }
+}
+
+/// Ссылка на ребро: общая граница двух именованных граней
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeRef {
+    pub faces: [FaceRef; 2],
+}
+
+impl std::fmt::Display for EdgeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} / {}", self.faces[0], self.faces[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_ref_serde_tagged() {
+        let face = FaceRef::ExtrudeSide {
+            feature_id: "extrude_1".to_string(),
+            element_id: "e2".to_string(),
+            segment: 3,
+        };
+        let json = serde_json::to_value(&face).unwrap();
+        assert_eq!(json["type"], "extrude_side");
+        assert_eq!(json["segment"], 3);
+        let back: FaceRef = serde_json::from_value(json).unwrap();
+        assert_eq!(back, face);
+    }
+
+    #[test]
+    fn test_edge_ref_roundtrip() {
+        let edge = EdgeRef {
+            faces: [
+                FaceRef::ExtrudeCap { feature_id: "base".to_string(), side: CapSide::End },
+                FaceRef::Primitive { feature_id: "cube".to_string(), face: PrimitiveFace::XMax },
+            ],
+        };
+        let json = serde_json::to_string(&edge).unwrap();
+        assert!(json.contains("\"side\":\"end\""));
+        assert!(json.contains("\"face\":\"x_max\""));
+        let back: EdgeRef = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, edge);
+        assert_eq!(back.to_string(), "base.End / cube.XMax");
+    }
+}
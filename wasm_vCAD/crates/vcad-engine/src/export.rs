@@ -16,6 +16,14 @@ pub fn build_scene_to_glb(scene_json: &str) -> Result<Vec<u8>, JsValue> {
     Ok(vec![])
 }
 
+/// Build scene bodies as B-rep solids and export as STEP AP214 text
+pub fn build_scene_to_step(scene_json: &str) -> Result<String, JsValue> {
+    let scene: shared::SceneInput = serde_json::from_str(scene_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse scene: {}", e)))?;
+
+    crate::step::scene_to_step(&scene.into_v2()).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 /// Inspect scene and return metrics
 pub fn inspect_scene_metrics(scene_json: &str) -> Result<JsValue, JsValue> {
     tracing::info!("Inspecting scene metrics (stub implementation)");
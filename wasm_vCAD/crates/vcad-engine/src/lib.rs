@@ -7,6 +7,7 @@ mod extrude;
 mod fillet;
 mod boolean;
 mod export;
+mod step;  // B-rep solids for STEP export
 mod truck_primitives;
 pub mod sketch;  // Real sketch operations from desktop app
 mod sketch_operations;  // WASM bindings for sketch ops
@@ -17,6 +18,7 @@ pub use extrude::*;
 pub use fillet::*;
 pub use boolean::*;
 pub use export::*;
+pub use step::{scene_to_step, StepError};
 pub use sketch_operations::*;  // Real sketch operations
 
 /// Initialize WASM module with panic hook and logging
@@ -53,6 +55,13 @@ impl VcadEngine {
         build_scene_to_glb(scene_json)
     }
 
+    /// Build scene as exact B-rep solids and return STEP AP214 text.
+    /// Fails for bodies with mesh-only features (imported meshes, booleans)
+    #[wasm_bindgen]
+    pub fn build_scene_step(&self, scene_json: &str) -> Result<String, JsValue> {
+        build_scene_to_step(scene_json)
+    }
+
     /// Get scene metrics (volume, surface area, bounding box)
     #[wasm_bindgen]
     pub fn inspect_scene(&self, scene_json: &str) -> Result<JsValue, JsValue> {
@@ -72,6 +81,12 @@ pub fn has_manifold() -> bool {
     cfg!(feature = "manifold")
 }
 
+/// Export scene as STEP AP214 (primitive, extrude and revolve bodies)
+#[wasm_bindgen]
+pub fn export_scene_step(scene_json: &str) -> Result<String, JsValue> {
+    build_scene_to_step(scene_json)
+}
+
 // ========== Truck-based Mesh Generation ==========
 
 /// Generate mesh for a cube
@@ -37,16 +37,28 @@ pub fn solve_constraints(sketch: &mut Sketch) -> bool {
 }
 
 /// Apply a single constraint
+///
+/// Constraints reference elements by id; references to missing elements
+/// are treated as satisfied.
 fn apply_constraint(sketch: &mut Sketch, constraint: &SketchConstraint) -> bool {
+    let index = |id: &str| sketch.element_index(id);
     match constraint {
-        SketchConstraint::Horizontal { element } => apply_horizontal(sketch, *element),
-        SketchConstraint::Vertical { element } => apply_vertical(sketch, *element),
-        SketchConstraint::Parallel { element1, element2 } => {
-            apply_parallel(sketch, *element1, *element2)
-        }
-        SketchConstraint::Perpendicular { element1, element2 } => {
-            apply_perpendicular(sketch, *element1, *element2)
-        }
+        SketchConstraint::Horizontal { element } => match index(element) {
+            Some(e) => apply_horizontal(sketch, e),
+            None => true,
+        },
+        SketchConstraint::Vertical { element } => match index(element) {
+            Some(e) => apply_vertical(sketch, e),
+            None => true,
+        },
+        SketchConstraint::Parallel { element1, element2 } => match (index(element1), index(element2)) {
+            (Some(e1), Some(e2)) => apply_parallel(sketch, e1, e2),
+            _ => true,
+        },
+        SketchConstraint::Perpendicular { element1, element2 } => match (index(element1), index(element2)) {
+            (Some(e1), Some(e2)) => apply_perpendicular(sketch, e1, e2),
+            _ => true,
+        },
         SketchConstraint::Coincident { point1, point2 } => {
             apply_coincident(sketch, point1, point2)
         }
@@ -55,18 +67,26 @@ fn apply_constraint(sketch: &mut Sketch, constraint: &SketchConstraint) -> bool
             // The actual prevention is done in drag handling
             true
         }
-        SketchConstraint::Equal { element1, element2 } => {
-            apply_equal(sketch, *element1, *element2)
-        }
-        SketchConstraint::Tangent { element1, element2 } => {
-            apply_tangent(sketch, *element1, *element2)
-        }
-        SketchConstraint::Concentric { element1, element2 } => {
-            apply_concentric(sketch, *element1, *element2)
-        }
+        SketchConstraint::Equal { element1, element2 } => match (index(element1), index(element2)) {
+            (Some(e1), Some(e2)) => apply_equal(sketch, e1, e2),
+            _ => true,
+        },
+        SketchConstraint::Tangent { element1, element2 } => match (index(element1), index(element2)) {
+            (Some(e1), Some(e2)) => apply_tangent(sketch, e1, e2),
+            _ => true,
+        },
+        SketchConstraint::Concentric { element1, element2 } => match (index(element1), index(element2)) {
+            (Some(e1), Some(e2)) => apply_concentric(sketch, e1, e2),
+            _ => true,
+        },
         SketchConstraint::Symmetric { element1, element2, axis } => {
-            apply_symmetric(sketch, *element1, *element2, *axis)
+            match (index(element1), index(element2), index(axis)) {
+                (Some(e1), Some(e2), Some(a)) => apply_symmetric(sketch, e1, e2, a),
+                _ => true,
+            }
         }
+        // Dimensional and point-on-curve constraints are solved by the desktop solver only
+        _ => true,
     }
 }
 
@@ -600,7 +620,7 @@ fn reflect_point(point: (f64, f64), axis: ((f64, f64), (f64, f64))) -> (f64, f64
 
 /// Get the position of a point reference
 fn get_point_position(sketch: &Sketch, point_ref: &PointRef) -> Option<(f64, f64)> {
-    let elem = sketch.elements.get(point_ref.element_index)?;
+    let elem = sketch.element_by_id(&point_ref.element_id)?;
 
     match elem {
         SketchElement::Line { start, end, .. } => match point_ref.point_index {
@@ -651,7 +671,8 @@ fn get_point_position(sketch: &Sketch, point_ref: &PointRef) -> Option<(f64, f64
 
 /// Set the position of a point reference
 fn set_point_position(sketch: &mut Sketch, point_ref: &PointRef, pos: (f64, f64)) {
-    if let Some(elem) = sketch.elements.get_mut(point_ref.element_index) {
+    let Some(index) = sketch.element_index(&point_ref.element_id) else { return };
+    if let Some(elem) = sketch.elements.get_mut(index) {
         match elem {
             SketchElement::Line { start, end, .. } => match point_ref.point_index {
                 0 => {
@@ -1351,7 +1351,7 @@ pub fn reflect_element_about_line(element: &SketchElement, axis: ((f64, f64), (f
                 value: *value,
                 parameter_name: parameter_name.clone(),
                 dimension_line_pos: reflected_dim_line_pos,
-                target_element: *target_element,
+                target_element: target_element.clone(),
                 dimension_type: *dimension_type,
             }
         }
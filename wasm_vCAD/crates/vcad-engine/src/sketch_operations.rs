@@ -134,13 +134,10 @@ pub fn sketch_trim_element(
     // Apply trim result
     match trim_result {
         crate::sketch::types::TrimResult::Removed => {
-            sketch.elements.remove(element_index);
+            sketch.remove_elements(&[element_index]);
         }
         crate::sketch::types::TrimResult::Replaced(new_elements) => {
-            sketch.elements.remove(element_index);
-            for (i, elem) in new_elements.into_iter().enumerate() {
-                sketch.elements.insert(element_index + i, elem);
-            }
+            sketch.replace_element(element_index, new_elements);
         }
         crate::sketch::types::TrimResult::NoChange => {
             return Err(JsValue::from_str("No intersection found to trim"));
@@ -0,0 +1,585 @@
+//! STEP (AP214) export through the truck B-rep kernel
+//!
+//! The mesh kernel only has triangles, so exact geometry is rebuilt here from
+//! the feature tree: `BasePrimitive`, `BaseExtrude` and `BaseRevolve` bodies
+//! become truck solids with planar, cylindrical, conical and spherical faces,
+//! and sketch arcs stay arcs. Bodies whose geometry only exists as a mesh
+//! (imported meshes, boolean/fillet/chamfer modifications) are rejected with
+//! `StepError::NotRepresentable` instead of being exported approximately.
+//!
+//! Placement follows the desktop builder (`cad-core`): cubes and spheres are
+//! centred, cylinders and cones stand on z = 0, XY/XZ sketches extrude along
+//! +Z/+Y and YZ sketches along -X, shifted back by `height_backward`.
+
+use std::f64::consts::TAU;
+use std::fmt;
+
+use shared::{Body, Feature, Primitive, SceneDescriptionV2, Sketch, SketchElement, SketchPlane, Transform};
+use truck_modeling::*;
+use truck_stepio::out;
+
+/// Sketch endpoints closer than this are joined (same as the mesh kernel)
+const CHAIN_TOLERANCE: f64 = 0.01;
+
+/// FILE_SCHEMA of AP214 (automotive design)
+const AP214_SCHEMA: &str = "FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));";
+
+/// Why a scene can't be written as STEP
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepError {
+    /// The feature has no exact B-rep form (imported mesh, CSG modification)
+    NotRepresentable {
+        body: String,
+        feature: String,
+        reason: &'static str,
+    },
+    /// The sketch of a feature doesn't form closed faces
+    InvalidProfile { body: String, message: String },
+    /// No visible body with geometry
+    Empty,
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StepError::NotRepresentable { body, feature, reason } => {
+                write!(f, "Body '{}' is not representable in STEP: feature '{}' {}", body, feature, reason)
+            }
+            StepError::InvalidProfile { body, message } => write!(f, "Body '{}': {}", body, message),
+            StepError::Empty => write!(f, "Nothing to export: no visible bodies"),
+        }
+    }
+}
+
+impl std::error::Error for StepError {}
+
+/// Build all visible bodies and write them as one STEP AP214 file
+pub fn scene_to_step(scene: &SceneDescriptionV2) -> Result<String, StepError> {
+    let mut solids = Vec::new();
+    for body in scene.bodies.iter().filter(|body| body.visible) {
+        solids.extend(body_solids(body)?);
+    }
+    if solids.is_empty() {
+        return Err(StepError::Empty);
+    }
+
+    let compressed: Vec<_> = solids.iter().map(|solid| solid.compress()).collect();
+    let step = out::CompleteStepDisplay::new(
+        out::StepModels::from_iter(compressed.iter()),
+        out::StepHeaderDescriptor {
+            file_name: "scene.step".to_owned(),
+            organization_system: "vCAD".to_owned(),
+            ..Default::default()
+        },
+    )
+    .to_string();
+    Ok(with_ap214_schema(step))
+}
+
+/// B-rep solids of a body: one per primitive, one per outer outline of an
+/// extruded or revolved sketch
+pub fn body_solids(body: &Body) -> Result<Vec<Solid>, StepError> {
+    let not_representable = |feature: &Feature, reason| StepError::NotRepresentable {
+        body: body.name.clone(),
+        feature: feature.id().clone(),
+        reason,
+    };
+    let invalid = |message: String| StepError::InvalidProfile { body: body.name.clone(), message };
+
+    let mut solids = Vec::new();
+    for feature in &body.features {
+        match feature {
+            Feature::BasePrimitive { primitive, transform, .. } => {
+                let solid = primitive_solid(primitive).map_err(invalid)?;
+                solids.push(builder::transformed(&solid, transform_matrix(transform)));
+            }
+            Feature::BaseExtrude { sketch, sketch_transform, height, height_backward, draft_angle, .. } => {
+                if draft_angle.abs() >= 0.01 {
+                    return Err(not_representable(feature, "has a draft angle"));
+                }
+                solids.extend(extrude_solids(sketch, sketch_transform, *height, *height_backward).map_err(invalid)?);
+            }
+            Feature::BaseRevolve { sketch, sketch_transform, angle, .. } => {
+                solids.extend(revolve_solids(sketch, sketch_transform, *angle).map_err(invalid)?);
+            }
+            Feature::BaseMesh { .. } => {
+                return Err(not_representable(feature, "is an imported mesh without exact surfaces"));
+            }
+            // Reference geometry only
+            Feature::Sketch { .. } => {}
+            Feature::Extrude { .. } | Feature::Revolve { .. } | Feature::BooleanModify { .. } => {
+                return Err(not_representable(feature, "is a boolean operation, which is only built as a mesh"));
+            }
+            Feature::Fillet3D { .. } | Feature::Chamfer3D { .. } => {
+                return Err(not_representable(feature, "is a mesh fillet/chamfer"));
+            }
+        }
+    }
+    Ok(solids)
+}
+
+/// truck writes its own schema name; AP214 readers expect AUTOMOTIVE_DESIGN
+fn with_ap214_schema(step: String) -> String {
+    let Some(start) = step.find("FILE_SCHEMA(") else {
+        return step;
+    };
+    let end = step[start..].find(';').map_or(step.len(), |e| start + e + 1);
+    format!("{}{}{}", &step[..start], AP214_SCHEMA, &step[end..])
+}
+
+/// Scale, then rotate about X, Y, Z (degrees), then translate — the order
+/// the mesh kernel applies a `Transform` in
+fn transform_matrix(transform: &Transform) -> Matrix4 {
+    let [tx, ty, tz] = transform.position;
+    let [rx, ry, rz] = transform.rotation;
+    let [sx, sy, sz] = transform.scale;
+    Matrix4::from_translation(Vector3::new(tx, ty, tz))
+        * Matrix4::from_angle_z(Deg(rz))
+        * Matrix4::from_angle_y(Deg(ry))
+        * Matrix4::from_angle_x(Deg(rx))
+        * Matrix4::from_nonuniform_scale(sx, sy, sz)
+}
+
+// ── Primitives ──────────────────────────────────────────────
+
+fn primitive_solid(primitive: &Primitive) -> Result<Solid, String> {
+    let positive = |values: &[f64]| values.iter().all(|v| *v > 0.0);
+    match *primitive {
+        Primitive::Cube { width, height, depth } if positive(&[width, height, depth]) => {
+            let vertex = builder::vertex(Point3::new(-width / 2.0, -height / 2.0, -depth / 2.0));
+            let edge = builder::tsweep(&vertex, width * Vector3::unit_x());
+            let face = builder::tsweep(&edge, height * Vector3::unit_y());
+            Ok(builder::tsweep(&face, depth * Vector3::unit_z()))
+        }
+        Primitive::Cylinder { radius, height } if positive(&[radius, height]) => {
+            let points = [[0.0, height], [radius, height], [radius, 0.0], [0.0, 0.0]];
+            Ok(z_revolution(polyline_wire(&points)))
+        }
+        Primitive::Cone { radius, height } if positive(&[radius, height]) => {
+            let points = [[0.0, height], [radius, 0.0], [0.0, 0.0]];
+            Ok(z_revolution(polyline_wire(&points)))
+        }
+        Primitive::Sphere { radius } if positive(&[radius]) => {
+            let top = builder::vertex(Point3::new(0.0, 0.0, radius));
+            let bottom = builder::vertex(Point3::new(0.0, 0.0, -radius));
+            let meridian = builder::circle_arc(&top, &bottom, Point3::new(radius, 0.0, 0.0));
+            Ok(z_revolution(Wire::from(vec![meridian])))
+        }
+        _ => Err("primitive has a zero or negative size".to_string()),
+    }
+}
+
+/// Wire through `(r, z)` points in the XZ half-plane
+fn polyline_wire(points: &[[f64; 2]]) -> Wire {
+    let vertices: Vec<Vertex> = points.iter().map(|[r, z]| builder::vertex(Point3::new(*r, 0.0, *z))).collect();
+    vertices.windows(2).map(|pair| builder::line(&pair[0], &pair[1])).collect()
+}
+
+/// Full turn of a profile wire about +Z; the wire starts and ends on the axis
+fn z_revolution(wire: Wire) -> Solid {
+    Solid::new(vec![builder::cone(&wire, Vector3::unit_z(), Rad(TAU))])
+}
+
+// ── Sketch outlines ─────────────────────────────────────────
+
+/// Edge of a sketch outline, in sketch coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    Line([f64; 2], [f64; 2]),
+    /// Circular arc from the first to the last point through the middle one
+    Arc([f64; 2], [f64; 2], [f64; 2]),
+}
+
+impl Segment {
+    fn start(&self) -> [f64; 2] {
+        match self {
+            Segment::Line(start, _) | Segment::Arc(start, _, _) => *start,
+        }
+    }
+
+    fn end(&self) -> [f64; 2] {
+        match self {
+            Segment::Line(_, end) | Segment::Arc(_, _, end) => *end,
+        }
+    }
+
+    fn reversed(&self) -> Self {
+        match *self {
+            Segment::Line(start, end) => Segment::Line(end, start),
+            Segment::Arc(start, mid, end) => Segment::Arc(end, mid, start),
+        }
+    }
+}
+
+fn near(a: [f64; 2], b: [f64; 2]) -> bool {
+    (a[0] - b[0]).hypot(a[1] - b[1]) <= CHAIN_TOLERANCE
+}
+
+/// Closed loops of the sketch geometry (construction elements excluded).
+/// Circles and rectangles are loops by themselves, other elements are
+/// chained by their end points.
+fn sketch_loops(sketch: &Sketch) -> Result<Vec<Vec<Segment>>, String> {
+    let mut loops: Vec<Vec<Segment>> = Vec::new();
+    let mut open: Vec<Segment> = Vec::new();
+    let circle = |[cx, cy]: [f64; 2], r: f64| {
+        let (right, left) = ([cx + r, cy], [cx - r, cy]);
+        vec![Segment::Arc(right, [cx, cy + r], left), Segment::Arc(left, [cx, cy - r], right)]
+    };
+
+    for (_, element) in sketch.geometry_elements() {
+        match element {
+            SketchElement::Circle { center, radius, .. } => loops.push(circle([center.x, center.y], *radius)),
+            SketchElement::Rectangle { corner, width, height, .. } => {
+                let (x0, y0, x1, y1) = (corner.x, corner.y, corner.x + width, corner.y + height);
+                let corners = [[x0, y0], [x1, y0], [x1, y1], [x0, y1]];
+                loops.push((0..4).map(|i| Segment::Line(corners[i], corners[(i + 1) % 4])).collect());
+            }
+            SketchElement::Line { start, end, .. } => open.push(Segment::Line([start.x, start.y], [end.x, end.y])),
+            SketchElement::Arc { center, radius, start_angle, end_angle, .. } => {
+                let mut span = end_angle - start_angle;
+                if span < 0.0 {
+                    span += TAU;
+                }
+                let at = |angle: f64| [center.x + radius * angle.cos(), center.y + radius * angle.sin()];
+                if span >= TAU - 1e-9 {
+                    loops.push(circle([center.x, center.y], *radius));
+                } else if span > 1e-9 {
+                    open.push(Segment::Arc(at(*start_angle), at(start_angle + span / 2.0), at(start_angle + span)));
+                }
+            }
+            // Splines are built from their points as straight segments, like the mesh kernel does
+            SketchElement::Polyline { points, .. } | SketchElement::Spline { points, .. } => {
+                for pair in points.windows(2) {
+                    let (a, b) = ([pair[0].x, pair[0].y], [pair[1].x, pair[1].y]);
+                    if a != b {
+                        open.push(Segment::Line(a, b));
+                    }
+                }
+            }
+            SketchElement::Dimension { .. } => {}
+        }
+    }
+
+    while !open.is_empty() {
+        let mut chain = vec![open.remove(0)];
+        while !near(chain[chain.len() - 1].end(), chain[0].start()) {
+            let end = chain[chain.len() - 1].end();
+            let Some(index) = open.iter().position(|s| near(s.start(), end) || near(s.end(), end)) else {
+                return Err(format!("sketch outline is open at ({:.3}, {:.3})", end[0], end[1]));
+            };
+            let segment = open.remove(index);
+            chain.push(if near(segment.start(), end) { segment } else { segment.reversed() });
+        }
+        loops.push(chain);
+    }
+    if loops.is_empty() {
+        return Err("sketch has no closed outline".to_string());
+    }
+    Ok(loops)
+}
+
+/// Polygon through the loop points (arc midpoints included), for area and
+/// containment tests
+fn loop_polygon(segments: &[Segment]) -> Vec<[f64; 2]> {
+    segments
+        .iter()
+        .flat_map(|segment| match *segment {
+            Segment::Line(start, _) => vec![start],
+            Segment::Arc(start, mid, _) => vec![start, mid],
+        })
+        .collect()
+}
+
+fn signed_area(polygon: &[[f64; 2]]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn point_in_polygon(point: [f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + n - 1) % n]);
+        if (a[1] > point[1]) != (b[1] > point[1])
+            && point[0] < (b[0] - a[0]) * (point[1] - a[1]) / (b[1] - a[1]) + a[0]
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Group loops into faces: an outer loop followed by its holes. Loops
+/// nested an odd number of times are holes of their smallest container.
+/// Outer loops are oriented counter-clockwise when `ccw`, holes opposite.
+fn group_regions(loops: Vec<Vec<Segment>>, ccw: bool) -> Vec<Vec<Vec<Segment>>> {
+    let mut loops: Vec<(Vec<Segment>, Vec<[f64; 2]>)> = loops
+        .into_iter()
+        .map(|segments| {
+            let polygon = loop_polygon(&segments);
+            (segments, polygon)
+        })
+        .collect();
+    loops.sort_by(|a, b| signed_area(&b.1).abs().total_cmp(&signed_area(&a.1).abs()));
+
+    let mut regions: Vec<Vec<Vec<Segment>>> = Vec::new();
+    // Region index and nesting depth of each loop so far
+    let mut placed: Vec<(usize, usize)> = Vec::new();
+    for i in 0..loops.len() {
+        let container = (0..i).rev().find(|&j| point_in_polygon(loops[i].1[0], &loops[j].1));
+        let depth = container.map_or(0, |j| placed[j].1 + 1);
+        let hole = depth % 2 == 1;
+
+        let (segments, polygon) = &loops[i];
+        let counter_clockwise = signed_area(polygon) > 0.0;
+        let segments = if counter_clockwise == (ccw != hole) {
+            segments.clone()
+        } else {
+            segments.iter().rev().map(Segment::reversed).collect()
+        };
+        let region = match container {
+            Some(j) if hole => {
+                regions[placed[j].0].push(segments);
+                placed[j].0
+            }
+            _ => {
+                regions.push(vec![segments]);
+                regions.len() - 1
+            }
+        };
+        placed.push((region, depth));
+    }
+    regions
+}
+
+/// Sketch point in world coordinates (sketch plane, offset and position)
+fn sketch_point(sketch: &Sketch, transform: &Transform, [x, y]: [f64; 2]) -> Point3 {
+    let [px, py, pz] = transform.position;
+    let offset = sketch.offset;
+    match sketch.plane {
+        SketchPlane::Xy => Point3::new(x + px, y + py, offset + pz),
+        SketchPlane::Xz => Point3::new(x + px, offset + py, y + pz),
+        SketchPlane::Yz => Point3::new(offset + px, x + py, y + pz),
+    }
+}
+
+/// Normal of a counter-clockwise sketch loop in world coordinates
+fn sketch_normal(plane: &SketchPlane) -> Vector3 {
+    match plane {
+        SketchPlane::Xy => Vector3::unit_z(),
+        // Sketch axes map to X and Z: X × Z = -Y
+        SketchPlane::Xz => -Vector3::unit_y(),
+        SketchPlane::Yz => Vector3::unit_x(),
+    }
+}
+
+/// Planar face with the outer loop first, shared vertices at the joints
+fn planar_face(region: &[Vec<Segment>], to_world: impl Fn([f64; 2]) -> Point3) -> Result<Face, String> {
+    let wires: Vec<Wire> = region
+        .iter()
+        .map(|segments| {
+            let vertices: Vec<Vertex> = segments.iter().map(|s| builder::vertex(to_world(s.start()))).collect();
+            segments
+                .iter()
+                .enumerate()
+                .map(|(i, segment)| {
+                    let (from, to) = (&vertices[i], &vertices[(i + 1) % vertices.len()]);
+                    match segment {
+                        Segment::Line(..) => builder::line(from, to),
+                        Segment::Arc(_, mid, _) => builder::circle_arc(from, to, to_world(*mid)),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    builder::try_attach_plane(&wires).map_err(|e| format!("sketch outline is not a valid face: {}", e))
+}
+
+fn extrude_solids(
+    sketch: &Sketch,
+    transform: &Transform,
+    height: f64,
+    height_backward: f64,
+) -> Result<Vec<Solid>, String> {
+    let direction = match sketch.plane {
+        SketchPlane::Xy => Vector3::unit_z(),
+        SketchPlane::Xz => Vector3::unit_y(),
+        SketchPlane::Yz => -Vector3::unit_x(),
+    };
+    // The face normal must point along the sweep for an outward-facing solid
+    let ccw = sketch_normal(&sketch.plane).dot(direction) > 0.0;
+
+    group_regions(sketch_loops(sketch)?, ccw)
+        .iter()
+        .map(|region| {
+            let face = planar_face(region, |p| sketch_point(sketch, transform, p))?;
+            // The solid spans -height_backward..height along the direction,
+            // swept from whichever end is lower (same as `create_extrude_part_full`)
+            let start = (-height_backward).min(height);
+            let face = builder::translated(&face, start * direction);
+            Ok(builder::tsweep(&face, (height + height_backward).abs() * direction))
+        })
+        .collect()
+}
+
+fn revolve_solids(sketch: &Sketch, transform: &Transform, angle: f64) -> Result<Vec<Solid>, String> {
+    let to_world = |p: [f64; 2]| sketch_point(sketch, transform, p);
+    // Revolve axis line, or the sketch Y axis through the sketch origin
+    let axis_line = sketch.revolve_axis.as_deref().and_then(|id| match sketch.element_by_id(id) {
+        Some(SketchElement::Line { start, end, .. }) => Some(([start.x, start.y], [end.x, end.y])),
+        _ => None,
+    });
+    let (origin, axis) = match axis_line {
+        Some((start, end)) => (to_world(start), to_world(end) - to_world(start)),
+        None => (to_world([0.0, 0.0]), to_world([0.0, 1.0]) - to_world([0.0, 0.0])),
+    };
+    if axis.magnitude() < 1e-9 {
+        return Err("revolve axis has zero length".to_string());
+    }
+    let axis = axis.normalize();
+    let normal = sketch_normal(&sketch.plane);
+
+    // The axis line is not part of the outline (`geometry_elements` skips it)
+    group_regions(sketch_loops(sketch)?, true)
+        .into_iter()
+        .map(|region| {
+            // Orient the face so that it sweeps along its normal
+            let polygon = loop_polygon(&region[0]);
+            let n = polygon.len() as f64;
+            let centroid = polygon.iter().fold([0.0, 0.0], |c, p| [c[0] + p[0] / n, c[1] + p[1] / n]);
+            let velocity = axis.cross(to_world(centroid) - origin);
+            let region = if normal.dot(velocity) >= 0.0 {
+                region
+            } else {
+                region.iter().map(|segments| segments.iter().rev().map(Segment::reversed).collect()).collect()
+            };
+            let face = planar_face(&region, to_world)?;
+            Ok(builder::rsweep(&face, origin, axis, Rad(angle.to_radians())))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::Point2D;
+
+    fn body(features: Vec<Feature>) -> Body {
+        Body {
+            id: "body_1".to_string(),
+            name: "Part".to_string(),
+            features,
+            visible: true,
+            parameters: Default::default(),
+            bindings: Default::default(),
+            appearance: None,
+        }
+    }
+
+    fn plate_with_hole() -> Sketch {
+        let mut sketch = Sketch { plane: SketchPlane::Xy, offset: 0.0, ..Default::default() };
+        sketch.push_element(SketchElement::Rectangle {
+            id: None,
+            corner: Point2D { x: 0.0, y: 0.0 },
+            width: 20.0,
+            height: 10.0,
+        });
+        sketch.push_element(SketchElement::Circle { id: None, center: Point2D { x: 10.0, y: 5.0 }, radius: 2.0 });
+        sketch
+    }
+
+    #[test]
+    fn test_loops_chain_and_nest() {
+        let mut sketch = plate_with_hole();
+        // Open triangle elements given out of order and reversed
+        let line = |a: [f64; 2], b: [f64; 2]| SketchElement::Line {
+            id: None,
+            start: Point2D { x: a[0], y: a[1] },
+            end: Point2D { x: b[0], y: b[1] },
+        };
+        sketch.push_element(line([30.0, 0.0], [40.0, 0.0]));
+        sketch.push_element(line([30.0, 0.0], [35.0, 5.0]));
+        sketch.push_element(line([40.0, 0.0], [35.0, 5.0]));
+
+        let loops = sketch_loops(&sketch).unwrap();
+        assert_eq!(loops.len(), 3);
+        let regions = group_regions(loops, true);
+        assert_eq!(regions.len(), 2);
+        let plate = regions.iter().find(|r| r.len() == 2).unwrap();
+        assert!(signed_area(&loop_polygon(&plate[0])) > 0.0);
+        assert!(signed_area(&loop_polygon(&plate[1])) < 0.0);
+    }
+
+    #[test]
+    fn test_extrude_and_primitive_to_step() {
+        let scene = SceneDescriptionV2 {
+            bodies: vec![
+                body(vec![Feature::BaseExtrude {
+                    id: "extrude_1".to_string(),
+                    sketch: plate_with_hole(),
+                    sketch_transform: Transform::new(),
+                    height: 3.0,
+                    height_backward: 0.0,
+                    draft_angle: 0.0,
+                }]),
+                body(vec![Feature::BasePrimitive {
+                    id: "primitive_1".to_string(),
+                    primitive: Primitive::Cylinder { radius: 1.0, height: 2.0 },
+                    transform: Transform::new(),
+                }]),
+            ],
+            ..Default::default()
+        };
+        let solids = body_solids(&scene.bodies[0]).unwrap();
+        // Four walls, two hole walls (two arcs) and both caps
+        assert_eq!(solids[0].boundaries()[0].len(), 8);
+
+        let step = scene_to_step(&scene).unwrap();
+        assert!(step.starts_with("ISO-10303-21;"));
+        assert!(step.contains(AP214_SCHEMA));
+        assert_eq!(step.matches("MANIFOLD_SOLID_BREP").count(), 2);
+    }
+
+    #[test]
+    fn test_extrude_height_backward_extent() {
+        let extrude = body(vec![Feature::BaseExtrude {
+            id: "extrude_1".to_string(),
+            sketch: plate_with_hole(),
+            sketch_transform: Transform::new(),
+            height: 3.0,
+            height_backward: 2.0,
+            draft_angle: 0.0,
+        }]);
+        let solids = body_solids(&extrude).unwrap();
+        let (min, max) = solids[0].boundaries()[0]
+            .vertex_iter()
+            .map(|v| v.point().z)
+            .fold((f64::MAX, f64::MIN), |(min, max), z| (min.min(z), max.max(z)));
+        assert!((min + 2.0).abs() < 1e-9, "min z = {min}");
+        assert!((max - 3.0).abs() < 1e-9, "max z = {max}");
+    }
+
+    #[test]
+    fn test_mesh_feature_not_representable() {
+        let part = body(vec![Feature::BaseMesh {
+            id: "mesh_1".to_string(),
+            mesh: shared::ImportedMesh {
+                positions: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+                indices: vec![0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3],
+                source: None,
+            },
+            transform: Transform::new(),
+        }]);
+        let scene = SceneDescriptionV2 { bodies: vec![part], ..Default::default() };
+
+        let error = scene_to_step(&scene).unwrap_err();
+        assert!(matches!(&error, StepError::NotRepresentable { feature, .. } if feature == "mesh_1"));
+        assert!(error.to_string().contains("not representable"));
+    }
+}
@@ -411,6 +411,27 @@ export class VcadEngine {
       'generateConeMesh'
     )
   }
+
+  // ========== Export (WASM) ==========
+
+  /**
+   * Export scene as STEP AP214 text. Throws for bodies that only exist
+   * as meshes (imported meshes, boolean operations, fillets)
+   */
+  exportStep(sceneJson: string): string {
+    this.ensureReady()
+    return wasmModule.export_scene_step(sceneJson)
+  }
+
+  /**
+   * Safe STEP export
+   */
+  exportStepSafe(sceneJson: string): WasmResult<string> {
+    return this.safeCall(
+      () => wasmModule.export_scene_step(sceneJson),
+      'exportStep'
+    )
+  }
 }
 
 // ─── Singleton instance ──────────────────────────────────────────────────────